@@ -0,0 +1,367 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs,
+    io::{Read, Write},
+    net::{IpAddr, TcpStream, ToSocketAddrs},
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::Duration,
+};
+
+use crate::config::EnrichmentConfig;
+
+const WHOIS_SERVER: &str = "whois.cymru.com:43";
+const WHOIS_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// The autonomous system an address is routed to, and who runs it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsnInfo {
+    pub asn: u32,
+    pub owner: String,
+    /// Announced prefix containing the address, if the source knows it
+    pub prefix: Option<String>,
+    pub country: Option<String>,
+}
+
+impl fmt::Display for AsnInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AS{} {}", self.asn, self.owner)?;
+        if let Some(country) = &self.country {
+            write!(f, " ({})", country)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where ASN lookups are answered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsnSource {
+    /// Team Cymru's bulk whois service, which sees every address looked up
+    Whois,
+    /// A GeoLite2-style CSV of network,autonomous_system_number,autonomous_system_organization
+    Database(PathBuf),
+}
+
+impl AsnSource {
+    /// The source the config asks for, None when lookups are off
+    pub fn from_config(config: &EnrichmentConfig) -> Option<Self> {
+        match (&config.asn_database, config.asn_lookup) {
+            (Some(path), _) => Some(AsnSource::Database(path.clone())),
+            (None, true) => Some(AsnSource::Whois),
+            (None, false) => None,
+        }
+    }
+}
+
+/// The address a target names, for a single IP or the network address of a CIDR block
+pub fn target_address(target: &str) -> Option<IpAddr> {
+    target.split('/').next()?.parse().ok()
+}
+
+/// The query for Team Cymru's bulk whois interface
+pub fn whois_query(addresses: &[IpAddr]) -> String {
+    let mut query = String::from("begin\nverbose\n");
+    for address in addresses {
+        query.push_str(&address.to_string());
+        query.push('\n');
+    }
+    query.push_str("end\n");
+    query
+}
+
+/// Parse a verbose bulk whois answer: AS | IP | BGP Prefix | CC | Registry | Allocated | AS Name
+pub fn parse_whois(response: &str) -> Vec<(IpAddr, AsnInfo)> {
+    response
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+            if fields.len() < 7 {
+                return None;
+            }
+            let asn = fields[0].parse().ok()?;
+            let address = fields[1].parse().ok()?;
+            let country = (!fields[3].is_empty()).then(|| fields[3].to_string());
+            // Names end with the country again, as in "GOOGLE, US"
+            let owner = match &country {
+                Some(country) => fields[6]
+                    .strip_suffix(country.as_str())
+                    .and_then(|owner| owner.strip_suffix(", "))
+                    .unwrap_or(fields[6]),
+                None => fields[6],
+            };
+            Some((
+                address,
+                AsnInfo {
+                    asn,
+                    owner: owner.to_string(),
+                    prefix: (!fields[2].is_empty()).then(|| fields[2].to_string()),
+                    country,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn query_whois(addresses: &[IpAddr]) -> Result<Vec<(IpAddr, AsnInfo)>, String> {
+    let server = WHOIS_SERVER
+        .to_socket_addrs()
+        .map_err(|err| format!("Failed to resolve {}: {}", WHOIS_SERVER, err))?
+        .next()
+        .ok_or_else(|| format!("Failed to resolve {}", WHOIS_SERVER))?;
+    let mut stream = TcpStream::connect_timeout(&server, WHOIS_TIMEOUT)
+        .map_err(|err| format!("Failed to reach {}: {}", WHOIS_SERVER, err))?;
+    stream.set_read_timeout(Some(WHOIS_TIMEOUT)).ok();
+    stream
+        .write_all(whois_query(addresses).as_bytes())
+        .map_err(|err| format!("ASN whois query failed: {}", err))?;
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .map_err(|err| format!("ASN whois query failed: {}", err))?;
+    Ok(parse_whois(&response))
+}
+
+/// Address blocks of an offline ASN database, sorted by first address
+#[derive(Debug, Clone, Default)]
+pub struct AsnDatabase {
+    blocks: Vec<(u128, u128, AsnInfo)>,
+}
+
+impl AsnDatabase {
+    pub fn load(path: &PathBuf) -> Result<Self, String> {
+        fs::read_to_string(path)
+            .map(|content| Self::parse(&content))
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))
+    }
+
+    /// Parse GeoLite2 ASN block CSV lines; lines that aren't blocks, like the header, are skipped
+    pub fn parse(content: &str) -> Self {
+        let mut blocks: Vec<(u128, u128, AsnInfo)> = content
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, ',');
+                let network = fields.next()?;
+                let asn = fields.next()?.trim().parse().ok()?;
+                let owner = fields.next().unwrap_or_default().trim();
+                let owner = owner
+                    .strip_prefix('"')
+                    .and_then(|owner| owner.strip_suffix('"'))
+                    .map_or(owner.to_string(), |owner| owner.replace("\"\"", "\""));
+                let (start, end) = network_bounds(network)?;
+                Some((
+                    start,
+                    end,
+                    AsnInfo {
+                        asn,
+                        owner,
+                        prefix: Some(network.to_string()),
+                        country: None,
+                    },
+                ))
+            })
+            .collect();
+        blocks.sort_by_key(|(start, _, _)| *start);
+        Self { blocks }
+    }
+
+    pub fn lookup(&self, address: IpAddr) -> Option<&AsnInfo> {
+        let value = address_value(address);
+        let index = self
+            .blocks
+            .partition_point(|(start, _, _)| *start <= value)
+            .checked_sub(1)?;
+        let (_, end, info) = &self.blocks[index];
+        (value <= *end).then_some(info)
+    }
+}
+
+/// Addresses on one number line, IPv4 mapped into IPv6 so both families sort apart
+fn address_value(address: IpAddr) -> u128 {
+    match address {
+        IpAddr::V4(address) => u128::from(address.to_ipv6_mapped()),
+        IpAddr::V6(address) => u128::from(address),
+    }
+}
+
+fn network_bounds(network: &str) -> Option<(u128, u128)> {
+    let (address, prefix) = network.split_once('/')?;
+    let address: IpAddr = address.parse().ok()?;
+    let prefix: u32 = prefix.parse().ok()?;
+    let host_bits = match address {
+        IpAddr::V4(_) => 32u32.checked_sub(prefix)?,
+        IpAddr::V6(_) => 128u32.checked_sub(prefix)?,
+    };
+    let mask = u128::MAX.checked_shl(host_bits).unwrap_or(0);
+    let start = address_value(address) & mask;
+    Some((start, start | !mask))
+}
+
+/// Whether an address is routed on the internet, and so has an AS to look up
+fn is_public(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => {
+            !(address.is_private()
+                || address.is_loopback()
+                || address.is_link_local()
+                || address.is_unspecified()
+                || address.is_broadcast()
+                || address.is_documentation()
+                || address.is_multicast())
+        }
+        IpAddr::V6(address) => {
+            !(address.is_loopback()
+                || address.is_unspecified()
+                || address.is_multicast()
+                || address.is_unique_local()
+                || address.is_unicast_link_local())
+        }
+    }
+}
+
+enum Reply {
+    Database(Result<AsnDatabase, String>),
+    Whois(Result<Vec<(IpAddr, AsnInfo)>, String>),
+}
+
+/// ASN lookups of the addresses the app shows, answered in the background and cached
+pub struct AsnLookup {
+    source: AsnSource,
+    database: Option<AsnDatabase>,
+    cache: HashMap<IpAddr, AsnInfo>,
+    requested: HashSet<IpAddr>,
+    receiver: Option<Receiver<Reply>>,
+    error: Option<String>,
+}
+
+impl AsnLookup {
+    pub fn new(source: AsnSource) -> Self {
+        Self {
+            source,
+            database: None,
+            cache: HashMap::new(),
+            requested: HashSet::new(),
+            receiver: None,
+            error: None,
+        }
+    }
+
+    /// Look up the public addresses that haven't been yet, one batch at a time
+    pub fn request(&mut self, addresses: impl IntoIterator<Item = IpAddr>) {
+        if self.receiver.is_some() || self.error.is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        match &self.source {
+            AsnSource::Database(path) => {
+                if self.database.is_some() {
+                    return;
+                }
+                let path = path.clone();
+                thread::spawn(move || sender.send(Reply::Database(AsnDatabase::load(&path))));
+            }
+            AsnSource::Whois => {
+                let addresses: Vec<IpAddr> = addresses
+                    .into_iter()
+                    .filter(|address| is_public(*address) && !self.requested.contains(address))
+                    .collect::<HashSet<_>>()
+                    .into_iter()
+                    .collect();
+                if addresses.is_empty() {
+                    return;
+                }
+                self.requested.extend(addresses.iter().copied());
+                thread::spawn(move || sender.send(Reply::Whois(query_whois(&addresses))));
+            }
+        }
+        self.receiver = Some(receiver);
+    }
+
+    /// Collect a finished lookup, returning true when new answers arrived
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = &self.receiver else {
+            return false;
+        };
+        let reply = match receiver.try_recv() {
+            Ok(reply) => reply,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => {
+                Reply::Whois(Err("ASN lookup stopped unexpectedly".to_string()))
+            }
+        };
+        self.receiver = None;
+        match reply {
+            Reply::Database(Ok(database)) => self.database = Some(database),
+            Reply::Whois(Ok(found)) => self.cache.extend(found),
+            Reply::Database(Err(err)) | Reply::Whois(Err(err)) => self.error = Some(err),
+        }
+        true
+    }
+
+    pub fn get(&self, address: IpAddr) -> Option<&AsnInfo> {
+        match &self.database {
+            Some(database) => database.lookup(address),
+            None => self.cache.get(&address),
+        }
+    }
+
+    /// Why lookups stopped, if they failed
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_whois() {
+        let response = "Bulk mode; whois.cymru.com [2024-01-01 12:00:00 +0000]\n\
+            15169   | 8.8.8.8          | 8.8.8.0/24          | US | arin     | 2023-12-28 | GOOGLE, US\n\
+            NA      | 10.0.0.1         | NA                  |    | other    |            | NA\n";
+        let found = parse_whois(response);
+        assert_eq!(
+            found,
+            vec![(
+                "8.8.8.8".parse().unwrap(),
+                AsnInfo {
+                    asn: 15169,
+                    owner: "GOOGLE".to_string(),
+                    prefix: Some("8.8.8.0/24".to_string()),
+                    country: Some("US".to_string()),
+                }
+            )]
+        );
+        assert_eq!(found[0].1.to_string(), "AS15169 GOOGLE (US)");
+        assert_eq!(target_address("8.8.8.0/24"), "8.8.8.0".parse().ok());
+        assert_eq!(target_address("scanme.nmap.org"), None);
+        assert_eq!(
+            whois_query(&["8.8.8.8".parse().unwrap()]),
+            "begin\nverbose\n8.8.8.8\nend\n"
+        );
+    }
+
+    #[test]
+    fn test_database() {
+        let database = AsnDatabase::parse(
+            "network,autonomous_system_number,autonomous_system_organization\n\
+             1.0.0.0/24,13335,CLOUDFLARENET\n\
+             8.8.8.0/24,15169,\"GOOGLE, \"\"LLC\"\"\"\n\
+             2001:4860::/32,15169,GOOGLE\n",
+        );
+        let lookup = |address: &str| database.lookup(address.parse().unwrap());
+        assert_eq!(lookup("1.0.0.255").map(|info| info.asn), Some(13335));
+        assert_eq!(
+            lookup("8.8.4.4").map(|info| info.asn),
+            None,
+            "8.8.4.4 is outside 8.8.8.0/24"
+        );
+        assert_eq!(
+            lookup("8.8.8.8").map(|info| info.owner.as_str()),
+            Some("GOOGLE, \"LLC\"")
+        );
+        assert_eq!(lookup("2001:4860::8888").map(|info| info.asn), Some(15169));
+        assert_eq!(lookup("1.0.1.0"), None);
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::scan::{
+    model::NmapScan,
+    parser::{NmapParser, ParseError},
+};
+
+pub const USAGE: &str = "\
+Usage: lazynmap [options]
+       lazynmap --dump-json [nmap command]
+
+Options:
+  --from-json <file>  Start from a scan saved as JSON; use - to read stdin
+  --dump-json         Print the scan as JSON and exit; the scan is parsed from
+                      the nmap command that follows, or left at the defaults
+  -h, --help          Show this help
+
+Environment:
+  LAZYNMAP_CONFIG_DIR, LAZYNMAP_DATA_DIR, LAZYNMAP_STATE_DIR, LAZYNMAP_CACHE_DIR
+                      Keep files in these directories instead of the platform's
+                      usual ones, such as the XDG base directories
+";
+
+/// Error type for invalid command-line arguments
+#[derive(Debug)]
+pub enum CliError {
+    UnexpectedArgument(String),
+    MissingValue(String),
+    InvalidCommand(ParseError),
+    Io(io::Error),
+    InvalidJson(String),
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CliError::UnexpectedArgument(arg) => {
+                write!(f, "Unexpected argument '{}'\n\n{}", arg, USAGE)
+            }
+            CliError::MissingValue(arg) => write!(f, "Missing value for {}\n\n{}", arg, USAGE),
+            CliError::InvalidCommand(err) => write!(f, "Invalid nmap command: {}", err),
+            CliError::Io(err) => write!(f, "Failed to read scan JSON: {}", err),
+            CliError::InvalidJson(err) => write!(f, "Invalid scan JSON: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Parsed command-line arguments
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    pub help: bool,
+    pub dump_json: bool,
+    /// JSON file holding the scan to start from, or "-" for stdin
+    pub from_json: Option<PathBuf>,
+    /// Nmap command to start from, split into arguments
+    pub command: Vec<String>,
+}
+
+impl Args {
+    /// Parse the arguments after the program name; anything after the options is an nmap command
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => parsed.help = true,
+                "--dump-json" => parsed.dump_json = true,
+                "--from-json" => {
+                    let file = args.next().ok_or(CliError::MissingValue(arg))?;
+                    parsed.from_json = Some(PathBuf::from(file));
+                }
+                "--" => break,
+                _ => {
+                    parsed.command.push(arg);
+                    break;
+                }
+            }
+        }
+        parsed.command.extend(args);
+
+        // A command is only accepted with --dump-json, as an alternative to --from-json
+        if (!parsed.dump_json || parsed.from_json.is_some()) && !parsed.command.is_empty() {
+            return Err(CliError::UnexpectedArgument(parsed.command.remove(0)));
+        }
+        Ok(parsed)
+    }
+
+    /// Build the scan to start from
+    pub fn scan(&self) -> Result<NmapScan, CliError> {
+        if let Some(file) = &self.from_json {
+            let json = if file.as_os_str() == "-" {
+                let mut json = String::new();
+                io::stdin().read_to_string(&mut json).map(|_| json)
+            } else {
+                fs::read_to_string(file)
+            }
+            .map_err(CliError::Io)?;
+            return serde_json::from_str(&json)
+                .map_err(|err| CliError::InvalidJson(err.to_string()));
+        }
+        if self.command.is_empty() {
+            return Ok(NmapScan::new());
+        }
+        let command = self
+            .command
+            .iter()
+            .map(|arg| quote_arg(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        NmapParser::parse(&command).map_err(CliError::InvalidCommand)
+    }
+}
+
+/// Quote an argument so that `NmapParser::tokenize` reads it back as a single token
+fn quote_arg(arg: &str) -> String {
+    if arg.is_empty() || arg.contains([' ', '\t', '\n', '"', '\\']) {
+        format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        arg.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, CliError> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(args(&[]).unwrap(), Args::default());
+        assert!(args(&["--help"]).unwrap().help);
+        assert!(matches!(
+            args(&["-sV"]),
+            Err(CliError::UnexpectedArgument(arg)) if arg == "-sV"
+        ));
+
+        let parsed = args(&["--dump-json", "nmap", "-sV", "--script-args", "user=a b"]).unwrap();
+        assert!(parsed.dump_json);
+        let scan = parsed.scan().unwrap();
+        assert!(scan.service_detection.enabled);
+        assert_eq!(scan.script_scan.script_args.as_deref(), Some("user=a b"));
+    }
+
+    #[test]
+    fn test_from_json() {
+        let parsed = args(&["--from-json", "scan.json", "--dump-json"]).unwrap();
+        assert_eq!(parsed.from_json, Some(PathBuf::from("scan.json")));
+        assert!(parsed.dump_json);
+        assert!(matches!(
+            args(&["--from-json"]),
+            Err(CliError::MissingValue(_))
+        ));
+        assert!(matches!(
+            args(&["--dump-json", "--from-json", "-", "-sV"]),
+            Err(CliError::UnexpectedArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,576 @@
+use serde::Deserialize;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use strum::IntoEnumIterator;
+
+use crate::{
+    paths,
+    scan::{
+        bundles::OptionBundle,
+        model::TimingPerformance,
+        rate_presets::{self, RatePreset, parse_timing},
+    },
+    tui::theme::Theme,
+    webhook::WebhookKind,
+};
+
+/// Error type for failures loading the configuration file
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Invalid(String),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(err) => write!(f, "Failed to read config: {}", err),
+            ConfigError::Invalid(err) => write!(f, "Invalid config: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// User configuration, read from config.toml in the config directory
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub integrations: IntegrationsConfig,
+    pub hooks: HooksConfig,
+    pub notifications: NotificationsConfig,
+    pub webhook: WebhookConfig,
+    pub policy: PolicyConfig,
+    pub results: ResultsConfig,
+    pub enrichment: EnrichmentConfig,
+    pub ui: UiConfig,
+    pub layout: LayoutConfig,
+    pub logging: LoggingConfig,
+    pub rate_presets: RatePresetsConfig,
+    pub bundles: BundlesConfig,
+}
+
+/// Handoff of scan results to other tools
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IntegrationsConfig {
+    /// Command that receives web URLs on stdin; {file} expands to the URL file
+    pub web_command: Option<String>,
+    /// Command that opens a terminal pane; {command} expands to the nmap command line.
+    /// Defaults to a tmux or screen split when lazynmap runs inside one
+    pub pane_command: Option<String>,
+    /// Run scans in a new pane instead of the embedded output view
+    pub run_in_pane: bool,
+}
+
+/// Shell commands run when a scan finishes; {xml}, {targets} and {status} are expanded
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HooksConfig {
+    pub on_complete: Vec<String>,
+    pub on_failure: Vec<String>,
+}
+
+/// Alerts for scans that finish while the terminal is in the background
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationsConfig {
+    /// Ring the terminal bell
+    pub bell: bool,
+    /// Send a desktop notification
+    pub desktop: bool,
+    /// Also alert when the terminal is focused
+    pub when_focused: bool,
+}
+
+/// Webhook posted to when a scan completes
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    pub url: Option<String>,
+    pub kind: WebhookKind,
+}
+
+/// Location of the policy file, for teams that share one outside the config directory
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyConfig {
+    pub file: Option<PathBuf>,
+}
+
+/// How the results view presents hosts
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResultsConfig {
+    /// Prefix length used to group IPv4 hosts into subnets
+    pub subnet_prefix: u8,
+    /// Prefix length used to group IPv6 hosts into subnets
+    pub ipv6_subnet_prefix: u8,
+    /// Look up MAC vendors in the embedded OUI table when nmap doesn't report one
+    pub mac_vendor_lookup: bool,
+    /// Open the results view when a scan run from the editor finishes with XML output
+    pub auto_switch: bool,
+    /// Finished runs kept in the searchable history, the oldest removed first
+    pub history_limit: usize,
+}
+
+/// Extra facts about target and result addresses, looked up outside of nmap
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnrichmentConfig {
+    /// Show the AS and owner of public addresses, asking Team Cymru's whois service
+    pub asn_lookup: bool,
+    /// GeoLite2 ASN blocks CSV to look owners up offline instead; setting it turns lookups on
+    pub asn_database: Option<PathBuf>,
+}
+
+/// Appearance of the interface
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    /// Color scheme; NO_COLOR in the environment forces monochrome
+    pub theme: Theme,
+    /// Whether the editor shows every option or only the common ones
+    pub mode: UiMode,
+    /// How long a chord such as `g g` waits for its next key, in milliseconds
+    pub chord_timeout_ms: u64,
+}
+
+/// How many options the editor shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UiMode {
+    /// Only the most common options, with longer descriptions
+    Beginner,
+    #[default]
+    Expert,
+}
+
+impl UiMode {
+    pub fn toggle(self) -> Self {
+        match self {
+            UiMode::Beginner => UiMode::Expert,
+            UiMode::Expert => UiMode::Beginner,
+        }
+    }
+}
+
+impl std::fmt::Display for UiMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UiMode::Beginner => write!(f, "beginner"),
+            UiMode::Expert => write!(f, "expert"),
+        }
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            mode: UiMode::default(),
+            chord_timeout_ms: 1000,
+        }
+    }
+}
+
+/// Sizes of the panes in cells, changed at runtime with the resize keys
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LayoutConfig {
+    /// Width of the section list
+    pub sidebar_width: u16,
+    /// Height of the nmap command box; long commands wrap when it is taller than 3
+    pub footer_height: u16,
+    /// Height of the scan output pane
+    pub output_height: u16,
+    /// Width of the queue progress next to the scan output
+    pub queue_width: u16,
+    /// Show the scan output and results next to the editor on wide terminals
+    pub split_view: bool,
+}
+
+/// A pane whose size can be changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Sidebar,
+    Footer,
+    Output,
+    Queue,
+}
+
+impl LayoutConfig {
+    /// Grow or shrink a pane, within limits that leave room for the rest of the screen
+    pub fn resize(&mut self, pane: Pane, delta: i16) {
+        let (size, min, max) = match pane {
+            Pane::Sidebar => (&mut self.sidebar_width, 15, 60),
+            Pane::Footer => (&mut self.footer_height, 3, 10),
+            Pane::Output => (&mut self.output_height, 4, 40),
+            Pane::Queue => (&mut self.queue_width, 20, 100),
+        };
+        *size = size.saturating_add_signed(delta).clamp(min, max);
+    }
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            sidebar_width: 25,
+            footer_height: 3,
+            output_height: 10,
+            queue_width: 40,
+            split_view: false,
+        }
+    }
+}
+
+/// Diagnostic log shown in the debug console and optionally written to a file
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// Lowest level logged, or a filter such as "lazynmap=debug"; RUST_LOG overrides it
+    pub level: String,
+    /// File the log is appended to as well
+    pub file: Option<PathBuf>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            file: None,
+        }
+    }
+}
+
+/// Timing options set by the environment presets of the timing section, as nmap options
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RatePresetsConfig {
+    pub production_safe: String,
+    pub lab: String,
+    pub internet_survey: String,
+}
+
+impl Default for RatePresetsConfig {
+    fn default() -> Self {
+        Self {
+            production_safe: "-T2 --max-rate 100 --max-retries 1".to_string(),
+            lab: "-T4 --min-rate 1000".to_string(),
+            internet_survey: "-T4 --max-rate 1000 --max-retries 1 --host-timeout 10m".to_string(),
+        }
+    }
+}
+
+impl RatePresetsConfig {
+    pub fn options(&self, preset: RatePreset) -> &str {
+        match preset {
+            RatePreset::ProductionSafe => &self.production_safe,
+            RatePreset::Lab => &self.lab,
+            RatePreset::InternetSurvey => &self.internet_survey,
+        }
+    }
+
+    /// The timing options of a preset, none if they fail to parse
+    pub fn timing(&self, preset: RatePreset) -> TimingPerformance {
+        parse_timing(self.options(preset)).unwrap_or_default()
+    }
+
+    /// The preset the timing options were set with, if any
+    pub fn matching(&self, timing: &TimingPerformance) -> Option<RatePreset> {
+        RatePreset::iter().find(|preset| rate_presets::matches(timing, &self.timing(*preset)))
+    }
+}
+
+/// Options set by the quick toggles of the editor, as nmap options
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BundlesConfig {
+    pub udp_essentials: String,
+}
+
+impl Default for BundlesConfig {
+    fn default() -> Self {
+        Self {
+            udp_essentials: "-sU --top-ports 100 --version-intensity 0 --max-retries 1".to_string(),
+        }
+    }
+}
+
+impl Default for ResultsConfig {
+    fn default() -> Self {
+        Self {
+            subnet_prefix: 24,
+            ipv6_subnet_prefix: 64,
+            mac_vendor_lookup: true,
+            auto_switch: true,
+            history_limit: 100,
+        }
+    }
+}
+
+impl Config {
+    /// Load the user configuration, falling back to defaults when there is no config file
+    pub fn load() -> Result<Self, ConfigError> {
+        match paths::config_dir() {
+            Some(dir) => Self::load_from(&dir.join("config.toml")),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(ConfigError::Io(err)),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(content)
+            .map_err(|err| ConfigError::Invalid(err.message().to_string()))?;
+        for preset in RatePreset::iter() {
+            parse_timing(config.rate_presets.options(preset)).map_err(|err| {
+                ConfigError::Invalid(format!("rate_presets, {}: {}", preset, err))
+            })?;
+        }
+        OptionBundle::parse(&config.bundles.udp_essentials)
+            .map_err(|err| ConfigError::Invalid(format!("bundles, udp_essentials: {}", err)))?;
+        Ok(config)
+    }
+
+    /// Remember the UI mode in the user configuration file
+    pub fn save_ui_mode(mode: UiMode) -> Result<(), ConfigError> {
+        match paths::config_dir() {
+            Some(dir) => save_ui_mode_to(&dir.join("config.toml"), mode),
+            None => Ok(()),
+        }
+    }
+
+    /// Remember the pane sizes in the user configuration file
+    pub fn save_layout(layout: &LayoutConfig) -> Result<(), ConfigError> {
+        match paths::config_dir() {
+            Some(dir) => save_layout_to(&dir.join("config.toml"), layout),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Set `mode` in the [ui] table of a config file
+pub fn save_ui_mode_to(path: &Path, mode: UiMode) -> Result<(), ConfigError> {
+    save_values_to(
+        path,
+        "ui",
+        [("mode", toml_edit::Value::from(mode.to_string()))],
+    )
+}
+
+/// Set the pane sizes in the [layout] table of a config file
+pub fn save_layout_to(path: &Path, layout: &LayoutConfig) -> Result<(), ConfigError> {
+    let size = |value: u16| toml_edit::Value::from(i64::from(value));
+    save_values_to(
+        path,
+        "layout",
+        [
+            ("sidebar_width", size(layout.sidebar_width)),
+            ("footer_height", size(layout.footer_height)),
+            ("output_height", size(layout.output_height)),
+            ("queue_width", size(layout.queue_width)),
+            ("split_view", toml_edit::Value::from(layout.split_view)),
+        ],
+    )
+}
+
+/// Set values in a table of a config file, editing it in place to keep its other settings,
+/// formatting and comments
+fn save_values_to<const N: usize>(
+    path: &Path,
+    name: &str,
+    values: [(&str, toml_edit::Value); N],
+) -> Result<(), ConfigError> {
+    let mut document = match fs::read_to_string(path) {
+        Ok(content) => content
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|err| ConfigError::Invalid(err.message().to_string()))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => toml_edit::DocumentMut::new(),
+        Err(err) => return Err(ConfigError::Io(err)),
+    };
+    let Some(section) = document
+        .entry(name)
+        .or_insert_with(toml_edit::table)
+        .as_table_like_mut()
+    else {
+        return Err(ConfigError::Invalid(format!("{} is not a table", name)));
+    };
+    for (key, mut value) in values {
+        match section.get_mut(key).and_then(toml_edit::Item::as_value_mut) {
+            // Keep the comment following the old value
+            Some(old) => {
+                *value.decor_mut() = old.decor().clone();
+                *old = value;
+            }
+            None => {
+                section.insert(key, toml_edit::Item::Value(value));
+            }
+        }
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(ConfigError::Io)?;
+    }
+    fs::write(path, document.to_string()).map_err(ConfigError::Io)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r#"
+            [integrations]
+            web_command = "httpx -silent -o {file}.httpx"
+            pane_command = "tmux new-window -n nmap {command}"
+            run_in_pane = true
+
+            [hooks]
+            on_complete = ["notify-send 'Scan finished' {xml}"]
+
+            [notifications]
+            bell = true
+
+            [webhook]
+            url = "https://hooks.slack.com/services/T000/B000/XXXX"
+            kind = "slack"
+
+            [results]
+            subnet_prefix = 16
+            mac_vendor_lookup = false
+            auto_switch = false
+
+            [enrichment]
+            asn_database = "/usr/share/GeoLite2-ASN-Blocks-IPv4.csv"
+
+            [ui]
+            theme = "colorblind"
+            mode = "beginner"
+            chord_timeout_ms = 600
+
+            [logging]
+            level = "debug"
+            file = "/tmp/lazynmap.log"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.integrations.web_command.as_deref(),
+            Some("httpx -silent -o {file}.httpx")
+        );
+        assert_eq!(
+            config.integrations.pane_command.as_deref(),
+            Some("tmux new-window -n nmap {command}")
+        );
+        assert!(config.integrations.run_in_pane);
+        assert_eq!(
+            config.hooks.on_complete,
+            vec!["notify-send 'Scan finished' {xml}"]
+        );
+        assert!(config.hooks.on_failure.is_empty());
+        assert!(config.notifications.bell);
+        assert!(!config.notifications.desktop);
+        assert_eq!(config.webhook.kind, WebhookKind::Slack);
+        assert_eq!(config.results.subnet_prefix, 16);
+        assert_eq!(config.results.ipv6_subnet_prefix, 64);
+        assert!(!config.results.mac_vendor_lookup);
+        assert!(!config.results.auto_switch);
+        assert!(!config.enrichment.asn_lookup);
+        assert_eq!(
+            config.enrichment.asn_database,
+            Some(PathBuf::from("/usr/share/GeoLite2-ASN-Blocks-IPv4.csv"))
+        );
+        assert_eq!(config.ui.theme, Theme::Colorblind);
+        assert_eq!(config.ui.mode, UiMode::Beginner);
+        assert_eq!(config.ui.chord_timeout_ms, 600);
+        assert_eq!(config.logging.level, "debug");
+        assert_eq!(
+            config.logging.file,
+            Some(PathBuf::from("/tmp/lazynmap.log"))
+        );
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_unknown_fields() {
+        assert!(matches!(
+            Config::parse("[integrations]\nweb_comand = \"httpx\""),
+            Err(ConfigError::Invalid(_))
+        ));
+        assert!(matches!(
+            Config::parse("[rate_presets]\nlab = \"--max-rate fast\""),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_save_ui_mode() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-config-{}", std::process::id()));
+        let path = dir.join("config.toml");
+        save_ui_mode_to(&path, UiMode::Beginner).unwrap();
+        let created = Config::load_from(&path).unwrap();
+
+        fs::write(
+            &path,
+            "# Mine\n[notifications]\nbell = true\n\n[ui]\ntheme = \"monochrome\"\n\
+             mode = \"beginner\" # set by M\n",
+        )
+        .unwrap();
+        save_ui_mode_to(&path, UiMode::Expert).unwrap();
+        let updated = Config::load_from(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert!(content.starts_with("# Mine\n"));
+        assert!(content.contains("mode = \"expert\" # set by M\n"));
+        assert_eq!(created.ui.mode, UiMode::Beginner);
+        assert_eq!(updated.ui.mode, UiMode::Expert);
+        assert_eq!(updated.ui.theme, Theme::Monochrome);
+        assert!(updated.notifications.bell);
+    }
+
+    #[test]
+    fn test_save_layout() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-layout-{}", std::process::id()));
+        let path = dir.join("config.toml");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            &path,
+            "[layout]
+sidebar_width = 30
+
+[ui]
+mode = \"beginner\"
+",
+        )
+        .unwrap();
+        let mut layout = Config::load_from(&path).unwrap().layout;
+        assert_eq!(layout.sidebar_width, 30);
+        assert_eq!(layout.output_height, 10);
+
+        layout.resize(Pane::Output, 4);
+        layout.resize(Pane::Sidebar, -100);
+        layout.resize(Pane::Footer, 100);
+        layout.split_view = true;
+        save_layout_to(&path, &layout).unwrap();
+        let updated = Config::load_from(&path).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(updated.layout.output_height, 14);
+        assert_eq!(updated.layout.sidebar_width, 15);
+        assert_eq!(updated.layout.footer_height, 10);
+        assert!(updated.layout.split_view);
+        assert_eq!(updated.ui.mode, UiMode::Beginner);
+    }
+}
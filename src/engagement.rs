@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, fs, io, path::Path, time::Duration};
+
+use crate::{
+    results::model::{HostStatus, ScanResults},
+    scan::stats::RunStats,
+};
+
+/// Level of effort spent on an engagement, added up over the scans run since its clock started
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Engagement {
+    /// Unix time the clock started
+    pub started: u64,
+    pub scans: u32,
+    pub failed: u32,
+    /// Seconds nmap spent running
+    pub scanning_secs: u64,
+    /// Packets nmap reported sending and receiving, which it does with -v or --packet-trace
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    /// Addresses of the hosts found up
+    pub hosts: BTreeSet<String>,
+}
+
+impl Engagement {
+    pub fn new(started: u64) -> Self {
+        Self {
+            started,
+            ..Self::default()
+        }
+    }
+
+    pub fn load(path: &Path) -> Option<Self> {
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Add a finished scan, with the results it wrote if any
+    pub fn record(
+        &mut self,
+        completed: bool,
+        elapsed: Duration,
+        stats: &RunStats,
+        results: Option<&ScanResults>,
+    ) {
+        self.scans += 1;
+        if !completed {
+            self.failed += 1;
+        }
+        self.scanning_secs += elapsed.as_secs();
+        self.packets_sent += stats.packets_sent;
+        self.packets_received += stats.packets_received;
+        if let Some(results) = results {
+            self.hosts.extend(
+                results
+                    .hosts
+                    .iter()
+                    .filter(|host| host.status == HostStatus::Up)
+                    .map(|host| host.address().to_string()),
+            );
+        }
+    }
+
+    pub fn scanning(&self) -> Duration {
+        Duration::from_secs(self.scanning_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser;
+
+    #[test]
+    fn test_record() {
+        let results = parser::parse_xml(
+            r#"<nmaprun>
+<host><status state="up"/><address addr="10.0.0.1" addrtype="ipv4"/></host>
+<host><status state="down"/><address addr="10.0.0.2" addrtype="ipv4"/></host>
+</nmaprun>"#,
+        )
+        .unwrap();
+        let stats = RunStats {
+            packets_sent: 2000,
+            packets_received: 1003,
+            ..RunStats::default()
+        };
+        let mut engagement = Engagement::new(1_700_000_000);
+        engagement.record(true, Duration::from_secs(90), &stats, Some(&results));
+        engagement.record(false, Duration::from_secs(30), &stats, Some(&results));
+        engagement.record(true, Duration::from_secs(5), &RunStats::default(), None);
+
+        assert_eq!(engagement.scans, 3);
+        assert_eq!(engagement.failed, 1);
+        assert_eq!(engagement.scanning(), Duration::from_secs(125));
+        assert_eq!(engagement.packets_sent, 4000);
+        assert_eq!(engagement.packets_received, 2006);
+        assert_eq!(engagement.hosts.iter().collect::<Vec<_>>(), ["10.0.0.1"]);
+
+        let path = std::env::temp_dir()
+            .join(format!("lazynmap-engagement-{}", std::process::id()))
+            .join("engagement.json");
+        engagement.save(&path).unwrap();
+        assert_eq!(Engagement::load(&path), Some(engagement));
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+}
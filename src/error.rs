@@ -0,0 +1,59 @@
+use std::io;
+
+use thiserror::Error;
+
+use crate::{config::ConfigError, scan::parser::ParseError};
+
+/// Error type for failures of the app and the nmap runner
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Parse(#[from] ParseError),
+    /// The nmap process could not be started
+    #[error("Failed to start nmap: {0}")]
+    Nmap(#[source] io::Error),
+    #[error("{0}")]
+    Config(#[from] ConfigError),
+    /// Drawing to or reading events from the terminal failed
+    #[error("Terminal error: {0}")]
+    Terminal(#[source] io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Whether trying again may succeed, as when a call was interrupted or a file was busy
+    pub fn is_transient(&self) -> bool {
+        let (Error::Io(err) | Error::Nmap(err) | Error::Terminal(err)) = self else {
+            return false;
+        };
+        matches!(
+            err.kind(),
+            io::ErrorKind::Interrupted
+                | io::ErrorKind::WouldBlock
+                | io::ErrorKind::TimedOut
+                | io::ErrorKind::ResourceBusy
+                | io::ErrorKind::ExecutableFileBusy
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient() {
+        let busy = Error::Nmap(io::Error::from(io::ErrorKind::ExecutableFileBusy));
+        assert!(busy.is_transient());
+        assert_eq!(
+            busy.to_string(),
+            "Failed to start nmap: executable file busy"
+        );
+        assert!(!Error::Nmap(io::Error::from(io::ErrorKind::NotFound)).is_transient());
+        assert!(!Error::from(ParseError::MissingValue("-p".to_string())).is_transient());
+        assert!(Error::Terminal(io::Error::from(io::ErrorKind::Interrupted)).is_transient());
+    }
+}
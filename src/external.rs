@@ -0,0 +1,144 @@
+use std::{
+    env,
+    io::{self, Write},
+    process::{Command, Stdio},
+    thread,
+};
+
+/// Substitute `{name}` placeholders in a command template with shell-quoted values
+pub fn expand_placeholders(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |command, (name, value)| {
+            command.replace(&format!("{{{}}}", name), &shell_quote(value))
+        })
+}
+
+/// Quote a value for safe use as a single POSIX shell word
+pub fn shell_quote(value: &str) -> String {
+    let is_safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if is_safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Template opening a new pane in the tmux or screen session lazynmap runs in, if any. Panes
+/// start with the environment of the multiplexer, so the given variables are handed over
+pub fn multiplexer_pane_command(variables: &[String]) -> Option<String> {
+    if env::var_os("TMUX").is_some() {
+        let options = variables
+            .iter()
+            .map(|name| format!("-e \"{}=${}\" ", name, name))
+            .collect::<String>();
+        Some(format!("tmux split-window -h {}{{command}}", options))
+    } else if env::var_os("STY").is_some() {
+        let setenv = variables
+            .iter()
+            .map(|name| format!("screen -X setenv {} \"${}\"; ", name, name))
+            .collect::<String>();
+        Some(format!("{}screen -X screen sh -c {{command}}", setenv))
+    } else {
+        None
+    }
+}
+
+/// Shell command that runs nmap and keeps its output on screen until Enter is pressed
+pub fn held_command(command: &str) -> String {
+    format!(
+        "{}; printf '\\nnmap exited with status %s, press Enter to close' \"$?\"; read _",
+        command
+    )
+}
+
+/// Run a shell command in the background with the given environment variables, feeding it the
+/// given input on stdin
+pub fn spawn_shell(
+    command: &str,
+    input: Option<String>,
+    variables: &[(String, String)],
+) -> io::Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .envs(variables.iter().map(|(name, value)| (name, value)))
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let stdin = child.stdin.take();
+    thread::spawn(move || {
+        if let (Some(mut stdin), Some(input)) = (stdin, input) {
+            let _ = stdin.write_all(input.as_bytes());
+        }
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// Open a URL with the default handler of the desktop
+pub fn open_url(url: &str) -> io::Result<()> {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+    let mut child = command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_placeholders() {
+        assert_eq!(
+            expand_placeholders(
+                "httpx -l {file} -o {file}.out",
+                &[("file", "/tmp/my scans/urls.txt")]
+            ),
+            "httpx -l '/tmp/my scans/urls.txt' -o '/tmp/my scans/urls.txt'.out"
+        );
+        assert_eq!(
+            expand_placeholders("cat {file}", &[("file", "/tmp/urls.txt")]),
+            "cat /tmp/urls.txt"
+        );
+    }
+
+    #[test]
+    fn test_held_command() {
+        assert_eq!(
+            expand_placeholders(
+                "tmux split-window -h {command}",
+                &[("command", &held_command("nmap -sT 10.0.0.1"))]
+            ),
+            "tmux split-window -h 'nmap -sT 10.0.0.1; printf '\\''\\nnmap exited with status %s, press Enter to close'\\'' \"$?\"; read _'"
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+}
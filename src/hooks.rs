@@ -0,0 +1,175 @@
+use std::{
+    fs, io,
+    path::{self, Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+use crate::{
+    config::HooksConfig,
+    external::{expand_placeholders, spawn_shell},
+    paths,
+    scan::{model::NmapScan, session::SessionStatus},
+};
+
+/// Run the hooks configured for a finished scan in the background, sending the commands that
+/// failed to start once every hook was tried
+pub fn run_hooks(
+    config: &HooksConfig,
+    scan: &NmapScan,
+    status: SessionStatus,
+) -> Option<Receiver<Vec<String>>> {
+    let commands = commands(config, status).to_vec();
+    if commands.is_empty() {
+        return None;
+    }
+
+    let scan = scan.clone();
+    let cache_dir = paths::cache_dir();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let targets = cache_dir.and_then(|dir| targets_file(&scan, &dir));
+        let failed = expand_commands(&commands, &scan, targets.as_deref(), status)
+            .into_iter()
+            .filter(|command| match spawn_shell(command, None, &[]) {
+                Ok(_) => {
+                    tracing::debug!(%command, "Started hook");
+                    false
+                }
+                Err(err) => {
+                    tracing::warn!(%command, %err, "Failed to run hook");
+                    true
+                }
+            })
+            .collect();
+        sender.send(failed).ok();
+    });
+    Some(receiver)
+}
+
+/// Hooks to run for a scan that ended with the given status
+fn commands(config: &HooksConfig, status: SessionStatus) -> &[String] {
+    match status {
+        SessionStatus::Completed => &config.on_complete,
+        SessionStatus::Failed => &config.on_failure,
+        SessionStatus::Running | SessionStatus::Interrupted => &[],
+    }
+}
+
+/// The hook commands with {xml}, {targets} and {status} filled in
+fn expand_commands(
+    commands: &[String],
+    scan: &NmapScan,
+    targets: Option<&Path>,
+    status: SessionStatus,
+) -> Vec<String> {
+    let xml = scan
+        .xml_output()
+        .map(|path| path::absolute(&path).unwrap_or(path))
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let targets = targets
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_default();
+    commands
+        .iter()
+        .map(|command| {
+            expand_placeholders(
+                command,
+                &[
+                    ("xml", &xml),
+                    ("targets", &targets),
+                    ("status", status.as_str()),
+                ],
+            )
+        })
+        .collect()
+}
+
+/// File listing the scan targets, one per line, for the {targets} placeholder, written to `dir`
+/// unless the -iL file lists them all
+fn targets_file(scan: &NmapScan, dir: &Path) -> Option<PathBuf> {
+    let ts = &scan.target_specification;
+    if ts.targets.is_empty() {
+        return ts.input_file.clone();
+    }
+
+    let path = dir.join("targets.txt");
+    let mut content = ts.targets.join("\n");
+    content.push('\n');
+    if let Some(input_file) = &ts.input_file {
+        content.push_str(&fs::read_to_string(input_file).unwrap_or_default());
+    }
+    write_file(&path, &content).ok()?;
+    Some(path)
+}
+
+fn write_file(path: &Path, content: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands() {
+        let config = HooksConfig {
+            on_complete: vec!["notify-send done".to_string()],
+            on_failure: vec!["notify-send failed".to_string()],
+        };
+        assert_eq!(
+            commands(&config, SessionStatus::Completed),
+            ["notify-send done"]
+        );
+        assert_eq!(
+            commands(&config, SessionStatus::Failed),
+            ["notify-send failed"]
+        );
+        assert!(commands(&config, SessionStatus::Interrupted).is_empty());
+        assert!(commands(&config, SessionStatus::Running).is_empty());
+    }
+
+    #[test]
+    fn test_expand_commands() {
+        let mut scan = NmapScan::new();
+        scan.output.xml = Some(PathBuf::from("/srv/my scans/out.xml"));
+        let commands = ["report {xml} {targets} {status}".to_string()];
+        assert_eq!(
+            expand_commands(
+                &commands,
+                &scan,
+                Some(Path::new("/tmp/targets.txt")),
+                SessionStatus::Completed
+            ),
+            ["report '/srv/my scans/out.xml' /tmp/targets.txt completed"]
+        );
+        scan.output.xml = None;
+        assert_eq!(
+            expand_commands(&commands, &scan, None, SessionStatus::Failed),
+            ["report '' '' failed"]
+        );
+    }
+
+    #[test]
+    fn test_targets_file() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-hooks-{}", std::process::id()));
+        let input_file = dir.join("input.txt");
+        write_file(&input_file, "10.0.1.0/24\n").unwrap();
+
+        let mut scan = NmapScan::new();
+        scan.target_specification.input_file = Some(input_file.clone());
+        let only_input = targets_file(&scan, &dir.join("cache"));
+        scan.target_specification.targets = vec!["10.0.0.1".to_string(), "10.0.0.2".to_string()];
+        let written = targets_file(&scan, &dir.join("cache")).unwrap();
+        let content = fs::read_to_string(&written).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(only_input, Some(input_file));
+        assert_eq!(written, dir.join("cache").join("targets.txt"));
+        assert_eq!(content, "10.0.0.1\n10.0.0.2\n10.0.1.0/24\n");
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    fs::{self, OpenOptions},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{
+    EnvFilter, fmt as format, layer::Context, layer::Layer, prelude::*, registry,
+};
+
+use crate::config::{ConfigError, LoggingConfig};
+
+/// Events kept for the debug console; older ones are dropped
+const CAPACITY: usize = 500;
+
+/// A log event as the debug console shows it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// Time since logging started
+    pub elapsed: Duration,
+    pub level: Level,
+    pub target: String,
+    /// The message followed by the other fields of the event, as name=value
+    pub message: String,
+}
+
+/// Recent log events, shared by the subscriber that records them and the console that shows them
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<VecDeque<LogEntry>>>);
+
+impl LogBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&self, entry: LogEntry) {
+        let Ok(mut entries) = self.0.lock() else {
+            return;
+        };
+        if entries.len() >= CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// The last `count` events, oldest first
+    pub fn recent(&self, count: usize) -> Vec<LogEntry> {
+        let Ok(entries) = self.0.lock() else {
+            return Vec::new();
+        };
+        entries
+            .iter()
+            .skip(entries.len().saturating_sub(count))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Layer recording every event into a `LogBuffer`
+struct RingLayer {
+    buffer: LogBuffer,
+    started: Instant,
+}
+
+impl<S: Subscriber> Layer<S> for RingLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.buffer.push(LogEntry {
+            elapsed: self.started.elapsed(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+        });
+    }
+}
+
+/// Collects the fields of an event into one line
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{}", value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            write!(self.message, "{:?}", value).ok();
+        } else {
+            write!(self.message, " {}={:?}", field.name(), value).ok();
+        }
+    }
+}
+
+/// Send log events to the returned buffer, and to the log file when the config names one.
+/// RUST_LOG takes precedence over the configured level
+pub fn init(config: &LoggingConfig) -> Result<LogBuffer, ConfigError> {
+    let filter = match EnvFilter::try_from_default_env() {
+        Ok(filter) => filter,
+        Err(_) => EnvFilter::try_new(&config.level)
+            .map_err(|err| ConfigError::Invalid(format!("logging level: {}", err)))?,
+    };
+    let file_layer = match &config.file {
+        Some(path) => {
+            if let Some(parent) = path.parent()
+                && !parent.as_os_str().is_empty()
+            {
+                fs::create_dir_all(parent).map_err(ConfigError::Io)?;
+            }
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(ConfigError::Io)?;
+            Some(
+                format::layer()
+                    .with_writer(Mutex::new(file))
+                    .with_ansi(false),
+            )
+        }
+        None => None,
+    };
+
+    let buffer = LogBuffer::new();
+    let ring = RingLayer {
+        buffer: buffer.clone(),
+        started: Instant::now(),
+    };
+    // Fails only when a subscriber is already set, in which case it keeps the events
+    registry()
+        .with(filter)
+        .with(ring)
+        .with(file_layer)
+        .try_init()
+        .ok();
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_layer() {
+        let buffer = LogBuffer::new();
+        let ring = RingLayer {
+            buffer: buffer.clone(),
+            started: Instant::now(),
+        };
+        tracing::subscriber::with_default(registry().with(ring), || {
+            tracing::info!(code = 0, "nmap exited");
+            for line in 0..CAPACITY {
+                tracing::debug!(line, "output");
+            }
+        });
+
+        let recent = buffer.recent(2);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[1].level, Level::DEBUG);
+        assert_eq!(recent[1].message, format!("output line={}", CAPACITY - 1));
+        let all = buffer.recent(usize::MAX);
+        assert_eq!(all.len(), CAPACITY);
+        assert_eq!(all[0].message, "output line=0");
+    }
+
+    #[test]
+    fn test_message_fields() {
+        let buffer = LogBuffer::new();
+        let ring = RingLayer {
+            buffer: buffer.clone(),
+            started: Instant::now(),
+        };
+        tracing::subscriber::with_default(registry().with(ring), || {
+            tracing::warn!(path = "out.xml", code = 2, "nmap exited");
+        });
+        let entry = &buffer.recent(1)[0];
+        assert_eq!(entry.message, "nmap exited path=out.xml code=2");
+        assert_eq!(entry.level, Level::WARN);
+        assert!(entry.target.ends_with("logging::tests"));
+    }
+}
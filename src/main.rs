@@ -1,13 +1,59 @@
-use std::error::Error;
+use std::{env, error::Error, process};
 
+pub mod asn;
+pub mod cli;
+pub mod config;
+pub mod engagement;
+pub mod error;
+pub mod external;
+pub mod hooks;
+pub mod logging;
+pub mod monitor;
+pub mod notifications;
+pub mod paths;
+pub mod policy;
+pub mod profiles;
+pub mod results;
 pub mod scan;
+pub mod scope;
 pub mod tui;
+pub mod webhook;
 
-use scan::model::NmapScan;
+use cli::{Args, CliError, USAGE};
+use config::Config;
+use policy::Policy;
+use scan::parser::NmapParser;
+use scope::Scope;
 use tui::app::App;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut scan = NmapScan::new();
-    App::new(&mut scan).start()?;
+    let args = Args::parse(env::args().skip(1)).unwrap_or_else(|err| exit_with(err));
+    if args.help {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+
+    let mut scan = args.scan().unwrap_or_else(|err| exit_with(err));
+    if args.dump_json {
+        for (legacy, modern) in NmapParser::legacy_flags(&args.command.join(" ")) {
+            eprintln!("{} is deprecated, using {}", legacy, modern);
+        }
+        println!("{}", serde_json::to_string_pretty(&scan)?);
+        return Ok(());
+    }
+
+    let config = Config::load()?;
+    let logs = logging::init(&config.logging)?;
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "lazynmap started");
+    let policy = Policy::load(&config.policy)?;
+    let scope = Scope::load(&env::current_dir()?)?;
+    App::new(&mut scan, config, policy, scope)
+        .with_logs(logs)
+        .start()?;
     Ok(())
 }
+
+fn exit_with(err: CliError) -> ! {
+    eprintln!("{}", err);
+    process::exit(2);
+}
@@ -0,0 +1,157 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    paths,
+    profiles::Profile,
+    results::{
+        diff::{PortChange, TimelineEvent, diff, timeline},
+        model::ScanResults,
+        parser,
+        snapshots::SnapshotStore,
+    },
+    scan::model::NmapScan,
+};
+
+/// Re-runs a profile at a fixed interval and keeps the results of every run
+pub struct Monitor {
+    name: String,
+    scan: NmapScan,
+    interval: Duration,
+    store: Option<SnapshotStore>,
+    snapshots: Vec<(u64, ScanResults)>,
+    next_run: Option<Instant>,
+    runs: usize,
+    last_changes: Vec<PortChange>,
+    last_error: Option<String>,
+}
+
+impl Monitor {
+    /// Start monitoring a profile, continuing the timeline of earlier monitors of the same profile
+    pub fn new(profile: Profile, interval: Duration) -> Self {
+        let mut scan = profile.scan;
+        if scan.xml_output().is_none() {
+            scan.output.xml = Some(Self::work_file(&profile.name));
+        }
+        let store = paths::data_dir()
+            .map(|dir| SnapshotStore::new(dir.join("results").join("monitor").join(&profile.name)));
+        let snapshots = store.as_ref().map(SnapshotStore::load).unwrap_or_default();
+
+        Self {
+            name: profile.name,
+            scan,
+            interval,
+            store,
+            snapshots,
+            next_run: Some(Instant::now()),
+            runs: 0,
+            last_changes: Vec::new(),
+            last_error: None,
+        }
+    }
+
+    /// XML output written by runs of a profile that doesn't save XML itself
+    fn work_file(name: &str) -> PathBuf {
+        env::temp_dir().join(format!(
+            "lazynmap-monitor-{}-{}.xml",
+            name,
+            std::process::id()
+        ))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn scan(&self) -> &NmapScan {
+        &self.scan
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+
+    pub fn last_changes(&self) -> &[PortChange] {
+        &self.last_changes
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Whether a run has been started and not finished yet
+    pub fn is_running(&self) -> bool {
+        self.next_run.is_none()
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.next_run
+            .is_some_and(|next_run| Instant::now() >= next_run)
+    }
+
+    /// Time left until the next run, or None while a run is in progress
+    pub fn next_run_in(&self) -> Option<Duration> {
+        self.next_run
+            .map(|next_run| next_run.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn start_run(&mut self) {
+        self.next_run = None;
+        self.runs += 1;
+    }
+
+    /// Record the outcome of the current run and schedule the next one, returning the ports that changed
+    pub fn finish_run(&mut self, completed: bool) -> Result<Vec<PortChange>, String> {
+        self.next_run = Some(Instant::now() + self.interval);
+        let result = if completed {
+            self.record()
+        } else {
+            Err("Scan did not complete".to_string())
+        };
+        match &result {
+            Ok(changes) => {
+                self.last_changes = changes.clone();
+                self.last_error = None;
+            }
+            Err(err) => self.last_error = Some(err.clone()),
+        }
+        result
+    }
+
+    fn record(&mut self) -> Result<Vec<PortChange>, String> {
+        let xml = self
+            .scan
+            .xml_output()
+            .ok_or_else(|| "Scan has no XML output".to_string())?;
+        let (taken, results) = match &self.store {
+            Some(store) => store.add(&xml),
+            None => parser::load(&xml).map(|results| (results.start.unwrap_or_default(), results)),
+        }
+        .map_err(|err| err.to_string())?;
+
+        let changes = match self.snapshots.last() {
+            Some((_, previous)) => diff(previous, &results),
+            None => Vec::new(),
+        };
+        self.snapshots.push((taken, results));
+        Ok(changes)
+    }
+
+    /// When each port of each host opened or closed across all stored runs
+    pub fn timeline(&self) -> BTreeMap<String, Vec<TimelineEvent>> {
+        timeline(&self.snapshots)
+    }
+
+    /// Number of stored runs, including those of earlier monitors of the profile
+    pub fn snapshot_count(&self) -> usize {
+        self.snapshots.len()
+    }
+}
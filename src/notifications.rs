@@ -0,0 +1,28 @@
+use std::{
+    io::{self, Write},
+    thread,
+};
+
+use notify_rust::Notification;
+
+use crate::config::NotificationsConfig;
+
+/// Alert the user that a scan has finished, as configured
+pub fn notify(config: &NotificationsConfig, summary: &str, body: &str) {
+    if config.bell {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+    }
+    if config.desktop {
+        let summary = summary.to_string();
+        let body = body.to_string();
+        // Talking to the notification daemon can block, so keep it off the UI thread
+        thread::spawn(move || {
+            let _ = Notification::new()
+                .appname("lazynmap")
+                .summary(&summary)
+                .body(&body)
+                .show();
+        });
+    }
+}
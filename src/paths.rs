@@ -0,0 +1,187 @@
+use std::env;
+use std::path::PathBuf;
+
+const APP_NAME: &str = "lazynmap";
+
+/// Directory for user configuration
+pub fn config_dir() -> Option<PathBuf> {
+    Kind::Config.dir()
+}
+
+/// Directory for user data such as saved profiles
+pub fn data_dir() -> Option<PathBuf> {
+    Kind::Data.dir()
+}
+
+/// Directory for state that should persist between sessions
+pub fn state_dir() -> Option<PathBuf> {
+    Kind::State.dir()
+}
+
+/// Directory for files that can be regenerated, such as target lists handed to hooks
+pub fn cache_dir() -> Option<PathBuf> {
+    Kind::Cache.dir()
+}
+
+/// Conventions for where applications keep their files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Platform {
+    /// The XDG base directories, used on Linux and other Unix systems
+    Unix,
+    MacOs,
+    Windows,
+}
+
+impl Platform {
+    fn current() -> Self {
+        if cfg!(windows) {
+            Platform::Windows
+        } else if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else {
+            Platform::Unix
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Config,
+    Data,
+    State,
+    Cache,
+}
+
+impl Kind {
+    fn dir(self) -> Option<PathBuf> {
+        self.resolve(Platform::current(), |name| {
+            env::var_os(name)
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+        })
+    }
+
+    /// Variable naming the directory itself, ahead of any convention
+    fn override_var(self) -> &'static str {
+        match self {
+            Kind::Config => "LAZYNMAP_CONFIG_DIR",
+            Kind::Data => "LAZYNMAP_DATA_DIR",
+            Kind::State => "LAZYNMAP_STATE_DIR",
+            Kind::Cache => "LAZYNMAP_CACHE_DIR",
+        }
+    }
+
+    fn xdg_var(self) -> &'static str {
+        match self {
+            Kind::Config => "XDG_CONFIG_HOME",
+            Kind::Data => "XDG_DATA_HOME",
+            Kind::State => "XDG_STATE_HOME",
+            Kind::Cache => "XDG_CACHE_HOME",
+        }
+    }
+
+    /// Base directory under the home directory when its XDG variable is unset
+    fn xdg_default(self) -> &'static str {
+        match self {
+            Kind::Config => ".config",
+            Kind::Data => ".local/share",
+            Kind::State => ".local/state",
+            Kind::Cache => ".cache",
+        }
+    }
+
+    /// The directory on a platform, given how to read an environment variable. XDG variables
+    /// are honored on macOS too, where command-line users often set them
+    fn resolve(self, platform: Platform, var: impl Fn(&str) -> Option<PathBuf>) -> Option<PathBuf> {
+        if let Some(dir) = var(self.override_var()) {
+            return Some(dir);
+        }
+        if platform != Platform::Windows
+            && let Some(base) = var(self.xdg_var())
+        {
+            return Some(base.join(APP_NAME));
+        }
+        let base = match platform {
+            Platform::Unix => var("HOME")?.join(self.xdg_default()),
+            Platform::MacOs => {
+                let library = var("HOME")?.join("Library");
+                match self {
+                    Kind::Cache => library.join("Caches"),
+                    _ => library.join("Application Support"),
+                }
+            }
+            Platform::Windows => match self {
+                Kind::Config | Kind::Data => var("APPDATA")?,
+                Kind::State | Kind::Cache => var("LOCALAPPDATA")?,
+            },
+        };
+        let dir = base.join(APP_NAME);
+        // State and cache share the local app data directory on Windows
+        Some(match (platform, self) {
+            (Platform::Windows, Kind::Cache) => dir.join("cache"),
+            _ => dir,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(set: &[(&str, &str)]) -> impl Fn(&str) -> Option<PathBuf> {
+        let set = set
+            .iter()
+            .map(|(name, value)| (name.to_string(), PathBuf::from(value)))
+            .collect::<Vec<_>>();
+        move |name| {
+            set.iter()
+                .find(|(set_name, _)| set_name == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let home = vars(&[("HOME", "/home/ada")]);
+        assert_eq!(
+            Kind::State.resolve(Platform::Unix, &home),
+            Some(PathBuf::from("/home/ada/.local/state/lazynmap"))
+        );
+        assert_eq!(
+            Kind::Cache.resolve(Platform::MacOs, &home),
+            Some(PathBuf::from("/home/ada/Library/Caches/lazynmap"))
+        );
+        assert_eq!(
+            Kind::Config.resolve(Platform::MacOs, &home),
+            Some(PathBuf::from(
+                "/home/ada/Library/Application Support/lazynmap"
+            ))
+        );
+
+        let xdg = vars(&[("HOME", "/home/ada"), ("XDG_CONFIG_HOME", "/etc/xdg")]);
+        assert_eq!(
+            Kind::Config.resolve(Platform::MacOs, &xdg),
+            Some(PathBuf::from("/etc/xdg/lazynmap"))
+        );
+        let windows = vars(&[
+            ("XDG_CONFIG_HOME", "/etc/xdg"),
+            ("APPDATA", "C:/Users/ada/AppData/Roaming"),
+            ("LOCALAPPDATA", "C:/Users/ada/AppData/Local"),
+        ]);
+        assert_eq!(
+            Kind::Config.resolve(Platform::Windows, &windows),
+            Some(PathBuf::from("C:/Users/ada/AppData/Roaming/lazynmap"))
+        );
+        assert_eq!(
+            Kind::Cache.resolve(Platform::Windows, &windows),
+            Some(PathBuf::from("C:/Users/ada/AppData/Local/lazynmap/cache"))
+        );
+
+        let overridden = vars(&[("HOME", "/home/ada"), ("LAZYNMAP_DATA_DIR", "/srv/nmap")]);
+        assert_eq!(
+            Kind::Data.resolve(Platform::Unix, &overridden),
+            Some(PathBuf::from("/srv/nmap"))
+        );
+        assert_eq!(Kind::Data.resolve(Platform::Unix, vars(&[])), None);
+    }
+}
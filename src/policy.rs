@@ -0,0 +1,180 @@
+use serde::Deserialize;
+use std::{fs, io, path::Path};
+
+use crate::{
+    config::{ConfigError, PolicyConfig},
+    paths,
+    scan::{
+        compare::{options, split_attached_value},
+        model::NmapScan,
+        parser::{NmapParser, modern_flag},
+    },
+};
+
+/// Options a team forbids or requires, read from policy.toml in the config directory
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    pub forbid: Vec<PolicyRule>,
+    pub require: Vec<PolicyRule>,
+}
+
+/// A single option as it appears in the nmap command, e.g. "-T5" or "--exclude-file corp.txt"
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyRule {
+    pub option: String,
+    pub reason: Option<String>,
+}
+
+/// A policy rule broken by a scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub message: String,
+    pub reason: Option<String>,
+}
+
+impl PolicyRule {
+    /// Whether the scan contains this option; a rule without a value matches any value
+    fn matches(&self, scan_options: &[(String, String)]) -> bool {
+        let mut tokens = NmapParser::tokenize(&self.option).into_iter();
+        let Some(first) = tokens.next() else {
+            return false;
+        };
+        let (flag, attached) = split_attached_value(&first);
+        // Rules written with a legacy option still match the modern one the builder emits
+        let flag = modern_flag(&flag).into_owned();
+        let value = if attached.is_empty() {
+            tokens.collect::<Vec<_>>().join(" ")
+        } else {
+            attached
+        };
+
+        scan_options.iter().any(|(other, other_value)| {
+            *other == flag && (value.is_empty() || *other_value == value)
+        })
+    }
+}
+
+impl Policy {
+    /// Load the policy file named in the config, or policy.toml in the config directory if present
+    pub fn load(config: &PolicyConfig) -> Result<Self, ConfigError> {
+        if let Some(file) = &config.file {
+            return fs::read_to_string(file)
+                .map_err(ConfigError::Io)
+                .and_then(|content| Self::parse(&content));
+        }
+        match paths::config_dir() {
+            Some(dir) => Self::load_from(&dir.join("policy.toml")),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(ConfigError::Io(err)),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        let policy: Self = toml::from_str(content)
+            .map_err(|err| ConfigError::Invalid(err.message().to_string()))?;
+        if let Some(rule) = policy
+            .forbid
+            .iter()
+            .chain(&policy.require)
+            .find(|rule| rule.option.trim().is_empty())
+        {
+            return Err(ConfigError::Invalid(format!(
+                "policy rule has no option{}",
+                rule.reason
+                    .as_ref()
+                    .map(|reason| format!(" ({})", reason))
+                    .unwrap_or_default()
+            )));
+        }
+        Ok(policy)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.forbid.is_empty() && self.require.is_empty()
+    }
+
+    /// List the rules the scan breaks
+    pub fn check(&self, scan: &NmapScan) -> Vec<Violation> {
+        let scan_options = options(scan);
+        let forbidden = self
+            .forbid
+            .iter()
+            .filter(|rule| rule.matches(&scan_options))
+            .map(|rule| Violation {
+                message: format!("{} is forbidden by policy", rule.option),
+                reason: rule.reason.clone(),
+            });
+        let missing = self
+            .require
+            .iter()
+            .filter(|rule| !rule.matches(&scan_options))
+            .map(|rule| Violation {
+                message: format!("{} is required by policy", rule.option),
+                reason: rule.reason.clone(),
+            });
+        forbidden.chain(missing).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"
+        [[forbid]]
+        option = "-T5"
+        reason = "Insane timing drops packets on client networks"
+
+        [[forbid]]
+        option = "--script"
+
+        [[require]]
+        option = "--exclude-file corp-exclusions.txt"
+    "#;
+
+    #[test]
+    fn test_check() {
+        let policy = Policy::parse(POLICY).unwrap();
+
+        let scan = NmapParser::parse("nmap -T5 --script vuln 10.0.0.1").unwrap();
+        let messages: Vec<String> = policy
+            .check(&scan)
+            .into_iter()
+            .map(|violation| violation.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "-T5 is forbidden by policy",
+                "--script is forbidden by policy",
+                "--exclude-file corp-exclusions.txt is required by policy",
+            ]
+        );
+
+        let scan =
+            NmapParser::parse("nmap -T4 --exclude-file corp-exclusions.txt 10.0.0.1").unwrap();
+        assert!(policy.check(&scan).is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Policy::parse("").unwrap().is_empty());
+        assert!(matches!(
+            Policy::parse("[[forbid]]\nreason = \"no option\""),
+            Err(ConfigError::Invalid(_))
+        ));
+        assert!(matches!(
+            Policy::parse("[[forbid]]\noptoin = \"-T5\""),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+}
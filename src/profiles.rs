@@ -0,0 +1,178 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::scan::{model::NmapScan, parser::NmapParser, secrets};
+
+const PROFILE_EXTENSION: &str = "toml";
+
+/// Presets available even when no profiles have been saved
+const BUILTIN_PROFILES: [(&str, &str); 3] = [
+    ("quick", "nmap -sS -F -T4"),
+    ("thorough", "nmap -sS -p - -sV -sC -O -T4 --reason"),
+    ("stealth", "nmap -Pn -sS -T2 -f --data-length 24"),
+];
+
+/// A named set of scan options
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub name: String,
+    pub scan: NmapScan,
+    pub builtin: bool,
+}
+
+/// Saved profiles, stored as one TOML file per profile
+pub struct ProfileStore {
+    dir: Option<PathBuf>,
+    profiles: Vec<Profile>,
+}
+
+impl ProfileStore {
+    /// Load saved profiles from a directory, followed by any built-in presets they don't override
+    pub fn load(dir: Option<PathBuf>) -> Self {
+        let mut profiles: Vec<Profile> = dir
+            .as_deref()
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| Self::load_profile(&entry.ok()?.path()))
+            .collect();
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for (name, command) in BUILTIN_PROFILES {
+            if !profiles.iter().any(|profile| profile.name == name)
+                && let Ok(scan) = NmapParser::parse(command)
+            {
+                profiles.push(Profile {
+                    name: name.to_string(),
+                    scan,
+                    builtin: true,
+                });
+            }
+        }
+
+        Self { dir, profiles }
+    }
+
+    fn load_profile(path: &Path) -> Option<Profile> {
+        if path.extension()? != PROFILE_EXTENSION {
+            return None;
+        }
+        let content = fs::read_to_string(path).ok()?;
+        Some(Profile {
+            name: path.file_stem()?.to_string_lossy().to_string(),
+            scan: toml::from_str(&content).ok()?,
+            builtin: false,
+        })
+    }
+
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Save a scan under the given name, replacing any profile with the same name. Secrets
+    /// typed into its script arguments are left out, to be asked for when it runs
+    pub fn save(&mut self, name: &str, scan: &NmapScan) -> io::Result<()> {
+        let path = self.path(name)?;
+        let scan = &secrets::stripped(scan);
+        let content = toml::to_string_pretty(scan)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+
+        let profile = Profile {
+            name: name.to_string(),
+            scan: scan.clone(),
+            builtin: false,
+        };
+        self.profiles.retain(|profile| profile.name != name);
+        let index = self
+            .profiles
+            .iter()
+            .position(|existing| existing.builtin || existing.name > profile.name)
+            .unwrap_or(self.profiles.len());
+        self.profiles.insert(index, profile);
+        Ok(())
+    }
+
+    /// Delete a saved profile; built-in presets cannot be deleted
+    pub fn delete(&mut self, name: &str) -> io::Result<()> {
+        if self.get(name).is_some_and(|profile| profile.builtin) {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "built-in profiles cannot be deleted",
+            ));
+        }
+        fs::remove_file(self.path(name)?)?;
+        self.profiles.retain(|profile| profile.name != name);
+        Ok(())
+    }
+
+    fn path(&self, name: &str) -> io::Result<PathBuf> {
+        let is_valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+            && !name.starts_with('.');
+        if !is_valid {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "profile names may only contain letters, digits, '-', '_' and '.'",
+            ));
+        }
+        let dir = self
+            .dir
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        Ok(dir.join(format!("{}.{}", name, PROFILE_EXTENSION)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::builder::NmapCommandBuilder;
+
+    #[test]
+    fn test_builtin_profiles() {
+        let store = ProfileStore::load(None);
+        assert_eq!(store.profiles().len(), BUILTIN_PROFILES.len());
+        for (name, command) in BUILTIN_PROFILES {
+            let profile = store.get(name).unwrap();
+            assert!(profile.builtin);
+            assert_eq!(NmapCommandBuilder::build(&profile.scan), command);
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-profiles-{}", std::process::id()));
+        let mut store = ProfileStore::load(Some(dir.clone()));
+        let mut scan = NmapParser::parse("nmap -sT -p 22,80 --max-retries 2 -T3 10.0.0.1").unwrap();
+        scan.notes = "ticket 42\nweb servers only".to_string();
+        store.save("web", &scan).unwrap();
+        store.save("quick", &scan).unwrap();
+        assert!(store.save("../evil", &scan).is_err());
+
+        let store = ProfileStore::load(Some(dir.clone()));
+        let names: Vec<&str> = store.profiles().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["quick", "web", "thorough", "stealth"]);
+        assert_eq!(
+            NmapCommandBuilder::build(&store.get("web").unwrap().scan),
+            NmapCommandBuilder::build(&scan)
+        );
+        assert_eq!(store.get("web").unwrap().scan.notes, scan.notes);
+
+        let mut store = store;
+        store.delete("web").unwrap();
+        assert!(store.delete("thorough").is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::results::{
+    diff::{ChangeKind, PortChange},
+    model::ScanResults,
+    parser::{self, ResultsError},
+};
+
+/// Results that later runs of a profile are compared against, stored as one XML file per profile
+pub struct BaselineStore {
+    dir: PathBuf,
+}
+
+impl BaselineStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Make a copy of an XML output file the baseline of a profile, replacing any earlier one
+    pub fn set(&self, profile: &str, xml: &Path) -> Result<ScanResults, ResultsError> {
+        let results = parser::load(xml)?;
+        fs::create_dir_all(&self.dir)?;
+        fs::copy(xml, self.path(profile))?;
+        Ok(results)
+    }
+
+    /// The baseline of a profile, if one was marked and can still be read
+    pub fn get(&self, profile: &str) -> Option<ScanResults> {
+        parser::load(&self.path(profile)).ok()
+    }
+
+    fn path(&self, profile: &str) -> PathBuf {
+        self.dir.join(format!("{}.xml", profile))
+    }
+}
+
+/// One line counting the ports that opened and closed since a baseline
+pub fn summarize(changes: &[PortChange]) -> String {
+    if changes.is_empty() {
+        return "No changes since the baseline".to_string();
+    }
+    let opened = changes
+        .iter()
+        .filter(|change| change.kind == ChangeKind::Opened)
+        .count();
+    let closed = changes.len() - opened;
+    let hosts: BTreeSet<&str> = changes.iter().map(|change| change.host.as_str()).collect();
+    format!(
+        "Since the baseline: {} opened, {} closed on {} {}",
+        opened,
+        closed,
+        hosts.len(),
+        if hosts.len() == 1 { "host" } else { "hosts" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::diff::diff;
+
+    #[test]
+    fn test_set_and_compare() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-baselines-{}", std::process::id()));
+        let store = BaselineStore::new(dir.join("baselines"));
+        assert!(store.get("quick").is_none());
+
+        fs::create_dir_all(&dir).unwrap();
+        let xml = dir.join("scan.xml");
+        let write = |ports: &[u16]| {
+            let ports: String = ports
+                .iter()
+                .map(|port| {
+                    format!(
+                        r#"<port protocol="tcp" portid="{}"><state state="open"/></port>"#,
+                        port
+                    )
+                })
+                .collect();
+            fs::write(
+                &xml,
+                format!(
+                    r#"<nmaprun><host><status state="up"/><address addr="10.0.0.1" addrtype="ipv4"/><ports>{}</ports></host></nmaprun>"#,
+                    ports
+                ),
+            )
+            .unwrap();
+        };
+        write(&[22, 80]);
+        store.set("quick", &xml).unwrap();
+        write(&[22, 443, 8080]);
+        let latest = parser::load(&xml).unwrap();
+        let baseline = store.get("quick");
+        fs::remove_dir_all(&dir).ok();
+
+        let changes = diff(&baseline.unwrap(), &latest);
+        assert_eq!(
+            summarize(&changes),
+            "Since the baseline: 2 opened, 1 closed on 1 host"
+        );
+        assert_eq!(summarize(&[]), "No changes since the baseline");
+
+        // Hosts are counted once however the changes are ordered
+        let change = |host: &str, kind| PortChange {
+            host: host.to_string(),
+            port: 22,
+            protocol: "tcp".to_string(),
+            kind,
+        };
+        let changes = [
+            change("10.0.0.1", ChangeKind::Opened),
+            change("10.0.0.2", ChangeKind::Closed),
+            change("10.0.0.1", ChangeKind::Closed),
+        ];
+        assert_eq!(
+            summarize(&changes),
+            "Since the baseline: 1 opened, 2 closed on 2 hosts"
+        );
+    }
+}
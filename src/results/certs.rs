@@ -0,0 +1,164 @@
+use crate::results::model::{ScanResults, ScriptTable};
+
+/// A TLS certificate reported by the ssl-cert script for a port
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Certificate {
+    pub host: String,
+    pub port: u16,
+    pub protocol: String,
+    /// Common name of the subject, or its organization without one
+    pub subject: String,
+    pub issuer: String,
+    /// Subject alternative names without their type, e.g. "www.example.com"
+    pub sans: Vec<String>,
+    /// End of the validity period as nmap reports it, e.g. "2025-03-01T12:00:00"
+    pub not_after: Option<String>,
+    pub self_signed: bool,
+}
+
+impl Certificate {
+    /// Port and protocol for display, e.g. "443/tcp"
+    pub fn port_label(&self) -> String {
+        format!("{}/{}", self.port, self.protocol)
+    }
+
+    /// Whether the certificate expired before `now`, a timestamp in the same format
+    pub fn is_expired(&self, now: &str) -> bool {
+        self.not_after
+            .as_deref()
+            .is_some_and(|not_after| not_after.get(..19).unwrap_or(not_after) < now)
+    }
+}
+
+/// The certificates found by ssl-cert on every port of every host
+pub fn collect_certificates(results: &ScanResults) -> Vec<Certificate> {
+    let mut certificates = Vec::new();
+    for host in &results.hosts {
+        for port in &host.ports {
+            for script in port.scripts.iter().filter(|script| script.id == "ssl-cert") {
+                let data = &script.data;
+                let line = |prefix: &str| {
+                    script
+                        .output
+                        .lines()
+                        .find_map(|line| line.trim().strip_prefix(prefix))
+                        .map(|value| value.trim().to_string())
+                };
+                let (subject, issuer) = match (data.table("subject"), data.table("issuer")) {
+                    (Some(subject), Some(issuer)) => (name(subject), name(issuer)),
+                    _ => (
+                        line("Subject:").map_or_else(String::new, |value| text_name(&value)),
+                        line("Issuer:").map_or_else(String::new, |value| text_name(&value)),
+                    ),
+                };
+                let self_signed = match (data.table("subject"), data.table("issuer")) {
+                    (Some(subject), Some(issuer)) => subject.elems == issuer.elems,
+                    _ => line("Subject:") == line("Issuer:") && line("Subject:").is_some(),
+                };
+                let sans = data
+                    .table("extensions")
+                    .into_iter()
+                    .flat_map(|extensions| &extensions.tables)
+                    .find(|extension| {
+                        extension.elem("name") == Some("X509v3 Subject Alternative Name")
+                    })
+                    .and_then(|extension| extension.elem("value").map(str::to_string))
+                    .or_else(|| line("Subject Alternative Name:"))
+                    .map(|value| alternative_names(&value))
+                    .unwrap_or_default();
+                let not_after = data
+                    .table("validity")
+                    .and_then(|validity| validity.elem("notAfter"))
+                    .map(str::to_string)
+                    .or_else(|| line("Not valid after:"));
+                certificates.push(Certificate {
+                    host: host.address().to_string(),
+                    port: port.port,
+                    protocol: port.protocol.clone(),
+                    subject,
+                    issuer,
+                    sans,
+                    not_after,
+                    self_signed,
+                });
+            }
+        }
+    }
+    certificates
+}
+
+/// Common name of a subject or issuer table, or its organization without one
+fn name(table: &ScriptTable) -> String {
+    table
+        .elem("commonName")
+        .or_else(|| table.elem("organizationName"))
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Common name of a subject or issuer line, e.g. "commonName=example.com/organizationName=Example"
+fn text_name(value: &str) -> String {
+    let field = |key: &str| {
+        value
+            .split('/')
+            .find_map(|part| part.strip_prefix(key)?.strip_prefix('='))
+    };
+    field("commonName")
+        .or_else(|| field("organizationName"))
+        .unwrap_or(value)
+        .to_string()
+}
+
+/// Names of a subject alternative name list, e.g. "DNS:example.com, IP Address:10.0.0.1"
+fn alternative_names(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|name| {
+            let name = name.trim();
+            name.split_once(':')
+                .map_or(name, |(_, name)| name)
+                .to_string()
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser::parse_xml;
+
+    #[test]
+    fn test_collect_certificates() {
+        let results = parse_xml(
+            r#"<nmaprun><host><status state="up"/><address addr="10.0.0.1" addrtype="ipv4"/><ports>
+<port protocol="tcp" portid="443"><state state="open"/>
+<script id="ssl-cert" output="Subject: commonName=example.com">
+<table key="subject"><elem key="commonName">example.com</elem></table>
+<table key="issuer"><elem key="commonName">R3</elem><elem key="organizationName">Let's Encrypt</elem></table>
+<table key="extensions"><table><elem key="name">X509v3 Subject Alternative Name</elem><elem key="value">DNS:example.com, DNS:www.example.com</elem></table></table>
+<table key="validity"><elem key="notBefore">2024-01-01T00:00:00</elem><elem key="notAfter">2024-04-01T00:00:00</elem></table>
+</script></port>
+<port protocol="tcp" portid="8443"><state state="open"/>
+<script id="ssl-cert" output="Subject: commonName=localhost/organizationName=Acme&#xa;Issuer: commonName=localhost/organizationName=Acme&#xa;Not valid after:  2034-01-01T00:00:00"/>
+</port></ports></host></nmaprun>"#,
+        )
+        .unwrap();
+        let certificates = collect_certificates(&results);
+        assert_eq!(certificates.len(), 2);
+        let signed = &certificates[0];
+        assert_eq!(
+            (signed.subject.as_str(), signed.issuer.as_str()),
+            ("example.com", "R3")
+        );
+        assert_eq!(signed.sans, vec!["example.com", "www.example.com"]);
+        assert!(!signed.self_signed);
+        assert!(signed.is_expired("2025-01-01T00:00:00"));
+
+        let local = &certificates[1];
+        assert_eq!(local.subject, "localhost");
+        assert!(local.self_signed);
+        assert_eq!(local.not_after.as_deref(), Some("2034-01-01T00:00:00"));
+        assert!(!local.is_expired("2025-01-01T00:00:00"));
+    }
+}
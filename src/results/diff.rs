@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::results::model::ScanResults;
+
+/// Whether a port started or stopped being open between two scans
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Opened,
+    Closed,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Opened => "opened",
+            ChangeKind::Closed => "closed",
+        }
+    }
+}
+
+/// A port of a host that changed state between two scans
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PortChange {
+    pub host: String,
+    pub port: u16,
+    pub protocol: String,
+    pub kind: ChangeKind,
+}
+
+impl std::fmt::Display for PortChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {}/{} {}",
+            self.host,
+            self.port,
+            self.protocol,
+            self.kind.as_str()
+        )
+    }
+}
+
+/// A port change seen at the time of a snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineEvent {
+    pub taken: u64,
+    pub change: PortChange,
+}
+
+fn open_ports(results: &ScanResults) -> BTreeSet<(String, u16, String)> {
+    results
+        .hosts
+        .iter()
+        .flat_map(|host| {
+            let address = host.address().to_string();
+            host.open_ports()
+                .map(move |port| (address.clone(), port.port, port.protocol.clone()))
+        })
+        .collect()
+}
+
+/// Ports that opened or closed between two scans, ordered by host and port
+pub fn diff(old: &ScanResults, new: &ScanResults) -> Vec<PortChange> {
+    let old = open_ports(old);
+    let new = open_ports(new);
+    let change = |(host, port, protocol): &(String, u16, String), kind| PortChange {
+        host: host.clone(),
+        port: *port,
+        protocol: protocol.clone(),
+        kind,
+    };
+
+    let mut changes: Vec<PortChange> = new
+        .difference(&old)
+        .map(|port| change(port, ChangeKind::Opened))
+        .chain(
+            old.difference(&new)
+                .map(|port| change(port, ChangeKind::Closed)),
+        )
+        .collect();
+    changes.sort();
+    changes
+}
+
+/// Changes per host across snapshots taken in order; the first snapshot counts as all ports opening
+pub fn timeline(snapshots: &[(u64, ScanResults)]) -> BTreeMap<String, Vec<TimelineEvent>> {
+    let mut timeline: BTreeMap<String, Vec<TimelineEvent>> = BTreeMap::new();
+    let empty = ScanResults::default();
+    let mut previous = &empty;
+    for (taken, results) in snapshots {
+        for change in diff(previous, results) {
+            timeline
+                .entry(change.host.clone())
+                .or_default()
+                .push(TimelineEvent {
+                    taken: *taken,
+                    change,
+                });
+        }
+        previous = results;
+    }
+    timeline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser::parse_xml;
+
+    fn results(ports: &[(&str, u16)]) -> ScanResults {
+        let mut xml = String::from("<nmaprun>");
+        for (address, port) in ports {
+            xml.push_str(&format!(
+                r#"<host><status state="up"/><address addr="{}" addrtype="ipv4"/><ports>
+                <port protocol="tcp" portid="{}"><state state="open"/></port></ports></host>"#,
+                address, port
+            ));
+        }
+        xml.push_str("</nmaprun>");
+        parse_xml(&xml).unwrap()
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = results(&[("10.0.0.1", 22), ("10.0.0.1", 80), ("10.0.0.2", 443)]);
+        let new = results(&[("10.0.0.1", 22), ("10.0.0.1", 8080), ("10.0.0.3", 3389)]);
+        let changes: Vec<String> = diff(&old, &new).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            changes,
+            vec![
+                "10.0.0.1: 80/tcp closed",
+                "10.0.0.1: 8080/tcp opened",
+                "10.0.0.2: 443/tcp closed",
+                "10.0.0.3: 3389/tcp opened",
+            ]
+        );
+        assert!(diff(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_timeline() {
+        let snapshots = vec![
+            (100, results(&[("10.0.0.1", 22)])),
+            (200, results(&[("10.0.0.1", 22)])),
+            (300, results(&[("10.0.0.1", 80)])),
+        ];
+        let timeline = timeline(&snapshots);
+        let events: Vec<(u64, String)> = timeline["10.0.0.1"]
+            .iter()
+            .map(|event| (event.taken, event.change.to_string()))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (100, "10.0.0.1: 22/tcp opened".to_string()),
+                (300, "10.0.0.1: 22/tcp closed".to_string()),
+                (300, "10.0.0.1: 80/tcp opened".to_string()),
+            ]
+        );
+    }
+}
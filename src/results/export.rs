@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+
+use crate::results::{
+    model::{AddressType, Host, HostStatus, Port, ScanResults},
+    web::{collect_web_services, csv_field},
+};
+
+/// Ports assumed to serve HTTP when nmap could not identify the service
+const WEB_PORTS: [u16; 6] = [80, 443, 8000, 8080, 8443, 8888];
+/// Ports assumed to serve HTTPS when nmap could not identify the service
+const TLS_PORTS: [u16; 2] = [443, 8443];
+
+/// Plain-text formats that other tools can ingest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    HostPorts,
+    WebUrls,
+    LiveHosts,
+    /// Titles and servers of web ports, as CSV with a header
+    WebInventory,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::HostPorts,
+        ExportFormat::WebUrls,
+        ExportFormat::LiveHosts,
+        ExportFormat::WebInventory,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::HostPorts => "host:port pairs of open ports",
+            ExportFormat::WebUrls => "URLs of open web ports",
+            ExportFormat::LiveHosts => "Live hosts (-iL compatible)",
+            ExportFormat::WebInventory => "Web inventory with titles and servers (CSV)",
+        }
+    }
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ExportFormat::HostPorts => "hostports.txt",
+            ExportFormat::WebUrls => "urls.txt",
+            ExportFormat::LiveHosts => "live-hosts.txt",
+            ExportFormat::WebInventory => "web-inventory.csv",
+        }
+    }
+}
+
+/// Render the results in the given format, one entry per line
+pub fn export(results: &ScanResults, format: ExportFormat) -> Vec<String> {
+    if format == ExportFormat::WebInventory {
+        let rows = collect_web_services(results).into_iter().map(|service| {
+            [
+                service.host,
+                service.port.to_string(),
+                service.url,
+                service.title.unwrap_or_default(),
+                service.server.unwrap_or_default(),
+            ]
+            .map(|field| csv_field(&field))
+            .join(",")
+        });
+        return std::iter::once("host,port,url,title,server".to_string())
+            .chain(rows)
+            .collect();
+    }
+    let mut seen = HashSet::new();
+    let lines = results.hosts.iter().flat_map(|host| match format {
+        ExportFormat::HostPorts => host
+            .open_ports()
+            .map(|port| format!("{}:{}", host_part(host), port.port))
+            .collect(),
+        ExportFormat::WebUrls => host
+            .open_ports()
+            .filter_map(|port| web_url(host, port))
+            .collect(),
+        ExportFormat::LiveHosts if host.status == HostStatus::Up => {
+            vec![host.address().to_string()]
+        }
+        ExportFormat::LiveHosts | ExportFormat::WebInventory => Vec::new(),
+    });
+    lines.filter(|line| seen.insert(line.clone())).collect()
+}
+
+/// Host address for use in a URL or host:port pair, bracketing IPv6 addresses
+pub fn host_part(host: &Host) -> String {
+    let is_ipv6 = host
+        .addresses
+        .iter()
+        .any(|address| address.addr_type == AddressType::Ipv6 && address.addr == host.address());
+    if is_ipv6 {
+        format!("[{}]", host.address())
+    } else {
+        host.address().to_string()
+    }
+}
+
+/// URL of a port that serves the web, going by its service or, for unknown services, its number
+pub fn web_url(host: &Host, port: &Port) -> Option<String> {
+    let scheme = match &port.service {
+        Some(service) if service.name.contains("http") => {
+            if service.name.contains("https")
+                || service.name.starts_with("ssl")
+                || service.tunnel.as_deref() == Some("ssl")
+            {
+                "https"
+            } else {
+                "http"
+            }
+        }
+        Some(service) if service.name != "unknown" => return None,
+        _ if TLS_PORTS.contains(&port.port) => "https",
+        _ if WEB_PORTS.contains(&port.port) => "http",
+        _ => return None,
+    };
+
+    let default_port = if scheme == "https" { 443 } else { 80 };
+    if port.port == default_port {
+        Some(format!("{}://{}", scheme, host_part(host)))
+    } else {
+        Some(format!("{}://{}:{}", scheme, host_part(host), port.port))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser::parse_xml;
+
+    const SAMPLE: &str = r#"<?xml version="1.0"?>
+<nmaprun scanner="nmap">
+<host><status state="up"/><address addr="10.0.0.1" addrtype="ipv4"/>
+<ports>
+<port protocol="tcp" portid="22"><state state="open"/><service name="ssh"/></port>
+<port protocol="tcp" portid="80"><state state="open"/><service name="http"/></port>
+<port protocol="tcp" portid="443"><state state="open"/><service name="http" tunnel="ssl"/></port>
+<port protocol="tcp" portid="8443"><state state="open"/></port>
+<port protocol="tcp" portid="9000"><state state="open"/><service name="https"/></port>
+<port protocol="tcp" portid="8080"><state state="closed"/><service name="http-proxy"/></port>
+</ports></host>
+<host><status state="up"/><address addr="fe80::1" addrtype="ipv6"/>
+<ports><port protocol="tcp" portid="80"><state state="open"/><service name="http"/></port></ports>
+</host>
+<host><status state="down"/><address addr="10.0.0.2" addrtype="ipv4"/></host>
+</nmaprun>"#;
+
+    #[test]
+    fn test_host_ports() {
+        let results = parse_xml(SAMPLE).unwrap();
+        assert_eq!(
+            export(&results, ExportFormat::HostPorts),
+            vec![
+                "10.0.0.1:22",
+                "10.0.0.1:80",
+                "10.0.0.1:443",
+                "10.0.0.1:8443",
+                "10.0.0.1:9000",
+                "[fe80::1]:80"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_web_urls() {
+        let results = parse_xml(SAMPLE).unwrap();
+        assert_eq!(
+            export(&results, ExportFormat::WebUrls),
+            vec![
+                "http://10.0.0.1",
+                "https://10.0.0.1",
+                "https://10.0.0.1:8443",
+                "https://10.0.0.1:9000",
+                "http://[fe80::1]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_web_inventory() {
+        let results = parse_xml(SAMPLE).unwrap();
+        let lines = export(&results, ExportFormat::WebInventory);
+        assert_eq!(lines[0], "host,port,url,title,server");
+        assert_eq!(lines[1], "10.0.0.1,80,http://10.0.0.1,,");
+        assert_eq!(lines.len(), 6);
+    }
+
+    #[test]
+    fn test_live_hosts() {
+        let results = parse_xml(SAMPLE).unwrap();
+        assert_eq!(
+            export(&results, ExportFormat::LiveHosts),
+            vec!["10.0.0.1", "fe80::1"]
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::results::{export::web_url, model::Host, model::Port};
+
+/// An NSE script worth running against a kind of service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptSuggestion {
+    pub script: &'static str,
+    pub description: &'static str,
+}
+
+const fn suggestion(script: &'static str, description: &'static str) -> ScriptSuggestion {
+    ScriptSuggestion {
+        script,
+        description,
+    }
+}
+
+const SMB_SCRIPTS: [ScriptSuggestion; 5] = [
+    suggestion("smb-os-discovery", "OS, computer name and domain"),
+    suggestion(
+        "smb-protocols",
+        "SMB dialects the server accepts, such as SMBv1",
+    ),
+    suggestion("smb-security-mode", "Whether message signing is required"),
+    suggestion("smb-enum-shares", "Shares and the access they allow"),
+    suggestion("smb-vuln-ms17-010", "EternalBlue (MS17-010)"),
+];
+
+const HTTP_SCRIPTS: [ScriptSuggestion; 5] = [
+    suggestion("http-title", "Title of the default page"),
+    suggestion(
+        "http-headers",
+        "Response headers, such as the server version",
+    ),
+    suggestion("http-methods", "HTTP methods allowed, flagging risky ones"),
+    suggestion("http-robots.txt", "Paths disallowed in robots.txt"),
+    suggestion("http-enum", "Common applications and directories"),
+];
+
+const SSL_SCRIPTS: [ScriptSuggestion; 3] = [
+    suggestion(
+        "ssl-cert",
+        "Subject, issuer and validity of the certificate",
+    ),
+    suggestion(
+        "ssl-enum-ciphers",
+        "TLS versions and ciphers, graded by strength",
+    ),
+    suggestion("ssl-heartbleed", "Heartbleed (CVE-2014-0160)"),
+];
+
+const RDP_SCRIPTS: [ScriptSuggestion; 2] = [
+    suggestion("rdp-ntlm-info", "Computer name, domain and OS version"),
+    suggestion(
+        "rdp-enum-encryption",
+        "Security layers and encryption levels",
+    ),
+];
+
+/// Kinds of services with scripts to suggest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceKind {
+    Smb,
+    Http,
+    Ssl,
+    Rdp,
+}
+
+impl ServiceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceKind::Smb => "SMB",
+            ServiceKind::Http => "HTTP",
+            ServiceKind::Ssl => "TLS",
+            ServiceKind::Rdp => "RDP",
+        }
+    }
+
+    pub fn scripts(&self) -> &'static [ScriptSuggestion] {
+        match self {
+            ServiceKind::Smb => &SMB_SCRIPTS,
+            ServiceKind::Http => &HTTP_SCRIPTS,
+            ServiceKind::Ssl => &SSL_SCRIPTS,
+            ServiceKind::Rdp => &RDP_SCRIPTS,
+        }
+    }
+}
+
+/// Kinds of service a port runs, going by the name nmap gave it or, for unknown services, the
+/// port number. HTTPS counts as both HTTP and TLS
+pub fn service_kinds(host: &Host, port: &Port) -> Vec<ServiceKind> {
+    let service = port.service.as_ref();
+    let name = service.map_or("", |service| service.name.as_str());
+    let unknown = name.is_empty() || name == "unknown";
+    let url = web_url(host, port);
+    let tls = service.is_some_and(|service| service.tunnel.as_deref() == Some("ssl"))
+        || name.starts_with("ssl")
+        || url.as_deref().is_some_and(|url| url.starts_with("https"));
+
+    let mut kinds = Vec::new();
+    if matches!(name, "microsoft-ds" | "netbios-ssn" | "smb")
+        || (unknown && matches!(port.port, 139 | 445))
+    {
+        kinds.push(ServiceKind::Smb);
+    }
+    if matches!(name, "ms-wbt-server" | "rdp") || (unknown && port.port == 3389) {
+        kinds.push(ServiceKind::Rdp);
+    }
+    if url.is_some() {
+        kinds.push(ServiceKind::Http);
+    }
+    if tls {
+        kinds.push(ServiceKind::Ssl);
+    }
+    kinds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser::parse_xml;
+
+    #[test]
+    fn test_service_kinds() {
+        let results = parse_xml(
+            r#"<nmaprun><host><status state="up"/><address addr="10.0.0.1" addrtype="ipv4"/><ports>
+            <port protocol="tcp" portid="22"><state state="open"/><service name="ssh"/></port>
+            <port protocol="tcp" portid="443"><state state="open"/><service name="http" tunnel="ssl"/></port>
+            <port protocol="tcp" portid="445"><state state="open"/><service name="microsoft-ds"/></port>
+            <port protocol="tcp" portid="993"><state state="open"/><service name="imap" tunnel="ssl"/></port>
+            <port protocol="tcp" portid="3389"><state state="open"/></port>
+            </ports></host></nmaprun>"#,
+        )
+        .unwrap();
+        let host = &results.hosts[0];
+        let kinds: Vec<Vec<ServiceKind>> = host
+            .ports
+            .iter()
+            .map(|port| service_kinds(host, port))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                vec![],
+                vec![ServiceKind::Http, ServiceKind::Ssl],
+                vec![ServiceKind::Smb],
+                vec![ServiceKind::Ssl],
+                vec![ServiceKind::Rdp],
+            ]
+        );
+    }
+}
@@ -0,0 +1,17 @@
+pub mod baseline;
+pub mod certs;
+pub mod diff;
+pub mod export;
+pub mod follow_up;
+pub mod model;
+pub mod notes;
+pub mod oui;
+pub mod parser;
+pub mod search;
+pub mod smb;
+pub mod snapshots;
+pub mod subnets;
+pub mod summary;
+pub mod topology;
+pub mod vulns;
+pub mod web;
@@ -0,0 +1,282 @@
+use std::fmt;
+
+/// Results of a completed nmap scan, as read from its XML output
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanResults {
+    pub args: Option<String>,
+    pub start: Option<u64>,
+    pub hosts: Vec<Host>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Host {
+    pub status: HostStatus,
+    pub addresses: Vec<Address>,
+    pub hostnames: Vec<String>,
+    pub ports: Vec<Port>,
+    pub scripts: Vec<Script>,
+    pub os_matches: Vec<OsMatch>,
+    /// Uptime guessed from TCP timestamps during OS detection
+    pub uptime: Option<Uptime>,
+    /// Network distance in hops
+    pub distance: Option<u8>,
+    /// Route to the host found by --traceroute, nearest hop first
+    pub trace: Vec<Hop>,
+}
+
+impl Host {
+    /// The IP address of the host, falling back to any known address
+    pub fn address(&self) -> &str {
+        self.addresses
+            .iter()
+            .find(|address| address.addr_type != AddressType::Mac)
+            .or(self.addresses.first())
+            .map_or("unknown", |address| address.addr.as_str())
+    }
+
+    /// A display name combining the hostname and address
+    pub fn display_name(&self) -> String {
+        match self.hostnames.first() {
+            Some(hostname) => format!("{} ({})", self.address(), hostname),
+            None => self.address().to_string(),
+        }
+    }
+
+    /// The MAC address of the host, only reported for hosts on the local network
+    pub fn mac(&self) -> Option<&Address> {
+        self.addresses
+            .iter()
+            .find(|address| address.addr_type == AddressType::Mac)
+    }
+
+    pub fn open_ports(&self) -> impl Iterator<Item = &Port> {
+        self.ports
+            .iter()
+            .filter(|port| port.state == PortState::Open)
+    }
+}
+
+/// An operating system guess from OS detection (-O), best match first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsMatch {
+    pub name: String,
+    pub accuracy: u8,
+    /// OS family of the most likely class, e.g. "Linux" or "Windows"
+    pub family: Option<String>,
+}
+
+/// Uptime estimate from OS detection (-O)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Uptime {
+    pub seconds: u64,
+    /// Last boot time as nmap formats it, e.g. "Tue Nov 14 10:00:00 2023"
+    pub last_boot: Option<String>,
+}
+
+/// A router on the way to a host, as found by --traceroute
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hop {
+    pub ttl: u8,
+    pub address: String,
+    pub hostname: Option<String>,
+    /// Round trip time in milliseconds
+    pub rtt: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HostStatus {
+    Up,
+    Down,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Address {
+    pub addr: String,
+    pub addr_type: AddressType,
+    /// Vendor of a MAC address, as reported by nmap
+    pub vendor: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+    Ipv4,
+    Ipv6,
+    Mac,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Port {
+    pub protocol: String,
+    pub port: u16,
+    pub state: PortState,
+    /// Why nmap gave the port its state, e.g. "syn-ack" or "no-response"
+    pub reason: Option<String>,
+    pub reason_ttl: Option<u8>,
+    pub service: Option<Service>,
+    pub scripts: Vec<Script>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortState {
+    Open,
+    Closed,
+    Filtered,
+    Unfiltered,
+    OpenFiltered,
+    ClosedFiltered,
+}
+
+impl PortState {
+    pub const ALL: [PortState; 6] = [
+        PortState::Open,
+        PortState::OpenFiltered,
+        PortState::Unfiltered,
+        PortState::Filtered,
+        PortState::ClosedFiltered,
+        PortState::Closed,
+    ];
+
+    pub fn from_nmap(state: &str) -> Option<Self> {
+        match state {
+            "open" => Some(PortState::Open),
+            "closed" => Some(PortState::Closed),
+            "filtered" => Some(PortState::Filtered),
+            "unfiltered" => Some(PortState::Unfiltered),
+            "open|filtered" => Some(PortState::OpenFiltered),
+            "closed|filtered" => Some(PortState::ClosedFiltered),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PortState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = match self {
+            PortState::Open => "open",
+            PortState::Closed => "closed",
+            PortState::Filtered => "filtered",
+            PortState::Unfiltered => "unfiltered",
+            PortState::OpenFiltered => "open|filtered",
+            PortState::ClosedFiltered => "closed|filtered",
+        };
+        write!(f, "{}", state)
+    }
+}
+
+/// Service detected on a port by nmap's version detection
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Service {
+    pub name: String,
+    pub product: Option<String>,
+    pub version: Option<String>,
+    pub extra_info: Option<String>,
+    pub tunnel: Option<String>,
+    pub cpes: Vec<String>,
+}
+
+impl Service {
+    /// Product and version joined for display, e.g. "OpenSSH 8.9p1"
+    pub fn product_version(&self) -> String {
+        [self.product.as_deref(), self.version.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Product, version and extra info, e.g. "OpenSSH 8.9p1 (Ubuntu Linux; protocol 2.0)"
+    pub fn banner(&self) -> String {
+        match &self.extra_info {
+            Some(extra_info) => format!("{} ({})", self.product_version(), extra_info)
+                .trim()
+                .to_string(),
+            None => self.product_version(),
+        }
+    }
+}
+
+/// Output of an NSE script run against a host or port
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Script {
+    pub id: String,
+    pub output: String,
+    pub data: ScriptTable,
+}
+
+/// Structured script output, made of keyed elements and nested tables
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScriptTable {
+    pub key: Option<String>,
+    pub elems: Vec<(Option<String>, String)>,
+    pub tables: Vec<ScriptTable>,
+}
+
+impl ScriptTable {
+    /// Value of the first element with the given key
+    pub fn elem(&self, key: &str) -> Option<&str> {
+        self.elems
+            .iter()
+            .find(|(elem_key, _)| elem_key.as_deref() == Some(key))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// First nested table with the given key
+    pub fn table(&self, key: &str) -> Option<&ScriptTable> {
+        self.tables
+            .iter()
+            .find(|table| table.key.as_deref() == Some(key))
+    }
+}
+
+/// Build an NVD vulnerability search URL for a CPE
+pub fn nvd_search_url(cpe: &str) -> String {
+    let encoded: String = cpe
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect();
+    format!(
+        "https://nvd.nist.gov/vuln/search/results?form_type=Advanced&results_type=overview&search_type=all&cpe_version={}",
+        encoded
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_banner() {
+        let mut service = Service {
+            name: "ssh".to_string(),
+            product: Some("OpenSSH".to_string()),
+            version: Some("8.9p1".to_string()),
+            extra_info: Some("Ubuntu Linux; protocol 2.0".to_string()),
+            tunnel: None,
+            cpes: Vec::new(),
+        };
+        assert_eq!(
+            service.banner(),
+            "OpenSSH 8.9p1 (Ubuntu Linux; protocol 2.0)"
+        );
+        service.product = None;
+        service.version = None;
+        assert_eq!(service.banner(), "(Ubuntu Linux; protocol 2.0)");
+        service.extra_info = None;
+        assert_eq!(service.banner(), "");
+    }
+
+    #[test]
+    fn test_nvd_search_url() {
+        assert_eq!(
+            nvd_search_url("cpe:/a:openbsd:openssh:8.9p1"),
+            "https://nvd.nist.gov/vuln/search/results?form_type=Advanced&results_type=overview&search_type=all&cpe_version=cpe%3A%2Fa%3Aopenbsd%3Aopenssh%3A8.9p1"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Notes of a scan, kept next to its XML output as <output>.notes
+pub fn notes_path(xml: &Path) -> PathBuf {
+    let mut path = xml.as_os_str().to_owned();
+    path.push(".notes");
+    PathBuf::from(path)
+}
+
+/// Store the notes of a scan next to its results, removing stale notes when there are none
+pub fn save(xml: &Path, notes: &str) -> io::Result<()> {
+    let path = notes_path(xml);
+    if notes.trim().is_empty() {
+        return match fs::remove_file(path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        };
+    }
+    fs::write(path, notes)
+}
+
+pub fn load(xml: &Path) -> Option<String> {
+    fs::read_to_string(notes_path(xml))
+        .ok()
+        .filter(|notes| !notes.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notes_round_trip() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-notes-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let xml = dir.join("scan.xml");
+        assert_eq!(notes_path(&xml), dir.join("scan.xml.notes"));
+
+        save(&xml, "ticket 42\nlab only").unwrap();
+        let saved = load(&xml);
+        save(&xml, "  ").unwrap();
+        let cleared = load(&xml);
+        let left = notes_path(&xml).exists();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(saved.as_deref(), Some("ticket 42\nlab only"));
+        assert_eq!(cleared, None);
+        assert!(!left);
+    }
+}
@@ -0,0 +1,93 @@
+/// Vendors of common OUIs (the first three octets of a MAC address), sorted by prefix.
+/// A small fallback for when nmap doesn't report a vendor, not a full registry.
+const VENDORS: &[(&str, &str)] = &[
+    ("00000C", "Cisco"),
+    ("000393", "Apple"),
+    ("00044B", "NVIDIA"),
+    ("000569", "VMware"),
+    ("00090F", "Fortinet"),
+    ("000B86", "Aruba Networks"),
+    ("000C29", "VMware"),
+    ("000C42", "MikroTik"),
+    ("000DB9", "PC Engines"),
+    ("001018", "Broadcom"),
+    ("001132", "Synology"),
+    ("001422", "Dell"),
+    ("00155D", "Microsoft Hyper-V"),
+    ("00163E", "Xen"),
+    ("001788", "Philips Lighting"),
+    ("00180A", "Cisco Meraki"),
+    ("001A11", "Google"),
+    ("001B17", "Palo Alto Networks"),
+    ("001C42", "Parallels"),
+    ("002590", "Super Micro"),
+    ("002722", "Ubiquiti"),
+    ("005056", "VMware"),
+    ("00907F", "WatchGuard"),
+    ("00E04C", "Realtek"),
+    ("080027", "Oracle VirtualBox"),
+    ("18B430", "Nest Labs"),
+    ("24A43C", "Ubiquiti"),
+    ("3C5AB4", "Google"),
+    ("44650D", "Amazon"),
+    ("525400", "QEMU/KVM"),
+    ("B827EB", "Raspberry Pi"),
+    ("DCA632", "Raspberry Pi"),
+    ("E45F01", "Raspberry Pi"),
+    ("F01898", "Apple"),
+    ("F4F5D8", "Google"),
+];
+
+/// Look up the vendor of a MAC address such as "00:50:56:c0:00:08" in the embedded OUI table
+pub fn vendor(mac: &str) -> Option<&'static str> {
+    let prefix: String = mac
+        .chars()
+        .filter(char::is_ascii_hexdigit)
+        .take(6)
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if prefix.len() < 6 {
+        return None;
+    }
+    VENDORS
+        .binary_search_by(|(oui, _)| (*oui).cmp(prefix.as_str()))
+        .ok()
+        .map(|index| VENDORS[index].1)
+}
+
+/// OUIs of the vendors whose name contains the query, ignoring case
+pub fn search_vendors(query: &str) -> Vec<(&'static str, &'static str)> {
+    let query = query.to_lowercase();
+    let mut matches: Vec<_> = VENDORS
+        .iter()
+        .filter(|(_, vendor)| vendor.to_lowercase().contains(&query))
+        .copied()
+        .collect();
+    matches.sort_by_key(|(_, vendor)| *vendor);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_vendors() {
+        assert_eq!(
+            search_vendors("apple"),
+            vec![("000393", "Apple"), ("F01898", "Apple")]
+        );
+        assert_eq!(search_vendors("CISCO")[0], ("00000C", "Cisco"));
+        assert_eq!(search_vendors("").len(), VENDORS.len());
+        assert!(search_vendors("nope").is_empty());
+    }
+
+    #[test]
+    fn test_vendor() {
+        assert!(VENDORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(vendor("00:50:56:c0:00:08"), Some("VMware"));
+        assert_eq!(vendor("b8-27-eb-12-34-56"), Some("Raspberry Pi"));
+        assert_eq!(vendor("12:34:56:78:9a:bc"), None);
+        assert_eq!(vendor("00:50"), None);
+    }
+}
@@ -0,0 +1,323 @@
+use std::fs;
+use std::path::Path;
+
+use roxmltree::{Document, Node, ParsingOptions};
+
+use crate::results::model::{
+    Address, AddressType, Hop, Host, HostStatus, OsMatch, Port, PortState, ScanResults, Script,
+    ScriptTable, Service, Uptime,
+};
+
+/// Error type for failures reading nmap XML output
+#[derive(Debug)]
+pub enum ResultsError {
+    Io(std::io::Error),
+    InvalidXml(String),
+    NotNmapOutput,
+}
+
+impl std::fmt::Display for ResultsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ResultsError::Io(err) => write!(f, "Failed to read results: {}", err),
+            ResultsError::InvalidXml(err) => write!(f, "Invalid XML: {}", err),
+            ResultsError::NotNmapOutput => write!(f, "Not an nmap XML output file"),
+        }
+    }
+}
+
+impl std::error::Error for ResultsError {}
+
+impl From<std::io::Error> for ResultsError {
+    fn from(err: std::io::Error) -> Self {
+        ResultsError::Io(err)
+    }
+}
+
+/// Load and parse an nmap XML output file (-oX)
+pub fn load(path: &Path) -> Result<ScanResults, ResultsError> {
+    parse_xml(&fs::read_to_string(path)?)
+}
+
+/// Parse the contents of an nmap XML output file
+pub fn parse_xml(xml: &str) -> Result<ScanResults, ResultsError> {
+    // nmap output starts with a DOCTYPE declaration
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let document = Document::parse_with_options(xml, options)
+        .map_err(|err| ResultsError::InvalidXml(err.to_string()))?;
+
+    let root = document.root_element();
+    if !root.has_tag_name("nmaprun") {
+        return Err(ResultsError::NotNmapOutput);
+    }
+
+    Ok(ScanResults {
+        args: root.attribute("args").map(str::to_string),
+        start: root.attribute("start").and_then(|start| start.parse().ok()),
+        hosts: children(root, "host").map(parse_host).collect(),
+    })
+}
+
+fn parse_host(node: Node) -> Host {
+    let status = match children(node, "status")
+        .next()
+        .and_then(|status| status.attribute("state"))
+    {
+        Some("up") => HostStatus::Up,
+        Some("down") => HostStatus::Down,
+        _ => HostStatus::Unknown,
+    };
+
+    let addresses = children(node, "address")
+        .filter_map(|address| {
+            let addr_type = match address.attribute("addrtype")? {
+                "ipv4" => AddressType::Ipv4,
+                "ipv6" => AddressType::Ipv6,
+                "mac" => AddressType::Mac,
+                _ => return None,
+            };
+            Some(Address {
+                addr: address.attribute("addr")?.to_string(),
+                addr_type,
+                vendor: address.attribute("vendor").map(str::to_string),
+            })
+        })
+        .collect();
+
+    let hostnames = children(node, "hostnames")
+        .flat_map(|hostnames| children(hostnames, "hostname"))
+        .filter_map(|hostname| hostname.attribute("name"))
+        .map(str::to_string)
+        .collect();
+
+    let ports = children(node, "ports")
+        .flat_map(|ports| children(ports, "port"))
+        .filter_map(parse_port)
+        .collect();
+
+    let scripts = children(node, "hostscript")
+        .flat_map(|hostscript| children(hostscript, "script"))
+        .map(parse_script)
+        .collect();
+
+    let os_matches = children(node, "os")
+        .flat_map(|os| children(os, "osmatch"))
+        .map(|osmatch| OsMatch {
+            name: osmatch.attribute("name").unwrap_or_default().to_string(),
+            accuracy: osmatch
+                .attribute("accuracy")
+                .and_then(|accuracy| accuracy.parse().ok())
+                .unwrap_or_default(),
+            family: children(osmatch, "osclass")
+                .find_map(|osclass| osclass.attribute("osfamily"))
+                .map(str::to_string),
+        })
+        .collect();
+
+    let uptime = children(node, "uptime").next().and_then(|uptime| {
+        Some(Uptime {
+            seconds: uptime.attribute("seconds")?.parse().ok()?,
+            last_boot: uptime.attribute("lastboot").map(str::to_string),
+        })
+    });
+    let distance = children(node, "distance")
+        .next()
+        .and_then(|distance| distance.attribute("value")?.parse().ok());
+
+    let mut trace: Vec<Hop> = children(node, "trace")
+        .flat_map(|trace| children(trace, "hop"))
+        .filter_map(|hop| {
+            Some(Hop {
+                ttl: hop.attribute("ttl")?.parse().ok()?,
+                address: hop.attribute("ipaddr")?.to_string(),
+                hostname: hop.attribute("host").map(str::to_string),
+                rtt: hop.attribute("rtt").and_then(|rtt| rtt.parse().ok()),
+            })
+        })
+        .collect();
+    trace.sort_by_key(|hop| hop.ttl);
+
+    Host {
+        status,
+        addresses,
+        hostnames,
+        ports,
+        scripts,
+        os_matches,
+        uptime,
+        distance,
+        trace,
+    }
+}
+
+fn parse_port(node: Node) -> Option<Port> {
+    let state_node = children(node, "state").next()?;
+    let state = state_node
+        .attribute("state")
+        .and_then(PortState::from_nmap)?;
+
+    Some(Port {
+        protocol: node.attribute("protocol").unwrap_or("tcp").to_string(),
+        port: node.attribute("portid")?.parse().ok()?,
+        state,
+        reason: state_node.attribute("reason").map(str::to_string),
+        reason_ttl: state_node
+            .attribute("reason_ttl")
+            .and_then(|ttl| ttl.parse().ok()),
+        service: children(node, "service").next().map(parse_service),
+        scripts: children(node, "script").map(parse_script).collect(),
+    })
+}
+
+fn parse_service(node: Node) -> Service {
+    let attribute = |name| node.attribute(name).map(str::to_string);
+    Service {
+        name: node.attribute("name").unwrap_or("unknown").to_string(),
+        product: attribute("product"),
+        version: attribute("version"),
+        extra_info: attribute("extrainfo"),
+        tunnel: attribute("tunnel"),
+        cpes: children(node, "cpe")
+            .filter_map(|cpe| cpe.text())
+            .map(|cpe| cpe.trim().to_string())
+            .collect(),
+    }
+}
+
+fn parse_script(node: Node) -> Script {
+    Script {
+        id: node.attribute("id").unwrap_or_default().to_string(),
+        output: node.attribute("output").unwrap_or_default().to_string(),
+        data: parse_script_table(node),
+    }
+}
+
+fn parse_script_table(node: Node) -> ScriptTable {
+    let key = |node: Node| node.attribute("key").map(str::to_string);
+    ScriptTable {
+        key: key(node),
+        elems: children(node, "elem")
+            .map(|elem| {
+                (
+                    key(elem),
+                    elem.text().unwrap_or_default().trim().to_string(),
+                )
+            })
+            .collect(),
+        tables: children(node, "table").map(parse_script_table).collect(),
+    }
+}
+
+fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(move |child| child.has_tag_name(tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nmaprun>
+<nmaprun scanner="nmap" args="nmap -sV -oX out.xml 10.0.0.5" start="1700000000">
+<host><status state="up" reason="syn-ack"/>
+<address addr="10.0.0.5" addrtype="ipv4"/>
+<address addr="AA:BB:CC:DD:EE:FF" addrtype="mac" vendor="Acme Networks"/>
+<hostnames><hostname name="web.local" type="PTR"/></hostnames>
+<ports>
+<port protocol="tcp" portid="22"><state state="open" reason="syn-ack" reason_ttl="64"/>
+<service name="ssh" product="OpenSSH" version="8.9p1" extrainfo="Ubuntu" method="probed" conf="10">
+<cpe>cpe:/a:openbsd:openssh:8.9p1</cpe><cpe>cpe:/o:linux:linux_kernel</cpe></service>
+<script id="vulners" output="cpe:/a:openbsd:openssh:8.9p1"><table key="cpe:/a:openbsd:openssh:8.9p1">
+<table><elem key="id">CVE-2023-38408</elem><elem key="cvss">9.8</elem><elem key="type">cve</elem><elem key="is_exploit">false</elem></table>
+</table></script></port>
+<port protocol="tcp" portid="80"><state state="filtered" reason="no-response"/></port>
+</ports>
+<os><osmatch name="Linux 5.0 - 5.14" accuracy="98" line="67773">
+<osclass type="general purpose" vendor="Linux" osfamily="Linux" osgen="5.X" accuracy="98"/></osmatch></os>
+<uptime seconds="93784" lastboot="Mon Nov 13 12:00:00 2023"/>
+<distance value="2"/>
+<trace port="22" proto="tcp"><hop ttl="2" ipaddr="10.0.0.5" rtt="1.20"/>
+<hop ttl="1" ipaddr="192.168.1.1" rtt="0.50" host="router.lan"/></trace>
+<hostscript><script id="smb-os-discovery" output="OS: Windows"><elem key="os">Windows</elem></script></hostscript>
+</host>
+</nmaprun>"#;
+
+    #[test]
+    fn test_parse_xml() {
+        let results = parse_xml(SAMPLE).unwrap();
+        assert_eq!(
+            results.args.as_deref(),
+            Some("nmap -sV -oX out.xml 10.0.0.5")
+        );
+        assert_eq!(results.start, Some(1700000000));
+        assert_eq!(results.hosts.len(), 1);
+
+        let host = &results.hosts[0];
+        assert_eq!(host.status, HostStatus::Up);
+        assert_eq!(host.address(), "10.0.0.5");
+        assert_eq!(host.display_name(), "10.0.0.5 (web.local)");
+        let mac = host.mac().unwrap();
+        assert_eq!(mac.addr, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(mac.vendor.as_deref(), Some("Acme Networks"));
+        assert_eq!(host.ports.len(), 2);
+        assert_eq!(host.open_ports().count(), 1);
+
+        let service = host.ports[0].service.as_ref().unwrap();
+        assert_eq!(service.name, "ssh");
+        assert_eq!(service.product_version(), "OpenSSH 8.9p1");
+        assert_eq!(
+            service.cpes,
+            vec!["cpe:/a:openbsd:openssh:8.9p1", "cpe:/o:linux:linux_kernel"]
+        );
+        let vulners = &host.ports[0].scripts[0];
+        assert_eq!(vulners.id, "vulners");
+        let entry = &vulners
+            .data
+            .table("cpe:/a:openbsd:openssh:8.9p1")
+            .unwrap()
+            .tables[0];
+        assert_eq!(entry.elem("id"), Some("CVE-2023-38408"));
+        assert_eq!(entry.elem("cvss"), Some("9.8"));
+        assert_eq!(host.scripts[0].data.elem("os"), Some("Windows"));
+        assert_eq!(host.os_matches[0].name, "Linux 5.0 - 5.14");
+        assert_eq!(host.os_matches[0].accuracy, 98);
+        assert_eq!(host.os_matches[0].family.as_deref(), Some("Linux"));
+        let uptime = host.uptime.as_ref().unwrap();
+        assert_eq!(uptime.seconds, 93784);
+        assert_eq!(
+            uptime.last_boot.as_deref(),
+            Some("Mon Nov 13 12:00:00 2023")
+        );
+        assert_eq!(host.distance, Some(2));
+        assert_eq!(host.trace.len(), 2);
+        assert_eq!(host.trace[0].address, "192.168.1.1");
+        assert_eq!(host.trace[0].hostname.as_deref(), Some("router.lan"));
+        assert_eq!(host.trace[0].rtt, Some(0.5));
+        assert_eq!(host.trace[1].ttl, 2);
+
+        assert_eq!(host.ports[0].reason.as_deref(), Some("syn-ack"));
+        assert_eq!(host.ports[0].reason_ttl, Some(64));
+        assert_eq!(host.ports[1].state, PortState::Filtered);
+        assert_eq!(host.ports[1].reason.as_deref(), Some("no-response"));
+        assert_eq!(host.ports[1].reason_ttl, None);
+        assert!(host.ports[1].service.is_none());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(matches!(
+            parse_xml("<html></html>"),
+            Err(ResultsError::NotNmapOutput)
+        ));
+        assert!(matches!(
+            parse_xml("not xml"),
+            Err(ResultsError::InvalidXml(_))
+        ));
+    }
+}
@@ -0,0 +1,111 @@
+use crate::results::model::{Host, ScanResults};
+
+/// A host whose addresses, hostnames, services or script output contain the search text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// Index of the host in the scan results
+    pub host: usize,
+    pub address: String,
+    /// What matched, e.g. "hostname" or "22/tcp ssh-hostkey"
+    pub field: String,
+    pub text: String,
+}
+
+/// Find every case-insensitive occurrence of the query in the hosts of a scan
+pub fn search(results: &ScanResults, query: &str) -> Vec<SearchMatch> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    for (index, host) in results.hosts.iter().enumerate() {
+        for (field, text) in searchable_fields(host) {
+            let lines = text
+                .lines()
+                .map(str::trim)
+                .filter(|line| line.to_lowercase().contains(&query));
+            for line in lines {
+                matches.push(SearchMatch {
+                    host: index,
+                    address: host.address().to_string(),
+                    field: field.clone(),
+                    text: line.to_string(),
+                });
+            }
+        }
+    }
+    matches
+}
+
+fn searchable_fields(host: &Host) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = host
+        .addresses
+        .iter()
+        .map(|address| ("address".to_string(), address.addr.clone()))
+        .chain(
+            host.hostnames
+                .iter()
+                .map(|hostname| ("hostname".to_string(), hostname.clone())),
+        )
+        .collect();
+    fields.extend(
+        host.scripts
+            .iter()
+            .map(|script| (script.id.clone(), script.output.clone())),
+    );
+    for port in &host.ports {
+        let label = format!("{}/{}", port.port, port.protocol);
+        if let Some(service) = &port.service {
+            let banner = format!("{} {}", service.name, service.banner());
+            fields.push((label.clone(), banner.trim().to_string()));
+        }
+        fields.extend(
+            port.scripts
+                .iter()
+                .map(|script| (format!("{} {}", label, script.id), script.output.clone())),
+        );
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser::parse_xml;
+
+    #[test]
+    fn test_search() {
+        let results = parse_xml(
+            r#"<nmaprun>
+            <host><status state="up"/><address addr="10.0.0.1" addrtype="ipv4"/>
+            <hostnames><hostname name="web.example.com"/></hostnames>
+            <ports><port protocol="tcp" portid="80"><state state="open"/>
+            <service name="http" product="nginx" version="1.18.0"/>
+            <script id="http-title" output="Welcome to nginx!&#xa;Site: example"/></port></ports></host>
+            <host><status state="up"/><address addr="10.0.0.2" addrtype="ipv4"/>
+            <ports><port protocol="tcp" portid="22"><state state="open"/>
+            <service name="ssh" product="OpenSSH" version="8.9p1"/></port></ports></host>
+            </nmaprun>"#,
+        )
+        .unwrap();
+
+        let matches = search(&results, "NGINX");
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| (m.address.as_str(), m.field.as_str(), m.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("10.0.0.1", "80/tcp", "http nginx 1.18.0"),
+                ("10.0.0.1", "80/tcp http-title", "Welcome to nginx!"),
+            ]
+        );
+
+        let matches = search(&results, "10.0.0.2");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].host, 1);
+        assert_eq!(search(&results, "example.com")[0].field, "hostname");
+        assert!(search(&results, "  ").is_empty());
+    }
+}
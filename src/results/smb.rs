@@ -0,0 +1,134 @@
+use crate::results::model::{Host, ScanResults, Script};
+
+/// Whether an SMB server signs messages; servers that don't require it allow NTLM relaying
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Signing {
+    Disabled,
+    /// Enabled but not required
+    Enabled,
+    Required,
+}
+
+impl Signing {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Signing::Disabled => "disabled",
+            Signing::Enabled => "not required",
+            Signing::Required => "required",
+        }
+    }
+}
+
+/// What smb-os-discovery, nbstat and the SMB security mode scripts say about a Windows host
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SmbIdentity {
+    pub host: String,
+    /// Fully qualified name, or the NetBIOS name without one
+    pub computer_name: Option<String>,
+    pub domain: Option<String>,
+    pub workgroup: Option<String>,
+    pub os: Option<String>,
+    pub signing: Option<Signing>,
+}
+
+/// Identities of the hosts that the SMB or NetBIOS scripts ran against, in host order
+pub fn collect_smb_identities(results: &ScanResults) -> Vec<SmbIdentity> {
+    results.hosts.iter().filter_map(identity).collect()
+}
+
+fn identity(host: &Host) -> Option<SmbIdentity> {
+    let script = |id: &str| host.scripts.iter().find(|script| script.id == id);
+    let discovery = script("smb-os-discovery");
+    let nbstat = script("nbstat");
+    let security = script("smb-security-mode");
+    let smb2 = script("smb2-security-mode");
+    if discovery.is_none() && nbstat.is_none() && security.is_none() && smb2.is_none() {
+        return None;
+    }
+
+    let elem = |key: &str| {
+        discovery
+            .and_then(|script| script.data.elem(key))
+            // NetBIOS names end in a NUL, which nmap writes out as \x00
+            .map(|value| value.trim_end_matches("\\x00").trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let netbios_name =
+        elem("server").or_else(|| nbstat.and_then(|script| output_field(script, "NetBIOS name:")));
+    let signing = security
+        .and_then(|script| script.data.elem("message_signing"))
+        .map(|signing| match signing {
+            "required" => Signing::Required,
+            "disabled" => Signing::Disabled,
+            _ => Signing::Enabled,
+        })
+        .or_else(|| smb2.and_then(smb2_signing));
+    Some(SmbIdentity {
+        host: host.address().to_string(),
+        computer_name: elem("fqdn").or(netbios_name),
+        domain: elem("domain_dns").or_else(|| elem("domain")),
+        workgroup: elem("workgroup"),
+        os: elem("os"),
+        signing,
+    })
+}
+
+/// Value after a label in script output, up to the next comma or line break
+fn output_field(script: &Script, label: &str) -> Option<String> {
+    let (_, rest) = script.output.split_once(label)?;
+    let value = rest.split([',', '\n']).next()?.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Signing of the strongest SMB2 dialect, from output such as "Message signing enabled and required"
+fn smb2_signing(script: &Script) -> Option<Signing> {
+    let output = script.output.to_lowercase();
+    if output.contains("enabled and required") {
+        Some(Signing::Required)
+    } else if output.contains("enabled but not required") {
+        Some(Signing::Enabled)
+    } else if output.contains("disabled") {
+        Some(Signing::Disabled)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser::parse_xml;
+
+    #[test]
+    fn test_collect_smb_identities() {
+        let results = parse_xml(
+            r#"<nmaprun>
+<host><status state="up"/><address addr="10.0.0.5" addrtype="ipv4"/><hostscript>
+<script id="smb-os-discovery" output="OS: Windows Server 2019"><elem key="os">Windows Server 2019 Standard 17763</elem><elem key="server">DC01\x00</elem><elem key="fqdn">dc01.corp.local</elem><elem key="domain_dns">corp.local</elem><elem key="workgroup">CORP\x00</elem></script>
+<script id="smb-security-mode" output=""><elem key="message_signing">required</elem></script>
+</hostscript></host>
+<host><status state="up"/><address addr="10.0.0.6" addrtype="ipv4"/><hostscript>
+<script id="nbstat" output="NetBIOS name: WS01, NetBIOS user: &lt;unknown&gt;, NetBIOS MAC: 00:11:22:33:44:55"/>
+<script id="smb2-security-mode" output="&#xa;  3:1:1: &#xa;    Message signing enabled but not required"/>
+</hostscript></host>
+<host><status state="up"/><address addr="10.0.0.7" addrtype="ipv4"/></host>
+</nmaprun>"#,
+        )
+        .unwrap();
+        let identities = collect_smb_identities(&results);
+        assert_eq!(identities.len(), 2);
+        assert_eq!(
+            identities[0],
+            SmbIdentity {
+                host: "10.0.0.5".to_string(),
+                computer_name: Some("dc01.corp.local".to_string()),
+                domain: Some("corp.local".to_string()),
+                workgroup: Some("CORP".to_string()),
+                os: Some("Windows Server 2019 Standard 17763".to_string()),
+                signing: Some(Signing::Required),
+            }
+        );
+        assert_eq!(identities[1].computer_name.as_deref(), Some("WS01"));
+        assert_eq!(identities[1].signing, Some(Signing::Enabled));
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::results::{
+    model::ScanResults,
+    parser::{self, ResultsError},
+};
+
+const SNAPSHOT_EXTENSION: &str = "xml";
+
+/// Results of repeated runs of one scan, stored as one XML file per run named after its start time
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+/// A snapshot found anywhere under the results directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredScan {
+    /// Store the snapshot belongs to, relative to the results directory, e.g. "monitor/quick"
+    pub store: String,
+    pub taken: u64,
+    pub path: PathBuf,
+}
+
+impl SnapshotStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Copy the XML output of a finished run into the store, returning when it was taken and its results
+    pub fn add(&self, xml: &Path) -> Result<(u64, ScanResults), ResultsError> {
+        let results = parser::load(xml)?;
+        let taken = taken(&results);
+        fs::create_dir_all(&self.dir)?;
+        fs::copy(xml, self.snapshot_path(taken))?;
+        Ok((taken, results))
+    }
+
+    /// Write the XML of a run already parsed into `results`, then remove the oldest snapshots
+    /// beyond `limit`
+    pub fn keep(&self, xml: &str, results: &ScanResults, limit: usize) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.snapshot_path(taken(results)), xml)?;
+        self.prune(limit)
+    }
+
+    /// Remove the oldest snapshots so that at most `limit` are left
+    pub fn prune(&self, limit: usize) -> io::Result<()> {
+        let mut files = Self::snapshot_files(&self.dir);
+        files.sort_by_key(|(taken, _)| *taken);
+        for (_, path) in files.iter().rev().skip(limit) {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    fn snapshot_path(&self, taken: u64) -> PathBuf {
+        self.dir.join(format!("{}.{}", taken, SNAPSHOT_EXTENSION))
+    }
+
+    /// Every readable snapshot, oldest first
+    pub fn load(&self) -> Vec<(u64, ScanResults)> {
+        let mut snapshots: Vec<(u64, ScanResults)> = Self::snapshot_files(&self.dir)
+            .into_iter()
+            .filter_map(|(taken, path)| Some((taken, parser::load(&path).ok()?)))
+            .collect();
+        snapshots.sort_by_key(|(taken, _)| *taken);
+        snapshots
+    }
+
+    fn snapshot_files(dir: &Path) -> Vec<(u64, PathBuf)> {
+        fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != SNAPSHOT_EXTENSION {
+                    return None;
+                }
+                let taken = path.file_stem()?.to_str()?.parse().ok()?;
+                Some((taken, path))
+            })
+            .collect()
+    }
+}
+
+/// When a run was taken: its start time, or now if nmap didn't record one
+fn taken(results: &ScanResults) -> u64 {
+    results.start.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    })
+}
+
+/// Every snapshot in the stores under a results directory, newest first
+pub fn stored_scans(root: &Path) -> Vec<StoredScan> {
+    let mut dirs = vec![root.to_path_buf()];
+    let mut scans = Vec::new();
+    while let Some(dir) = dirs.pop() {
+        let store = dir
+            .strip_prefix(root)
+            .unwrap_or(&dir)
+            .to_string_lossy()
+            .to_string();
+        for (taken, path) in SnapshotStore::snapshot_files(&dir) {
+            scans.push(StoredScan {
+                store: store.clone(),
+                taken,
+                path,
+            });
+        }
+        dirs.extend(
+            fs::read_dir(&dir)
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.is_dir()),
+        );
+    }
+    scans.sort_by(|a, b| b.taken.cmp(&a.taken).then_with(|| a.store.cmp(&b.store)));
+    scans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_load() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-snapshots-{}", std::process::id()));
+        let store = SnapshotStore::new(dir.join("monitor").join("quick"));
+        assert!(store.load().is_empty());
+
+        fs::create_dir_all(&dir).unwrap();
+        let xml = dir.join("scan.xml");
+        let write = |start: u64| {
+            fs::write(
+                &xml,
+                format!(
+                    r#"<nmaprun start="{}"><host><status state="up"/><address addr="10.0.0.1" addrtype="ipv4"/></host></nmaprun>"#,
+                    start
+                ),
+            )
+            .unwrap();
+        };
+        write(200);
+        assert_eq!(store.add(&xml).unwrap().0, 200);
+        write(100);
+        store.add(&xml).unwrap();
+        fs::write(
+            dir.join("monitor").join("quick").join("notes.txt"),
+            "ignored",
+        )
+        .unwrap();
+        write(300);
+        SnapshotStore::new(dir.join("history")).add(&xml).unwrap();
+
+        let snapshots = store.load();
+        let stored = stored_scans(&dir);
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(
+            snapshots
+                .iter()
+                .map(|(taken, _)| *taken)
+                .collect::<Vec<_>>(),
+            vec![100, 200]
+        );
+        assert_eq!(snapshots[0].1.hosts[0].address(), "10.0.0.1");
+        assert_eq!(
+            stored
+                .iter()
+                .map(|scan| (scan.store.as_str(), scan.taken))
+                .collect::<Vec<_>>(),
+            vec![
+                ("history", 300),
+                ("monitor/quick", 200),
+                ("monitor/quick", 100)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keep_prunes_oldest() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-prune-{}", std::process::id()));
+        let store = SnapshotStore::new(dir.clone());
+        for start in [300, 100, 200] {
+            let results = ScanResults {
+                start: Some(start),
+                ..Default::default()
+            };
+            store.keep("<nmaprun/>", &results, 2).unwrap();
+        }
+
+        let mut kept = SnapshotStore::snapshot_files(&dir);
+        kept.sort();
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(
+            kept.iter().map(|(taken, _)| *taken).collect::<Vec<_>>(),
+            vec![200, 300]
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::net::IpAddr;
+
+use crate::results::model::{AddressType, ScanResults};
+
+/// Label of the group for hosts without an IP address
+const OTHER_GROUP: &str = "other";
+
+/// Hosts that share a subnet, in scan order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubnetGroup {
+    /// Network in CIDR notation, e.g. "10.0.0.0/24"
+    pub subnet: String,
+    /// Indices of the hosts in the scan results
+    pub hosts: Vec<usize>,
+    pub open_ports: usize,
+}
+
+/// Group hosts by the network of their IP address, ordered by network address
+pub fn group_by_subnet(
+    results: &ScanResults,
+    ipv4_prefix: u8,
+    ipv6_prefix: u8,
+) -> Vec<SubnetGroup> {
+    let mut groups: Vec<(Option<IpAddr>, SubnetGroup)> = Vec::new();
+    for (index, host) in results.hosts.iter().enumerate() {
+        let network = host
+            .addresses
+            .iter()
+            .filter(|address| address.addr_type != AddressType::Mac)
+            .find_map(|address| address.addr.parse::<IpAddr>().ok())
+            .map(|ip| network(ip, ipv4_prefix, ipv6_prefix));
+        let key = network.map(|(ip, _)| ip);
+        let open_ports = host.open_ports().count();
+        match groups.iter_mut().find(|(other, _)| *other == key) {
+            Some((_, group)) => {
+                group.hosts.push(index);
+                group.open_ports += open_ports;
+            }
+            None => groups.push((
+                key,
+                SubnetGroup {
+                    subnet: network.map_or(OTHER_GROUP.to_string(), |(ip, prefix)| {
+                        format!("{}/{}", ip, prefix)
+                    }),
+                    hosts: vec![index],
+                    open_ports,
+                },
+            )),
+        }
+    }
+    // Hosts without an IP sort last
+    groups.sort_by_key(|(network, _)| (network.is_none(), *network));
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+/// The network address of an IP and the prefix length used, clamped to the address size
+fn network(ip: IpAddr, ipv4_prefix: u8, ipv6_prefix: u8) -> (IpAddr, u8) {
+    match ip {
+        IpAddr::V4(ip) => {
+            let prefix = ipv4_prefix.min(32);
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            (IpAddr::V4((u32::from(ip) & mask).into()), prefix)
+        }
+        IpAddr::V6(ip) => {
+            let prefix = ipv6_prefix.min(128);
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            (IpAddr::V6((u128::from(ip) & mask).into()), prefix)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser::parse_xml;
+
+    #[test]
+    fn test_group_by_subnet() {
+        let host = |address: &str, addr_type: &str, open: usize| {
+            let ports = (0..open)
+                .map(|port| {
+                    format!(
+                        r#"<port protocol="tcp" portid="{}"><state state="open"/></port>"#,
+                        port + 1
+                    )
+                })
+                .collect::<String>();
+            format!(
+                r#"<host><status state="up"/><address addr="{}" addrtype="{}"/><ports>{}</ports></host>"#,
+                address, addr_type, ports
+            )
+        };
+        let xml = [
+            host("10.0.1.7", "ipv4", 1),
+            host("10.0.0.5", "ipv4", 2),
+            host("00:11:22:33:44:55", "mac", 0),
+            host("10.0.1.9", "ipv4", 3),
+            host("fe80::1", "ipv6", 0),
+        ]
+        .concat();
+        let results = parse_xml(&format!("<nmaprun>{}</nmaprun>", xml)).unwrap();
+
+        let groups = group_by_subnet(&results, 24, 64);
+        assert_eq!(
+            groups
+                .iter()
+                .map(|group| (group.subnet.as_str(), group.hosts.clone(), group.open_ports))
+                .collect::<Vec<_>>(),
+            vec![
+                ("10.0.0.0/24", vec![1], 2),
+                ("10.0.1.0/24", vec![0, 3], 4),
+                ("fe80::/64", vec![4], 0),
+                ("other", vec![2], 0),
+            ]
+        );
+
+        let groups = group_by_subnet(&results, 16, 64);
+        assert_eq!(groups[0].subnet, "10.0.0.0/16");
+        assert_eq!(groups[0].hosts, vec![0, 1, 3]);
+        assert_eq!(group_by_subnet(&results, 0, 0)[0].subnet, "0.0.0.0/0");
+    }
+}
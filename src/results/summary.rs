@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use crate::results::model::{HostStatus, ScanResults};
+
+/// Label for hosts that are up but have no OS match
+const UNKNOWN_OS: &str = "unknown";
+
+/// Aggregate counts over the hosts of a scan, for the summary dashboard
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub up: usize,
+    pub down: usize,
+    /// Open ports such as "22/tcp" and the number of hosts they are open on, most common first
+    pub top_ports: Vec<(String, u64)>,
+    /// OS families of the best OS match of each host that is up, most common first
+    pub os_families: Vec<(String, u64)>,
+}
+
+/// Count hosts by status and OS family, and the most common open ports
+pub fn summarize(results: &ScanResults, max_ports: usize) -> Summary {
+    let mut summary = Summary::default();
+    let mut ports: HashMap<String, u64> = HashMap::new();
+    let mut os_families: HashMap<String, u64> = HashMap::new();
+    for host in &results.hosts {
+        match host.status {
+            HostStatus::Up => summary.up += 1,
+            HostStatus::Down => summary.down += 1,
+            HostStatus::Unknown => {}
+        }
+        for port in host.open_ports() {
+            *ports
+                .entry(format!("{}/{}", port.port, port.protocol))
+                .or_default() += 1;
+        }
+        if host.status == HostStatus::Up {
+            let family = host
+                .os_matches
+                .first()
+                .and_then(|os| os.family.clone())
+                .unwrap_or_else(|| UNKNOWN_OS.to_string());
+            *os_families.entry(family).or_default() += 1;
+        }
+    }
+
+    summary.top_ports = most_common(ports);
+    summary.top_ports.truncate(max_ports);
+    summary.os_families = most_common(os_families);
+    summary
+}
+
+fn most_common(counts: HashMap<String, u64>) -> Vec<(String, u64)> {
+    let mut counts: Vec<(String, u64)> = counts.into_iter().collect();
+    counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser::parse_xml;
+
+    #[test]
+    fn test_summarize() {
+        let host = |state: &str, ports: &[u16], family: Option<&str>| {
+            let ports = ports
+                .iter()
+                .map(|port| {
+                    format!(
+                        r#"<port protocol="tcp" portid="{}"><state state="open"/></port>"#,
+                        port
+                    )
+                })
+                .collect::<String>();
+            let os = family.map_or(String::new(), |family| {
+                format!(
+                    r#"<os><osmatch name="{0}" accuracy="95"><osclass osfamily="{0}"/></osmatch></os>"#,
+                    family
+                )
+            });
+            format!(
+                r#"<host><status state="{}"/><address addr="10.0.0.1" addrtype="ipv4"/><ports>{}</ports>{}</host>"#,
+                state, ports, os
+            )
+        };
+        let xml = [
+            host("up", &[22, 80], Some("Linux")),
+            host("up", &[22, 443], Some("Linux")),
+            host("up", &[3389], Some("Windows")),
+            host("up", &[], None),
+            host("down", &[], None),
+        ]
+        .concat();
+        let results = parse_xml(&format!("<nmaprun>{}</nmaprun>", xml)).unwrap();
+
+        let summary = summarize(&results, 3);
+        assert_eq!((summary.up, summary.down), (4, 1));
+        assert_eq!(
+            summary.top_ports,
+            vec![
+                ("22/tcp".to_string(), 2),
+                ("3389/tcp".to_string(), 1),
+                ("443/tcp".to_string(), 1),
+            ]
+        );
+        assert_eq!(
+            summary.os_families,
+            vec![
+                ("Linux".to_string(), 2),
+                ("Windows".to_string(), 1),
+                ("unknown".to_string(), 1),
+            ]
+        );
+    }
+}
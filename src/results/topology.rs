@@ -0,0 +1,166 @@
+use crate::results::model::ScanResults;
+
+/// A hop of the merged --traceroute routes, with the routes that continue past it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HopNode {
+    /// Address of the router, None for a hop that didn't answer
+    pub address: Option<String>,
+    pub hostname: Option<String>,
+    pub children: Vec<HopNode>,
+    /// Indices of the scanned hosts whose route ends at this hop
+    pub hosts: Vec<usize>,
+}
+
+impl HopNode {
+    /// Number of scanned hosts reached through this hop
+    pub fn host_count(&self) -> usize {
+        self.hosts.len() + self.children.iter().map(HopNode::host_count).sum::<usize>()
+    }
+}
+
+/// A line of the hop tree as drawn: tree glyphs leading up to the hop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeLine<'a> {
+    pub prefix: String,
+    pub node: &'a HopNode,
+}
+
+/// Merge the routes to every traced host into one tree, so routes sharing their first hops
+/// share the nodes for them. Hops that didn't answer show up as nodes without an address.
+pub fn hop_tree(results: &ScanResults) -> Vec<HopNode> {
+    let mut roots: Vec<HopNode> = Vec::new();
+    for (index, host) in results.hosts.iter().enumerate() {
+        let Some(last) = host.trace.last() else {
+            continue;
+        };
+        let mut level = &mut roots;
+        let mut ttl = 1;
+        for hop in &host.trace {
+            // nmap leaves out hops that timed out
+            while ttl < hop.ttl {
+                level = &mut child(level, None, None).children;
+                ttl += 1;
+            }
+            let node = child(level, Some(&hop.address), hop.hostname.as_deref());
+            if hop.ttl == last.ttl {
+                node.hosts.push(index);
+                break;
+            }
+            level = &mut node.children;
+            ttl = hop.ttl + 1;
+        }
+    }
+    roots
+}
+
+/// The tree in drawing order, with the glyphs that connect each hop to its parent
+pub fn tree_lines(roots: &[HopNode]) -> Vec<TreeLine<'_>> {
+    fn walk<'a>(nodes: &'a [HopNode], indent: &str, lines: &mut Vec<TreeLine<'a>>) {
+        for (index, node) in nodes.iter().enumerate() {
+            let last = index + 1 == nodes.len();
+            lines.push(TreeLine {
+                prefix: format!("{}{}", indent, if last { "└─ " } else { "├─ " }),
+                node,
+            });
+            let indent = format!("{}{}", indent, if last { "   " } else { "│  " });
+            walk(&node.children, &indent, lines);
+        }
+    }
+
+    let mut lines = Vec::new();
+    walk(roots, "", &mut lines);
+    lines
+}
+
+/// The node for a hop among the children of a level, added if the level doesn't have it yet
+fn child<'a>(
+    level: &'a mut Vec<HopNode>,
+    address: Option<&str>,
+    hostname: Option<&str>,
+) -> &'a mut HopNode {
+    let position = level
+        .iter()
+        .position(|node| node.address.as_deref() == address);
+    let index = match position {
+        Some(index) => index,
+        None => {
+            level.push(HopNode {
+                address: address.map(str::to_string),
+                hostname: hostname.map(str::to_string),
+                ..HopNode::default()
+            });
+            level.len() - 1
+        }
+    };
+    &mut level[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::model::{Hop, Host};
+
+    fn traced(hops: &[(u8, &str)]) -> Host {
+        Host {
+            trace: hops
+                .iter()
+                .map(|(ttl, address)| Hop {
+                    ttl: *ttl,
+                    address: address.to_string(),
+                    hostname: None,
+                    rtt: None,
+                })
+                .collect(),
+            ..Host::default()
+        }
+    }
+
+    #[test]
+    fn test_hop_tree() {
+        let results = ScanResults {
+            hosts: vec![
+                traced(&[(1, "192.168.1.1"), (2, "10.0.0.1"), (3, "10.0.1.5")]),
+                traced(&[(1, "192.168.1.1"), (2, "10.0.0.1"), (3, "10.0.2.7")]),
+                traced(&[(1, "192.168.1.1"), (3, "172.16.0.9")]),
+                Host::default(),
+                traced(&[(1, "192.168.1.1")]),
+            ],
+            ..ScanResults::default()
+        };
+        let roots = hop_tree(&results);
+        assert_eq!(roots.len(), 1);
+        let gateway = &roots[0];
+        assert_eq!(gateway.hosts, vec![4]);
+        assert_eq!(gateway.host_count(), 4);
+        assert_eq!(gateway.children.len(), 2);
+        assert_eq!(gateway.children[0].children.len(), 2);
+        assert_eq!(gateway.children[0].children[1].hosts, vec![1]);
+        assert_eq!(gateway.children[1].address, None);
+        assert_eq!(
+            gateway.children[1].children[0].address.as_deref(),
+            Some("172.16.0.9")
+        );
+
+        let lines: Vec<String> = tree_lines(&roots)
+            .iter()
+            .map(|line| {
+                format!(
+                    "{}{}",
+                    line.prefix,
+                    line.node.address.as_deref().unwrap_or("*")
+                )
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "└─ 192.168.1.1",
+                "   ├─ 10.0.0.1",
+                "   │  ├─ 10.0.1.5",
+                "   │  └─ 10.0.2.7",
+                "   └─ *",
+                "      └─ 172.16.0.9",
+            ]
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use std::cmp::Ordering;
+use std::net::IpAddr;
+
+use crate::results::model::{Host, Port, ScanResults, Script, ScriptTable};
+
+/// A vulnerability reported by an NSE script against a host or port
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub id: String,
+    pub cvss: Option<f32>,
+    pub host: String,
+    pub port: Option<u16>,
+    pub protocol: Option<String>,
+    pub script: String,
+    pub exploit: bool,
+}
+
+impl Finding {
+    /// Port and protocol for display, e.g. "22/tcp", or "-" for host scripts
+    pub fn port_label(&self) -> String {
+        match (self.port, &self.protocol) {
+            (Some(port), Some(protocol)) => format!("{}/{}", port, protocol),
+            (Some(port), None) => port.to_string(),
+            _ => "-".to_string(),
+        }
+    }
+}
+
+/// Column the findings table is sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FindingSort {
+    #[default]
+    Cvss,
+    Id,
+    Host,
+    Port,
+    Script,
+}
+
+/// Collect the findings of all vulnerability scripts across all hosts
+pub fn collect_findings(results: &ScanResults) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = Vec::new();
+    for host in &results.hosts {
+        for script in &host.scripts {
+            add_findings(&mut findings, host, None, script);
+        }
+        for port in &host.ports {
+            for script in &port.scripts {
+                add_findings(&mut findings, host, Some(port), script);
+            }
+        }
+    }
+    sort_findings(&mut findings, FindingSort::default());
+    findings
+}
+
+/// Sort findings by the given column; CVSS sorts highest first
+pub fn sort_findings(findings: &mut [Finding], sort: FindingSort) {
+    findings.sort_by(|a, b| match sort {
+        FindingSort::Cvss => compare_cvss(a, b).then_with(|| a.id.cmp(&b.id)),
+        FindingSort::Id => a.id.cmp(&b.id).then_with(|| compare_cvss(a, b)),
+        FindingSort::Host => compare_hosts(&a.host, &b.host).then_with(|| compare_cvss(a, b)),
+        FindingSort::Port => a.port.cmp(&b.port).then_with(|| compare_cvss(a, b)),
+        FindingSort::Script => a.script.cmp(&b.script).then_with(|| compare_cvss(a, b)),
+    });
+}
+
+fn compare_cvss(a: &Finding, b: &Finding) -> Ordering {
+    let score = |finding: &Finding| finding.cvss.unwrap_or(-1.0);
+    score(b).total_cmp(&score(a))
+}
+
+/// Order host addresses numerically, and names alphabetically
+pub fn compare_hosts(a: &str, b: &str) -> Ordering {
+    match (a.parse::<IpAddr>(), b.parse::<IpAddr>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+fn add_findings(findings: &mut Vec<Finding>, host: &Host, port: Option<&Port>, script: &Script) {
+    let mut reported = Vec::new();
+    if script.id == "vulners" {
+        vulners_findings(&script.data, &mut reported);
+    } else {
+        vulns_library_findings(&script.data, &mut reported);
+    }
+    // Scripts such as vulscan only produce text output
+    if reported.is_empty() && script.id.contains("vul") {
+        reported = find_cve_ids(&script.output)
+            .into_iter()
+            .map(|id| (id, None, false))
+            .collect();
+    }
+
+    for (id, cvss, exploit) in reported {
+        let finding = Finding {
+            id,
+            cvss,
+            host: host.address().to_string(),
+            port: port.map(|port| port.port),
+            protocol: port.map(|port| port.protocol.clone()),
+            script: script.id.clone(),
+            exploit,
+        };
+        match findings.iter_mut().find(|existing| {
+            existing.id == finding.id
+                && existing.host == finding.host
+                && existing.port == finding.port
+        }) {
+            Some(existing) => {
+                existing.cvss = match (existing.cvss, finding.cvss) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (a, b) => a.or(b),
+                };
+                existing.exploit |= finding.exploit;
+            }
+            None => findings.push(finding),
+        }
+    }
+}
+
+/// vulners lists entries with id, cvss and is_exploit elements, grouped by CPE
+fn vulners_findings(data: &ScriptTable, reported: &mut Vec<(String, Option<f32>, bool)>) {
+    for cpe in &data.tables {
+        for entry in &cpe.tables {
+            if let Some(id) = entry.elem("id") {
+                reported.push((
+                    id.to_string(),
+                    entry.elem("cvss").and_then(|cvss| cvss.parse().ok()),
+                    entry.elem("is_exploit") == Some("true"),
+                ));
+            }
+        }
+    }
+}
+
+/// Scripts built on the NSE vulns library report one table per vulnerability with a state
+fn vulns_library_findings(data: &ScriptTable, reported: &mut Vec<(String, Option<f32>, bool)>) {
+    for vuln in &data.tables {
+        let Some(state) = vuln.elem("state") else {
+            continue;
+        };
+        if !state.starts_with("VULNERABLE") && !state.starts_with("LIKELY VULNERABLE") {
+            continue;
+        }
+
+        let cvss = vuln.table("scores").and_then(|scores| {
+            scores
+                .elems
+                .iter()
+                .filter_map(|(_, score)| score.parse::<f32>().ok())
+                .reduce(f32::max)
+        });
+        let ids: Vec<String> = vuln
+            .table("ids")
+            .map(|ids| {
+                ids.elems
+                    .iter()
+                    .map(|(_, id)| id.strip_prefix("CVE:").unwrap_or(id).to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if ids.is_empty() {
+            let id = vuln
+                .elem("title")
+                .or(vuln.key.as_deref())
+                .unwrap_or("unknown");
+            reported.push((id.to_string(), cvss, false));
+        } else {
+            reported.extend(ids.into_iter().map(|id| (id, cvss, false)));
+        }
+    }
+}
+
+/// Find CVE identifiers such as CVE-2021-44228 in free-form text
+fn find_cve_ids(text: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for (start, _) in text.match_indices("CVE-") {
+        let rest = &text[start + 4..];
+        let year: String = rest.chars().take_while(char::is_ascii_digit).collect();
+        let Some(number) = rest[year.len()..].strip_prefix('-') else {
+            continue;
+        };
+        let number: String = number.chars().take_while(char::is_ascii_digit).collect();
+        if year.len() == 4 && number.len() >= 4 {
+            let id = format!("CVE-{}-{}", year, number);
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser::parse_xml;
+
+    const SAMPLE: &str = r#"<?xml version="1.0"?>
+<!DOCTYPE nmaprun>
+<nmaprun scanner="nmap">
+<host><status state="up"/><address addr="10.0.0.9" addrtype="ipv4"/>
+<ports>
+<port protocol="tcp" portid="22"><state state="open"/><service name="ssh"/>
+<script id="vulners" output="..."><table key="cpe:/a:openbsd:openssh:8.9p1">
+<table><elem key="id">CVE-2023-38408</elem><elem key="cvss">9.8</elem><elem key="is_exploit">true</elem></table>
+<table><elem key="id">CVE-2023-28531</elem><elem key="cvss">7.5</elem><elem key="is_exploit">false</elem></table>
+</table></script>
+<script id="vulscan" output="[CVE-2023-28531] OpenSSH ssh-add&#xa;[CVE-2016-20012] OpenSSH user enumeration"/>
+</port>
+</ports>
+<hostscript><script id="smb-vuln-ms17-010" output="VULNERABLE">
+<table key="CVE-2017-0143"><elem key="title">Remote Code Execution vulnerability in Microsoft SMBv1 servers (ms17-010)</elem>
+<elem key="state">VULNERABLE</elem><table key="ids"><elem>CVE:CVE-2017-0143</elem></table></table>
+</script></hostscript>
+</host>
+<host><status state="up"/><address addr="10.0.0.10" addrtype="ipv4"/>
+<hostscript><script id="smb-vuln-ms10-054" output="">
+<table key="CVE-2010-2550"><elem key="state">NOT VULNERABLE</elem><table key="ids"><elem>CVE:CVE-2010-2550</elem></table></table>
+</script></hostscript>
+</host>
+</nmaprun>"#;
+
+    #[test]
+    fn test_collect_findings() {
+        let findings = collect_findings(&parse_xml(SAMPLE).unwrap());
+        let ids: Vec<&str> = findings.iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "CVE-2023-38408",
+                "CVE-2023-28531",
+                "CVE-2016-20012",
+                "CVE-2017-0143"
+            ]
+        );
+        assert!(findings[0].exploit);
+        assert_eq!(findings[0].port_label(), "22/tcp");
+        assert_eq!(findings[1].cvss, Some(7.5));
+        assert_eq!(findings[3].port_label(), "-");
+        assert_eq!(findings[3].script, "smb-vuln-ms17-010");
+    }
+
+    #[test]
+    fn test_sort_findings() {
+        let mut findings = collect_findings(&parse_xml(SAMPLE).unwrap());
+        sort_findings(&mut findings, FindingSort::Id);
+        assert_eq!(findings[0].id, "CVE-2016-20012");
+        sort_findings(&mut findings, FindingSort::Port);
+        assert_eq!(findings[0].port, None);
+    }
+
+    #[test]
+    fn test_find_cve_ids() {
+        assert_eq!(
+            find_cve_ids("CVE-2021-44228, CVE-21-1 and CVE-2021-44228 again"),
+            vec!["CVE-2021-44228"]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::results::{
+    export::web_url,
+    model::{Port, ScanResults},
+};
+
+/// An open web port with what http-title and the server banner say about it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebService {
+    pub host: String,
+    pub port: u16,
+    pub url: String,
+    pub title: Option<String>,
+    /// Server header reported by http-server-header, or the product nmap detected
+    pub server: Option<String>,
+}
+
+/// Every open web port across the results, in host and port order
+pub fn collect_web_services(results: &ScanResults) -> Vec<WebService> {
+    results
+        .hosts
+        .iter()
+        .flat_map(|host| {
+            host.open_ports().filter_map(move |port| {
+                Some(WebService {
+                    host: host.address().to_string(),
+                    port: port.port,
+                    url: web_url(host, port)?,
+                    title: title(port),
+                    server: server(port),
+                })
+            })
+        })
+        .collect()
+}
+
+fn script_output<'a>(port: &'a Port, id: &str) -> Option<&'a str> {
+    port.scripts
+        .iter()
+        .find(|script| script.id == id)
+        .map(|script| script.output.trim())
+}
+
+fn title(port: &Port) -> Option<String> {
+    let script = port
+        .scripts
+        .iter()
+        .find(|script| script.id == "http-title")?;
+    // Pages without a title only get an explanation in the output
+    match script.data.elem("title") {
+        Some(title) => Some(title.trim().to_string()),
+        None if script.data.elems.is_empty() => {
+            let line = script.output.trim().lines().next()?;
+            (!line.starts_with("Site doesn't have a title")).then(|| line.to_string())
+        }
+        None => None,
+    }
+}
+
+fn server(port: &Port) -> Option<String> {
+    script_output(port, "http-server-header")
+        .map(str::to_string)
+        .or_else(|| {
+            port.service
+                .as_ref()
+                .map(|service| service.product_version())
+        })
+        .filter(|server| !server.is_empty())
+}
+
+/// Quote a CSV field if it contains a separator, a quote or a line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::parser::parse_xml;
+
+    #[test]
+    fn test_collect_web_services() {
+        let results = parse_xml(
+            r#"<nmaprun><host><status state="up"/><address addr="10.0.0.1" addrtype="ipv4"/><ports>
+<port protocol="tcp" portid="22"><state state="open"/><service name="ssh"/></port>
+<port protocol="tcp" portid="80"><state state="open"/><service name="http" product="nginx" version="1.18.0"/>
+<script id="http-title" output="Welcome, admin"><elem key="title">Welcome, admin</elem></script></port>
+<port protocol="tcp" portid="8080"><state state="open"/><service name="http-proxy"/>
+<script id="http-title" output="Site doesn't have a title (text/html)."/>
+<script id="http-server-header" output="Jetty(9.4.z)"><elem>Jetty(9.4.z)</elem></script></port>
+</ports></host></nmaprun>"#,
+        )
+        .unwrap();
+        let services = collect_web_services(&results);
+        assert_eq!(
+            services
+                .iter()
+                .map(|service| (
+                    service.url.as_str(),
+                    service.title.as_deref(),
+                    service.server.as_deref()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "http://10.0.0.1",
+                    Some("Welcome, admin"),
+                    Some("nginx 1.18.0")
+                ),
+                ("http://10.0.0.1:8080", None, Some("Jetty(9.4.z)")),
+            ]
+        );
+        assert_eq!(csv_field("Welcome, admin"), "\"Welcome, admin\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
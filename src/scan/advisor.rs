@@ -0,0 +1,331 @@
+use std::fs;
+use std::time::Duration;
+
+use crate::scan::{
+    duration::parse_time_spec,
+    model::{NmapScan, ScanTechnique, TimingTemplate},
+};
+
+/// Ports scanned when no port option is given
+const DEFAULT_PORTS: u64 = 1000;
+/// Ports scanned in fast mode (-F)
+const FAST_MODE_PORTS: u64 = 100;
+/// Probes sent per host during host discovery unless -Pn is given
+const DISCOVERY_PROBES: u64 = 4;
+/// Fraction of probes assumed to go unanswered and be retransmitted
+const RETRANSMIT_FRACTION: f64 = 0.1;
+/// UDP probes answered per second by each host, which rate-limit the ICMP port unreachable
+/// replies UDP scans wait for
+const UDP_HOST_RATE: f64 = 1.0;
+/// Send rates above this are unlikely to produce accurate results
+const MAX_SANE_RATE: f64 = 10_000.0;
+
+/// Rough estimate of how much work a scan will do and how long it will take
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanEstimate {
+    pub targets: u64,
+    pub ports: u64,
+    pub probes: u64,
+    pub rate: f64,
+}
+
+impl ScanEstimate {
+    pub fn duration(&self) -> Duration {
+        if self.rate <= 0.0 {
+            return Duration::MAX;
+        }
+        Duration::try_from_secs_f64(self.probes as f64 / self.rate).unwrap_or(Duration::MAX)
+    }
+}
+
+/// Suggested timing changes to fit a scan into a time budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateAdvice {
+    pub min_rate: Option<u32>,
+    pub max_retries: Option<u32>,
+    pub estimate: ScanEstimate,
+    pub warning: Option<String>,
+}
+
+/// Estimate the duration of a scan with its current timing settings
+pub fn estimate(scan: &NmapScan) -> ScanEstimate {
+    estimate_with(scan, scan.timing.min_rate, scan.timing.max_retries)
+}
+
+/// Suggest --min-rate and --max-retries values that fit the scan into the budget
+pub fn advise(scan: &NmapScan, budget: Duration) -> RateAdvice {
+    let current = estimate(scan);
+    if current.duration() <= budget {
+        return RateAdvice {
+            min_rate: None,
+            max_retries: None,
+            estimate: current,
+            warning: None,
+        };
+    }
+
+    let budget_secs = budget.as_secs_f64().max(1.0);
+    let mut max_retries = None;
+    let mut required =
+        estimate_with(scan, None, scan.timing.max_retries).probes as f64 / budget_secs;
+    if required > MAX_SANE_RATE {
+        // Fewer retransmissions is the cheapest way to cut the probe count
+        max_retries = Some(1);
+        required = estimate_with(scan, None, max_retries).probes as f64 / budget_secs;
+    }
+
+    let min_rate = required.ceil().min(u32::MAX as f64) as u32;
+    let estimate = estimate_with(
+        scan,
+        Some(min_rate),
+        max_retries.or(scan.timing.max_retries),
+    );
+    let warning = if let Some(max_rate) = scan.timing.max_rate.filter(|&rate| rate < min_rate) {
+        Some(format!(
+            "--max-rate {} caps the send rate below the required {} packets/s",
+            max_rate, min_rate
+        ))
+    } else if required > MAX_SANE_RATE {
+        Some("This budget needs an unrealistic send rate; scan fewer ports or targets".to_string())
+    } else {
+        None
+    };
+
+    RateAdvice {
+        min_rate: Some(min_rate),
+        max_retries,
+        estimate,
+        warning,
+    }
+}
+
+fn estimate_with(scan: &NmapScan, min_rate: Option<u32>, max_retries: Option<u32>) -> ScanEstimate {
+    let targets = count_targets(scan);
+    let ports = count_ports(scan);
+
+    let retries = max_retries.unwrap_or(match scan.timing.template {
+        Some(TimingTemplate::Aggressive) => 6,
+        Some(TimingTemplate::Insane) => 2,
+        _ => 10,
+    });
+    let discovery = if scan.host_discovery.skip_port_scan {
+        0
+    } else {
+        DISCOVERY_PROBES
+    };
+    let per_host = if scan.host_discovery.list_scan {
+        0.0
+    } else {
+        discovery as f64 + ports as f64 * (1.0 + RETRANSMIT_FRACTION * retries as f64)
+    };
+    let probes = (targets as f64 * per_host).ceil() as u64;
+
+    let mut rate = template_rate(scan.timing.template);
+    if let Some(min_rate) = min_rate {
+        rate = rate.max(min_rate as f64);
+    }
+    if let Some(max_rate) = scan.timing.max_rate {
+        rate = rate.min(max_rate as f64);
+    }
+    if let Some(delay) = scan.timing.scan_delay.as_deref().and_then(parse_time_spec)
+        && !delay.is_zero()
+    {
+        rate = rate.min(1.0 / delay.as_secs_f64());
+    }
+    if matches!(scan.scan_technique, ScanTechnique::Udp) {
+        rate = rate.min(targets.max(1) as f64 * UDP_HOST_RATE);
+    }
+
+    ScanEstimate {
+        targets,
+        ports,
+        probes,
+        rate,
+    }
+}
+
+/// Typical probes per second sent with each timing template
+fn template_rate(template: Option<TimingTemplate>) -> f64 {
+    match template {
+        Some(TimingTemplate::Paranoid) => 1.0 / 300.0,
+        Some(TimingTemplate::Sneaky) => 1.0 / 15.0,
+        Some(TimingTemplate::Polite) => 2.5,
+        Some(TimingTemplate::Normal) | None => 300.0,
+        Some(TimingTemplate::Aggressive) => 1000.0,
+        Some(TimingTemplate::Insane) => 3000.0,
+    }
+}
+
+/// Number of hosts the scan will target, expanding CIDR blocks and octet ranges
+pub fn count_targets(scan: &NmapScan) -> u64 {
+    let ts = &scan.target_specification;
+    let mut count = ts
+        .targets
+        .iter()
+        .map(|target| count_target(target))
+        .fold(0u64, u64::saturating_add);
+
+    if let Some(content) = ts
+        .input_file
+        .as_ref()
+        .and_then(|file| fs::read_to_string(file).ok())
+    {
+        count = content
+            .lines()
+            .flat_map(|line| line.split('#').next().unwrap_or("").split_whitespace())
+            .map(count_target)
+            .fold(count, u64::saturating_add);
+    }
+
+    count.saturating_add(ts.random_targets.unwrap_or(0) as u64)
+}
+
+/// Number of hosts in one target, e.g. 256 for "10.0.0.0/24"
+pub fn count_target(target: &str) -> u64 {
+    if let Some((address, prefix)) = target.split_once('/') {
+        let bits = if address.contains(':') { 128 } else { 32 };
+        return match prefix.parse::<u32>() {
+            Ok(prefix) if prefix <= bits => 1u64.checked_shl(bits - prefix).unwrap_or(u64::MAX),
+            _ => 1,
+        };
+    }
+
+    let octets: Vec<&str> = target.split('.').collect();
+    let is_ipv4_pattern = octets.len() == 4
+        && octets.iter().all(|octet| {
+            octet
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '-' || c == ',' || c == '*')
+        });
+    if !is_ipv4_pattern {
+        return 1;
+    }
+
+    octets
+        .iter()
+        .map(|octet| count_octet(octet))
+        .fold(1u64, u64::saturating_mul)
+}
+
+fn count_octet(octet: &str) -> u64 {
+    if octet == "*" {
+        return 256;
+    }
+    octet
+        .split(',')
+        .map(|part| match part.split_once('-') {
+            Some((start, end)) => {
+                let start: u64 = start.parse().unwrap_or(0);
+                let end: u64 = end.parse().unwrap_or(255);
+                end.saturating_sub(start) + 1
+            }
+            None => 1,
+        })
+        .sum()
+}
+
+/// Number of ports probed on each host
+pub fn count_ports(scan: &NmapScan) -> u64 {
+    let ps = &scan.ports;
+    if scan.host_discovery.ping_scan || scan.host_discovery.list_scan {
+        0
+    } else if let Some(ports) = &ps.ports {
+        count_port_spec(ports)
+    } else if let Some(top_ports) = ps.top_ports {
+        top_ports as u64
+    } else if ps.fast_mode {
+        FAST_MODE_PORTS
+    } else {
+        DEFAULT_PORTS
+    }
+}
+
+fn count_port_spec(spec: &str) -> u64 {
+    spec.split(',')
+        .map(|part| {
+            let part = part.split_once(':').map_or(part, |(_, ports)| ports).trim();
+            match part.split_once('-') {
+                Some((start, end)) => {
+                    let start: u64 = start.parse().unwrap_or(1);
+                    let end: u64 = end.parse().unwrap_or(65535);
+                    end.saturating_sub(start) + 1
+                }
+                None if part.is_empty() => 0,
+                None => 1,
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_targets() {
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets = vec![
+            "192.168.1.0/24".to_string(),
+            "10.0.0.1-10".to_string(),
+            "10.0.1,2.1".to_string(),
+            "scanme.nmap.org".to_string(),
+        ];
+        scan.target_specification.random_targets = Some(5);
+        assert_eq!(count_targets(&scan), 256 + 10 + 2 + 1 + 5);
+    }
+
+    #[test]
+    fn test_count_ports() {
+        let mut scan = NmapScan::new();
+        assert_eq!(count_ports(&scan), DEFAULT_PORTS);
+
+        scan.ports.fast_mode = true;
+        assert_eq!(count_ports(&scan), FAST_MODE_PORTS);
+
+        scan.ports.ports = Some("22,80,T:1000-1999,U:53".to_string());
+        assert_eq!(count_ports(&scan), 1003);
+
+        scan.ports.ports = Some("-".to_string());
+        assert_eq!(count_ports(&scan), 65535);
+    }
+
+    #[test]
+    fn test_estimate_respects_rate_limits() {
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets = vec!["10.0.0.1".to_string()];
+        scan.timing.min_rate = Some(5000);
+        scan.timing.max_rate = Some(2000);
+        assert_eq!(estimate(&scan).rate, 2000.0);
+
+        scan.timing.scan_delay = Some("1s".to_string());
+        assert_eq!(estimate(&scan).rate, 1.0);
+
+        scan.timing.scan_delay = None;
+        scan.scan_technique = ScanTechnique::Udp;
+        assert_eq!(estimate(&scan).rate, UDP_HOST_RATE);
+    }
+
+    #[test]
+    fn test_advise() {
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets = vec!["10.0.0.0/16".to_string()];
+
+        let advice = advise(&scan, Duration::from_secs(3600));
+        let min_rate = advice.min_rate.unwrap();
+        assert!(min_rate as f64 > template_rate(None));
+        assert!(advice.estimate.duration() <= Duration::from_secs(3600));
+
+        let advice = advise(&scan, Duration::from_secs(60));
+        assert_eq!(advice.max_retries, Some(1));
+        assert!(advice.warning.is_some());
+    }
+
+    #[test]
+    fn test_advise_within_budget() {
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets = vec!["10.0.0.1".to_string()];
+        let advice = advise(&scan, Duration::from_secs(3600));
+        assert_eq!(advice.min_rate, None);
+        assert_eq!(advice.max_retries, None);
+    }
+}
@@ -1,11 +1,14 @@
 use std::fmt::Write;
 
 use crate::scan::model::{
-    EvasionSpoofing, HostDiscovery, MiscOptions, NmapScan, OsDetection, OutputOptions,
-    PortSpecification, ScanTechnique, ScriptScan, ServiceDetection, TargetSpecification,
-    TimingPerformance,
+    DnsResolution, EvasionSpoofing, HostDiscovery, MiscOptions, NmapScan, OsDetection,
+    OutputOptions, PacketLayer, PortSpecification, Privileges, ScanTechnique, ScriptScan,
+    ServiceDetection, TargetSpecification, TimingPerformance,
 };
 
+/// Characters a shell treats specially in an unquoted word
+const SHELL_META: &str = " \t\n\"'\\$`;&|<>()*?[]{}~#!";
+
 /// Builder for converting NmapScan structs into command strings
 pub struct NmapCommandBuilder;
 
@@ -14,6 +17,12 @@ impl NmapCommandBuilder {
     pub fn build(scan: &NmapScan) -> String {
         let mut cmd = String::from("nmap");
 
+        // --resume cannot be combined with any other option
+        if let Some(ref resume) = scan.output.resume {
+            write!(cmd, " --resume {}", Self::quote_path(resume)).ok();
+            return cmd;
+        }
+
         // Host discovery
         Self::build_host_discovery(&mut cmd, &scan.host_discovery);
 
@@ -81,20 +90,30 @@ impl NmapCommandBuilder {
         if hd.icmp_netmask {
             cmd.push_str(" -PM");
         }
+        if hd.arp_ping {
+            cmd.push_str(" -PR");
+        }
         if !hd.ip_protocol_ping.is_empty() {
             write!(cmd, " -PO{}", Self::format_int_list(&hd.ip_protocol_ping)).ok();
         }
-        if hd.no_resolve {
-            cmd.push_str(" -n");
+        if hd.disable_arp_ping {
+            cmd.push_str(" --disable-arp-ping");
         }
-        if hd.always_resolve {
-            cmd.push_str(" -R");
+        match hd.resolution {
+            DnsResolution::Never => cmd.push_str(" -n"),
+            DnsResolution::Always => cmd.push_str(" -R"),
+            DnsResolution::Default => {}
         }
         if hd.traceroute {
             cmd.push_str(" --traceroute");
         }
         if !hd.dns_servers.is_empty() {
-            write!(cmd, " --dns-servers {}", hd.dns_servers.join(",")).ok();
+            write!(
+                cmd,
+                " --dns-servers {}",
+                Self::quote_if_needed(&hd.dns_servers.join(","))
+            )
+            .ok();
         }
         if hd.system_dns {
             cmd.push_str(" --system-dns");
@@ -113,7 +132,7 @@ impl NmapCommandBuilder {
             ScanTechnique::Fin => cmd.push_str(" -sF"),
             ScanTechnique::Xmas => cmd.push_str(" -sX"),
             ScanTechnique::Scanflags(flags) => {
-                write!(cmd, " --scanflags {}", Self::quote_if_needed(flags)).ok();
+                write!(cmd, " --scanflags {}", flags).ok();
             }
             ScanTechnique::Idle(zombie) => {
                 write!(cmd, " -sI {}", Self::quote_if_needed(zombie)).ok();
@@ -176,7 +195,12 @@ impl NmapCommandBuilder {
             cmd.push_str(" -sC");
         }
         if !ss.scripts.is_empty() {
-            write!(cmd, " --script {}", ss.scripts.join(",")).ok();
+            write!(
+                cmd,
+                " --script {}",
+                Self::quote_if_needed(&ss.scripts.join(","))
+            )
+            .ok();
         }
         if let Some(ref args) = ss.script_args {
             write!(cmd, " --script-args {}", Self::quote_if_needed(args)).ok();
@@ -295,7 +319,7 @@ impl NmapCommandBuilder {
             write!(cmd, " --mtu {}", mtu).ok();
         }
         if !es.decoys.is_empty() {
-            write!(cmd, " -D {}", es.decoys.join(",")).ok();
+            write!(cmd, " -D {}", Self::quote_if_needed(&es.decoys.join(","))).ok();
         }
         if let Some(ref spoof_ip) = es.spoof_ip {
             write!(cmd, " -S {}", spoof_ip).ok();
@@ -352,28 +376,12 @@ impl NmapCommandBuilder {
             write!(cmd, " -oA {}", Self::quote_if_needed(all_formats)).ok();
         }
 
-        // Handle verbose flag
-        match out.verbose {
-            0 => {}
-            1 => cmd.push_str(" -v"),
-            2 => cmd.push_str(" -vv"),
-            n => {
-                for _ in 0..n {
-                    cmd.push_str(" -v");
-                }
-            }
+        // Levels repeat the letter of the option, as in -vvv
+        if out.verbose > 0 {
+            write!(cmd, " -{}", "v".repeat(out.verbose as usize)).ok();
         }
-
-        // Handle debug flag
-        match out.debug {
-            0 => {}
-            1 => cmd.push_str(" -d"),
-            2 => cmd.push_str(" -dd"),
-            n => {
-                for _ in 0..n {
-                    cmd.push_str(" -d");
-                }
-            }
+        if out.debug > 0 {
+            write!(cmd, " -{}", "d".repeat(out.debug as usize)).ok();
         }
 
         if out.reason {
@@ -394,9 +402,6 @@ impl NmapCommandBuilder {
         if out.append_output {
             cmd.push_str(" --append-output");
         }
-        if let Some(ref resume) = out.resume {
-            write!(cmd, " --resume {}", Self::quote_path(resume)).ok();
-        }
         if let Some(ref stylesheet) = out.stylesheet {
             write!(cmd, " --stylesheet {}", Self::quote_path(stylesheet)).ok();
         }
@@ -418,17 +423,29 @@ impl NmapCommandBuilder {
         if let Some(ref datadir) = misc.datadir {
             write!(cmd, " --datadir {}", Self::quote_path(datadir)).ok();
         }
-        if misc.send_eth {
-            cmd.push_str(" --send-eth");
+        if let Some(ref servicedb) = misc.servicedb {
+            write!(cmd, " --servicedb {}", Self::quote_path(servicedb)).ok();
+        }
+        if let Some(ref versiondb) = misc.versiondb {
+            write!(cmd, " --versiondb {}", Self::quote_path(versiondb)).ok();
         }
-        if misc.send_ip {
-            cmd.push_str(" --send-ip");
+        if !misc.proxies.is_empty() {
+            write!(
+                cmd,
+                " --proxies {}",
+                Self::quote_if_needed(&misc.proxies.join(","))
+            )
+            .ok();
         }
-        if misc.privileged {
-            cmd.push_str(" --privileged");
+        match misc.packet_layer {
+            PacketLayer::Ethernet => cmd.push_str(" --send-eth"),
+            PacketLayer::Ip => cmd.push_str(" --send-ip"),
+            PacketLayer::Default => {}
         }
-        if misc.unprivileged {
-            cmd.push_str(" --unprivileged");
+        match misc.privileges {
+            Privileges::Privileged => cmd.push_str(" --privileged"),
+            Privileges::Unprivileged => cmd.push_str(" --unprivileged"),
+            Privileges::Default => {}
         }
         if misc.release_memory {
             cmd.push_str(" --release-memory");
@@ -445,6 +462,9 @@ impl NmapCommandBuilder {
         if misc.log_errors {
             cmd.push_str(" --log-errors");
         }
+        if misc.noninteractive {
+            cmd.push_str(" --noninteractive");
+        }
     }
 
     fn build_target_specification(cmd: &mut String, ts: &TargetSpecification) {
@@ -455,7 +475,12 @@ impl NmapCommandBuilder {
             write!(cmd, " -iR {}", random_targets).ok();
         }
         if !ts.exclude.is_empty() {
-            write!(cmd, " --exclude {}", ts.exclude.join(",")).ok();
+            write!(
+                cmd,
+                " --exclude {}",
+                Self::quote_if_needed(&ts.exclude.join(","))
+            )
+            .ok();
         }
         if let Some(ref exclude_file) = ts.exclude_file {
             write!(cmd, " --exclude-file {}", Self::quote_path(exclude_file)).ok();
@@ -476,12 +501,21 @@ impl NmapCommandBuilder {
             .join(",")
     }
 
+    /// Double-quote a value a shell would split or expand, escaping what still expands in quotes
     fn quote_if_needed(s: &str) -> String {
-        if s.contains(' ') || s.contains('\t') || s.contains('"') {
-            format!("\"{}\"", s.replace('\"', "\\\""))
-        } else {
-            s.to_string()
+        if !s.contains(|c| SHELL_META.contains(c)) {
+            return s.to_string();
+        }
+        let mut quoted = String::with_capacity(s.len() + 2);
+        quoted.push('"');
+        for c in s.chars() {
+            if matches!(c, '"' | '\\' | '$' | '`') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
         }
+        quoted.push('"');
+        quoted
     }
 
     fn quote_path(path: &std::path::Path) -> String {
@@ -554,6 +588,10 @@ mod tests {
 
         let cmd = NmapCommandBuilder::build(&scan);
         assert!(cmd.contains("--script vuln,exploit"));
+
+        scan.script_scan.scripts = vec!["default and safe".to_string(), "http-title".to_string()];
+        let cmd = NmapCommandBuilder::build(&scan);
+        assert!(cmd.contains("--script \"default and safe,http-title\""));
     }
 
     #[test]
@@ -564,8 +602,9 @@ mod tests {
         scan.output.debug = 3;
 
         let cmd = NmapCommandBuilder::build(&scan);
-        assert!(cmd.contains("-vv"));
-        assert!(cmd.matches("-d").count() == 3);
+        assert!(cmd.contains(" -vv "));
+        assert!(cmd.contains(" -ddd"));
+        assert_eq!(cmd.matches("-d").count(), 1);
     }
 
     #[test]
@@ -609,7 +648,7 @@ mod tests {
         scan.host_discovery.ack_discovery = vec![22];
         scan.host_discovery.udp_discovery = vec![53];
         scan.host_discovery.icmp_echo = true;
-        scan.host_discovery.no_resolve = true;
+        scan.host_discovery.resolution = DnsResolution::Never;
         scan.host_discovery.dns_servers = vec!["8.8.8.8".to_string(), "1.1.1.1".to_string()];
 
         let cmd = NmapCommandBuilder::build(&scan);
@@ -681,6 +720,17 @@ mod tests {
         assert!(cmd.contains(" scanme.nmap.org"));
     }
 
+    #[test]
+    fn test_resume() {
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets = vec!["192.168.1.0/24".to_string()];
+        scan.output.verbose = 1;
+        scan.output.resume = Some(PathBuf::from("scan.nmap"));
+
+        let cmd = NmapCommandBuilder::build(&scan);
+        assert_eq!(cmd, "nmap --resume scan.nmap");
+    }
+
     #[test]
     fn test_misc_flags() {
         let mut scan = NmapScan::new();
@@ -693,4 +743,454 @@ mod tests {
         assert!(cmd.contains(" -A"));
         assert!(cmd.contains(" example.com"));
     }
+
+    mod properties {
+        use std::net::IpAddr;
+
+        use proptest::prelude::*;
+
+        use crate::scan::{model::*, parser::NmapParser, tcp_flags::TcpFlags};
+
+        use super::*;
+
+        /// A value typed into a field, possibly with spaces, quotes and shell metacharacters
+        fn value() -> impl Strategy<Value = String> {
+            "[a-zA-Z0-9._:/][a-zA-Z0-9._:/ \t\"'\\\\$`;&|<>()*?{}\\[\\]~#!=-]{0,12}"
+        }
+
+        /// An entry of a comma-separated list
+        fn item() -> impl Strategy<Value = String> {
+            "[a-zA-Z0-9._:/][a-zA-Z0-9._:/ \"'\\\\$`;&|<>()*?~#=-]{0,8}"
+        }
+
+        fn items() -> impl Strategy<Value = Vec<String>> {
+            prop::collection::vec(item(), 0..4)
+        }
+
+        /// URLs the parser accepts for --proxies
+        fn proxies() -> impl Strategy<Value = Vec<String>> {
+            prop::collection::vec(
+                "(http|socks4)://[a-z0-9][a-z0-9.-]{0,15}(:[1-9][0-9]{0,3})?",
+                0..4,
+            )
+        }
+
+        /// Ports and protocols without repeats, as the parser keeps only the first of each
+        fn numbers() -> impl Strategy<Value = Vec<u32>> {
+            prop::collection::vec(0..65536u32, 0..4).prop_map(|mut numbers| {
+                crate::scan::parser::dedupe(&mut numbers);
+                numbers
+            })
+        }
+
+        /// Port specifications as the parser normalizes them: unique entries joined by commas
+        fn port_list() -> impl Strategy<Value = String> {
+            prop::collection::vec("([TUSP]:)?[0-9]{1,5}(-[0-9]{1,5})?|[a-z*?]{1,6}|-", 1..4)
+                .prop_map(|mut ports| {
+                    crate::scan::parser::dedupe(&mut ports);
+                    ports.join(",")
+                })
+        }
+
+        fn path() -> impl Strategy<Value = Option<std::path::PathBuf>> {
+            prop::option::of(value().prop_map(Into::into))
+        }
+
+        fn targets() -> impl Strategy<Value = TargetSpecification> {
+            (
+                prop::collection::vec(value(), 0..4),
+                path(),
+                any::<Option<u32>>(),
+                items(),
+                path(),
+            )
+                .prop_map(
+                    |(targets, input_file, random_targets, exclude, exclude_file)| {
+                        TargetSpecification {
+                            targets,
+                            input_file,
+                            random_targets,
+                            exclude,
+                            exclude_file,
+                        }
+                    },
+                )
+        }
+
+        fn host_discovery() -> impl Strategy<Value = HostDiscovery> {
+            (
+                any::<[bool; 11]>(),
+                [numbers(), numbers(), numbers(), numbers(), numbers()],
+                items(),
+                prop_oneof![
+                    Just(DnsResolution::Default),
+                    Just(DnsResolution::Never),
+                    Just(DnsResolution::Always),
+                ],
+            )
+                .prop_map(
+                    |(flags, [syn, ack, udp, sctp, protocols], dns_servers, resolution)| {
+                        HostDiscovery {
+                            list_scan: flags[0],
+                            ping_scan: flags[1],
+                            skip_port_scan: flags[2],
+                            syn_discovery: syn,
+                            ack_discovery: ack,
+                            udp_discovery: udp,
+                            sctp_discovery: sctp,
+                            icmp_echo: flags[3],
+                            icmp_timestamp: flags[4],
+                            icmp_netmask: flags[5],
+                            arp_ping: flags[7],
+                            ip_protocol_ping: protocols,
+                            disable_arp_ping: flags[6],
+                            resolution,
+                            dns_servers,
+                            system_dns: flags[8],
+                            traceroute: flags[9],
+                        }
+                    },
+                )
+        }
+
+        fn scan_technique() -> impl Strategy<Value = ScanTechnique> {
+            prop_oneof![
+                Just(ScanTechnique::Syn),
+                Just(ScanTechnique::Connect),
+                Just(ScanTechnique::Ack),
+                Just(ScanTechnique::Window),
+                Just(ScanTechnique::Maimon),
+                Just(ScanTechnique::Udp),
+                Just(ScanTechnique::TcpNull),
+                Just(ScanTechnique::Fin),
+                Just(ScanTechnique::Xmas),
+                (1..=255u8).prop_map(|bits| ScanTechnique::Scanflags(
+                    bits.to_string().parse::<TcpFlags>().unwrap()
+                )),
+                value().prop_map(ScanTechnique::Idle),
+                Just(ScanTechnique::SctpInit),
+                Just(ScanTechnique::SctpCookie),
+                Just(ScanTechnique::IpProtocol),
+                value().prop_map(ScanTechnique::Ftp),
+            ]
+        }
+
+        fn ports() -> impl Strategy<Value = PortSpecification> {
+            (
+                prop::option::of(port_list()),
+                prop::option::of(port_list()),
+                any::<[bool; 2]>(),
+                any::<Option<u32>>(),
+                prop::option::of(0.0..1.0f32),
+            )
+                .prop_map(|(ports, exclude_ports, flags, top_ports, port_ratio)| {
+                    PortSpecification {
+                        ports,
+                        exclude_ports,
+                        fast_mode: flags[0],
+                        consecutive_ports: flags[1],
+                        top_ports,
+                        port_ratio,
+                    }
+                })
+        }
+
+        fn detection() -> impl Strategy<Value = (ServiceDetection, ScriptScan, OsDetection)> {
+            (
+                any::<[bool; 10]>(),
+                any::<Option<u32>>(),
+                items(),
+                [prop::option::of(value()), prop::option::of(value())],
+                path(),
+                any::<Option<u32>>(),
+            )
+                .prop_map(
+                    |(flags, intensity, scripts, [script_args, script_help], args_file, tries)| {
+                        (
+                            ServiceDetection {
+                                enabled: flags[0],
+                                intensity,
+                                light: flags[1],
+                                all: flags[2],
+                                trace: flags[3],
+                            },
+                            ScriptScan {
+                                default: flags[4],
+                                scripts,
+                                script_args,
+                                script_args_file: args_file,
+                                script_trace: flags[5],
+                                script_updatedb: flags[6],
+                                script_help,
+                            },
+                            OsDetection {
+                                enabled: flags[7],
+                                limit: flags[8],
+                                guess: flags[9],
+                                max_retries: tries,
+                            },
+                        )
+                    },
+                )
+        }
+
+        fn timing() -> impl Strategy<Value = TimingPerformance> {
+            (
+                prop::option::of((0..6usize).prop_map(|i| TimingTemplate::from_index(i).unwrap())),
+                any::<[Option<u32>; 7]>(),
+                [
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                ],
+                any::<[bool; 2]>(),
+            )
+                .prop_map(|(template, numbers, strings, flags)| {
+                    let [
+                        min_rtt,
+                        max_rtt,
+                        initial_rtt,
+                        host,
+                        script,
+                        delay,
+                        max_delay,
+                        engine,
+                    ] = strings;
+                    TimingPerformance {
+                        template,
+                        min_hostgroup: numbers[0],
+                        max_hostgroup: numbers[1],
+                        min_parallelism: numbers[2],
+                        max_parallelism: numbers[3],
+                        min_rtt_timeout: min_rtt,
+                        max_rtt_timeout: max_rtt,
+                        initial_rtt_timeout: initial_rtt,
+                        max_retries: numbers[4],
+                        host_timeout: host,
+                        script_timeout: script,
+                        scan_delay: delay,
+                        max_scan_delay: max_delay,
+                        min_rate: numbers[5],
+                        max_rate: numbers[6],
+                        defeat_rst_ratelimit: flags[0],
+                        defeat_icmp_ratelimit: flags[1],
+                        nsock_engine: engine,
+                    }
+                })
+        }
+
+        fn evasion() -> impl Strategy<Value = EvasionSpoofing> {
+            (
+                any::<[bool; 4]>(),
+                any::<[Option<u32>; 4]>(),
+                items(),
+                any::<Option<IpAddr>>(),
+                [
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                    prop::option::of(value()),
+                ],
+            )
+                .prop_map(|(flags, numbers, decoys, spoof_ip, strings)| {
+                    let [interface, data, data_string, ip_options, spoof_mac] = strings;
+                    EvasionSpoofing {
+                        fragment_packets: flags[0],
+                        mtu: numbers[0],
+                        decoys,
+                        spoof_ip,
+                        interface,
+                        source_port: numbers[1],
+                        data,
+                        data_string,
+                        data_length: numbers[2],
+                        ip_options,
+                        ttl: numbers[3],
+                        randomize_hosts: flags[1],
+                        spoof_mac,
+                        badsum: flags[2],
+                        adler32: flags[3],
+                    }
+                })
+        }
+
+        /// Output options without --resume, which drops every other option
+        fn output() -> impl Strategy<Value = OutputOptions> {
+            (
+                [path(), path(), path(), path(), path()],
+                prop::option::of(value()),
+                [0..=MAX_LEVEL, 0..=MAX_LEVEL],
+                prop::option::of(value()),
+                any::<[bool; 7]>(),
+            )
+                .prop_map(
+                    |(
+                        [normal, xml, script_kiddie, grepable, stylesheet],
+                        all,
+                        levels,
+                        stats,
+                        flags,
+                    )| {
+                        OutputOptions {
+                            normal,
+                            xml,
+                            script_kiddie,
+                            grepable,
+                            all_formats: all,
+                            verbose: levels[0],
+                            debug: levels[1],
+                            reason: flags[0],
+                            stats_every: stats,
+                            packet_trace: flags[1],
+                            open_only: flags[2],
+                            iflist: flags[3],
+                            append_output: flags[4],
+                            resume: None,
+                            stylesheet,
+                            webxml: flags[5],
+                            no_stylesheet: flags[6],
+                        }
+                    },
+                )
+        }
+
+        fn misc() -> impl Strategy<Value = MiscOptions> {
+            (
+                any::<[bool; 11]>(),
+                [path(), path(), path()],
+                proxies(),
+                prop_oneof![
+                    Just(PacketLayer::Default),
+                    Just(PacketLayer::Ethernet),
+                    Just(PacketLayer::Ip),
+                ],
+                prop_oneof![
+                    Just(Privileges::Default),
+                    Just(Privileges::Privileged),
+                    Just(Privileges::Unprivileged),
+                ],
+            )
+                .prop_map(
+                    |(
+                        flags,
+                        [datadir, servicedb, versiondb],
+                        proxies,
+                        packet_layer,
+                        privileges,
+                    )| {
+                        MiscOptions {
+                            ipv6: flags[0],
+                            aggressive: flags[1],
+                            datadir,
+                            servicedb,
+                            versiondb,
+                            proxies,
+                            packet_layer,
+                            privileges,
+                            release_memory: flags[6],
+                            version: flags[7],
+                            help: flags[8],
+                            unique: flags[9],
+                            log_errors: flags[10],
+                            noninteractive: flags[2],
+                        }
+                    },
+                )
+        }
+
+        fn scan() -> impl Strategy<Value = NmapScan> {
+            (
+                targets(),
+                host_discovery(),
+                scan_technique(),
+                ports(),
+                detection(),
+                timing(),
+                evasion(),
+                output(),
+                misc(),
+            )
+                .prop_map(
+                    |(
+                        targets,
+                        discovery,
+                        technique,
+                        ports,
+                        detection,
+                        timing,
+                        evasion,
+                        output,
+                        misc,
+                    )| {
+                        let (service_detection, script_scan, os_detection) = detection;
+                        NmapScan {
+                            notes: String::new(),
+                            target_specification: targets,
+                            host_discovery: discovery,
+                            scan_technique: technique,
+                            ports,
+                            service_detection,
+                            script_scan,
+                            os_detection,
+                            timing,
+                            evasion,
+                            output,
+                            misc,
+                        }
+                    },
+                )
+        }
+
+        /// Characters of a command a shell would act on: metacharacters outside double quotes,
+        /// and inside them the ones that still expand unless escaped
+        fn unescaped_meta(command: &str) -> Vec<char> {
+            let mut found = Vec::new();
+            let mut in_quotes = false;
+            let mut chars = command.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => in_quotes = !in_quotes,
+                    '\\' if in_quotes => match chars.next() {
+                        Some('"' | '\\' | '$' | '`') => {}
+                        other => found.extend(other),
+                    },
+                    '$' | '`' if in_quotes => found.push(c),
+                    ' ' if !in_quotes => {}
+                    c if !in_quotes && SHELL_META.contains(c) => found.push(c),
+                    _ => {}
+                }
+            }
+            if in_quotes {
+                found.push('"');
+            }
+            found
+        }
+
+        proptest! {
+            #[test]
+            fn test_parse_inverts_build(scan in scan()) {
+                let command = NmapCommandBuilder::build(&scan);
+                let parsed = NmapParser::parse(&command)
+                    .map_err(|err| TestCaseError::fail(format!("{}: {}", err, command)))?;
+                prop_assert_eq!(
+                    serde_json::to_value(&parsed).unwrap(),
+                    serde_json::to_value(&scan).unwrap(),
+                    "{}",
+                    command
+                );
+            }
+
+            #[test]
+            fn test_build_escapes_shell_metacharacters(scan in scan()) {
+                let command = NmapCommandBuilder::build(&scan);
+                prop_assert_eq!(unescaped_meta(&command), Vec::<char>::new(), "{}", command);
+            }
+        }
+    }
 }
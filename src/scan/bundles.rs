@@ -0,0 +1,106 @@
+use serde_json::Value;
+
+use crate::scan::{
+    model::NmapScan,
+    parser::{NmapParser, ParseError},
+};
+
+/// Options turned on and off together by a quick toggle, such as the UDP essentials
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionBundle {
+    /// JSON pointer of each scan field the options set, with its value when on and when off
+    fields: Vec<(String, Value, Value)>,
+}
+
+impl OptionBundle {
+    /// The bundle of options given on a command line, such as "-sU --top-ports 100"
+    pub fn parse(options: &str) -> Result<Self, ParseError> {
+        let on = NmapParser::parse(&format!("nmap {}", options))?;
+        let mut fields = Vec::new();
+        diff(
+            String::new(),
+            to_value(&on),
+            to_value(&NmapScan::new()),
+            &mut fields,
+        );
+        Ok(Self { fields })
+    }
+
+    /// Whether the scan has every option of the bundle
+    pub fn is_applied(&self, scan: &NmapScan) -> bool {
+        let value = to_value(scan);
+        self.fields
+            .iter()
+            .all(|(pointer, on, _)| value.pointer(pointer) == Some(on))
+    }
+
+    /// Set the options of the bundle, leaving the others alone
+    pub fn apply(&self, scan: &mut NmapScan) {
+        self.set(scan, |(_, on, _)| on);
+    }
+
+    /// Unset the options of the bundle, leaving the others alone
+    pub fn remove(&self, scan: &mut NmapScan) {
+        self.set(scan, |(_, _, off)| off);
+    }
+
+    fn set(&self, scan: &mut NmapScan, pick: impl Fn(&(String, Value, Value)) -> &Value) {
+        let mut value = to_value(scan);
+        for field in &self.fields {
+            if let Some(target) = value.pointer_mut(&field.0) {
+                *target = pick(field).clone();
+            }
+        }
+        if let Ok(changed) = serde_json::from_value(value) {
+            *scan = changed;
+        }
+    }
+}
+
+fn to_value(scan: &NmapScan) -> Value {
+    serde_json::to_value(scan).unwrap_or_default()
+}
+
+/// Collect the fields that differ between two scans, down to the leaves both have
+fn diff(pointer: String, on: Value, off: Value, fields: &mut Vec<(String, Value, Value)>) {
+    match (on, off) {
+        (Value::Object(on), Value::Object(mut off)) => {
+            for (key, on) in on {
+                let off = off.remove(&key).unwrap_or_default();
+                diff(format!("{}/{}", pointer, key), on, off, fields);
+            }
+        }
+        (on, off) if on != off => fields.push((pointer, on, off)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::model::ScanTechnique;
+
+    #[test]
+    fn test_bundle() {
+        let bundle =
+            OptionBundle::parse("-sU --top-ports 100 --version-intensity 0 --max-retries 1")
+                .unwrap();
+        let mut scan = NmapParser::parse("nmap -sV -T4 --max-retries 3 10.0.0.1").unwrap();
+        assert!(!bundle.is_applied(&scan));
+
+        bundle.apply(&mut scan);
+        assert!(bundle.is_applied(&scan));
+        assert!(matches!(scan.scan_technique, ScanTechnique::Udp));
+        assert_eq!(scan.ports.top_ports, Some(100));
+        assert_eq!(scan.service_detection.intensity, Some(0));
+        assert_eq!(scan.timing.max_retries, Some(1));
+        assert!(scan.service_detection.enabled);
+
+        bundle.remove(&mut scan);
+        assert!(!bundle.is_applied(&scan));
+        assert!(matches!(scan.scan_technique, ScanTechnique::Syn));
+        assert_eq!(scan.timing.max_retries, None);
+        assert_eq!(scan.target_specification.targets, ["10.0.0.1"]);
+        assert!(OptionBundle::parse("--top-ports many").is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::scan::{builder::NmapCommandBuilder, model::NmapScan, parser::NmapParser};
+
+/// One option of two compared scans, with its value on each side if present
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionDiff {
+    pub flag: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl OptionDiff {
+    pub fn is_different(&self) -> bool {
+        self.left != self.right
+    }
+}
+
+/// Split the command built from a scan into (flag, value) pairs, with targets listed last
+pub fn options(scan: &NmapScan) -> Vec<(String, String)> {
+    let mut without_targets = scan.clone();
+    without_targets.target_specification.targets.clear();
+    let command = NmapCommandBuilder::build(&without_targets);
+
+    let mut options: Vec<(String, String)> = Vec::new();
+    for token in NmapParser::tokenize(&command).into_iter().skip(1) {
+        // "-" on its own is a value, as in "-p -"
+        let is_flag = token.len() > 1
+            && token.starts_with('-')
+            && !token[1..].starts_with(|c: char| c.is_ascii_digit());
+        match options.last_mut() {
+            Some((_, value)) if !is_flag => {
+                if !value.is_empty() {
+                    value.push(' ');
+                }
+                value.push_str(&token);
+            }
+            _ => options.push(split_attached_value(&token)),
+        }
+    }
+
+    let targets = &scan.target_specification.targets;
+    if !targets.is_empty() {
+        options.push(("targets".to_string(), targets.join(" ")));
+    }
+    options
+}
+
+/// Split flags that carry their value, such as "-T4" or "-PS22,80", into flag and value
+pub fn split_attached_value(token: &str) -> (String, String) {
+    let prefix = ["-PS", "-PA", "-PU", "-PY", "-PO", "-T"]
+        .into_iter()
+        .find(|prefix| token.len() > prefix.len() && token.starts_with(prefix));
+    match prefix {
+        Some(prefix) => (prefix.to_string(), token[prefix.len()..].to_string()),
+        None => (token.to_string(), String::new()),
+    }
+}
+
+/// Compare the options of two scans, merged in the order the builder emits them
+pub fn compare(left: &NmapScan, right: &NmapScan) -> Vec<OptionDiff> {
+    let left = options(left);
+    let right = options(right);
+    let value_of = |options: &[(String, String)], flag: &str| {
+        options
+            .iter()
+            .find(|(other, _)| other == flag)
+            .map(|(_, value)| value.clone())
+    };
+    let right_only = |(flag, value): &(String, String)| OptionDiff {
+        flag: flag.clone(),
+        left: None,
+        right: Some(value.clone()),
+    };
+
+    let mut diffs = Vec::new();
+    let mut next_right = 0;
+    for (flag, value) in &left {
+        if let Some(offset) = right[next_right..]
+            .iter()
+            .position(|(other, _)| other == flag)
+        {
+            let skipped = &right[next_right..next_right + offset];
+            diffs.extend(
+                skipped
+                    .iter()
+                    .filter(|(other, _)| value_of(&left, other).is_none())
+                    .map(right_only),
+            );
+            next_right += offset + 1;
+        }
+        diffs.push(OptionDiff {
+            flag: flag.clone(),
+            left: Some(value.clone()),
+            right: value_of(&right, flag),
+        });
+    }
+    diffs.extend(
+        right[next_right..]
+            .iter()
+            .filter(|(other, _)| value_of(&left, other).is_none())
+            .map(right_only),
+    );
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options() {
+        let scan = NmapParser::parse("nmap -sV -p - --max-retries 2 10.0.0.1 10.0.0.2").unwrap();
+        assert_eq!(
+            options(&scan),
+            vec![
+                ("-sS".to_string(), String::new()),
+                ("-p".to_string(), "-".to_string()),
+                ("-sV".to_string(), String::new()),
+                ("--max-retries".to_string(), "2".to_string()),
+                ("targets".to_string(), "10.0.0.1 10.0.0.2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let quick = NmapParser::parse("nmap -F -T4").unwrap();
+        let thorough = NmapParser::parse("nmap -p - -sV -T3").unwrap();
+        let diffs = compare(&quick, &thorough);
+        let flags: Vec<&str> = diffs.iter().map(|diff| diff.flag.as_str()).collect();
+        assert_eq!(flags, vec!["-sS", "-F", "-p", "-sV", "-T"]);
+        assert!(!diffs[0].is_different());
+        assert_eq!(diffs[1].right, None);
+        assert_eq!(diffs[2].left, None);
+        assert_eq!(diffs[2].right.as_deref(), Some("-"));
+        assert_eq!(diffs[4].left.as_deref(), Some("4"));
+        assert_eq!(diffs[4].right.as_deref(), Some("3"));
+        assert!(diffs.iter().skip(1).all(OptionDiff::is_different));
+    }
+}
@@ -0,0 +1,184 @@
+use std::io;
+
+use crate::{error::Error, scan::runner::RunStatus};
+
+/// Human-readable explanation of why an nmap run failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnosis {
+    pub problem: String,
+    pub suggestion: String,
+}
+
+impl Diagnosis {
+    fn new(problem: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self {
+            problem: problem.into(),
+            suggestion: suggestion.into(),
+        }
+    }
+
+    /// Explain a failure to start the nmap process at all
+    pub fn from_spawn_error(err: &Error) -> Self {
+        let kind = match err {
+            Error::Nmap(err) => Some(err.kind()),
+            _ => None,
+        };
+        match kind {
+            Some(io::ErrorKind::NotFound) => Self::new(
+                "nmap executable not found",
+                "Install nmap or make sure it is on your PATH",
+            ),
+            Some(io::ErrorKind::PermissionDenied) => Self::new(
+                "Not allowed to execute nmap",
+                "Check the permissions of the nmap binary",
+            ),
+            _ if err.is_transient() => Self::new(err.to_string(), "The problem may pass"),
+            _ => Self::new(err.to_string(), "Check your nmap installation"),
+        }
+    }
+}
+
+/// Summarize the stderr of a finished run into a list of known problems
+pub fn diagnose(status: RunStatus, stderr: &[String]) -> Vec<Diagnosis> {
+    // Interrupted runs are resumable rather than failed
+    if !matches!(status, RunStatus::Exited(code) if code != 0) {
+        return Vec::new();
+    }
+
+    let mut diagnoses: Vec<Diagnosis> = Vec::new();
+    for line in stderr {
+        if let Some(diagnosis) = diagnose_line(line)
+            && !diagnoses.contains(&diagnosis)
+        {
+            diagnoses.push(diagnosis);
+        }
+    }
+
+    if diagnoses.is_empty() {
+        let last_error = stderr
+            .iter()
+            .rev()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty() && *line != "QUITTING!")
+            .unwrap_or("nmap exited with an error");
+        diagnoses.push(Diagnosis::new(
+            last_error,
+            "See the output pane for the full error message",
+        ));
+    }
+
+    diagnoses
+}
+
+fn diagnose_line(line: &str) -> Option<Diagnosis> {
+    let lower = line.to_lowercase();
+
+    if lower.contains("requires root privileges") {
+        Some(Diagnosis::new(
+            "The selected scan type requires root privileges",
+            "Run lazynmap with sudo, or switch to a connect scan (-sT)",
+        ))
+    } else if lower.contains("operation not permitted") || lower.contains("dnet: failed to open") {
+        Some(Diagnosis::new(
+            "Raw packet access was denied",
+            "Run lazynmap with sudo, or use --unprivileged with a connect scan (-sT)",
+        ))
+    } else if lower.starts_with("failed to resolve") {
+        let name = line.split('"').nth(1).unwrap_or("target");
+        Some(Diagnosis::new(
+            format!("Failed to resolve \"{}\"", name),
+            "Check the spelling of the target, or set --dns-servers",
+        ))
+    } else if lower.contains("which was specified by -e")
+        || lower.contains("unable to find interface")
+    {
+        Some(Diagnosis::new(
+            "The network interface could not be found",
+            "Check the -e interface name (nmap --iflist lists valid interfaces)",
+        ))
+    } else if lower.contains("no targets were specified") {
+        Some(Diagnosis::new(
+            "No targets were specified",
+            "Add targets, an input file (-iL) or random targets (-iR)",
+        ))
+    } else if lower.contains("failed to open") && lower.contains("output file") {
+        Some(Diagnosis::new(
+            "An output file could not be opened for writing",
+            "Check that the output directory exists and is writable",
+        ))
+    } else if lower.contains("unrecognized option") || lower.contains("invalid option") {
+        Some(Diagnosis::new(
+            line.trim(),
+            "Your nmap version may not support this option; check nmap --version",
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_successful_run() {
+        assert!(diagnose(RunStatus::Exited(0), &lines(&["warning"])).is_empty());
+    }
+
+    #[test]
+    fn test_root_required() {
+        let stderr = lines(&[
+            "You requested a scan type which requires root privileges.",
+            "QUITTING!",
+        ]);
+        let diagnoses = diagnose(RunStatus::Exited(1), &stderr);
+        assert_eq!(diagnoses.len(), 1);
+        assert!(diagnoses[0].suggestion.contains("sudo"));
+    }
+
+    #[test]
+    fn test_failed_to_resolve() {
+        let stderr = lines(&[
+            "Failed to resolve \"nosuchhost.invalid\".",
+            "Failed to resolve \"nosuchhost.invalid\".",
+        ]);
+        let diagnoses = diagnose(RunStatus::Exited(1), &stderr);
+        assert_eq!(diagnoses.len(), 1);
+        assert_eq!(
+            diagnoses[0].problem,
+            "Failed to resolve \"nosuchhost.invalid\""
+        );
+    }
+
+    #[test]
+    fn test_interface_not_found() {
+        let stderr = lines(&["Could not find interface eth9 which was specified by -e"]);
+        let diagnoses = diagnose(RunStatus::Exited(1), &stderr);
+        assert!(diagnoses[0].suggestion.contains("-e"));
+    }
+
+    #[test]
+    fn test_unknown_error() {
+        let stderr = lines(&["Something odd happened", "QUITTING!"]);
+        let diagnoses = diagnose(RunStatus::Exited(255), &stderr);
+        assert_eq!(diagnoses[0].problem, "Something odd happened");
+    }
+
+    #[test]
+    fn test_spawn_error() {
+        let missing = Error::Nmap(io::Error::from(io::ErrorKind::NotFound));
+        assert_eq!(
+            Diagnosis::from_spawn_error(&missing).problem,
+            "nmap executable not found"
+        );
+        let busy = Error::Nmap(io::Error::from(io::ErrorKind::ExecutableFileBusy));
+        assert_eq!(
+            Diagnosis::from_spawn_error(&busy).suggestion,
+            "The problem may pass"
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use crate::scan::{flags::NmapFlag, model::NmapScan};
+
+/// Where the targets are, relative to the machine running nmap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Local,
+    Remote,
+}
+
+/// How firewalls between nmap and the targets treat discovery probes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icmp {
+    Allowed,
+    Blocked,
+    /// Hosts answer no probe at all
+    Filtered,
+}
+
+/// Answers to the host discovery wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiscoveryAnswers {
+    pub network: Network,
+    pub icmp: Icmp,
+    /// Whether nmap runs as root, which raw packet probes need
+    pub privileged: bool,
+}
+
+/// Host discovery probes suited to the answers, with why each was chosen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryStrategy {
+    pub icmp_echo: bool,
+    pub syn_ports: Vec<u32>,
+    pub ack_ports: Vec<u32>,
+    pub skip_discovery: bool,
+    pub reasons: Vec<&'static str>,
+}
+
+impl DiscoveryStrategy {
+    /// Flags the strategy sets, which any other discovery probes are cleared from
+    pub const FLAGS: [NmapFlag; 9] = [
+        NmapFlag::SkipPortScan,
+        NmapFlag::SynDiscovery,
+        NmapFlag::AckDiscovery,
+        NmapFlag::UdpDiscovery,
+        NmapFlag::SctpDiscovery,
+        NmapFlag::IcmpEcho,
+        NmapFlag::IcmpTimestamp,
+        NmapFlag::IcmpNetmask,
+        NmapFlag::IpProtocolPing,
+    ];
+
+    /// Replace the discovery probes of the scan with those of the strategy
+    pub fn apply(&self, scan: &mut NmapScan) {
+        let hd = &mut scan.host_discovery;
+        hd.skip_port_scan = self.skip_discovery;
+        hd.icmp_echo = self.icmp_echo;
+        hd.syn_discovery = self.syn_ports.clone();
+        hd.ack_discovery = self.ack_ports.clone();
+        hd.udp_discovery.clear();
+        hd.sctp_discovery.clear();
+        hd.icmp_timestamp = false;
+        hd.icmp_netmask = false;
+        hd.ip_protocol_ping.clear();
+    }
+
+    /// The flags as they appear on the command line, empty when nmap's defaults apply
+    pub fn flags(&self) -> String {
+        let ports = |ports: &[u32]| {
+            ports
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let mut flags = Vec::new();
+        if self.skip_discovery {
+            flags.push("-Pn".to_string());
+        }
+        if self.icmp_echo {
+            flags.push("-PE".to_string());
+        }
+        if !self.syn_ports.is_empty() {
+            flags.push(format!("-PS{}", ports(&self.syn_ports)));
+        }
+        if !self.ack_ports.is_empty() {
+            flags.push(format!("-PA{}", ports(&self.ack_ports)));
+        }
+        flags.join(" ")
+    }
+}
+
+/// Choose discovery probes for the network, firewall and privileges described by the answers
+pub fn recommend(answers: DiscoveryAnswers) -> DiscoveryStrategy {
+    let mut strategy = DiscoveryStrategy {
+        icmp_echo: false,
+        syn_ports: Vec::new(),
+        ack_ports: Vec::new(),
+        skip_discovery: false,
+        reasons: Vec::new(),
+    };
+
+    if answers.icmp == Icmp::Filtered {
+        strategy.skip_discovery = true;
+        strategy.reasons.extend([
+            "-Pn: hosts that answer no probe would be reported down, so every target is treated as up",
+            "Every address gets port scanned, so scans of sparse ranges take much longer",
+        ]);
+        return strategy;
+    }
+
+    match (answers.network, answers.privileged) {
+        (Network::Local, true) => strategy.reasons.extend([
+            "On the local network nmap finds hosts with ARP or IPv6 neighbor discovery",
+            "Host firewalls can't drop ARP, so no extra probes are needed",
+        ]),
+        (Network::Local, false) => {
+            strategy.syn_ports = vec![22, 80, 135, 443, 445, 3389];
+            strategy.reasons.extend([
+                "Without root nmap can't send ARP requests and falls back to connecting to ports",
+                "-PS: connects to ports that Linux and Windows hosts commonly have open",
+            ]);
+        }
+        (Network::Remote, false) => {
+            strategy.syn_ports = vec![22, 80, 443, 3389, 8080];
+            strategy.reasons.extend([
+                "Without root ICMP and ACK probes need raw sockets nmap can't open",
+                "-PS: a connection attempt to common service ports finds hosts that run them",
+            ]);
+        }
+        (Network::Remote, true) => {
+            strategy.icmp_echo = answers.icmp == Icmp::Allowed;
+            strategy.syn_ports = vec![21, 22, 25, 80, 443, 3389];
+            strategy.ack_ports = vec![80, 443];
+            if strategy.icmp_echo {
+                strategy
+                    .reasons
+                    .push("-PE: echo requests find hosts that answer ping");
+            } else {
+                strategy
+                    .reasons
+                    .push("ICMP is blocked, so echo requests would only add timeouts");
+                strategy.syn_ports.push(8080);
+            }
+            strategy.reasons.extend([
+                "-PS: SYN probes to common ports find hosts that drop ICMP but run services",
+                "-PA: ACK probes get through stateless firewalls that only block new connections",
+            ]);
+        }
+    }
+    strategy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommend() {
+        let answers = |network, icmp, privileged| DiscoveryAnswers {
+            network,
+            icmp,
+            privileged,
+        };
+        assert_eq!(
+            recommend(answers(Network::Local, Icmp::Allowed, true)).flags(),
+            ""
+        );
+        assert_eq!(
+            recommend(answers(Network::Remote, Icmp::Allowed, true)).flags(),
+            "-PE -PS21,22,25,80,443,3389 -PA80,443"
+        );
+        assert_eq!(
+            recommend(answers(Network::Remote, Icmp::Blocked, true)).flags(),
+            "-PS21,22,25,80,443,3389,8080 -PA80,443"
+        );
+        assert_eq!(
+            recommend(answers(Network::Remote, Icmp::Blocked, false)).flags(),
+            "-PS22,80,443,3389,8080"
+        );
+        assert_eq!(
+            recommend(answers(Network::Local, Icmp::Filtered, false)).flags(),
+            "-Pn"
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut scan = NmapScan::new();
+        scan.host_discovery.skip_port_scan = true;
+        scan.host_discovery.udp_discovery = vec![53];
+        scan.host_discovery.icmp_timestamp = true;
+        let strategy = recommend(DiscoveryAnswers {
+            network: Network::Remote,
+            icmp: Icmp::Allowed,
+            privileged: true,
+        });
+        strategy.apply(&mut scan);
+
+        let hd = &scan.host_discovery;
+        assert!(!hd.skip_port_scan);
+        assert!(hd.icmp_echo);
+        assert!(!hd.icmp_timestamp);
+        assert!(hd.udp_discovery.is_empty());
+        assert_eq!(hd.ack_discovery, vec![80, 443]);
+    }
+}
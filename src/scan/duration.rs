@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+/// Parse an nmap time specification such as "500ms", "30s", "5m" or "2h"; bare numbers are seconds
+pub fn parse_time_spec(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (number, multiplier) = if let Some(number) = s.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = s.strip_suffix('s') {
+        (number, 1.0)
+    } else if let Some(number) = s.strip_suffix('m') {
+        (number, 60.0)
+    } else if let Some(number) = s.strip_suffix('h') {
+        (number, 3600.0)
+    } else {
+        (s, 1.0)
+    };
+
+    let value: f64 = number.parse().ok()?;
+    if !value.is_finite() || value < 0.0 {
+        return None;
+    }
+    Some(Duration::from_secs_f64(value * multiplier))
+}
+
+/// Format a duration as a compact human-readable string, e.g. "1h05m" or "42s"
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 86400 {
+        format!("{}d{:02}h", secs / 86400, secs % 86400 / 3600)
+    } else if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Format a unix timestamp as "YYYY-MM-DD HH:MM" in UTC
+pub fn format_timestamp(secs: u64) -> String {
+    let (year, month, day) = civil_date(secs);
+    let time = secs % 86400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60
+    )
+}
+
+/// Format a unix timestamp as "YYYY-MM-DDTHH:MM:SS" in UTC, as NSE scripts report times
+pub fn format_iso_timestamp(secs: u64) -> String {
+    let (year, month, day) = civil_date(secs);
+    let time = secs % 86400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Format a unix timestamp as "YYYYMMDD-HHMMSS" in UTC, for adding to file names
+pub fn format_file_stamp(secs: u64) -> String {
+    let (year, month, day) = civil_date(secs);
+    let time = secs % 86400;
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Year, month and day of a unix timestamp
+fn civil_date(secs: u64) -> (i64, i64, i64) {
+    let days = (secs / 86400) as i64;
+
+    // Convert days since the epoch to a civil date (Howard Hinnant's algorithm)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_spec() {
+        assert_eq!(parse_time_spec("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_time_spec("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_time_spec("5m"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_time_spec("2h"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_time_spec("1.5"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_time_spec("soon"), None);
+        assert_eq!(parse_time_spec("-5s"), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m05s");
+        assert_eq!(format_duration(Duration::from_secs(3900)), "1h05m");
+        assert_eq!(format_duration(Duration::from_secs(90000)), "1d01h");
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+        assert_eq!(format_timestamp(1_704_067_199), "2023-12-31 23:59");
+        assert_eq!(format_file_stamp(1_704_067_199), "20231231-235959");
+        assert_eq!(format_iso_timestamp(1_704_067_199), "2023-12-31T23:59:59");
+    }
+}
@@ -0,0 +1,69 @@
+use std::{collections::HashSet, fs, io};
+
+use crate::scan::model::NmapScan;
+
+/// Leave hosts out of later runs by appending them to the file the scan reads --exclude-file
+/// from, or to --exclude without one. Returns the hosts added, skipping those already excluded
+pub fn exclude_hosts(scan: &mut NmapScan, hosts: &[String]) -> io::Result<Vec<String>> {
+    let ts = &mut scan.target_specification;
+    let Some(file) = ts.exclude_file.as_ref() else {
+        let mut excluded: HashSet<String> = ts.exclude.iter().cloned().collect();
+        let added: Vec<String> = hosts
+            .iter()
+            .filter(|host| excluded.insert(host.to_string()))
+            .cloned()
+            .collect();
+        ts.exclude.extend(added.iter().cloned());
+        return Ok(added);
+    };
+    let mut content = match fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    let mut excluded: HashSet<String> = content
+        .lines()
+        .map(|line| line.trim().to_string())
+        .collect();
+    let added: Vec<String> = hosts
+        .iter()
+        .filter(|host| excluded.insert(host.to_string()))
+        .cloned()
+        .collect();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for host in &added {
+        content.push_str(host);
+        content.push('\n');
+    }
+    fs::write(file, content)?;
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclude_hosts() {
+        let hosts = vec!["10.0.0.1".to_string(), "10.0.0.2".to_string()];
+        let mut scan = NmapScan::new();
+        scan.target_specification.exclude = vec!["10.0.0.1".to_string()];
+        assert_eq!(exclude_hosts(&mut scan, &hosts).unwrap(), vec!["10.0.0.2"]);
+        assert_eq!(
+            scan.target_specification.exclude,
+            vec!["10.0.0.1", "10.0.0.2"]
+        );
+
+        let file = std::env::temp_dir().join(format!("lazynmap-exclude-{}", std::process::id()));
+        fs::write(&file, "10.0.0.2").unwrap();
+        scan.target_specification.exclude_file = Some(file.clone());
+        let added = exclude_hosts(&mut scan, &hosts).unwrap();
+        let content = fs::read_to_string(&file).unwrap();
+        fs::remove_file(&file).ok();
+        assert_eq!(added, vec!["10.0.0.1"]);
+        assert_eq!(content, "10.0.0.2\n10.0.0.1\n");
+        assert_eq!(scan.target_specification.exclude.len(), 2);
+    }
+}
@@ -1,10 +1,18 @@
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use strum::{EnumCount, IntoEnumIterator};
 use strum_macros::{Display, EnumIter, EnumMessage};
 
-use crate::scan::model::{NmapScan, TimingTemplate};
+use crate::scan::{
+    model::{DnsResolution, NmapScan, ScanTechnique, TimingPerformance, TimingTemplate},
+    rate_presets::RatePreset,
+    scripts::CategorySelection,
+    tcp_flags::TcpFlags,
+};
 
-#[derive(Debug, Display, Clone, Copy, Eq, Hash, PartialEq, EnumIter, EnumMessage)]
+#[derive(
+    Debug, Display, Clone, Copy, Eq, Hash, PartialEq, EnumIter, EnumMessage, Serialize, Deserialize,
+)]
 pub enum NmapFlag {
     // Target specification
     #[strum(
@@ -57,24 +65,115 @@ pub enum NmapFlag {
     IpProtocolPing,
     #[strum(to_string = "System DNS (--system-dns)")]
     SystemDns,
-    #[strum(to_string = "No resolve (-n)")]
-    NoResolve,
-    #[strum(to_string = "Always Resolve (-R)")]
-    AlwaysResolve,
+    #[strum(to_string = "DNS resolution (-n/-R)")]
+    DnsResolution,
+    #[strum(to_string = "No ARP (--disable-arp-ping)")]
+    DisableArpPing,
     #[strum(to_string = "DNS servers (--dns-servers)", message = "Server list")]
     DnsServers,
 
+    // Scan technique
+    #[strum(to_string = "TCP flags (--scanflags)")]
+    Scanflags,
+    #[strum(to_string = "FTP bounce (-b)", message = "[user:pass@]server[:port]")]
+    FtpRelay,
+
+    // Port specification
+    #[strum(
+        to_string = "Port ratio (--port-ratio)",
+        message = "0 to 1, how often the port is found open"
+    )]
+    PortRatio,
+
     #[strum(to_string = "Timing template")]
     TimingTemplate,
+    #[strum(to_string = "Environment preset")]
+    RatePreset,
+    #[strum(
+        to_string = "Host timeout (--host-timeout)",
+        message = "e.g. 30m, space for presets"
+    )]
+    HostTimeout,
+    #[strum(
+        to_string = "Script timeout (--script-timeout)",
+        message = "e.g. 2m, space for presets"
+    )]
+    ScriptTimeout,
+
+    // Script scan
+    #[strum(to_string = "Default scripts (-sC)")]
+    ScriptDefault,
+    #[strum(
+        to_string = "Scripts (--script)",
+        message = "Scripts, categories or expressions"
+    )]
+    Scripts,
+    #[strum(
+        to_string = "Script args (--script-args)",
+        message = "key=value, space for one per line"
+    )]
+    ScriptArgs,
+    #[strum(to_string = "Script categories")]
+    ScriptCategories,
+
+    // Evasion and spoofing
+    #[strum(
+        to_string = "Source port (-g)",
+        message = "Port number, space for presets"
+    )]
+    SourcePort,
+    #[strum(
+        to_string = "Spoof MAC (--spoof-mac)",
+        message = "0, vendor, prefix or MAC, space to search vendors"
+    )]
+    SpoofMac,
+    #[strum(
+        to_string = "IP options (--ip-options)",
+        message = "R, T, U, L or S with hops, or hex, space to build"
+    )]
+    IpOptions,
+    #[strum(
+        to_string = "Interface (-e)",
+        message = "Device name, space to list interfaces"
+    )]
+    Interface,
+    #[strum(
+        to_string = "Proxies (--proxies)",
+        message = "http:// or socks4:// URLs, comma-separated"
+    )]
+    Proxies,
+    #[strum(to_string = "Payload (--data)", message = "Hex bytes, e.g. 0xdeadbeef")]
+    Data,
+
+    // Output
+    #[strum(to_string = "Verbosity (-v)")]
+    Verbosity,
+    #[strum(to_string = "Debug level (-d)")]
+    DebugLevel,
+    #[strum(
+        to_string = "Stats every (--stats-every)",
+        message = "e.g. 10s, space for presets"
+    )]
+    StatsEvery,
 }
 
 pub enum FlagValue<'a> {
     Bool(&'a mut bool),
     Int(&'a mut Option<u32>),
+    Float(&'a mut Option<f32>),
+    String(&'a mut Option<String>),
     VecInt(&'a mut Vec<u32>),
     VecString(&'a mut Vec<String>),
     Path(&'a mut Option<PathBuf>),
     TimingTemplate(&'a mut Option<TimingTemplate>),
+    /// All the timing options, set together by an environment preset
+    RatePreset(&'a mut TimingPerformance),
+    ScriptCategories(&'a mut Vec<String>),
+    Scanflags(&'a mut ScanTechnique),
+    FtpRelay(&'a mut ScanTechnique),
+    DnsResolution(&'a mut DnsResolution),
+    /// A level from 0 to `MAX_LEVEL`, given as a repeated option
+    Level(&'a mut u32),
 }
 
 impl NmapFlag {
@@ -105,11 +204,41 @@ impl NmapFlag {
                 FlagValue::VecInt(&mut scan.host_discovery.ip_protocol_ping)
             }
             NmapFlag::SystemDns => FlagValue::Bool(&mut scan.host_discovery.system_dns),
-            NmapFlag::NoResolve => FlagValue::Bool(&mut scan.host_discovery.no_resolve),
-            NmapFlag::AlwaysResolve => FlagValue::Bool(&mut scan.host_discovery.always_resolve),
+            NmapFlag::DisableArpPing => FlagValue::Bool(&mut scan.host_discovery.disable_arp_ping),
+            NmapFlag::DnsResolution => {
+                FlagValue::DnsResolution(&mut scan.host_discovery.resolution)
+            }
             NmapFlag::DnsServers => FlagValue::VecString(&mut scan.host_discovery.dns_servers),
 
+            NmapFlag::Scanflags => FlagValue::Scanflags(&mut scan.scan_technique),
+            NmapFlag::FtpRelay => FlagValue::FtpRelay(&mut scan.scan_technique),
+
+            NmapFlag::PortRatio => FlagValue::Float(&mut scan.ports.port_ratio),
+
             NmapFlag::TimingTemplate => FlagValue::TimingTemplate(&mut scan.timing.template),
+            NmapFlag::RatePreset => FlagValue::RatePreset(&mut scan.timing),
+            NmapFlag::HostTimeout => FlagValue::String(&mut scan.timing.host_timeout),
+            NmapFlag::ScriptTimeout => FlagValue::String(&mut scan.timing.script_timeout),
+
+            // Script scan
+            NmapFlag::ScriptDefault => FlagValue::Bool(&mut scan.script_scan.default),
+            NmapFlag::Scripts => FlagValue::VecString(&mut scan.script_scan.scripts),
+            NmapFlag::ScriptArgs => FlagValue::String(&mut scan.script_scan.script_args),
+            NmapFlag::ScriptCategories => {
+                FlagValue::ScriptCategories(&mut scan.script_scan.scripts)
+            }
+
+            // Evasion and spoofing
+            NmapFlag::SourcePort => FlagValue::Int(&mut scan.evasion.source_port),
+            NmapFlag::SpoofMac => FlagValue::String(&mut scan.evasion.spoof_mac),
+            NmapFlag::IpOptions => FlagValue::String(&mut scan.evasion.ip_options),
+            NmapFlag::Interface => FlagValue::String(&mut scan.evasion.interface),
+            NmapFlag::Proxies => FlagValue::VecString(&mut scan.misc.proxies),
+            NmapFlag::Data => FlagValue::String(&mut scan.evasion.data),
+
+            NmapFlag::Verbosity => FlagValue::Level(&mut scan.output.verbose),
+            NmapFlag::DebugLevel => FlagValue::Level(&mut scan.output.debug),
+            NmapFlag::StatsEvery => FlagValue::String(&mut scan.output.stats_every),
         }
     }
 
@@ -134,7 +263,311 @@ impl NmapFlag {
     pub fn get_variant_count(self) -> Option<usize> {
         match self {
             NmapFlag::TimingTemplate => Some(TimingTemplate::COUNT),
+            NmapFlag::RatePreset => Some(RatePreset::COUNT),
+            NmapFlag::ScriptCategories => Some(CategorySelection::ITEMS),
+            NmapFlag::Scanflags => Some(TcpFlags::CHECKBOXES),
+            _ => None,
+        }
+    }
+
+    /// Common values that space cycles through before clearing the flag
+    pub fn presets(self) -> Option<&'static [&'static str]> {
+        match self {
+            NmapFlag::HostTimeout | NmapFlag::ScriptTimeout => Some(&["1m", "5m", "15m"]),
+            NmapFlag::StatsEvery => Some(&["2s", "5s", "10s", "30s", "1m"]),
+            NmapFlag::SourcePort => Some(&["53", "20", "88", "443"]),
+            _ => None,
+        }
+    }
+
+    /// One of the options most scans need, the only ones shown in beginner mode
+    pub fn is_common(self) -> bool {
+        self.description().is_some()
+    }
+
+    /// Longer explanation of a common option, shown while it is focused in beginner mode
+    pub fn description(self) -> Option<&'static str> {
+        match self {
+            NmapFlag::Targets => Some(
+                "What to scan: hostnames, addresses like 10.0.0.1, networks like 10.0.0.0/24 \
+                 or ranges like 10.0.0.1-20",
+            ),
+            NmapFlag::InputFile => {
+                Some("A file listing one target per line, handy for long or shared lists")
+            }
+            NmapFlag::Exclude => {
+                Some("Hosts or networks to leave out, such as your own machine or fragile devices")
+            }
+            NmapFlag::ExcludeFile => Some("A file listing hosts or networks to leave out"),
+            NmapFlag::ListScan => Some(
+                "Only list the targets and resolve their names, without sending them any packets",
+            ),
+            NmapFlag::PingScan => {
+                Some("Find which hosts are up without scanning their ports, a quick network sweep")
+            }
+            NmapFlag::SkipPortScan => {
+                Some("Treat every target as up and scan it anyway, for hosts that ignore pings")
+            }
+            NmapFlag::Traceroute => {
+                Some("Trace the network path to each host, shown as a tree in the results")
+            }
+            NmapFlag::SynDiscovery => Some(
+                "Check whether hosts are up by sending TCP SYN packets to these ports, \
+                 e.g. 22,80,443",
+            ),
+            NmapFlag::AckDiscovery => Some(
+                "Check whether hosts are up with TCP ACK packets, which get past some \
+                 stateless firewalls",
+            ),
+            NmapFlag::UdpDiscovery => {
+                Some("Check whether hosts are up with UDP packets, for hosts that filter TCP")
+            }
+            NmapFlag::IcmpEcho => Some(
+                "Check whether hosts are up with a classic ping, often blocked on the internet",
+            ),
+            NmapFlag::DnsResolution => Some(
+                "Skip reverse DNS lookups of the targets with -n, which makes large scans \
+                 faster, or look up every target with -R, even those that are down",
+            ),
+            NmapFlag::TimingTemplate => Some(
+                "How fast to scan: slower templates are stealthier and gentler, \
+                 faster ones may miss ports on slow networks",
+            ),
+            NmapFlag::HostTimeout => Some("Give up on a host that takes longer than this"),
+            NmapFlag::ScriptDefault => Some(
+                "Run the default set of scripts, which gather extra details and are safe \
+                 for most networks",
+            ),
+            NmapFlag::Scripts => {
+                Some("Scripts to run by name, category or expression, e.g. http-title or vuln")
+            }
+            NmapFlag::ScriptArgs => {
+                Some("Settings passed to the scripts, such as credentials or paths to try")
+            }
+            NmapFlag::ScriptCategories => Some(
+                "Pick whole categories of scripts; 'not' excludes one, 'match all' requires \
+                 every checked category",
+            ),
+            NmapFlag::Interface => {
+                Some("Network interface to send packets from, when the machine has more than one")
+            }
+            NmapFlag::Verbosity => Some(
+                "How much nmap reports while it runs, such as open ports as soon as they are \
+                 found; + and - change the level",
+            ),
+            NmapFlag::StatsEvery => Some(
+                "How often nmap reports its progress, which the progress bar is drawn from; \
+                 clear it to keep the output quiet",
+            ),
+            _ => None,
+        }
+    }
+
+    /// Advice shown while the flag is focused
+    pub fn tip(self) -> Option<&'static str> {
+        match self {
+            NmapFlag::SourcePort => Some(
+                "Filters that only check the source port often let replies from DNS (53), \
+                 FTP data (20), Kerberos (88) and HTTPS (443) through",
+            ),
+            NmapFlag::FtpRelay => {
+                Some("Rarely works anymore: modern FTP servers refuse PORT commands to other hosts")
+            }
+            NmapFlag::Proxies => Some(
+                "Only version detection and scripts connect through the proxies; host discovery \
+                 and port scanning still reach the targets directly",
+            ),
+            NmapFlag::Data => {
+                Some("nmap refuses --data together with --data-string or --data-length")
+            }
+            _ => None,
+        }
+    }
+
+    /// The nmap option the flag sets, e.g. "--exclude"
+    pub fn option(self) -> Option<&'static str> {
+        match self {
+            NmapFlag::Targets | NmapFlag::ScriptCategories => None,
+            NmapFlag::InputFile => Some("-iL"),
+            NmapFlag::Exclude => Some("--exclude"),
+            NmapFlag::ExcludeFile => Some("--exclude-file"),
+            NmapFlag::RandomTargets => Some("-iR"),
+            NmapFlag::ListScan => Some("-sL"),
+            NmapFlag::PingScan => Some("-sn"),
+            NmapFlag::SkipPortScan => Some("-Pn"),
+            NmapFlag::Traceroute => Some("--traceroute"),
+            NmapFlag::SynDiscovery => Some("-PS"),
+            NmapFlag::AckDiscovery => Some("-PA"),
+            NmapFlag::UdpDiscovery => Some("-PU"),
+            NmapFlag::SctpDiscovery => Some("-PY"),
+            NmapFlag::IcmpEcho => Some("-PE"),
+            NmapFlag::IcmpTimestamp => Some("-PP"),
+            NmapFlag::IcmpNetmask => Some("-PM"),
+            NmapFlag::IpProtocolPing => Some("-PO"),
+            NmapFlag::SystemDns => Some("--system-dns"),
+            NmapFlag::DisableArpPing => Some("--disable-arp-ping"),
+            NmapFlag::DnsResolution => Some("-n"),
+            NmapFlag::Verbosity => Some("-v"),
+            NmapFlag::DebugLevel => Some("-d"),
+            NmapFlag::StatsEvery => Some("--stats-every"),
+            NmapFlag::DnsServers => Some("--dns-servers"),
+            NmapFlag::Scanflags => Some("--scanflags"),
+            NmapFlag::FtpRelay => Some("-b"),
+            NmapFlag::PortRatio => Some("--port-ratio"),
+            NmapFlag::TimingTemplate => Some("-T"),
+            NmapFlag::RatePreset => None,
+            NmapFlag::HostTimeout => Some("--host-timeout"),
+            NmapFlag::ScriptTimeout => Some("--script-timeout"),
+            NmapFlag::ScriptDefault => Some("-sC"),
+            NmapFlag::Scripts => Some("--script"),
+            NmapFlag::ScriptArgs => Some("--script-args"),
+            NmapFlag::SourcePort => Some("-g"),
+            NmapFlag::SpoofMac => Some("--spoof-mac"),
+            NmapFlag::IpOptions => Some("--ip-options"),
+            NmapFlag::Interface => Some("-e"),
+            NmapFlag::Proxies => Some("--proxies"),
+            NmapFlag::Data => Some("--data"),
+        }
+    }
+
+    /// The flag set by an option as typed on the command line, with or without its value, e.g.
+    /// "--script=vuln", "-T4" or "-PS22,80"
+    pub fn from_option(option: &str) -> Option<Self> {
+        let option = option.trim();
+        if let Some(long) = option.strip_prefix("--") {
+            let name = long.split('=').next().unwrap_or_default();
+            if name == "source-port" {
+                return Some(NmapFlag::SourcePort);
+            }
+            return NmapFlag::iter()
+                .find(|flag| flag.option().and_then(|o| o.strip_prefix("--")) == Some(name));
+        }
+        if !option.starts_with('-') {
+            return None;
+        }
+        if option == "-R" {
+            return Some(NmapFlag::DnsResolution);
+        }
+        // Short options take their value without a space, so the longest one that starts the
+        // text is the one typed
+        NmapFlag::iter()
+            .filter_map(|flag| Some((flag, flag.option()?)))
+            .filter(|(_, short)| !short.starts_with("--") && option.starts_with(short))
+            .max_by_key(|(_, short)| short.len())
+            .map(|(flag, _)| flag)
+    }
+
+    /// Why the flag has no effect with the rest of the scan, naming what would enable it
+    pub fn unmet_dependency(self, scan: &NmapScan) -> Option<&'static str> {
+        let hd = &scan.host_discovery;
+        match self {
+            NmapFlag::SynDiscovery
+            | NmapFlag::AckDiscovery
+            | NmapFlag::UdpDiscovery
+            | NmapFlag::SctpDiscovery
+            | NmapFlag::IcmpEcho
+            | NmapFlag::IcmpTimestamp
+            | NmapFlag::IcmpNetmask
+            | NmapFlag::IpProtocolPing
+                if hd.skip_port_scan =>
+            {
+                Some("Host discovery is off, clear -Pn to use probes")
+            }
+            NmapFlag::SystemDns | NmapFlag::DnsServers if hd.resolution == DnsResolution::Never => {
+                Some("DNS resolution is off, clear -n to use it")
+            }
+            NmapFlag::Traceroute if hd.list_scan => {
+                Some("A list scan sends no packets, clear -sL to trace routes")
+            }
+            NmapFlag::ScriptArgs
+                if scan.script_scan.scripts.is_empty()
+                    && !scan.script_scan.default
+                    && !scan.misc.aggressive =>
+            {
+                Some("No scripts run, set --script or -sC to pass them arguments")
+            }
             _ => None,
         }
     }
 }
+
+/// The preset after the current value, wrapping to none after the last one
+pub fn next_preset(presets: &[&'static str], current: Option<&str>) -> Option<&'static str> {
+    match current.and_then(|current| presets.iter().position(|preset| *preset == current)) {
+        Some(index) => presets.get(index + 1).copied(),
+        None if current.is_some_and(|current| !current.is_empty()) => None,
+        None => presets.first().copied(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_preset() {
+        let presets = NmapFlag::HostTimeout.presets().unwrap();
+        assert_eq!(next_preset(presets, None), Some("1m"));
+        assert_eq!(next_preset(presets, Some("1m")), Some("5m"));
+        assert_eq!(next_preset(presets, Some("15m")), None);
+        // A value typed by hand is cleared, so the next press starts over
+        assert_eq!(next_preset(presets, Some("90s")), None);
+        assert_eq!(
+            next_preset(NmapFlag::SourcePort.presets().unwrap(), Some("88")),
+            Some("443")
+        );
+        assert_eq!(NmapFlag::Targets.presets(), None);
+    }
+
+    #[test]
+    fn test_from_option() {
+        assert_eq!(NmapFlag::from_option("--exclude"), Some(NmapFlag::Exclude));
+        assert_eq!(
+            NmapFlag::from_option(" --exclude-file "),
+            Some(NmapFlag::ExcludeFile)
+        );
+        assert_eq!(
+            NmapFlag::from_option("--script=vuln"),
+            Some(NmapFlag::Scripts)
+        );
+        assert_eq!(NmapFlag::from_option("-T4"), Some(NmapFlag::TimingTemplate));
+        assert_eq!(
+            NmapFlag::from_option("-PS22,80"),
+            Some(NmapFlag::SynDiscovery)
+        );
+        assert_eq!(NmapFlag::from_option("-Pn"), Some(NmapFlag::SkipPortScan));
+        assert_eq!(NmapFlag::from_option("-R"), Some(NmapFlag::DnsResolution));
+        assert_eq!(
+            NmapFlag::from_option("--source-port"),
+            Some(NmapFlag::SourcePort)
+        );
+        assert_eq!(NmapFlag::from_option("--osscan-guess"), None);
+        assert_eq!(NmapFlag::from_option("exclude"), None);
+        for flag in NmapFlag::iter().filter(|flag| flag.option().is_some()) {
+            assert_eq!(NmapFlag::from_option(flag.option().unwrap()), Some(flag));
+        }
+    }
+
+    #[test]
+    fn test_common_flags() {
+        let common = NmapFlag::iter().filter(|flag| flag.is_common()).count();
+        assert_eq!(common, 22);
+        assert!(NmapFlag::first().is_common());
+        assert!(!NmapFlag::IpOptions.is_common());
+    }
+
+    #[test]
+    fn test_unmet_dependency() {
+        let mut scan = NmapScan::new();
+        assert!(NmapFlag::ScriptArgs.unmet_dependency(&scan).is_some());
+        assert!(NmapFlag::SynDiscovery.unmet_dependency(&scan).is_none());
+
+        scan.script_scan.default = true;
+        scan.host_discovery.skip_port_scan = true;
+        scan.host_discovery.resolution = DnsResolution::Never;
+        assert!(NmapFlag::ScriptArgs.unmet_dependency(&scan).is_none());
+        assert!(NmapFlag::SynDiscovery.unmet_dependency(&scan).is_some());
+        assert!(NmapFlag::DnsServers.unmet_dependency(&scan).is_some());
+        assert!(NmapFlag::Targets.unmet_dependency(&scan).is_none());
+    }
+}
@@ -0,0 +1,240 @@
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::scan::targets::target_range;
+
+/// An address of a network interface, as listed by `nmap --iflist`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interface {
+    pub device: String,
+    /// Address with its prefix length, such as 192.168.1.10/24
+    pub address: String,
+    pub kind: String,
+    pub up: bool,
+    pub mtu: Option<u32>,
+    pub mac: Option<String>,
+}
+
+/// A route of the routing table nmap sees
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub destination: String,
+    pub device: String,
+    pub metric: Option<u32>,
+    pub gateway: Option<String>,
+}
+
+/// Interfaces and routes reported by `nmap --iflist`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InterfaceList {
+    pub interfaces: Vec<Interface>,
+    pub routes: Vec<Route>,
+}
+
+impl InterfaceList {
+    /// Names of the interfaces, each listed once, in the order nmap lists them
+    pub fn devices(&self) -> Vec<String> {
+        let mut devices: Vec<String> = Vec::new();
+        for interface in &self.interfaces {
+            if !devices.contains(&interface.device) {
+                devices.push(interface.device.clone());
+            }
+        }
+        devices
+    }
+
+    /// Interface of the IPv4 default route with the lowest metric, which nmap uses without -e
+    pub fn default_device(&self) -> Option<&str> {
+        self.routes
+            .iter()
+            .filter(|route| route.destination == "0.0.0.0/0")
+            .min_by_key(|route| route.metric.unwrap_or(u32::MAX))
+            .map(|route| route.device.as_str())
+    }
+
+    /// The interface whose subnet holds every target, if any, on which nmap discovers hosts by
+    /// ARP or neighbor discovery rather than with the -P probes
+    pub fn local_interface(&self, targets: &[String]) -> Option<&Interface> {
+        let ranges = targets
+            .iter()
+            .map(|target| target_range(target))
+            .collect::<Option<Vec<_>>>()?;
+        if ranges.is_empty() {
+            return None;
+        }
+        self.interfaces
+            .iter()
+            .filter(|interface| interface.up && interface.kind != "loopback")
+            .find(|interface| {
+                target_range(&interface.address)
+                    .is_some_and(|subnet| ranges.iter().all(|range| subnet.contains(*range)))
+            })
+    }
+}
+
+/// Parse the INTERFACES and ROUTES tables printed by `nmap --iflist`
+pub fn parse_iflist(stdout: &str) -> InterfaceList {
+    enum Table {
+        None,
+        Interfaces,
+        Routes,
+    }
+
+    let mut list = InterfaceList::default();
+    let mut table = Table::None;
+    for line in stdout.lines() {
+        if line.starts_with('*') {
+            table = if line.contains("INTERFACES") {
+                Table::Interfaces
+            } else if line.contains("ROUTES") {
+                Table::Routes
+            } else {
+                Table::None
+            };
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match table {
+            Table::Interfaces if fields.len() >= 5 && fields[0] != "DEV" => {
+                list.interfaces.push(Interface {
+                    device: fields[0].to_string(),
+                    address: fields[2].to_string(),
+                    kind: fields[3].to_string(),
+                    up: fields[4] == "up",
+                    mtu: fields.get(5).and_then(|mtu| mtu.parse().ok()),
+                    mac: fields.get(6).map(|mac| mac.to_string()),
+                });
+            }
+            Table::Routes if fields.len() >= 2 && fields[0] != "DST/MASK" => {
+                list.routes.push(Route {
+                    destination: fields[0].to_string(),
+                    device: fields[1].to_string(),
+                    metric: fields.get(2).and_then(|metric| metric.parse().ok()),
+                    gateway: fields.get(3).map(|gateway| gateway.to_string()),
+                });
+            }
+            _ => {}
+        }
+    }
+    list
+}
+
+/// Output of `nmap --iflist`, fetched in the background
+pub struct IflistFetch {
+    receiver: Option<Receiver<Result<InterfaceList, String>>>,
+    result: Option<Result<InterfaceList, String>>,
+}
+
+impl IflistFetch {
+    /// Start running `nmap --iflist`
+    pub fn start() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let result = Command::new("nmap")
+                .arg("--iflist")
+                .stdin(Stdio::null())
+                .output()
+                .map_err(|err| format!("Failed to run nmap: {}", err))
+                .and_then(|output| {
+                    let list = parse_iflist(&String::from_utf8_lossy(&output.stdout));
+                    if output.status.success() && !list.interfaces.is_empty() {
+                        Ok(list)
+                    } else {
+                        let stderr = String::from_utf8_lossy(&output.stderr);
+                        Err(stderr
+                            .lines()
+                            .map(str::trim)
+                            .find(|line| !line.is_empty() && *line != "QUITTING!")
+                            .unwrap_or("nmap --iflist found no interfaces")
+                            .to_string())
+                    }
+                });
+            sender.send(result).ok();
+        });
+
+        Self {
+            receiver: Some(receiver),
+            result: None,
+        }
+    }
+
+    /// Collect the output if nmap has finished, returning true when it just did
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = &self.receiver else {
+            return false;
+        };
+        match receiver.try_recv() {
+            Ok(result) => self.result = Some(result),
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => {
+                self.result = Some(Err("nmap --iflist exited unexpectedly".to_string()))
+            }
+        }
+        self.receiver = None;
+        true
+    }
+
+    /// The interfaces and routes, or None while nmap is still running
+    pub fn result(&self) -> Option<&Result<InterfaceList, String>> {
+        self.result.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iflist() {
+        let stdout = "Starting Nmap 7.94 ( https://nmap.org ) at 2024-01-01 12:00 UTC\n\
+            ************************INTERFACES************************\n\
+            DEV  (SHORT) IP/MASK                     TYPE     UP MTU   MAC\n\
+            lo   (lo)    127.0.0.1/8                 loopback up 65536\n\
+            eth0 (eth0)  192.168.1.10/24             ethernet up 1500  00:11:22:33:44:55\n\
+            eth0 (eth0)  fe80::211:22ff:fe33:4455/64 ethernet up 1500  00:11:22:33:44:55\n\
+            wlan0 (wlan0) (none)/0                   ethernet down 1500 66:77:88:99:AA:BB\n\
+            \n\
+            **************************ROUTES**************************\n\
+            DST/MASK                   DEV  METRIC GATEWAY\n\
+            192.168.1.0/24             eth0 100\n\
+            0.0.0.0/0                  wlan0 600   10.0.0.1\n\
+            0.0.0.0/0                  eth0 100    192.168.1.1\n\
+            ::1/128                    lo   0\n";
+        let list = parse_iflist(stdout);
+
+        assert_eq!(list.interfaces.len(), 4);
+        assert_eq!(
+            list.interfaces[1],
+            Interface {
+                device: "eth0".to_string(),
+                address: "192.168.1.10/24".to_string(),
+                kind: "ethernet".to_string(),
+                up: true,
+                mtu: Some(1500),
+                mac: Some("00:11:22:33:44:55".to_string()),
+            }
+        );
+        assert_eq!(list.interfaces[0].mac, None);
+        assert!(!list.interfaces[3].up);
+        assert_eq!(list.devices(), vec!["lo", "eth0", "wlan0"]);
+        assert_eq!(list.routes.len(), 4);
+        assert_eq!(list.routes[0].gateway, None);
+        assert_eq!(list.routes[2].gateway.as_deref(), Some("192.168.1.1"));
+        assert_eq!(list.default_device(), Some("eth0"));
+
+        let targets = |targets: &[&str]| targets.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        let local = list.local_interface(&targets(&["192.168.1.0/25", "192.168.1.200"]));
+        assert_eq!(
+            local.map(|interface| interface.device.as_str()),
+            Some("eth0")
+        );
+        assert_eq!(
+            list.local_interface(&targets(&["192.168.1.5", "10.0.0.1"])),
+            None
+        );
+        assert_eq!(list.local_interface(&targets(&["scanme.nmap.org"])), None);
+        assert_eq!(list.local_interface(&targets(&["127.0.0.1"])), None);
+        assert_eq!(parse_iflist(""), InterfaceList::default());
+    }
+}
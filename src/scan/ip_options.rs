@@ -0,0 +1,141 @@
+use std::{fmt, net::Ipv4Addr};
+
+/// Hops that fit in the 40 bytes of IP options, after the 3 byte route header
+pub const MAX_ROUTE_HOPS: usize = 9;
+
+/// Options nmap sets in the IP header of the packets it sends, as --ip-options takes them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpOptions {
+    /// R: record the route packets take
+    RecordRoute,
+    /// T: record timestamps
+    Timestamp,
+    /// U: record the route and timestamps
+    RouteAndTimestamp,
+    /// L: loose source routing through these hops, in order
+    LooseRoute(Vec<Ipv4Addr>),
+    /// S: strict source routing, every hop listed
+    StrictRoute(Vec<Ipv4Addr>),
+    /// Raw bytes in nmap's hex syntax, such as "\x01\x07\x04\x00*36\x01"
+    Hex(String),
+}
+
+impl IpOptions {
+    /// Kinds offered by the builder, in the order they are listed
+    pub const KINDS: [&str; 5] = [
+        "R: record route",
+        "T: record timestamps",
+        "U: record route and timestamps",
+        "L: loose source route",
+        "S: strict source route",
+    ];
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if value.starts_with("\\x") {
+            return Ok(IpOptions::Hex(value.to_string()));
+        }
+        let mut words = value.split_whitespace();
+        let kind = words.next().unwrap_or_default();
+        let hops = words
+            .map(|hop| {
+                hop.parse::<Ipv4Addr>()
+                    .map_err(|_| format!("Invalid hop address: {}", hop))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if hops.len() > MAX_ROUTE_HOPS {
+            return Err(format!("At most {} hops fit in IP options", MAX_ROUTE_HOPS));
+        }
+        let no_hops = |options| {
+            if hops.is_empty() {
+                Ok(options)
+            } else {
+                Err(format!("{} takes no hops", kind))
+            }
+        };
+        match kind.to_ascii_uppercase().as_str() {
+            "R" => no_hops(IpOptions::RecordRoute),
+            "T" => no_hops(IpOptions::Timestamp),
+            "U" => no_hops(IpOptions::RouteAndTimestamp),
+            "L" | "S" if hops.is_empty() => Err(format!("{} needs at least one hop", kind)),
+            "L" => Ok(IpOptions::LooseRoute(hops)),
+            "S" => Ok(IpOptions::StrictRoute(hops)),
+            _ => Err(format!(
+                "Expected R, T, U, L or S with hops, or hex such as \\x01: {}",
+                value
+            )),
+        }
+    }
+
+    /// Options for a kind of `KINDS`, with the hops used by source routing
+    pub fn from_kind(kind: usize, hops: Vec<Ipv4Addr>) -> Option<Self> {
+        match kind {
+            0 => Some(IpOptions::RecordRoute),
+            1 => Some(IpOptions::Timestamp),
+            2 => Some(IpOptions::RouteAndTimestamp),
+            3 => Some(IpOptions::LooseRoute(hops)),
+            4 => Some(IpOptions::StrictRoute(hops)),
+            _ => None,
+        }
+    }
+
+    /// Whether the kind of `KINDS` routes through a list of hops
+    pub fn kind_needs_hops(kind: usize) -> bool {
+        kind >= 3
+    }
+}
+
+impl fmt::Display for IpOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let route = |f: &mut fmt::Formatter, kind: &str, hops: &[Ipv4Addr]| {
+            write!(f, "{}", kind)?;
+            for hop in hops {
+                write!(f, " {}", hop)?;
+            }
+            Ok(())
+        };
+        match self {
+            IpOptions::RecordRoute => write!(f, "R"),
+            IpOptions::Timestamp => write!(f, "T"),
+            IpOptions::RouteAndTimestamp => write!(f, "U"),
+            IpOptions::LooseRoute(hops) => route(f, "L", hops),
+            IpOptions::StrictRoute(hops) => route(f, "S", hops),
+            IpOptions::Hex(hex) => write!(f, "{}", hex),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(IpOptions::parse("r"), Ok(IpOptions::RecordRoute));
+        assert_eq!(
+            IpOptions::parse("L 10.0.0.1  10.0.0.2"),
+            Ok(IpOptions::LooseRoute(vec![
+                Ipv4Addr::new(10, 0, 0, 1),
+                Ipv4Addr::new(10, 0, 0, 2)
+            ]))
+        );
+        assert_eq!(
+            IpOptions::parse("\\x01\\x07\\x04\\x00*36\\x01"),
+            Ok(IpOptions::Hex("\\x01\\x07\\x04\\x00*36\\x01".to_string()))
+        );
+        assert!(IpOptions::parse("S").is_err());
+        assert!(IpOptions::parse("T 10.0.0.1").is_err());
+        assert!(IpOptions::parse("L 10.0.0.300").is_err());
+        assert!(IpOptions::parse("X").is_err());
+        let hops = ["10.0.0.1"; MAX_ROUTE_HOPS + 1].join(" ");
+        assert!(IpOptions::parse(&format!("L {}", hops)).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let options = IpOptions::from_kind(4, vec![Ipv4Addr::new(192, 168, 0, 1)]).unwrap();
+        assert_eq!(options.to_string(), "S 192.168.0.1");
+        assert_eq!(IpOptions::parse(&options.to_string()), Ok(options));
+        assert_eq!(IpOptions::RouteAndTimestamp.to_string(), "U");
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::scan::{builder::NmapCommandBuilder, model::NmapScan};
+
+/// Longest command line cmd.exe accepts, and well before a single argument reaches
+/// Linux's 128 KiB limit, so commands under it can be pasted and run anywhere
+pub const COMMAND_LENGTH_LIMIT: usize = 8191;
+
+/// A list of hosts that nmap can also read from a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostList {
+    /// Targets, which -iL reads from a file
+    Targets,
+    /// --exclude, which --exclude-file replaces
+    Exclude,
+}
+
+impl fmt::Display for HostList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HostList::Targets => write!(f, "targets"),
+            HostList::Exclude => write!(f, "--exclude"),
+        }
+    }
+}
+
+/// A command too long to run portably, and the list that makes it long
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthWarning {
+    pub length: usize,
+    pub list: HostList,
+}
+
+impl fmt::Display for LengthWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Command is {} characters long, move the {} to a file",
+            self.length, self.list
+        )
+    }
+}
+
+/// Warn when the command is longer than shells and exec limits reliably accept
+pub fn check_length(scan: &NmapScan) -> Option<LengthWarning> {
+    let length = NmapCommandBuilder::build(scan).len();
+    if length <= COMMAND_LENGTH_LIMIT {
+        return None;
+    }
+    let ts = &scan.target_specification;
+    let list_length = |list: &[String]| list.iter().map(|host| host.len() + 1).sum::<usize>();
+    let list = if list_length(&ts.exclude) > list_length(&ts.targets) {
+        HostList::Exclude
+    } else {
+        HostList::Targets
+    };
+    Some(LengthWarning { length, list })
+}
+
+/// Write a host list to a file in `dir`, together with the hosts of the file the scan already
+/// reads for that list, and make the scan read the new file instead
+pub fn move_list_to_file(scan: &mut NmapScan, list: HostList, dir: &Path) -> io::Result<PathBuf> {
+    let ts = &mut scan.target_specification;
+    let (hosts, file, name) = match list {
+        HostList::Targets => (&mut ts.targets, &mut ts.input_file, "targets"),
+        HostList::Exclude => (&mut ts.exclude, &mut ts.exclude_file, "exclude"),
+    };
+    let mut content = String::new();
+    if let Some(existing) = file.as_ref() {
+        content = fs::read_to_string(existing)?;
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+    }
+    for host in hosts.iter() {
+        content.push_str(host);
+        content.push('\n');
+    }
+
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("lazynmap-{}-{}.txt", name, std::process::id()));
+    fs::write(&path, content)?;
+    hosts.clear();
+    *file = Some(path.clone());
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_length() {
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets = vec!["10.0.0.0/24".to_string()];
+        assert_eq!(check_length(&scan), None);
+
+        scan.target_specification.exclude = (0..1000)
+            .map(|i| format!("10.1.{}.{}", i / 256, i % 256))
+            .collect();
+        let warning = check_length(&scan).unwrap();
+        assert_eq!(warning.list, HostList::Exclude);
+        assert!(warning.length > COMMAND_LENGTH_LIMIT);
+    }
+
+    #[test]
+    fn test_move_list_to_file() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-length-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let existing = dir.join("existing.txt");
+        fs::write(&existing, "10.0.0.1").unwrap();
+
+        let mut scan = NmapScan::new();
+        scan.target_specification.exclude = vec!["10.0.0.2".to_string(), "10.0.0.3".to_string()];
+        scan.target_specification.exclude_file = Some(existing);
+        scan.target_specification.targets = vec!["10.0.0.0/24".to_string()];
+        let path = move_list_to_file(&mut scan, HostList::Exclude, &dir).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(content, "10.0.0.1\n10.0.0.2\n10.0.0.3\n");
+        let ts = &scan.target_specification;
+        assert!(ts.exclude.is_empty());
+        assert_eq!(ts.exclude_file, Some(path));
+        assert_eq!(ts.targets, vec!["10.0.0.0/24"]);
+    }
+}
@@ -0,0 +1,113 @@
+use std::{fmt, net::Ipv6Addr};
+
+use crate::scan::model::NmapScan;
+
+/// IPv6 link-local targets that nmap cannot reach as given: they need -6, and a %zone naming
+/// the interface to send from, since the same address can exist on every link
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkLocalWarning {
+    /// Link-local targets without a zone
+    pub unzoned: Vec<String>,
+    pub needs_ipv6: bool,
+}
+
+impl fmt::Display for LinkLocalWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let targets = match self.unzoned.as_slice() {
+            [] => "targets".to_string(),
+            [target] => target.clone(),
+            [target, rest @ ..] => format!("{} and {} more", target, rest.len()),
+        };
+        let needs = match (self.needs_ipv6, self.unzoned.is_empty()) {
+            (true, true) => "-6",
+            (true, false) => "-6 and a %interface zone",
+            _ => "a %interface zone",
+        };
+        let verb = if self.unzoned.len() == 1 {
+            "needs"
+        } else {
+            "need"
+        };
+        write!(f, "Link-local {} {} {}", targets, verb, needs)
+    }
+}
+
+/// Whether a target is an IPv6 link-local address or block, and whether it has a zone
+fn link_local(target: &str) -> Option<bool> {
+    let (address, zoned) = match target.trim().split_once('%') {
+        Some((address, _)) => (address, true),
+        None => (target.trim(), false),
+    };
+    let address = address.split('/').next().unwrap_or_default();
+    let ip = address.parse::<Ipv6Addr>().ok()?;
+    ip.is_unicast_link_local().then_some(zoned)
+}
+
+/// Warn when link-local targets lack the zone or the -6 nmap needs to scan them
+pub fn check_link_local(scan: &NmapScan) -> Option<LinkLocalWarning> {
+    let targets = &scan.target_specification.targets;
+    let mut found = false;
+    let mut warning = LinkLocalWarning::default();
+    for target in targets {
+        if let Some(zoned) = link_local(target) {
+            found = true;
+            if !zoned {
+                warning.unzoned.push(target.clone());
+            }
+        }
+    }
+    warning.needs_ipv6 = found && !scan.misc.ipv6;
+    (warning.needs_ipv6 || !warning.unzoned.is_empty()).then_some(warning)
+}
+
+/// The targets with the zone of an interface added to the link-local ones that have none,
+/// e.g. fe80::1%eth0 or fe80::%eth0/64
+pub fn add_zone(targets: &[String], interface: &str) -> Vec<String> {
+    targets
+        .iter()
+        .map(|target| {
+            if link_local(target) != Some(false) {
+                return target.clone();
+            }
+            match target.split_once('/') {
+                Some((address, prefix)) => format!("{}%{}/{}", address, interface, prefix),
+                None => format!("{}%{}", target, interface),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_local() {
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets =
+            ["10.0.0.1", "fe80::1", "fe80::%eth1/64", "2001:db8::1"]
+                .map(String::from)
+                .to_vec();
+        let warning = check_link_local(&scan).unwrap();
+        assert_eq!(warning.unzoned, ["fe80::1"]);
+        assert!(warning.needs_ipv6);
+        assert_eq!(
+            warning.to_string(),
+            "Link-local fe80::1 needs -6 and a %interface zone"
+        );
+
+        let targets = add_zone(
+            &[
+                "fe80::1".to_string(),
+                "fe80::/64".to_string(),
+                "::1".to_string(),
+            ],
+            "eth0",
+        );
+        assert_eq!(targets, ["fe80::1%eth0", "fe80::%eth0/64", "::1"]);
+
+        scan.target_specification.targets = targets;
+        scan.misc.ipv6 = true;
+        assert_eq!(check_link_local(&scan), None);
+    }
+}
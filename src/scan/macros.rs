@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::scan::{
+    flags::{FlagValue, NmapFlag},
+    model::{DnsResolution, NmapScan, ScanTechnique, TimingPerformance, TimingTemplate},
+    scripts::CategorySelection,
+    secrets,
+    tcp_flags::TcpFlags,
+};
+
+/// Owned copy of the value of a flag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EditValue {
+    Bool(bool),
+    Int(Option<u32>),
+    Float(Option<f32>),
+    String(Option<String>),
+    VecInt(Vec<u32>),
+    VecString(Vec<String>),
+    Path(Option<PathBuf>),
+    TimingTemplate(Option<TimingTemplate>),
+    Timing(Box<TimingPerformance>),
+    /// Flags of a --scanflags scan, None for any other technique
+    Scanflags(Option<TcpFlags>),
+    DnsResolution(DnsResolution),
+    Level(u32),
+}
+
+impl EditValue {
+    /// Copy the current value of a flag out of a scan
+    pub fn capture(flag: NmapFlag, scan: &mut NmapScan) -> Self {
+        match flag.get_flag_value(scan) {
+            FlagValue::Bool(value) => EditValue::Bool(*value),
+            FlagValue::Int(value) => EditValue::Int(*value),
+            FlagValue::Float(value) => EditValue::Float(*value),
+            FlagValue::String(value) => EditValue::String(value.clone()),
+            FlagValue::VecInt(value) => EditValue::VecInt(value.clone()),
+            FlagValue::VecString(value) | FlagValue::ScriptCategories(value) => {
+                EditValue::VecString(value.clone())
+            }
+            FlagValue::Path(value) => EditValue::Path(value.clone()),
+            FlagValue::TimingTemplate(value) => EditValue::TimingTemplate(*value),
+            FlagValue::RatePreset(value) => EditValue::Timing(Box::new(value.clone())),
+            FlagValue::FtpRelay(value) => EditValue::String(match value {
+                ScanTechnique::Ftp(relay) => Some(relay.clone()),
+                _ => None,
+            }),
+            FlagValue::Scanflags(value) => EditValue::Scanflags(match value {
+                ScanTechnique::Scanflags(flags) => Some(*flags),
+                _ => None,
+            }),
+            FlagValue::DnsResolution(value) => EditValue::DnsResolution(*value),
+            FlagValue::Level(value) => EditValue::Level(*value),
+        }
+    }
+
+    /// Set a flag of the scan to this value
+    pub fn apply(&self, flag: NmapFlag, scan: &mut NmapScan) {
+        match (self, flag.get_flag_value(scan)) {
+            (EditValue::Bool(value), FlagValue::Bool(target)) => *target = *value,
+            (EditValue::Int(value), FlagValue::Int(target)) => *target = *value,
+            (EditValue::Float(value), FlagValue::Float(target)) => *target = *value,
+            (EditValue::String(value), FlagValue::String(target)) => *target = value.clone(),
+            (EditValue::VecInt(value), FlagValue::VecInt(target)) => *target = value.clone(),
+            (EditValue::VecString(value), FlagValue::VecString(target)) => *target = value.clone(),
+            (EditValue::Path(value), FlagValue::Path(target)) => *target = value.clone(),
+            // Only the categories are replayed, leaving individually chosen scripts alone
+            (EditValue::VecString(value), FlagValue::ScriptCategories(target)) => {
+                CategorySelection::from_scripts(value).apply(target)
+            }
+            (EditValue::TimingTemplate(value), FlagValue::TimingTemplate(target)) => {
+                *target = *value
+            }
+            (EditValue::Timing(value), FlagValue::RatePreset(target)) => *target = *value.clone(),
+            (EditValue::DnsResolution(value), FlagValue::DnsResolution(target)) => *target = *value,
+            (EditValue::Level(value), FlagValue::Level(target)) => *target = *value,
+            (EditValue::Scanflags(Some(flags)), FlagValue::Scanflags(target)) => {
+                *target = ScanTechnique::Scanflags(*flags)
+            }
+            (EditValue::String(value), FlagValue::FtpRelay(target)) => match value {
+                Some(relay) => *target = ScanTechnique::Ftp(relay.clone()),
+                None if matches!(target, ScanTechnique::Ftp(_)) => {
+                    *target = ScanTechnique::default()
+                }
+                None => {}
+            },
+            // Clearing the flags only undoes a --scanflags scan, not another technique
+            (EditValue::Scanflags(None), FlagValue::Scanflags(target)) => {
+                if matches!(target, ScanTechnique::Scanflags(_)) {
+                    *target = ScanTechnique::default();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A single recorded change to a flag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlagEdit {
+    pub flag: NmapFlag,
+    pub value: EditValue,
+}
+
+/// A recorded sequence of flag edits that can be replayed on another scan
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Macro {
+    edits: Vec<FlagEdit>,
+}
+
+impl Macro {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the current value of a flag after it was edited. Secrets typed into script
+    /// arguments are kept as prompts, so that saving the macro leaves them out
+    pub fn record(&mut self, flag: NmapFlag, scan: &mut NmapScan) {
+        let value = match EditValue::capture(flag, scan) {
+            EditValue::String(Some(args)) if flag == NmapFlag::ScriptArgs => {
+                EditValue::String(Some(secrets::strip_args(&args)))
+            }
+            value => value,
+        };
+        // Only the final value matters when a flag is edited several times in a row
+        if let Some(last) = self.edits.last_mut()
+            && last.flag == flag
+        {
+            last.value = value;
+        } else {
+            self.edits.push(FlagEdit { flag, value });
+        }
+    }
+
+    /// Replay the recorded edits in order
+    pub fn apply(&self, scan: &mut NmapScan) {
+        for edit in &self.edits {
+            edit.value.apply(edit.flag, scan);
+        }
+    }
+
+    pub fn edits(&self) -> &[FlagEdit] {
+        &self.edits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    pub fn load(path: &Path) -> Option<Self> {
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_apply() {
+        let mut scan = NmapScan::new();
+        let mut recorded = Macro::new();
+
+        scan.host_discovery.skip_port_scan = true;
+        recorded.record(NmapFlag::SkipPortScan, &mut scan);
+        scan.timing.template = Some(TimingTemplate::Polite);
+        recorded.record(NmapFlag::TimingTemplate, &mut scan);
+        scan.timing.template = Some(TimingTemplate::Aggressive);
+        recorded.record(NmapFlag::TimingTemplate, &mut scan);
+        scan.target_specification.exclude = vec!["10.0.0.1".to_string()];
+        recorded.record(NmapFlag::Exclude, &mut scan);
+        assert_eq!(recorded.edits().len(), 3);
+
+        let mut other = NmapScan::new();
+        other.target_specification.targets = vec!["192.168.0.0/24".to_string()];
+        recorded.apply(&mut other);
+        assert!(other.host_discovery.skip_port_scan);
+        assert_eq!(other.timing.template, Some(TimingTemplate::Aggressive));
+        assert_eq!(other.target_specification.exclude, vec!["10.0.0.1"]);
+        assert_eq!(other.target_specification.targets, vec!["192.168.0.0/24"]);
+    }
+
+    #[test]
+    fn test_record_strips_secrets() {
+        let mut scan = NmapScan::new();
+        scan.script_scan.script_args = Some("smbpassword=hunter2,smbuser=admin".to_string());
+        let mut recorded = Macro::new();
+        recorded.record(NmapFlag::ScriptArgs, &mut scan);
+
+        let path = std::env::temp_dir().join(format!("lazynmap-macro-{}.json", std::process::id()));
+        recorded.save(&path).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+        assert!(!saved.contains("hunter2"), "{}", saved);
+        assert!(saved.contains("smbuser=admin"), "{}", saved);
+    }
+
+    #[test]
+    fn test_serialize() {
+        let mut scan = NmapScan::new();
+        let mut recorded = Macro::new();
+        recorded.record(NmapFlag::RandomTargets, &mut scan);
+        let json = serde_json::to_string(&recorded).unwrap();
+        assert_eq!(serde_json::from_str::<Macro>(&json).unwrap(), recorded);
+    }
+}
@@ -1,4 +1,34 @@
+pub mod advisor;
 pub mod builder;
+pub mod bundles;
+pub mod compare;
+pub mod diagnostics;
+pub mod discovery;
+pub mod duration;
+pub mod exclusions;
 pub mod flags;
+pub mod iflist;
+pub mod ip_options;
+pub mod length;
+pub mod link_local;
+pub mod macros;
 pub mod model;
 pub mod parser;
+pub mod ports;
+pub mod proxies;
+pub mod queue;
+pub mod rate_presets;
+pub mod rdns;
+pub mod runner;
+pub mod sampling;
+pub mod script;
+pub mod script_args;
+pub mod script_help;
+pub mod scripts;
+pub mod secrets;
+pub mod services;
+pub mod session;
+pub mod stats;
+pub mod targets;
+pub mod tcp_flags;
+pub mod techniques;
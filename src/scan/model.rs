@@ -1,11 +1,19 @@
+use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 use std::path::PathBuf;
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumCount, EnumIter};
 
+use crate::scan::tcp_flags::TcpFlags;
+
 /// Represents a complete nmap scan configuration
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NmapScan {
+    /// Free-text notes kept with the scan, never passed to nmap
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+
     // Target specification
     pub target_specification: TargetSpecification,
 
@@ -40,7 +48,8 @@ pub struct NmapScan {
     pub misc: MiscOptions,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TargetSpecification {
     pub targets: Vec<String>,
     pub input_file: Option<PathBuf>,
@@ -50,7 +59,8 @@ pub struct TargetSpecification {
 }
 
 /// Host discovery options
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct HostDiscovery {
     pub list_scan: bool,            // -sL
     pub ping_scan: bool,            // -sn
@@ -62,37 +72,89 @@ pub struct HostDiscovery {
     pub icmp_echo: bool,            // -PE
     pub icmp_timestamp: bool,       // -PP
     pub icmp_netmask: bool,         // -PM
+    pub arp_ping: bool,             // -PR
     pub ip_protocol_ping: Vec<u32>, // -PO
-    pub no_resolve: bool,           // -n
-    pub always_resolve: bool,       // -R
+    pub disable_arp_ping: bool,     // --disable-arp-ping
+    #[serde(flatten)]
+    pub resolution: DnsResolution, // -n/-R
     pub dns_servers: Vec<String>,   // --dns-servers
     pub system_dns: bool,           // --system-dns
     pub traceroute: bool,           // --traceroute
 }
 
+/// When nmap looks up the names of targets, -n and -R being mutually exclusive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "ResolveFlags", into = "ResolveFlags")]
+pub enum DnsResolution {
+    /// Only for hosts that are up
+    #[default]
+    Default,
+    Never,  // -n
+    Always, // -R
+}
+
+impl DnsResolution {
+    /// The state after this one, cycling through never and always back to the default
+    pub fn next(self) -> Self {
+        match self {
+            DnsResolution::Default => DnsResolution::Never,
+            DnsResolution::Never => DnsResolution::Always,
+            DnsResolution::Always => DnsResolution::Default,
+        }
+    }
+}
+
+/// How DNS resolution is saved in profiles, as the pair of options
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ResolveFlags {
+    no_resolve: bool,
+    always_resolve: bool,
+}
+
+impl From<ResolveFlags> for DnsResolution {
+    fn from(flags: ResolveFlags) -> Self {
+        match (flags.no_resolve, flags.always_resolve) {
+            (true, _) => DnsResolution::Never,
+            (false, true) => DnsResolution::Always,
+            (false, false) => DnsResolution::Default,
+        }
+    }
+}
+
+impl From<DnsResolution> for ResolveFlags {
+    fn from(resolution: DnsResolution) -> Self {
+        ResolveFlags {
+            no_resolve: resolution == DnsResolution::Never,
+            always_resolve: resolution == DnsResolution::Always,
+        }
+    }
+}
+
 /// Scan technique options
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub enum ScanTechnique {
     #[default]
     Syn, // -sS (default)
-    Connect,           // -sT
-    Ack,               // -sA
-    Window,            // -sW
-    Maimon,            // -sM
-    Udp,               // -sU
-    TcpNull,           // -sN
-    Fin,               // -sF
-    Xmas,              // -sX
-    Scanflags(String), // --scanflags
-    Idle(String),      // -sI (zombie host)
-    SctpInit,          // -sY
-    SctpCookie,        // -sZ
-    IpProtocol,        // -sO
-    Ftp(String),       // -b (FTP bounce)
+    Connect,             // -sT
+    Ack,                 // -sA
+    Window,              // -sW
+    Maimon,              // -sM
+    Udp,                 // -sU
+    TcpNull,             // -sN
+    Fin,                 // -sF
+    Xmas,                // -sX
+    Scanflags(TcpFlags), // --scanflags
+    Idle(String),        // -sI (zombie host)
+    SctpInit,            // -sY
+    SctpCookie,          // -sZ
+    IpProtocol,          // -sO
+    Ftp(String),         // -b (FTP bounce)
 }
 
 /// Port specification
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PortSpecification {
     pub ports: Option<String>,         // -p
     pub exclude_ports: Option<String>, // --exclude-ports
@@ -103,7 +165,8 @@ pub struct PortSpecification {
 }
 
 /// Service and version detection
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ServiceDetection {
     pub enabled: bool,          // -sV
     pub intensity: Option<u32>, // --version-intensity (0-9)
@@ -113,7 +176,8 @@ pub struct ServiceDetection {
 }
 
 /// Script scanning options
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ScriptScan {
     pub default: bool,                     // -sC
     pub scripts: Vec<String>,              // --script
@@ -125,7 +189,8 @@ pub struct ScriptScan {
 }
 
 /// OS detection options
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OsDetection {
     pub enabled: bool,            // -O
     pub limit: bool,              // --osscan-limit
@@ -134,7 +199,8 @@ pub struct OsDetection {
 }
 
 /// Timing and performance options
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TimingPerformance {
     pub template: Option<TimingTemplate>,    // -T<0-5>
     pub min_hostgroup: Option<u32>,          // --min-hostgroup
@@ -156,7 +222,9 @@ pub struct TimingPerformance {
     pub nsock_engine: Option<String>,        // --nsock-engine
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Copy, Display, EnumIter, EnumCount)]
+#[derive(
+    Debug, Eq, PartialEq, Clone, Copy, Display, EnumIter, EnumCount, Serialize, Deserialize,
+)]
 pub enum TimingTemplate {
     #[strum(to_string = "Paranoid (-T0)")]
     Paranoid = 0,
@@ -187,7 +255,8 @@ impl TimingTemplate {
 }
 
 /// Firewall/IDS evasion and spoofing
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct EvasionSpoofing {
     pub fragment_packets: bool,      // -f
     pub mtu: Option<u32>,            // --mtu
@@ -207,15 +276,16 @@ pub struct EvasionSpoofing {
 }
 
 /// Output options
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OutputOptions {
     pub normal: Option<PathBuf>,        // -oN
     pub xml: Option<PathBuf>,           // -oX
     pub script_kiddie: Option<PathBuf>, // -oS
     pub grepable: Option<PathBuf>,      // -oG
     pub all_formats: Option<String>,    // -oA (base filename)
-    pub verbose: u32,                   // -v, -vv, etc. (0-10+)
-    pub debug: u32,                     // -d, -dd, etc. (0-10+)
+    pub verbose: u32,                   // -v, -vv, etc. (0-10)
+    pub debug: u32,                     // -d, -dd, etc. (0-10)
     pub reason: bool,                   // --reason
     pub stats_every: Option<String>,    // --stats-every
     pub packet_trace: bool,             // --packet-trace
@@ -228,21 +298,115 @@ pub struct OutputOptions {
     pub no_stylesheet: bool,            // --no-stylesheet
 }
 
+/// Length of a timestamp added to an output file name, with its leading dash
+const STAMP_LEN: usize = "-20240101-120000".len();
+
+/// Whether text is a dash and a timestamp as `stamp_outputs` adds them
+fn is_file_stamp(text: &str) -> bool {
+    text.chars().enumerate().all(|(index, c)| match index {
+        0 | 9 => c == '-',
+        _ => c.is_ascii_digit(),
+    })
+}
+
+/// Highest verbosity and debug level the editor steps up to
+pub const MAX_LEVEL: u32 = 10;
+
+/// Layer nmap sends raw packets at, --send-eth and --send-ip being mutually exclusive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "PacketLayerFlags", into = "PacketLayerFlags")]
+pub enum PacketLayer {
+    /// Chosen by nmap for the platform
+    #[default]
+    Default,
+    Ethernet, // --send-eth
+    Ip,       // --send-ip
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PacketLayerFlags {
+    send_eth: bool,
+    send_ip: bool,
+}
+
+impl From<PacketLayerFlags> for PacketLayer {
+    fn from(flags: PacketLayerFlags) -> Self {
+        match (flags.send_eth, flags.send_ip) {
+            (true, _) => PacketLayer::Ethernet,
+            (false, true) => PacketLayer::Ip,
+            (false, false) => PacketLayer::Default,
+        }
+    }
+}
+
+impl From<PacketLayer> for PacketLayerFlags {
+    fn from(layer: PacketLayer) -> Self {
+        PacketLayerFlags {
+            send_eth: layer == PacketLayer::Ethernet,
+            send_ip: layer == PacketLayer::Ip,
+        }
+    }
+}
+
+/// Whether nmap assumes raw socket privileges, --privileged and --unprivileged being mutually
+/// exclusive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "PrivilegeFlags", into = "PrivilegeFlags")]
+pub enum Privileges {
+    /// Detected by nmap from the user it runs as
+    #[default]
+    Default,
+    Privileged,   // --privileged
+    Unprivileged, // --unprivileged
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PrivilegeFlags {
+    privileged: bool,
+    unprivileged: bool,
+}
+
+impl From<PrivilegeFlags> for Privileges {
+    fn from(flags: PrivilegeFlags) -> Self {
+        match (flags.privileged, flags.unprivileged) {
+            (true, _) => Privileges::Privileged,
+            (false, true) => Privileges::Unprivileged,
+            (false, false) => Privileges::Default,
+        }
+    }
+}
+
+impl From<Privileges> for PrivilegeFlags {
+    fn from(privileges: Privileges) -> Self {
+        PrivilegeFlags {
+            privileged: privileges == Privileges::Privileged,
+            unprivileged: privileges == Privileges::Unprivileged,
+        }
+    }
+}
+
 /// Miscellaneous options
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MiscOptions {
-    pub ipv6: bool,               // -6
-    pub aggressive: bool,         // -A (OS, version, script, traceroute)
-    pub datadir: Option<PathBuf>, // --datadir
-    pub send_eth: bool,           // --send-eth
-    pub send_ip: bool,            // --send-ip
-    pub privileged: bool,         // --privileged
-    pub unprivileged: bool,       // --unprivileged
-    pub release_memory: bool,     // --release-memory
-    pub version: bool,            // -V
-    pub help: bool,               // -h
-    pub unique: bool,             // --unique
-    pub log_errors: bool,         // --log-errors
+    pub ipv6: bool,                 // -6
+    pub aggressive: bool,           // -A (OS, version, script, traceroute)
+    pub datadir: Option<PathBuf>,   // --datadir
+    pub servicedb: Option<PathBuf>, // --servicedb
+    pub versiondb: Option<PathBuf>, // --versiondb
+    pub proxies: Vec<String>,       // --proxies
+    #[serde(flatten)]
+    pub packet_layer: PacketLayer, // --send-eth/--send-ip
+    #[serde(flatten)]
+    pub privileges: Privileges, // --privileged/--unprivileged
+    pub release_memory: bool,       // --release-memory
+    pub version: bool,              // -V
+    pub help: bool,                 // -h
+    pub unique: bool,               // --unique
+    pub log_errors: bool,           // --log-errors
+    pub noninteractive: bool,       // --noninteractive
 }
 
 impl NmapScan {
@@ -250,4 +414,145 @@ impl NmapScan {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// XML output file the scan writes to, from -oX or -oA
+    pub fn xml_output(&self) -> Option<PathBuf> {
+        self.output.xml.clone().or_else(|| {
+            self.output
+                .all_formats
+                .as_ref()
+                .map(|base| PathBuf::from(format!("{}.xml", base)))
+        })
+    }
+
+    /// Files the scan writes its output to, with the three that -oA names
+    pub fn output_files(&self) -> Vec<PathBuf> {
+        let output = &self.output;
+        let mut files = [
+            &output.normal,
+            &output.xml,
+            &output.script_kiddie,
+            &output.grepable,
+        ]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>();
+        if let Some(base) = &output.all_formats {
+            files.extend(
+                ["nmap", "xml", "gnmap"]
+                    .iter()
+                    .map(|extension| PathBuf::from(format!("{}.{}", base, extension))),
+            );
+        }
+        files
+    }
+
+    /// Add a suffix to the name of every output file, e.g. "scans/out.xml" becomes "scans/out-dmz.xml"
+    pub fn suffix_outputs(&mut self, suffix: &str) {
+        self.rename_outputs(|stem| format!("{}-{}", stem, suffix));
+    }
+
+    /// Add a timestamp from `format_file_stamp` to the name of every output file, replacing the
+    /// one an earlier run added
+    pub fn stamp_outputs(&mut self, stamp: &str) {
+        self.rename_outputs(|stem| {
+            let stamped = stem.len() > STAMP_LEN
+                && stem.is_char_boundary(stem.len() - STAMP_LEN)
+                && is_file_stamp(&stem[stem.len() - STAMP_LEN..]);
+            let stem = if stamped {
+                &stem[..stem.len() - STAMP_LEN]
+            } else {
+                stem
+            };
+            format!("{}-{}", stem, stamp)
+        });
+    }
+
+    /// Rename every output file, keeping its directory and extension
+    fn rename_outputs(&mut self, rename: impl Fn(&str) -> String) {
+        let output = &mut self.output;
+        for path in [
+            &mut output.normal,
+            &mut output.xml,
+            &mut output.script_kiddie,
+            &mut output.grepable,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let stem = rename(&path.file_stem().unwrap_or_default().to_string_lossy());
+            let name = match path.extension() {
+                Some(extension) => format!("{}.{}", stem, extension.to_string_lossy()),
+                None => stem,
+            };
+            *path = path.with_file_name(name);
+        }
+        if let Some(base) = output.all_formats.as_mut() {
+            *base = rename(base);
+        }
+    }
+
+    /// Whether nmap needs root to run the scan, for raw packet scans and OS detection
+    pub fn requires_root(&self) -> bool {
+        match self.misc.privileges {
+            Privileges::Unprivileged => return false,
+            Privileges::Privileged => return true,
+            Privileges::Default => {}
+        }
+        let raw_scan = !matches!(
+            self.scan_technique,
+            ScanTechnique::Connect | ScanTechnique::Ftp(_)
+        );
+        raw_scan || self.os_detection.enabled || self.misc.aggressive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclusive_pairs() {
+        // Profiles saved before the pairs were merged set both options of a pair as booleans
+        let scan: NmapScan = toml::from_str(
+            "[host_discovery]\nalways_resolve = true\n\n\
+             [misc]\nsend_eth = true\nsend_ip = true\nunprivileged = true\n",
+        )
+        .unwrap();
+        assert_eq!(scan.host_discovery.resolution, DnsResolution::Always);
+        assert_eq!(scan.misc.packet_layer, PacketLayer::Ethernet);
+        assert_eq!(scan.misc.privileges, Privileges::Unprivileged);
+        assert!(!scan.requires_root());
+
+        let saved = toml::to_string(&scan).unwrap();
+        assert!(saved.contains("no_resolve = false\nalways_resolve = true"));
+        assert!(saved.contains("send_eth = true\nsend_ip = false"));
+        assert_eq!(DnsResolution::Always.next(), DnsResolution::Default);
+    }
+
+    #[test]
+    fn test_stamp_outputs() {
+        let mut scan = NmapScan::new();
+        scan.output.xml = Some(PathBuf::from("scans/out.xml"));
+        scan.output.all_formats = Some("scans/full".to_string());
+        assert_eq!(scan.output_files().len(), 4);
+        assert_eq!(scan.output_files()[3], PathBuf::from("scans/full.gnmap"));
+
+        scan.stamp_outputs("20240101-120000");
+        assert_eq!(
+            scan.output.xml,
+            Some(PathBuf::from("scans/out-20240101-120000.xml"))
+        );
+        // A second run replaces the timestamp rather than adding another
+        scan.stamp_outputs("20240102-080000");
+        assert_eq!(
+            scan.output.xml,
+            Some(PathBuf::from("scans/out-20240102-080000.xml"))
+        );
+        assert_eq!(
+            scan.output.all_formats.as_deref(),
+            Some("scans/full-20240102-080000")
+        );
+    }
 }
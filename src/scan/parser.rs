@@ -1,8 +1,14 @@
+use std::borrow::Cow;
 use std::net::IpAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use crate::scan::model::{NmapScan, ScanTechnique, TimingTemplate};
+use crate::scan::{
+    model::{
+        DnsResolution, MAX_LEVEL, NmapScan, PacketLayer, Privileges, ScanTechnique, TimingTemplate,
+    },
+    proxies::check_proxy,
+};
 
 /// Error type for parsing failures
 #[derive(Debug, Clone)]
@@ -30,6 +36,58 @@ impl std::fmt::Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+/// Options nmap still accepts under an older name, with the name the builder writes instead
+const ALIASES: [(&str, &str); 9] = [
+    ("-P0", "-Pn"),
+    ("-PN", "-Pn"),
+    ("-sP", "-sn"),
+    ("-PT", "-PA"),
+    ("-PI", "-PE"),
+    ("-sR", "-sV"),
+    ("-oM", "-oG"),
+    ("--rH", "--randomize-hosts"),
+    ("--fuzzy", "--osscan-guess"),
+];
+
+/// The current spelling of an option, which differs for deprecated aliases and for long
+/// options written with underscores, such as --send_eth. Aliases keep a value attached to them,
+/// as in -PT80
+pub fn modern_flag(flag: &str) -> Cow<'_, str> {
+    if let Some((_, modern)) = ALIASES.iter().find(|(alias, _)| *alias == flag) {
+        return Cow::Borrowed(modern);
+    }
+    if let Some((value, modern)) = ALIASES
+        .iter()
+        .filter(|(alias, _)| !alias.starts_with("--"))
+        .find_map(|(alias, modern)| Some((flag.strip_prefix(alias)?, modern)))
+    {
+        return Cow::Owned(format!("{}{}", modern, value));
+    }
+    if flag.starts_with("--") && flag.contains('_') {
+        return Cow::Owned(flag.replace('_', "-"));
+    }
+    Cow::Borrowed(flag)
+}
+
+/// Entries of a list separated by commas, semicolons or whitespace, as pasted from spreadsheets
+pub fn list_items(list: &str) -> impl Iterator<Item = &str> {
+    list.split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|item| !item.is_empty())
+}
+
+/// Drop repeated values, keeping the first of each, and return how many were dropped
+pub fn dedupe<T: PartialEq>(values: &mut Vec<T>) -> usize {
+    let len = values.len();
+    let mut unique = Vec::with_capacity(len);
+    for value in values.drain(..) {
+        if !unique.contains(&value) {
+            unique.push(value);
+        }
+    }
+    *values = unique;
+    len - values.len()
+}
+
 /// Parser for nmap command strings
 pub struct NmapParser;
 
@@ -46,7 +104,7 @@ impl NmapParser {
             }
 
             if token.starts_with('-') {
-                Self::parse_flag(&mut scan, token, &mut iter)?;
+                Self::parse_flag(&mut scan, &modern_flag(token), &mut iter)?;
             } else {
                 // Target specification
                 scan.target_specification.targets.push(token.to_string());
@@ -56,7 +114,20 @@ impl NmapParser {
         Ok(scan)
     }
 
-    fn tokenize(command: &str) -> Vec<String> {
+    /// Deprecated or aliased options of a command, each with the option that replaces it
+    pub fn legacy_flags(command: &str) -> Vec<(String, String)> {
+        Self::tokenize(command)
+            .into_iter()
+            .filter(|token| token.starts_with('-'))
+            .filter_map(|token| match modern_flag(&token) {
+                Cow::Borrowed(modern) if modern == token => None,
+                modern => Some((token.clone(), modern.into_owned())),
+            })
+            .collect()
+    }
+
+    /// Split a command string into shell-like tokens, honoring double quotes
+    pub fn tokenize(command: &str) -> Vec<String> {
         let mut tokens = Vec::new();
         let mut current = String::new();
         let mut in_quotes = false;
@@ -141,13 +212,15 @@ impl NmapParser {
             "-PE" => scan.host_discovery.icmp_echo = true,
             "-PP" => scan.host_discovery.icmp_timestamp = true,
             "-PM" => scan.host_discovery.icmp_netmask = true,
+            "-PR" => scan.host_discovery.arp_ping = true,
             "-PO" => {
                 if let Some(val) = Self::peek_next_value(iter) {
                     scan.host_discovery.ip_protocol_ping = Self::parse_int_list(Some(val));
                 }
             }
-            "-n" => scan.host_discovery.no_resolve = true,
-            "-R" => scan.host_discovery.always_resolve = true,
+            // The last of each mutually exclusive pair wins, as in nmap
+            "-n" => scan.host_discovery.resolution = DnsResolution::Never,
+            "-R" => scan.host_discovery.resolution = DnsResolution::Always,
             "--traceroute" => scan.host_discovery.traceroute = true,
             "--dns-servers" => {
                 scan.host_discovery.dns_servers = Self::get_next_value(iter, flag)?
@@ -156,6 +229,7 @@ impl NmapParser {
                     .collect()
             }
             "--system-dns" => scan.host_discovery.system_dns = true,
+            "--disable-arp-ping" => scan.host_discovery.disable_arp_ping = true,
 
             // Scan techniques
             "-sS" => scan.scan_technique = ScanTechnique::Syn,
@@ -171,8 +245,12 @@ impl NmapParser {
             "-sZ" => scan.scan_technique = ScanTechnique::SctpCookie,
             "-sO" => scan.scan_technique = ScanTechnique::IpProtocol,
             "--scanflags" => {
-                scan.scan_technique =
-                    ScanTechnique::Scanflags(Self::get_next_value(iter, flag)?.clone())
+                let value = Self::get_next_value(iter, flag)?;
+                scan.scan_technique = ScanTechnique::Scanflags(
+                    value
+                        .parse()
+                        .map_err(|_| ParseError::InvalidValue(flag.to_string(), value.clone()))?,
+                )
             }
             "-sI" => {
                 scan.scan_technique = ScanTechnique::Idle(Self::get_next_value(iter, flag)?.clone())
@@ -184,11 +262,14 @@ impl NmapParser {
             // Port specification
             f if f.starts_with("-p") && f.len() > 2 => {
                 let rest = &flag[2..];
-                scan.ports.ports = Some(rest.to_string());
+                scan.ports.ports = Some(Self::parse_port_list(rest));
+            }
+            "-p" => {
+                scan.ports.ports = Some(Self::parse_port_list(Self::get_next_value(iter, flag)?))
             }
-            "-p" => scan.ports.ports = Some(Self::get_next_value(iter, flag)?.clone()),
             "--exclude-ports" => {
-                scan.ports.exclude_ports = Some(Self::get_next_value(iter, flag)?.clone())
+                scan.ports.exclude_ports =
+                    Some(Self::parse_port_list(Self::get_next_value(iter, flag)?))
             }
             "-F" => scan.ports.fast_mode = true,
             "-r" => scan.ports.consecutive_ports = true,
@@ -197,8 +278,12 @@ impl NmapParser {
                     Some(Self::parse_number(Self::get_next_value(iter, flag)?, flag)?)
             }
             "--port-ratio" => {
-                scan.ports.port_ratio =
-                    Some(Self::parse_float(Self::get_next_value(iter, flag)?, flag)?)
+                let value = Self::get_next_value(iter, flag)?;
+                let ratio = Self::parse_float(value, flag)?;
+                if !(0.0..=1.0).contains(&ratio) {
+                    return Err(ParseError::InvalidValue(flag.to_string(), value.clone()));
+                }
+                scan.ports.port_ratio = Some(ratio);
             }
 
             // Service/Version detection
@@ -357,10 +442,6 @@ impl NmapParser {
             }
             "-oG" => scan.output.grepable = Some(PathBuf::from(Self::get_next_value(iter, flag)?)),
             "-oA" => scan.output.all_formats = Some(Self::get_next_value(iter, flag)?.clone()),
-            "-v" => scan.output.verbose = scan.output.verbose.saturating_add(1),
-            "-vv" => scan.output.verbose = scan.output.verbose.saturating_add(2),
-            "-d" => scan.output.debug = scan.output.debug.saturating_add(1),
-            "-dd" => scan.output.debug = scan.output.debug.saturating_add(2),
             "--reason" => scan.output.reason = true,
             "--stats-every" => {
                 scan.output.stats_every = Some(Self::get_next_value(iter, flag)?.clone())
@@ -384,22 +465,79 @@ impl NmapParser {
             "--datadir" => {
                 scan.misc.datadir = Some(PathBuf::from(Self::get_next_value(iter, flag)?))
             }
-            "--send-eth" => scan.misc.send_eth = true,
-            "--send-ip" => scan.misc.send_ip = true,
-            "--privileged" => scan.misc.privileged = true,
-            "--unprivileged" => scan.misc.unprivileged = true,
+            "--servicedb" => {
+                scan.misc.servicedb = Some(PathBuf::from(Self::get_next_value(iter, flag)?))
+            }
+            "--versiondb" => {
+                scan.misc.versiondb = Some(PathBuf::from(Self::get_next_value(iter, flag)?))
+            }
+            "--proxies" => {
+                let value = Self::get_next_value(iter, flag)?;
+                let proxies: Vec<String> = value.split(',').map(String::from).collect();
+                if proxies.iter().any(|proxy| check_proxy(proxy).is_err()) {
+                    return Err(ParseError::InvalidValue(flag.to_string(), value.clone()));
+                }
+                scan.misc.proxies = proxies;
+            }
+            "--send-eth" => scan.misc.packet_layer = PacketLayer::Ethernet,
+            "--send-ip" => scan.misc.packet_layer = PacketLayer::Ip,
+            "--privileged" => scan.misc.privileges = Privileges::Privileged,
+            "--unprivileged" => scan.misc.privileges = Privileges::Unprivileged,
             "--release-memory" => scan.misc.release_memory = true,
             "-V" | "--version" => scan.misc.version = true,
             "-h" | "--help" => scan.misc.help = true,
             "--unique" => scan.misc.unique = true,
             "--log-errors" => scan.misc.log_errors = true,
+            "--noninteractive" => scan.misc.noninteractive = true,
 
-            _ => return Err(ParseError::InvalidFlag(flag.to_string())),
+            _ if flag.starts_with("-v") => {
+                scan.output.verbose = Self::level(flag, 'v', scan.output.verbose)
+                    .ok_or_else(|| ParseError::InvalidFlag(flag.to_string()))?
+            }
+            _ if flag.starts_with("-d") => {
+                scan.output.debug = Self::level(flag, 'd', scan.output.debug)
+                    .ok_or_else(|| ParseError::InvalidFlag(flag.to_string()))?
+            }
+
+            // Discovery probes with their ports attached, as nmap expects them, such as -PS22,80
+            _ => match flag.split_at_checked(3) {
+                Some((probe, ports)) if !ports.is_empty() => {
+                    let list = Self::discovery_ports(scan, probe)
+                        .ok_or_else(|| ParseError::InvalidFlag(flag.to_string()))?;
+                    *list = Self::parse_int_list(Some(ports));
+                }
+                _ => return Err(ParseError::InvalidFlag(flag.to_string())),
+            },
         }
 
         Ok(())
     }
 
+    /// Verbosity or debug level after an option that raises it by repeating its letter, as in
+    /// -vvv, or sets it with a number, as in -v3
+    fn level(flag: &str, letter: char, current: u32) -> Option<u32> {
+        let rest = flag.strip_prefix('-')?.strip_prefix(letter)?;
+        let level = if rest.chars().all(|c| c == letter) {
+            current + rest.len() as u32 + 1
+        } else {
+            rest.parse().ok()?
+        };
+        Some(level.min(MAX_LEVEL))
+    }
+
+    /// Ports or protocols of a discovery probe option such as -PS
+    fn discovery_ports<'s>(scan: &'s mut NmapScan, probe: &str) -> Option<&'s mut Vec<u32>> {
+        let discovery = &mut scan.host_discovery;
+        match probe {
+            "-PS" => Some(&mut discovery.syn_discovery),
+            "-PA" => Some(&mut discovery.ack_discovery),
+            "-PU" => Some(&mut discovery.udp_discovery),
+            "-PY" => Some(&mut discovery.sctp_discovery),
+            "-PO" => Some(&mut discovery.ip_protocol_ping),
+            _ => None,
+        }
+    }
+
     fn get_next_value<'a>(
         iter: &mut impl Iterator<Item = (usize, &'a String)>,
         flag: &str,
@@ -432,8 +570,18 @@ impl NmapParser {
     }
 
     fn parse_int_list(s: Option<&str>) -> Vec<u32> {
-        s.map(|s| s.split(',').filter_map(|p| p.parse().ok()).collect())
-            .unwrap_or_default()
+        let mut values: Vec<u32> = s
+            .map(|s| list_items(s).filter_map(|p| p.parse().ok()).collect())
+            .unwrap_or_default();
+        dedupe(&mut values);
+        values
+    }
+
+    /// Normalize a port specification such as "22; 80 T:443,80" to nmap's comma separated form
+    fn parse_port_list(s: &str) -> String {
+        let mut ports: Vec<&str> = list_items(s).collect();
+        dedupe(&mut ports);
+        ports.join(",")
     }
 }
 
@@ -498,8 +646,12 @@ mod tests {
         assert!(scan.host_discovery.list_scan);
         assert!(scan.host_discovery.ping_scan);
         assert!(scan.host_discovery.skip_port_scan);
-        assert!(scan.host_discovery.no_resolve);
+        assert_eq!(scan.host_discovery.resolution, DnsResolution::Never);
         assert_eq!(scan.target_specification.targets, vec!["192.168.1.0/24"]);
+
+        let scan = NmapParser::parse("nmap -n -R --unprivileged --privileged 10.0.0.1").unwrap();
+        assert_eq!(scan.host_discovery.resolution, DnsResolution::Always);
+        assert_eq!(scan.misc.privileges, Privileges::Privileged);
     }
 
     #[test]
@@ -530,6 +682,10 @@ mod tests {
         assert_eq!(scan.output.normal, Some(PathBuf::from("normal.txt")));
         assert_eq!(scan.output.verbose, 1);
         assert!(scan.output.open_only);
+
+        let scan = NmapParser::parse("nmap -v -vv -d3 -dddddddddddd 10.0.0.1").unwrap();
+        assert_eq!(scan.output.verbose, 3);
+        assert_eq!(scan.output.debug, MAX_LEVEL);
     }
 
     #[test]
@@ -539,6 +695,95 @@ mod tests {
         let scan = result.unwrap();
         assert!(scan.misc.ipv6);
         assert!(scan.misc.aggressive);
+
+        let scan = NmapParser::parse(
+            "nmap -PR --disable-arp-ping --noninteractive --servicedb svc --versiondb probes \
+             --proxies http://10.0.0.2:8080,socks4://10.0.0.3:1080 10.0.0.1",
+        )
+        .unwrap();
+        assert!(scan.host_discovery.arp_ping);
+        assert!(scan.host_discovery.disable_arp_ping);
+        assert!(scan.misc.noninteractive);
+        assert_eq!(scan.misc.servicedb, Some(PathBuf::from("svc")));
+        assert_eq!(scan.misc.versiondb, Some(PathBuf::from("probes")));
+        assert_eq!(
+            scan.misc.proxies,
+            ["http://10.0.0.2:8080", "socks4://10.0.0.3:1080"]
+        );
+        for proxies in ["foo", "socks5://x", "http://proxy:0", "http://x,"] {
+            let result = NmapParser::parse(&format!("nmap --proxies {} 10.0.0.1", proxies));
+            assert!(
+                matches!(&result, Err(ParseError::InvalidValue(flag, _)) if flag == "--proxies"),
+                "{}",
+                proxies
+            );
+        }
+    }
+
+    #[test]
+    fn test_scanflags() {
+        let scan = NmapParser::parse("nmap --scanflags synfin example.com").unwrap();
+        assert!(matches!(
+            scan.scan_technique,
+            ScanTechnique::Scanflags(flags) if flags.to_string() == "SYNFIN"
+        ));
+        assert!(NmapParser::parse("nmap --scanflags SYNX example.com").is_err());
+    }
+
+    #[test]
+    fn test_list_items() {
+        assert_eq!(
+            list_items("22; 80\t443,,8080\n").collect::<Vec<_>>(),
+            vec!["22", "80", "443", "8080"]
+        );
+        let mut ports = vec![80, 22, 80, 443, 22];
+        assert_eq!(dedupe(&mut ports), 2);
+        assert_eq!(ports, vec![80, 22, 443]);
+
+        let scan = NmapParser::parse("nmap -PS 22;80;22 10.0.0.1").unwrap();
+        assert_eq!(scan.host_discovery.syn_discovery, vec![22, 80]);
+        let scan = NmapParser::parse("nmap -PS22,80 -PO1 10.0.0.1").unwrap();
+        assert_eq!(scan.host_discovery.syn_discovery, vec![22, 80]);
+        assert_eq!(scan.host_discovery.ip_protocol_ping, vec![1]);
+        assert!(NmapParser::parse("nmap -PX22 10.0.0.1").is_err());
+    }
+
+    #[test]
+    fn test_port_list() {
+        let scan = NmapParser::parse(r#"nmap -p "22; 80 T:443,80" --exclude-ports 25;25 10.0.0.1"#)
+            .unwrap();
+        assert_eq!(scan.ports.ports.as_deref(), Some("22,80,T:443"));
+        assert_eq!(scan.ports.exclude_ports.as_deref(), Some("25"));
+
+        let scan = NmapParser::parse("nmap -p22,22,U:53 10.0.0.1").unwrap();
+        assert_eq!(scan.ports.ports.as_deref(), Some("22,U:53"));
+    }
+
+    #[test]
+    fn test_legacy_flags() {
+        let command = "nmap -P0 -sP -PT22,80 --send_eth --max_retries 2 -oM out.gnmap 10.0.0.1";
+        let scan = NmapParser::parse(command).unwrap();
+        assert!(scan.host_discovery.skip_port_scan);
+        assert!(scan.host_discovery.ping_scan);
+        assert_eq!(scan.host_discovery.ack_discovery, vec![22, 80]);
+        assert_eq!(scan.misc.packet_layer, PacketLayer::Ethernet);
+        assert_eq!(scan.timing.max_retries, Some(2));
+        assert_eq!(scan.output.grepable, Some(PathBuf::from("out.gnmap")));
+
+        let rebuilt = crate::scan::builder::NmapCommandBuilder::build(&scan);
+        assert!(rebuilt.contains("-Pn") && rebuilt.contains("--send-eth"));
+        assert!(!rebuilt.contains("-P0") && !rebuilt.contains('_'));
+        assert_eq!(
+            NmapParser::legacy_flags(command),
+            vec![
+                ("-P0".to_string(), "-Pn".to_string()),
+                ("-sP".to_string(), "-sn".to_string()),
+                ("-PT22,80".to_string(), "-PA22,80".to_string()),
+                ("--send_eth".to_string(), "--send-eth".to_string()),
+                ("--max_retries".to_string(), "--max-retries".to_string()),
+                ("-oM".to_string(), "-oG".to_string()),
+            ]
+        );
     }
 
     #[test]
@@ -552,6 +797,23 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_port_ratio_range() {
+        let scan = NmapParser::parse("nmap --port-ratio 0.25 10.0.0.1").unwrap();
+        assert_eq!(scan.ports.port_ratio, Some(0.25));
+        for ratio in ["5", "-1", "NaN"] {
+            let result = NmapParser::parse(&format!("nmap --port-ratio {} 10.0.0.1", ratio));
+            assert!(
+                matches!(
+                    &result,
+                    Err(ParseError::InvalidValue(flag, val)) if flag == "--port-ratio" && val == ratio
+                ),
+                "{}",
+                ratio
+            );
+        }
+    }
+
     #[test]
     fn test_combined_scan() {
         let command = "nmap -sS -sV -O -p- -T4 --min-rate 1000 -oA full_scan 192.168.1.1";
@@ -0,0 +1,66 @@
+use std::fmt;
+
+use crate::scan::model::{NmapScan, PortSpecification};
+
+/// Other port selections set along with an explicit -p list: nmap refuses -F with -p, and
+/// --top-ports then only picks among the listed ports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortConflict {
+    pub fast_mode: bool,
+    pub top_ports: bool,
+}
+
+impl PortConflict {
+    /// The options set along with -p
+    pub fn options(&self) -> &'static str {
+        match (self.fast_mode, self.top_ports) {
+            (true, true) => "-F and --top-ports",
+            (true, false) => "-F",
+            _ => "--top-ports",
+        }
+    }
+}
+
+impl fmt::Display for PortConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "-p conflicts with {}", self.options())
+    }
+}
+
+/// Warn when -p is combined with -F or --top-ports
+pub fn check_ports(scan: &NmapScan) -> Option<PortConflict> {
+    let ps = &scan.ports;
+    ps.ports.as_ref()?;
+    let conflict = PortConflict {
+        fast_mode: ps.fast_mode,
+        top_ports: ps.top_ports.is_some(),
+    };
+    (conflict.fast_mode || conflict.top_ports).then_some(conflict)
+}
+
+/// Keep the explicit -p list, clearing the selections that conflict with it
+pub fn keep_explicit_ports(ports: &mut PortSpecification) {
+    ports.fast_mode = false;
+    ports.top_ports = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::parser::NmapParser;
+
+    #[test]
+    fn test_check_ports() {
+        let mut scan = NmapParser::parse("nmap -p 1-1024 -F --top-ports 10 10.0.0.1").unwrap();
+        let conflict = check_ports(&scan).unwrap();
+        assert_eq!(conflict.to_string(), "-p conflicts with -F and --top-ports");
+
+        keep_explicit_ports(&mut scan.ports);
+        assert_eq!(check_ports(&scan), None);
+        assert_eq!(scan.ports.ports.as_deref(), Some("1-1024"));
+
+        scan.ports.ports = None;
+        scan.ports.fast_mode = true;
+        assert_eq!(check_ports(&scan), None);
+    }
+}
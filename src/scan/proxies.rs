@@ -0,0 +1,29 @@
+/// Check a proxy URL as --proxies takes it: http:// or socks4://, a host and an optional port
+pub fn check_proxy(proxy: &str) -> Result<(), String> {
+    let Some((scheme, server)) = proxy.split_once("://") else {
+        return Err(format!("Missing http:// or socks4:// in {}", proxy));
+    };
+    if !["http", "socks4"].contains(&scheme.to_lowercase().as_str()) {
+        return Err(format!("Unsupported proxy type: {}", scheme));
+    }
+    let server = server.strip_suffix('/').unwrap_or(server);
+    let host = match server.split_once(':') {
+        Some((host, port)) => {
+            if !port.parse::<u16>().is_ok_and(|port| port > 0) {
+                return Err(format!("Invalid port: {}", port));
+            }
+            host
+        }
+        None => server,
+    };
+    if host.is_empty() {
+        return Err(format!("Missing host in {}", proxy));
+    }
+    if let Some(c) = host
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !".-_".contains(*c))
+    {
+        return Err(format!("Invalid character in proxy host: {}", c));
+    }
+    Ok(())
+}
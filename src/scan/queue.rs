@@ -0,0 +1,391 @@
+use std::{
+    collections::VecDeque,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::scan::{model::NmapScan, parser::NmapParser};
+
+/// A scan waiting in the queue, labelled for the queue panel
+#[derive(Debug, Clone)]
+pub struct QueuedScan {
+    pub label: String,
+    pub scan: NmapScan,
+}
+
+/// Scans run one after another, tracking the progress of the whole queue
+pub struct ScanQueue {
+    name: String,
+    pending: VecDeque<QueuedScan>,
+    current: Option<String>,
+    completed: usize,
+    failed: usize,
+    total: usize,
+    /// Pause between the end of a scan and the start of the next
+    delay: Duration,
+    next_start: Option<Instant>,
+}
+
+impl ScanQueue {
+    pub fn new(name: String, scans: Vec<QueuedScan>) -> Self {
+        Self {
+            name,
+            total: scans.len(),
+            pending: scans.into(),
+            current: None,
+            completed: 0,
+            failed: 0,
+            delay: Duration::ZERO,
+            next_start: None,
+        }
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Label of the scan that is running
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &QueuedScan> {
+        self.pending.iter()
+    }
+
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.current.is_some()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.current.is_none() && self.pending.is_empty()
+    }
+
+    /// Time left before the next scan may start, while waiting between scans
+    pub fn next_start_in(&self) -> Option<Duration> {
+        if self.is_running() || self.pending.is_empty() {
+            return None;
+        }
+        self.next_start
+            .map(|next_start| next_start.saturating_duration_since(Instant::now()))
+    }
+
+    /// Take the next scan to run, if no scan of the queue is running and the delay has passed
+    pub fn start_next(&mut self) -> Option<NmapScan> {
+        if self.is_running()
+            || self
+                .next_start
+                .is_some_and(|next_start| Instant::now() < next_start)
+        {
+            return None;
+        }
+        let next = self.pending.pop_front()?;
+        self.current = Some(next.label);
+        Some(next.scan)
+    }
+
+    /// Add a scan to the end of the queue
+    pub fn push(&mut self, scan: QueuedScan) {
+        self.pending.push_back(scan);
+        self.total += 1;
+    }
+
+    /// Drop the scans that haven't started yet
+    pub fn cancel(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Record how the running scan ended
+    pub fn finish(&mut self, completed: bool) {
+        if self.current.take().is_none() {
+            return;
+        }
+        if completed {
+            self.completed += 1;
+        } else {
+            self.failed += 1;
+        }
+        if !self.delay.is_zero() {
+            self.next_start = Some(Instant::now() + self.delay);
+        }
+    }
+}
+
+/// One scan of a profile per target list file in a directory, with outputs named after each file
+pub fn batch_scans(scan: &NmapScan, dir: &Path) -> io::Result<Vec<QueuedScan>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+
+    Ok(files
+        .into_iter()
+        .map(|file| {
+            let label = file
+                .file_stem()
+                .unwrap_or(file.as_os_str())
+                .to_string_lossy()
+                .to_string();
+            scan_of_file(scan, file, label)
+        })
+        .collect())
+}
+
+/// Split the targets of a scan into at most `chunks` target list files in `dir`, one scan per file
+pub fn chunk_scans(scan: &NmapScan, chunks: usize, dir: &Path) -> io::Result<Vec<QueuedScan>> {
+    let targets = scan_targets(scan)?;
+    if targets.is_empty() {
+        return Ok(Vec::new());
+    }
+    fs::create_dir_all(dir)?;
+    let size = targets.len().div_ceil(chunks.max(1));
+    targets
+        .chunks(size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let label = format!("chunk-{}", index + 1);
+            let file = dir.join(format!("{}.txt", label));
+            fs::write(&file, format!("{}\n", chunk.join("\n")))?;
+            Ok(scan_of_file(scan, file, label))
+        })
+        .collect()
+}
+
+/// Targets given on the command line followed by those of the -iL file, without comments
+pub fn scan_targets(scan: &NmapScan) -> io::Result<Vec<String>> {
+    let ts = &scan.target_specification;
+    let mut targets = ts.targets.clone();
+    if let Some(file) = &ts.input_file {
+        let content = fs::read_to_string(file)?;
+        targets.extend(
+            content
+                .lines()
+                .map(|line| line.split('#').next().unwrap_or_default())
+                .flat_map(str::split_whitespace)
+                .map(str::to_string),
+        );
+    }
+    Ok(targets)
+}
+
+/// Version and default script scan of one port of a host, writing XML named after the port
+pub fn port_rescan(host: &str, port: u16, protocol: &str) -> QueuedScan {
+    port_scan(host, port, protocol, "-sC")
+}
+
+/// Version scan of one port of a host running the given NSE scripts, writing XML named after
+/// the port
+pub fn script_rescan(host: &str, port: u16, protocol: &str, scripts: &[&str]) -> QueuedScan {
+    port_scan(
+        host,
+        port,
+        protocol,
+        &format!("--script {}", scripts.join(",")),
+    )
+}
+
+fn port_scan(host: &str, port: u16, protocol: &str, scripts: &str) -> QueuedScan {
+    let mut command = format!("nmap -sV {}", scripts);
+    if host.contains(':') {
+        command.push_str(" -6");
+    }
+    if protocol == "udp" {
+        command.push_str(&format!(" -sU -pU:{}", port));
+    } else {
+        command.push_str(&format!(" -p{}", port));
+    }
+    command.push(' ');
+    command.push_str(host);
+    let mut scan = NmapParser::parse(&command).unwrap_or_default();
+    let label = format!("{} {}/{}", host, port, protocol);
+    name_outputs(
+        &mut scan,
+        &format!("rescan-{}-{}", host.replace(':', "-"), port),
+    );
+    QueuedScan { label, scan }
+}
+
+/// A copy of a scan that reads its targets from a file, with outputs named after the label
+fn scan_of_file(scan: &NmapScan, file: PathBuf, label: String) -> QueuedScan {
+    let mut scan = scan.clone();
+    scan.target_specification.targets.clear();
+    scan.target_specification.random_targets = None;
+    scan.target_specification.input_file = Some(file);
+    name_outputs(&mut scan, &label);
+    QueuedScan { label, scan }
+}
+
+/// Add a suffix to every output file of a scan, writing XML named after the suffix if there is no output
+fn name_outputs(scan: &mut NmapScan, suffix: &str) {
+    scan.suffix_outputs(suffix);
+    if scan.xml_output().is_none() {
+        scan.output.xml = Some(PathBuf::from(format!("{}.xml", suffix)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::builder::NmapCommandBuilder;
+
+    #[test]
+    fn test_batch_scans() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-batch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("dmz.txt"), "10.0.0.0/24\n").unwrap();
+        fs::write(dir.join("office.txt"), "10.1.0.0/24\n").unwrap();
+
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets = vec!["192.168.1.1".to_string()];
+        scan.output.xml = Some(PathBuf::from("scans/out.xml"));
+        scan.output.all_formats = Some("all".to_string());
+        let scans = batch_scans(&scan, &dir).unwrap();
+
+        let mut bare = NmapScan::new();
+        bare.output.normal = Some(PathBuf::from("out"));
+        let bare_scans = batch_scans(&bare, &dir).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(
+            scans.iter().map(|s| s.label.as_str()).collect::<Vec<_>>(),
+            vec!["dmz", "office"]
+        );
+        let first = &scans[0].scan;
+        assert!(first.target_specification.targets.is_empty());
+        assert_eq!(
+            first.target_specification.input_file,
+            Some(dir.join("dmz.txt"))
+        );
+        assert_eq!(first.output.xml, Some(PathBuf::from("scans/out-dmz.xml")));
+        assert_eq!(first.output.all_formats.as_deref(), Some("all-dmz"));
+        assert_eq!(
+            bare_scans[1].scan.output.normal,
+            Some(PathBuf::from("out-office"))
+        );
+        assert_eq!(
+            bare_scans[1].scan.output.xml,
+            Some(PathBuf::from("office.xml"))
+        );
+    }
+
+    #[test]
+    fn test_chunk_scans() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-chunks-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let list = dir.join("targets.txt");
+        fs::write(&list, "10.0.0.3 10.0.0.4\n# lab\n10.0.0.5 # router\n\n").unwrap();
+
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets = vec!["10.0.0.1".to_string(), "10.0.0.2".to_string()];
+        scan.target_specification.input_file = Some(list);
+        let scans = chunk_scans(&scan, 2, &dir.join("chunks")).unwrap();
+        let contents: Vec<String> = scans
+            .iter()
+            .map(|s| fs::read_to_string(s.scan.target_specification.input_file.as_ref().unwrap()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let empty = chunk_scans(&NmapScan::new(), 2, &dir.join("chunks")).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(
+            scans.iter().map(|s| s.label.as_str()).collect::<Vec<_>>(),
+            vec!["chunk-1", "chunk-2"]
+        );
+        assert_eq!(
+            contents,
+            vec!["10.0.0.1\n10.0.0.2\n10.0.0.3\n", "10.0.0.4\n10.0.0.5\n"]
+        );
+        assert!(scans[0].scan.target_specification.targets.is_empty());
+        assert_eq!(scans[1].scan.output.xml, Some(PathBuf::from("chunk-2.xml")));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_port_rescan() {
+        let rescan = port_rescan("10.0.0.1", 80, "tcp");
+        assert_eq!(rescan.label, "10.0.0.1 80/tcp");
+        assert_eq!(
+            NmapCommandBuilder::build(&rescan.scan),
+            "nmap -sS -p 80 -sV -sC -oX rescan-10.0.0.1-80.xml 10.0.0.1"
+        );
+        let udp = port_rescan("fe80::1", 161, "udp");
+        assert_eq!(
+            NmapCommandBuilder::build(&udp.scan),
+            "nmap -sU -p U:161 -sV -sC -oX rescan-fe80--1-161.xml -6 fe80::1"
+        );
+        let scripts = script_rescan(
+            "10.0.0.1",
+            445,
+            "tcp",
+            &["smb-enum-shares", "smb-os-discovery"],
+        );
+        assert_eq!(
+            NmapCommandBuilder::build(&scripts.scan),
+            "nmap -sS -p 445 -sV --script smb-enum-shares,smb-os-discovery -oX rescan-10.0.0.1-445.xml 10.0.0.1"
+        );
+    }
+
+    #[test]
+    fn test_delay() {
+        let scans = ["a", "b"]
+            .into_iter()
+            .map(|label| QueuedScan {
+                label: label.to_string(),
+                scan: NmapScan::new(),
+            })
+            .collect();
+        let mut queue =
+            ScanQueue::new("split".to_string(), scans).with_delay(Duration::from_secs(60));
+        assert!(queue.start_next().is_some());
+        assert_eq!(queue.next_start_in(), None);
+        queue.finish(true);
+        assert!(queue.start_next().is_none());
+        assert!(queue.next_start_in().unwrap() > Duration::from_secs(50));
+    }
+
+    #[test]
+    fn test_queue() {
+        let scans = ["a", "b", "c"]
+            .into_iter()
+            .map(|label| QueuedScan {
+                label: label.to_string(),
+                scan: NmapScan::new(),
+            })
+            .collect();
+        let mut queue = ScanQueue::new("batch".to_string(), scans);
+        assert!(queue.start_next().is_some());
+        assert_eq!(queue.current(), Some("a"));
+        assert!(queue.start_next().is_none());
+        queue.finish(true);
+        queue.start_next();
+        queue.finish(false);
+        queue.start_next();
+        assert!(!queue.is_done());
+        queue.finish(true);
+        assert!(queue.is_done());
+        assert_eq!(
+            (queue.completed(), queue.failed(), queue.total()),
+            (2, 1, 3)
+        );
+        assert!(queue.start_next().is_none());
+    }
+}
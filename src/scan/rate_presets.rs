@@ -0,0 +1,103 @@
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumCount, EnumIter};
+
+use crate::scan::{
+    model::TimingPerformance,
+    parser::{NmapParser, ParseError},
+};
+
+/// Bundles of timing options suited to the network being scanned, set in [rate_presets] of the
+/// config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter, EnumCount)]
+pub enum RatePreset {
+    #[strum(to_string = "Production-safe")]
+    ProductionSafe,
+    #[strum(to_string = "Lab")]
+    Lab,
+    #[strum(to_string = "Internet survey")]
+    InternetSurvey,
+}
+
+impl RatePreset {
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::iter().nth(index)
+    }
+
+    pub fn as_index(self) -> usize {
+        self as usize
+    }
+
+    pub fn all_labels() -> Vec<String> {
+        Self::iter().map(|preset| preset.to_string()).collect()
+    }
+}
+
+/// The timing options given on a command line, such as "-T2 --max-rate 100"; other options
+/// are ignored
+pub fn parse_timing(options: &str) -> Result<TimingPerformance, ParseError> {
+    NmapParser::parse(&format!("nmap {}", options)).map(|scan| scan.timing)
+}
+
+/// Replace the timing options with those of a preset, keeping the timeouts it leaves unset
+pub fn apply(timing: &mut TimingPerformance, preset: &TimingPerformance) {
+    let host_timeout = preset
+        .host_timeout
+        .clone()
+        .or_else(|| timing.host_timeout.take());
+    let script_timeout = preset
+        .script_timeout
+        .clone()
+        .or_else(|| timing.script_timeout.take());
+    *timing = TimingPerformance {
+        host_timeout,
+        script_timeout,
+        ..preset.clone()
+    };
+}
+
+/// Undo a preset, keeping the timeouts it leaves unset
+pub fn clear(timing: &mut TimingPerformance, preset: &TimingPerformance) {
+    *timing = TimingPerformance {
+        host_timeout: timing
+            .host_timeout
+            .take()
+            .filter(|_| preset.host_timeout.is_none()),
+        script_timeout: timing
+            .script_timeout
+            .take()
+            .filter(|_| preset.script_timeout.is_none()),
+        ..TimingPerformance::default()
+    };
+}
+
+/// Whether the timing options are those a preset sets
+pub fn matches(timing: &TimingPerformance, preset: &TimingPerformance) -> bool {
+    let mut applied = timing.clone();
+    apply(&mut applied, preset);
+    applied == *timing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let preset = parse_timing("-T2 --max-rate 100 --max-retries 1").unwrap();
+        let mut timing = parse_timing("-T5 --min-rate 5000 --host-timeout 5m").unwrap();
+        assert!(!matches(&timing, &preset));
+
+        apply(&mut timing, &preset);
+        assert!(matches(&timing, &preset));
+        assert_eq!(timing.max_rate, Some(100));
+        assert_eq!(timing.min_rate, None);
+        assert_eq!(timing.host_timeout.as_deref(), Some("5m"));
+
+        let survey = parse_timing("-T4 --host-timeout 10m").unwrap();
+        apply(&mut timing, &survey);
+        assert_eq!(timing.host_timeout.as_deref(), Some("10m"));
+        clear(&mut timing, &survey);
+        assert_eq!(timing, TimingPerformance::default());
+        assert!(parse_timing("--max-rate fast").is_err());
+    }
+}
@@ -0,0 +1,237 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    results::{model::ScanResults, parser::parse_xml},
+    scan::model::NmapScan,
+};
+
+/// The name a target address resolves to, if any
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostnameMapping {
+    pub address: String,
+    pub hostname: Option<String>,
+}
+
+/// Arguments of a list scan of the targets of a scan, which resolves their names without sending
+/// them packets. Only the options that choose targets and DNS servers are kept, since nmap
+/// refuses -sL with any port scan type.
+pub fn lookup_args(scan: &NmapScan) -> Vec<String> {
+    let ts = &scan.target_specification;
+    let hd = &scan.host_discovery;
+    let mut args = vec!["-sL".to_string(), "-oX".to_string(), "-".to_string()];
+    if !hd.dns_servers.is_empty() {
+        args.push("--dns-servers".to_string());
+        args.push(hd.dns_servers.join(","));
+    }
+    if hd.system_dns {
+        args.push("--system-dns".to_string());
+    }
+    if scan.misc.ipv6 {
+        args.push("-6".to_string());
+    }
+    if let Some(file) = &ts.input_file {
+        args.push("-iL".to_string());
+        args.push(file.to_string_lossy().to_string());
+    }
+    if !ts.exclude.is_empty() {
+        args.push("--exclude".to_string());
+        args.push(ts.exclude.join(","));
+    }
+    if let Some(file) = &ts.exclude_file {
+        args.push("--exclude-file".to_string());
+        args.push(file.to_string_lossy().to_string());
+    }
+    args.extend(ts.targets.iter().cloned());
+    args
+}
+
+/// Address and hostname of every host of a list scan
+pub fn hostname_mappings(results: &ScanResults) -> Vec<HostnameMapping> {
+    results
+        .hosts
+        .iter()
+        .map(|host| HostnameMapping {
+            address: host.address().to_string(),
+            hostname: host.hostnames.first().cloned(),
+        })
+        .collect()
+}
+
+/// A target list with one address per line, followed by its hostname as a comment
+pub fn annotated_target_list(mappings: &[HostnameMapping]) -> String {
+    mappings
+        .iter()
+        .map(|mapping| match &mapping.hostname {
+            Some(hostname) => format!("{} # {}\n", mapping.address, hostname),
+            None => format!("{}\n", mapping.address),
+        })
+        .collect()
+}
+
+/// Write the annotated target list to a file in `dir` and make the scan read its targets from it
+pub fn use_annotated_targets(
+    scan: &mut NmapScan,
+    mappings: &[HostnameMapping],
+    dir: &Path,
+) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let (path, mut file) = create_unique(dir)?;
+    file.write_all(annotated_target_list(mappings).as_bytes())?;
+    let ts = &mut scan.target_specification;
+    ts.targets.clear();
+    ts.random_targets = None;
+    ts.input_file = Some(path.clone());
+    Ok(path)
+}
+
+/// Create a target list file under a name no file had, rather than writing to one that
+/// someone else may have put there
+fn create_unique(dir: &Path) -> io::Result<(PathBuf, File)> {
+    loop {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.subsec_nanos());
+        let path = dir.join(format!("rdns-{}-{}.txt", process::id(), nanos));
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Reverse DNS lookups of the targets, run as an nmap list scan in the background
+pub struct ReverseDnsFetch {
+    receiver: Option<Receiver<Result<Vec<HostnameMapping>, String>>>,
+    result: Option<Result<Vec<HostnameMapping>, String>>,
+}
+
+impl ReverseDnsFetch {
+    /// Start the list scan of the targets of `scan`
+    pub fn start(scan: &NmapScan) -> Self {
+        let args = lookup_args(scan);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let result = Command::new("nmap")
+                .args(args)
+                .stdin(Stdio::null())
+                .output()
+                .map_err(|err| format!("Failed to run nmap: {}", err))
+                .and_then(|output| {
+                    if !output.status.success() {
+                        let stderr = String::from_utf8_lossy(&output.stderr);
+                        return Err(stderr
+                            .lines()
+                            .map(str::trim)
+                            .find(|line| !line.is_empty() && *line != "QUITTING!")
+                            .unwrap_or("nmap -sL failed")
+                            .to_string());
+                    }
+                    parse_xml(&String::from_utf8_lossy(&output.stdout))
+                        .map(|results| hostname_mappings(&results))
+                        .map_err(|err| err.to_string())
+                });
+            sender.send(result).ok();
+        });
+
+        Self {
+            receiver: Some(receiver),
+            result: None,
+        }
+    }
+
+    /// Collect the output if nmap has finished, returning true when it just did
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = &self.receiver else {
+            return false;
+        };
+        match receiver.try_recv() {
+            Ok(result) => self.result = Some(result),
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => {
+                self.result = Some(Err("nmap -sL exited unexpectedly".to_string()))
+            }
+        }
+        self.receiver = None;
+        true
+    }
+
+    /// The hostname of each target, or None while nmap is still running
+    pub fn result(&self) -> Option<&Result<Vec<HostnameMapping>, String>> {
+        self.result.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::scan::parser::NmapParser;
+
+    #[test]
+    fn test_lookup_args() {
+        let scan = NmapParser::parse(
+            "nmap -sS -p 80 -n -oN out.txt --dns-servers 10.0.0.53 --exclude 10.0.0.1 10.0.0.0/30",
+        )
+        .unwrap();
+        assert_eq!(
+            lookup_args(&scan).join(" "),
+            "-sL -oX - --dns-servers 10.0.0.53 --exclude 10.0.0.1 10.0.0.0/30"
+        );
+    }
+
+    #[test]
+    fn test_hostname_mappings() {
+        let xml = r#"<?xml version="1.0"?>
+<nmaprun args="nmap -sL -oX - 10.0.0.0/31">
+<host><status state="unknown" reason="user-set"/>
+<address addr="10.0.0.0" addrtype="ipv4"/><hostnames></hostnames></host>
+<host><status state="unknown" reason="user-set"/>
+<address addr="10.0.0.1" addrtype="ipv4"/>
+<hostnames><hostname name="gw.example.com" type="PTR"/></hostnames></host>
+</nmaprun>"#;
+        let mappings = hostname_mappings(&parse_xml(xml).unwrap());
+        assert_eq!(
+            mappings,
+            vec![
+                HostnameMapping {
+                    address: "10.0.0.0".to_string(),
+                    hostname: None,
+                },
+                HostnameMapping {
+                    address: "10.0.0.1".to_string(),
+                    hostname: Some("gw.example.com".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            annotated_target_list(&mappings),
+            "10.0.0.0\n10.0.0.1 # gw.example.com\n"
+        );
+    }
+
+    #[test]
+    fn test_use_annotated_targets() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-rdns-test-{}", process::id()));
+        let mappings = vec![HostnameMapping {
+            address: "10.0.0.1".to_string(),
+            hostname: Some("gw.example.com".to_string()),
+        }];
+        let mut scan = NmapParser::parse("nmap 10.0.0.0/31").unwrap();
+        let first = use_annotated_targets(&mut scan, &mappings, &dir).unwrap();
+        let second = use_annotated_targets(&mut scan, &mappings, &dir).unwrap();
+        let content = fs::read_to_string(&second).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_ne!(first, second);
+        assert_eq!(content, "10.0.0.1 # gw.example.com\n");
+        assert!(scan.target_specification.targets.is_empty());
+        assert_eq!(scan.target_specification.input_file, Some(second));
+    }
+}
@@ -0,0 +1,253 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{self, Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    error::Error,
+    scan::{
+        builder::NmapCommandBuilder, model::NmapScan, parser::NmapParser, secrets, stats::RunStats,
+    },
+};
+
+/// Maximum number of output lines kept in memory per stream
+const MAX_LINES: usize = 1000;
+
+/// A line of output produced by a running nmap process
+#[derive(Debug, Clone)]
+pub enum RunEvent {
+    Stdout(String),
+    Stderr(String),
+}
+
+/// Lifecycle state of an nmap process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunStatus {
+    Running,
+    Exited(i32),
+    Killed,
+}
+
+/// Runs nmap as a child process and collects its output in the background
+pub struct NmapRunner {
+    child: Child,
+    receiver: Receiver<RunEvent>,
+    scan: NmapScan,
+    command: String,
+    /// XML output added to a scan that had none, removed along with the runner
+    temp_xml: Option<PathBuf>,
+    started: Instant,
+    finished: Option<Instant>,
+    output: Vec<RunEvent>,
+    stderr: Vec<String>,
+    stats: RunStats,
+    status: RunStatus,
+}
+
+impl NmapRunner {
+    /// Spawn nmap with the arguments built from the given scan, writing XML output to a
+    /// temporary file when the scan sets none. The scan and command kept for showing have
+    /// their secret script arguments masked
+    pub fn spawn(scan: &NmapScan) -> Result<Self, Error> {
+        let mut scan = scan.clone();
+        let temp_xml = Self::add_temp_xml(&mut scan);
+        let tokens = NmapParser::tokenize(&NmapCommandBuilder::build(&scan));
+        let scan = secrets::masked(&scan);
+        let command = NmapCommandBuilder::build(&scan);
+        let (program, args) = tokens.split_first().ok_or_else(|| {
+            Error::Nmap(io::Error::new(io::ErrorKind::InvalidInput, "empty command"))
+        })?;
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(Error::Nmap)?;
+
+        tracing::info!(%command, "Started nmap");
+
+        let (sender, receiver) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            Self::forward_lines(stdout, sender.clone(), RunEvent::Stdout);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            Self::forward_lines(stderr, sender, RunEvent::Stderr);
+        }
+
+        Ok(Self {
+            child,
+            receiver,
+            scan,
+            command,
+            temp_xml,
+            started: Instant::now(),
+            finished: None,
+            output: Vec::new(),
+            stderr: Vec::new(),
+            stats: RunStats::default(),
+            status: RunStatus::Running,
+        })
+    }
+
+    /// Point a scan without XML output at a temporary file, so its results can be shown. Scans
+    /// that resume another one or only print information are left alone
+    fn add_temp_xml(scan: &mut NmapScan) -> Option<PathBuf> {
+        if scan.xml_output().is_some()
+            || scan.output.resume.is_some()
+            || scan.output.iflist
+            || scan.misc.version
+            || scan.misc.help
+        {
+            return None;
+        }
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.subsec_nanos());
+        let path = env::temp_dir().join(format!("lazynmap-{}-{}.xml", process::id(), nanos));
+        scan.output.xml = Some(path.clone());
+        Some(path)
+    }
+
+    fn forward_lines(
+        stream: impl Read + Send + 'static,
+        sender: Sender<RunEvent>,
+        wrap: fn(String) -> RunEvent,
+    ) {
+        thread::spawn(move || {
+            for line in BufReader::new(stream).lines().map_while(Result::ok) {
+                if sender.send(wrap(line)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Drain pending output and update the process status, returning the new events
+    pub fn poll(&mut self) -> Vec<RunEvent> {
+        let mut events = Vec::new();
+        if self.status != RunStatus::Running {
+            return events;
+        }
+
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) => {
+                    match &event {
+                        RunEvent::Stdout(line) => self.stats.update(line),
+                        RunEvent::Stderr(line) => {
+                            tracing::debug!(%line, "nmap stderr");
+                            Self::push_capped(&mut self.stderr, line.clone())
+                        }
+                    }
+                    Self::push_capped(&mut self.output, event.clone());
+                    events.push(event);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    // Both pipes are closed, so the process is exiting
+                    self.status = match self.child.wait() {
+                        Ok(status) => match status.code() {
+                            Some(code) => RunStatus::Exited(code),
+                            None => RunStatus::Killed,
+                        },
+                        Err(_) => RunStatus::Killed,
+                    };
+                    self.finished = Some(Instant::now());
+                    tracing::info!(status = ?self.status, elapsed = ?self.elapsed(), "nmap exited");
+                    break;
+                }
+            }
+        }
+
+        events
+    }
+
+    fn push_capped<T>(lines: &mut Vec<T>, line: T) {
+        if lines.len() >= MAX_LINES {
+            lines.remove(0);
+        }
+        lines.push(line);
+    }
+
+    /// Terminate the nmap process
+    pub fn kill(&mut self) {
+        if self.status == RunStatus::Running {
+            self.child.kill().ok();
+        }
+    }
+
+    pub fn status(&self) -> RunStatus {
+        self.status
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.status == RunStatus::Running
+    }
+
+    /// The scan this process was started from
+    pub fn scan(&self) -> &NmapScan {
+        &self.scan
+    }
+
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// The temporary file the results are written to when the scan set no XML output
+    pub fn temp_xml(&self) -> Option<&Path> {
+        self.temp_xml.as_deref()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.finished.unwrap_or_else(Instant::now) - self.started
+    }
+
+    /// Interleaved stdout and stderr lines in arrival order
+    pub fn output(&self) -> &[RunEvent] {
+        &self.output
+    }
+
+    pub fn stderr(&self) -> &[String] {
+        &self.stderr
+    }
+
+    pub fn stats(&self) -> &RunStats {
+        &self.stats
+    }
+}
+
+impl Drop for NmapRunner {
+    fn drop(&mut self) {
+        self.kill();
+        if let Some(path) = &self.temp_xml {
+            fs::remove_file(path).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_temp_xml() {
+        let mut scan = NmapScan::new();
+        let path = NmapRunner::add_temp_xml(&mut scan).unwrap();
+        assert_eq!(scan.xml_output(), Some(path.clone()));
+        assert!(path.starts_with(env::temp_dir()));
+        // Scans that already write XML keep their file
+        assert_eq!(NmapRunner::add_temp_xml(&mut scan), None);
+        assert_eq!(scan.xml_output(), Some(path));
+
+        let mut resumed = NmapScan::new();
+        resumed.output.resume = Some(PathBuf::from("scan.gnmap"));
+        assert_eq!(NmapRunner::add_temp_xml(&mut resumed), None);
+        assert_eq!(resumed.xml_output(), None);
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    collections::BTreeSet,
+    net::{Ipv4Addr, Ipv6Addr},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::scan::advisor::count_target;
+
+/// Pick `count` distinct hosts at random from targets, expanding CIDR blocks and octet ranges.
+/// Hostnames count as one host each. Hosts keep the order of the target list.
+pub fn sample_targets(targets: &[String], count: usize, seed: u64) -> Vec<String> {
+    let sizes: Vec<u64> = targets.iter().map(|target| count_target(target)).collect();
+    let total = sizes
+        .iter()
+        .fold(0u64, |sum, size| sum.saturating_add(*size));
+    let count = (count as u64).min(total);
+
+    // Floyd's algorithm: distinct indices without expanding every target
+    let mut rng = XorShift::new(seed);
+    let mut picked = BTreeSet::new();
+    for upper in total - count..total {
+        let index = rng.below(upper + 1);
+        if !picked.insert(index) {
+            picked.insert(upper);
+        }
+    }
+
+    let mut hosts = Vec::with_capacity(picked.len());
+    let mut offset = 0u64;
+    let mut picked = picked.into_iter().peekable();
+    for (target, size) in targets.iter().zip(sizes) {
+        while let Some(index) = picked.next_if(|index| *index < offset.saturating_add(size)) {
+            hosts.push(nth_host(target, index - offset));
+        }
+        offset = offset.saturating_add(size);
+    }
+    hosts
+}
+
+/// A seed that differs between runs
+pub fn random_seed() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    nanos ^ u64::from(std::process::id()).rotate_left(32)
+}
+
+/// The host at an index of a target, in nmap's order
+fn nth_host(target: &str, index: u64) -> String {
+    if let Some((address, prefix)) = target.split_once('/') {
+        let Ok(prefix) = prefix.parse::<u32>() else {
+            return target.to_string();
+        };
+        if let Ok(ip) = address.parse::<Ipv4Addr>()
+            && prefix <= 32
+        {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            let host = (u32::from(ip) & mask).wrapping_add(index as u32);
+            return Ipv4Addr::from(host).to_string();
+        }
+        if let Ok(ip) = address.parse::<Ipv6Addr>()
+            && prefix <= 128
+        {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            let host = (u128::from(ip) & mask).wrapping_add(u128::from(index));
+            return Ipv6Addr::from(host).to_string();
+        }
+        return target.to_string();
+    }
+
+    let octets: Vec<Vec<u8>> = target.split('.').map(expand_octet).collect();
+    if octets.len() != 4 || octets.iter().any(Vec::is_empty) || count_target(target) == 1 {
+        return target.to_string();
+    }
+    // The last octet varies fastest
+    let mut index = index;
+    let mut parts = [0u8; 4];
+    for (part, values) in parts.iter_mut().zip(&octets).rev() {
+        *part = values[(index % values.len() as u64) as usize];
+        index /= values.len() as u64;
+    }
+    Ipv4Addr::from(parts).to_string()
+}
+
+/// Values of an octet such as "1-10,20" or "*", empty if it isn't an octet pattern
+pub fn expand_octet(octet: &str) -> Vec<u8> {
+    if octet == "*" {
+        return (0..=255).collect();
+    }
+    let mut values = Vec::new();
+    for part in octet.split(',') {
+        let range = match part.split_once('-') {
+            Some((start, end)) => {
+                let start = if start.is_empty() {
+                    Ok(0)
+                } else {
+                    start.parse()
+                };
+                let end = if end.is_empty() { Ok(255) } else { end.parse() };
+                match (start, end) {
+                    (Ok(start), Ok(end)) => start..=end,
+                    _ => return Vec::new(),
+                }
+            }
+            None => match part.parse::<u8>() {
+                Ok(value) => value..=value,
+                Err(_) => return Vec::new(),
+            },
+        };
+        values.extend(range);
+    }
+    values
+}
+
+/// Small, fast generator; sampling targets doesn't need cryptographic randomness
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // A zero state would only ever produce zeros
+        Self(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// A number in 0..bound
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_targets() {
+        let targets = vec![
+            "10.0.0.0/30".to_string(),
+            "scanme.nmap.org".to_string(),
+            "192.168.1-2.5,9".to_string(),
+        ];
+        let all = sample_targets(&targets, 100, 7);
+        assert_eq!(
+            all,
+            vec![
+                "10.0.0.0",
+                "10.0.0.1",
+                "10.0.0.2",
+                "10.0.0.3",
+                "scanme.nmap.org",
+                "192.168.1.5",
+                "192.168.1.9",
+                "192.168.2.5",
+                "192.168.2.9",
+            ]
+        );
+
+        let sample = sample_targets(&targets, 4, 42);
+        assert_eq!(sample.len(), 4);
+        assert!(sample.iter().all(|host| all.contains(host)));
+        let mut unique = sample.clone();
+        unique.dedup();
+        assert_eq!(unique, sample);
+        assert_eq!(sample_targets(&targets, 4, 42), sample);
+
+        let large = sample_targets(&["10.0.0.0/8".to_string()], 3, 1);
+        assert_eq!(large.len(), 3);
+        assert!(large.iter().all(|host| host.starts_with("10.")));
+        assert_eq!(
+            sample_targets(&["fe80::/120".to_string()], 300, 1).len(),
+            256
+        );
+    }
+}
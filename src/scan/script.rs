@@ -0,0 +1,103 @@
+use std::{fs, io, path::Path};
+
+use crate::scan::{model::NmapScan, secrets};
+
+/// Shell variable holding the start time of a run, added to every output name
+const STAMP: &str = "${STAMP}";
+
+/// A POSIX shell script that runs the scan, with outputs named after the time each run starts.
+/// Scans that need root re-run nmap through sudo unless the script already runs as root.
+/// Secret script arguments are read from environment variables instead of written out
+pub fn shell_script(scan: &NmapScan) -> String {
+    let mut scan = scan.clone();
+    scan.suffix_outputs(STAMP);
+    let (command, variables) = secrets::shell_command(&scan);
+    // The builder escapes `$` so values reach nmap as typed; the stamp has to expand
+    let command = command.replace(&format!("\\{}", STAMP), STAMP);
+
+    let mut script = String::from("#!/bin/sh\n# Generated by lazynmap\nset -eu\n\n");
+    for (name, key) in &variables {
+        script.push_str(&format!(
+            ": \"${{{}:?set {} to the value of {}}}\"\n",
+            name, name, key
+        ));
+    }
+    script.push_str("STAMP=$(date +%Y%m%d-%H%M%S)\n");
+    if scan.requires_root() {
+        script.push_str("SUDO=\"\"\n");
+        script.push_str("if [ \"$(id -u)\" -ne 0 ]; then\n    SUDO=sudo\nfi\n\n");
+        script.push_str(&format!("$SUDO {} \"$@\"\n", command));
+    } else {
+        script.push_str(&format!("\n{} \"$@\"\n", command));
+    }
+    script
+}
+
+/// Write the script of a scan to a file and make it executable by its owner only, since it can
+/// name targets and credentials
+pub fn write_script(scan: &NmapScan, path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, shell_script(scan))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::model::ScanTechnique;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_shell_script() {
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets = vec!["10.0.0.0/24".to_string()];
+        scan.output.xml = Some(PathBuf::from("scans/out.xml"));
+        let script = shell_script(&scan);
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("STAMP=$(date +%Y%m%d-%H%M%S)"));
+        assert!(script.contains("SUDO=sudo"));
+        assert!(script.contains("$SUDO nmap "));
+        assert!(script.contains("-oX \"scans/out-${STAMP}.xml\""));
+        assert!(script.ends_with(" \"$@\"\n"));
+
+        scan.scan_technique = ScanTechnique::Connect;
+        let script = shell_script(&scan);
+        assert!(!script.contains("SUDO"));
+        assert!(script.contains("\nnmap "));
+
+        scan.script_scan.script_args = Some("smbpassword=hunter2,token=$API_TOKEN".to_string());
+        let script = shell_script(&scan);
+        assert!(!script.contains("hunter2"));
+        assert!(
+            script.contains(": \"${SMBPASSWORD:?set SMBPASSWORD to the value of smbpassword}\"\n")
+        );
+        assert!(script.contains("--script-args \"smbpassword=${SMBPASSWORD},token=${API_TOKEN}\""));
+    }
+
+    #[test]
+    fn test_write_script() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-script-{}", std::process::id()));
+        let path = dir.join("nested").join("scan.sh");
+        write_script(&NmapScan::new(), &path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            fs::metadata(&path).unwrap().permissions().mode()
+        };
+        fs::remove_dir_all(&dir).ok();
+
+        assert!(content.starts_with("#!/bin/sh"));
+        #[cfg(unix)]
+        assert_eq!(mode & 0o777, 0o700);
+    }
+}
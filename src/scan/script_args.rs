@@ -0,0 +1,282 @@
+/// Arguments accepted by common NSE scripts, offered as completions for --script-args
+const SCRIPT_ARGS: &[(&str, &[&str])] = &[
+    (
+        "broadcast-dhcp-discover",
+        &[
+            "broadcast-dhcp-discover.mac",
+            "broadcast-dhcp-discover.timeout",
+        ],
+    ),
+    (
+        "dns-brute",
+        &[
+            "dns-brute.domain",
+            "dns-brute.hostlist",
+            "dns-brute.threads",
+            "dns-brute.srv",
+        ],
+    ),
+    (
+        "dns-zone-transfer",
+        &[
+            "dns-zone-transfer.domain",
+            "dns-zone-transfer.server",
+            "dns-zone-transfer.port",
+        ],
+    ),
+    ("ftp-anon", &["ftp-anon.maxlist"]),
+    ("ftp-brute", &["ftp-brute.timeout"]),
+    (
+        "http-brute",
+        &[
+            "http-brute.path",
+            "http-brute.hostname",
+            "http-brute.method",
+        ],
+    ),
+    (
+        "http-enum",
+        &[
+            "http-enum.basepath",
+            "http-enum.category",
+            "http-enum.displayall",
+            "http-enum.fingerprintfile",
+        ],
+    ),
+    (
+        "http-form-brute",
+        &[
+            "http-form-brute.path",
+            "http-form-brute.method",
+            "http-form-brute.hostname",
+            "http-form-brute.uservar",
+            "http-form-brute.passvar",
+            "http-form-brute.onsuccess",
+            "http-form-brute.onfailure",
+        ],
+    ),
+    (
+        "http-headers",
+        &["http-headers.path", "http-headers.useget"],
+    ),
+    (
+        "http-methods",
+        &["http-methods.url-path", "http-methods.test-all"],
+    ),
+    ("http-title", &["http-title.url"]),
+    (
+        "http-wordpress-enum",
+        &[
+            "http-wordpress-enum.root",
+            "http-wordpress-enum.search-limit",
+            "http-wordpress-enum.type",
+            "http-wordpress-enum.check-latest",
+        ],
+    ),
+    (
+        "ldap-search",
+        &[
+            "ldap.username",
+            "ldap.password",
+            "ldap.qfilter",
+            "ldap.attrib",
+            "ldap.base",
+            "ldap.maxobjects",
+        ],
+    ),
+    ("mysql-brute", &["mysql-brute.timeout"]),
+    ("smb-brute", &["smblockout", "canaries", "brutelimit"]),
+    ("smb-vuln-ms17-010", &["smb-vuln-ms17-010.sharename"]),
+    ("snmp-brute", &["snmp-brute.communitiesdb"]),
+    ("ssh-brute", &["ssh-brute.timeout"]),
+    ("ssh-hostkey", &["ssh_hostkey"]),
+    (
+        "targets-asn",
+        &[
+            "targets-asn.asn",
+            "targets-asn.whois_server",
+            "targets-asn.whois_port",
+        ],
+    ),
+    ("vulners", &["vulners.showall", "mincvss"]),
+    ("vulscan", &["vulscandb", "vulscanoutput", "vulscanshowall"]),
+];
+
+/// Arguments of the NSE libraries, shared by every script that uses the library
+const BRUTE_ARGS: &[&str] = &[
+    "brute.credfile",
+    "brute.delay",
+    "brute.emptypass",
+    "brute.firstonly",
+    "brute.guesses",
+    "brute.mode",
+    "brute.passonly",
+    "brute.retries",
+    "brute.threads",
+    "brute.unique",
+    "brute.useraspass",
+    "userdb",
+    "passdb",
+    "unpwdb.userlimit",
+    "unpwdb.passlimit",
+    "unpwdb.timelimit",
+];
+const HTTP_ARGS: &[&str] = &[
+    "http.host",
+    "http.useragent",
+    "http.max-cache-size",
+    "http.pipeline",
+    "http.max-pipeline",
+];
+const SMB_ARGS: &[&str] = &[
+    "smbdomain",
+    "smbusername",
+    "smbpassword",
+    "smbhash",
+    "smbtype",
+    "smbport",
+    "smbnoguest",
+    "smbbasic",
+    "smbsign",
+];
+const SSL_ARGS: &[&str] = &["tls.servername"];
+
+/// Split --script-args on the commas between entries, leaving those in quotes and tables alone
+pub fn entries(args: &str) -> Vec<&str> {
+    split_entries(args, &[','])
+}
+
+/// Arguments written one entry per line, joined into a --script-args value
+pub fn from_lines(text: &str) -> String {
+    split_entries(text, &[',', '\n'])
+        .into_iter()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// A --script-args value written one entry per line
+pub fn to_lines(args: &str) -> String {
+    entries(args)
+        .into_iter()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn split_entries<'a>(args: &'a str, separators: &[char]) -> Vec<&'a str> {
+    let mut entries = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    let mut start = 0;
+    for (index, c) in args.char_indices() {
+        match (quote, c) {
+            _ if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{') => depth += 1,
+            (None, '}') => depth = depth.saturating_sub(1),
+            (None, c) if depth == 0 && separators.contains(&c) => {
+                entries.push(&args[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    entries.push(&args[start..]);
+    entries
+}
+
+/// Known --script-args keys for the given --script entries; wildcards such as "http-*" match by prefix
+pub fn args_for(scripts: &[String]) -> Vec<&'static str> {
+    let mut args: Vec<&'static str> = Vec::new();
+    for script in scripts {
+        let matches = |name: &str| match script.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == script,
+        };
+        for (name, script_args) in SCRIPT_ARGS {
+            if matches(name) {
+                args.extend(*script_args);
+            }
+        }
+
+        // Library arguments follow the script naming conventions
+        let library_args = [
+            (script.ends_with("-brute") || script == "brute", BRUTE_ARGS),
+            (script.starts_with("http-"), HTTP_ARGS),
+            (script.starts_with("smb-"), SMB_ARGS),
+            (
+                script.starts_with("ssl-") || script.starts_with("tls-"),
+                SSL_ARGS,
+            ),
+        ];
+        for (uses_library, library) in library_args {
+            if uses_library {
+                args.extend(library);
+            }
+        }
+    }
+
+    let mut seen = Vec::new();
+    args.retain(|arg| {
+        let first = !seen.contains(arg);
+        seen.push(*arg);
+        first
+    });
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripts(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_args_for() {
+        let args = args_for(&scripts(&["http-brute", "http-title"]));
+        assert_eq!(
+            &args[..3],
+            [
+                "http-brute.path",
+                "http-brute.hostname",
+                "http-brute.method"
+            ]
+        );
+        assert!(args.contains(&"userdb"));
+        assert!(args.contains(&"http-title.url"));
+        assert_eq!(
+            args.iter().filter(|&&arg| arg == "http.useragent").count(),
+            1
+        );
+
+        let args = args_for(&scripts(&["smb-*"]));
+        assert!(args.contains(&"smb-vuln-ms17-010.sharename"));
+        assert!(args.contains(&"smbusername"));
+
+        assert!(args_for(&scripts(&["banner"])).is_empty());
+    }
+
+    #[test]
+    fn test_lines() {
+        let args = r#"userdb=users.txt,http.useragent="a, b",creds={user=a,pass=b}"#;
+        let lines = to_lines(args);
+        assert_eq!(
+            lines,
+            "userdb=users.txt\nhttp.useragent=\"a, b\"\ncreds={user=a,pass=b}"
+        );
+        assert_eq!(from_lines(&lines), args);
+        assert_eq!(
+            from_lines("  a=1\n\nb={x=1,\ny=2}, c=3\n"),
+            "a=1,b={x=1,\ny=2},c=3"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+/// Output of `nmap --script-help` for one script, category or expression, fetched in the background
+pub struct ScriptHelp {
+    receiver: Option<Receiver<Result<Vec<String>, String>>>,
+    result: Option<Result<Vec<String>, String>>,
+}
+
+impl ScriptHelp {
+    /// Start running `nmap --script-help` for the given scripts
+    pub fn fetch(scripts: &str) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let scripts = scripts.to_string();
+        thread::spawn(move || {
+            let result = Command::new("nmap")
+                .arg("--script-help")
+                .arg(&scripts)
+                .stdin(Stdio::null())
+                .output()
+                .map_err(|err| format!("Failed to run nmap: {}", err))
+                .and_then(|output| {
+                    if output.status.success() {
+                        Ok(help_lines(&String::from_utf8_lossy(&output.stdout)))
+                    } else {
+                        let stderr = String::from_utf8_lossy(&output.stderr);
+                        Err(stderr
+                            .lines()
+                            .map(str::trim)
+                            .find(|line| !line.is_empty() && *line != "QUITTING!")
+                            .unwrap_or("nmap --script-help failed")
+                            .to_string())
+                    }
+                });
+            sender.send(result).ok();
+        });
+
+        Self {
+            receiver: Some(receiver),
+            result: None,
+        }
+    }
+
+    /// Collect the output if nmap has finished
+    pub fn poll(&mut self) {
+        let Some(receiver) = &self.receiver else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(result) => self.result = Some(result),
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.result = Some(Err("nmap --script-help exited unexpectedly".to_string()))
+            }
+        }
+        self.receiver = None;
+    }
+
+    /// The help text, or None while nmap is still running
+    pub fn result(&self) -> Option<&Result<Vec<String>, String>> {
+        self.result.as_ref()
+    }
+}
+
+/// Strip the nmap banner and surrounding blank lines from --script-help output
+fn help_lines(stdout: &str) -> Vec<String> {
+    let lines: Vec<&str> = stdout
+        .lines()
+        .skip_while(|line| line.starts_with("Starting Nmap") || line.trim().is_empty())
+        .collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |index| index + 1);
+    lines[..end].iter().map(|line| line.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_help_lines() {
+        let stdout = "Starting Nmap 7.94 ( https://nmap.org ) at 2024-01-01 12:00 UTC\n\
+                      \n\
+                      http-title\n\
+                      Categories: default discovery safe\n\
+                      https://nmap.org/nsedoc/scripts/http-title.html\n\
+                      \x20 Shows the title of the default page of a web server.\n\
+                      \n";
+        assert_eq!(
+            help_lines(stdout),
+            vec![
+                "http-title",
+                "Categories: default discovery safe",
+                "https://nmap.org/nsedoc/scripts/http-title.html",
+                "  Shows the title of the default page of a web server.",
+            ]
+        );
+        assert!(help_lines("").is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+use strum::{EnumCount, IntoEnumIterator};
+use strum_macros::{Display, EnumCount, EnumIter, EnumString};
+
+/// NSE script categories that can be selected with --script
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter, EnumCount, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum ScriptCategory {
+    Auth,
+    Broadcast,
+    Brute,
+    Default,
+    Discovery,
+    Dos,
+    Exploit,
+    External,
+    Fuzzer,
+    Intrusive,
+    Malware,
+    Safe,
+    Version,
+    Vuln,
+}
+
+impl ScriptCategory {
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::iter().nth(index)
+    }
+}
+
+/// Whether a category is left out, required or ruled out of the script expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CategoryState {
+    Off,
+    Included,
+    Excluded,
+}
+
+/// Categories selected in the NSE section, composed into one --script expression
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CategorySelection {
+    pub included: Vec<ScriptCategory>,
+    pub excluded: Vec<ScriptCategory>,
+    /// Require every included category ("and") instead of any of them ("or")
+    pub match_all: bool,
+}
+
+impl CategorySelection {
+    /// Number of selectable items: every category plus the and/or toggle
+    pub const ITEMS: usize = ScriptCategory::COUNT + 1;
+
+    /// Read the selection from the --script entries that are category expressions
+    pub fn from_scripts(scripts: &[String]) -> Self {
+        let mut selection = Self::default();
+        for parsed in scripts.iter().filter_map(|script| Self::parse(script)) {
+            for category in parsed.included {
+                if !selection.included.contains(&category) {
+                    selection.included.push(category);
+                }
+            }
+            for category in parsed.excluded {
+                if !selection.excluded.contains(&category) {
+                    selection.excluded.push(category);
+                }
+            }
+            selection.match_all |= parsed.match_all;
+        }
+        selection
+    }
+
+    /// Replace the category expressions in the --script entries with this selection
+    pub fn apply(&self, scripts: &mut Vec<String>) {
+        scripts.retain(|script| Self::parse(script).is_none());
+        if let Some(expression) = self.expression() {
+            scripts.insert(0, expression);
+        }
+    }
+
+    pub fn state(&self, category: ScriptCategory) -> CategoryState {
+        if self.included.contains(&category) {
+            CategoryState::Included
+        } else if self.excluded.contains(&category) {
+            CategoryState::Excluded
+        } else {
+            CategoryState::Off
+        }
+    }
+
+    /// Cycle a category from off to included to excluded and back
+    pub fn cycle(&mut self, category: ScriptCategory) {
+        match self.state(category) {
+            CategoryState::Off => self.included.push(category),
+            CategoryState::Included => {
+                self.included.retain(|&other| other != category);
+                self.excluded.push(category);
+            }
+            CategoryState::Excluded => self.excluded.retain(|&other| other != category),
+        }
+    }
+
+    /// Compose the selection into an NSE expression, e.g. "(default or vuln) and not intrusive"
+    pub fn expression(&self) -> Option<String> {
+        let operator = if self.match_all { " and " } else { " or " };
+        let included = self
+            .included
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(operator);
+        let excluded = self
+            .excluded
+            .iter()
+            .map(|category| format!("not {}", category));
+
+        let mut terms = Vec::new();
+        if self.included.len() > 1 && !self.match_all && !self.excluded.is_empty() {
+            terms.push(format!("({})", included));
+        } else if !included.is_empty() {
+            terms.push(included);
+        }
+        terms.extend(excluded);
+        (!terms.is_empty()).then(|| terms.join(" and "))
+    }
+
+    /// Parse an expression made only of categories, as produced by `expression`
+    fn parse(script: &str) -> Option<Self> {
+        let mut selection = Self::default();
+        let mut operators = Vec::new();
+        let mut negate = false;
+        let unparenthesized = script.replace(['(', ')'], " ");
+        for token in unparenthesized.split_whitespace() {
+            match token {
+                "and" | "or" => operators.push(token),
+                "not" => negate = true,
+                _ => {
+                    let category = token.parse().ok()?;
+                    if negate {
+                        selection.excluded.push(category);
+                    } else {
+                        selection.included.push(category);
+                    }
+                    negate = false;
+                }
+            }
+        }
+        selection.match_all = operators
+            .iter()
+            .take(selection.included.len().saturating_sub(1))
+            .all(|&operator| operator == "and")
+            && selection.included.len() > 1;
+
+        // Anything that does not round-trip is a hand-written expression and is left alone
+        let normalized = script.split_whitespace().collect::<Vec<_>>().join(" ");
+        (selection.expression().as_deref() == Some(normalized.as_str())).then_some(selection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expression() {
+        let mut selection = CategorySelection::default();
+        assert_eq!(selection.expression(), None);
+
+        selection.cycle(ScriptCategory::Default);
+        selection.cycle(ScriptCategory::Safe);
+        assert_eq!(selection.expression().as_deref(), Some("default or safe"));
+
+        selection.match_all = true;
+        assert_eq!(selection.expression().as_deref(), Some("default and safe"));
+
+        selection.match_all = false;
+        selection.cycle(ScriptCategory::Safe);
+        selection.cycle(ScriptCategory::Vuln);
+        selection.cycle(ScriptCategory::Intrusive);
+        selection.cycle(ScriptCategory::Intrusive);
+        assert_eq!(
+            selection.expression().as_deref(),
+            Some("(default or vuln) and not safe and not intrusive")
+        );
+    }
+
+    #[test]
+    fn test_from_scripts() {
+        let mut scripts = vec![
+            "default".to_string(),
+            "http-title".to_string(),
+            "vuln and not dos".to_string(),
+            "default or vuln and not dos".to_string(),
+        ];
+        let mut selection = CategorySelection::from_scripts(&scripts);
+        assert_eq!(
+            selection.included,
+            vec![ScriptCategory::Default, ScriptCategory::Vuln]
+        );
+        assert_eq!(selection.excluded, vec![ScriptCategory::Dos]);
+
+        selection.match_all = true;
+        selection.apply(&mut scripts);
+        assert_eq!(
+            scripts,
+            vec![
+                "default and vuln and not dos",
+                "http-title",
+                "default or vuln and not dos",
+            ]
+        );
+        assert_eq!(CategorySelection::from_scripts(&scripts), selection);
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+
+use crate::scan::{builder::NmapCommandBuilder, model::NmapScan, script_args::entries};
+
+/// Value of a --script-args entry asking for the secret each session, when the scan first runs
+pub const PROMPT: &str = "?";
+
+/// Shown in place of secret values
+const MASK: &str = "****";
+
+/// Endings of --script-args keys holding credentials, such as smbpassword or ldap.password
+const SECRET_SUFFIXES: &[&str] = &[
+    "password",
+    "passwd",
+    "pass",
+    "passphrase",
+    "secret",
+    "token",
+    "hash",
+    "apikey",
+];
+
+/// Where the value of a secret --script-args entry comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source<'a> {
+    /// Typed into the arguments themselves
+    Literal(&'a str),
+    /// Read from an environment variable given as $NAME or ${NAME}
+    Env(&'a str),
+    /// Asked for when the scan runs
+    Prompt,
+}
+
+impl<'a> Source<'a> {
+    fn of(value: &'a str) -> Self {
+        let value = value.trim();
+        if value == PROMPT {
+            return Source::Prompt;
+        }
+        let name = value.strip_prefix('$').map(|name| {
+            name.strip_prefix('{')
+                .and_then(|name| name.strip_suffix('}'))
+                .unwrap_or(name)
+        });
+        match name {
+            Some(name)
+                if !name.is_empty()
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+            {
+                Source::Env(name)
+            }
+            _ => Source::Literal(value),
+        }
+    }
+}
+
+/// Whether a --script-args key holds a credential
+pub fn is_secret_key(key: &str) -> bool {
+    let key = key.trim().to_ascii_lowercase();
+    let name = key.rsplit('.').next().unwrap_or_default();
+    SECRET_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+}
+
+/// Rebuild the arguments, replacing the values of secret entries for which `replace` returns one
+fn replace_secrets(args: &str, mut replace: impl FnMut(&str, Source) -> Option<String>) -> String {
+    entries(args)
+        .into_iter()
+        .map(|entry| {
+            if let Some((key, value)) = entry.split_once('=')
+                && is_secret_key(key)
+                && let Some(value) = replace(key.trim(), Source::of(value))
+            {
+                format!("{}={}", key, value)
+            } else {
+                entry.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quote a value for --script-args when it holds characters that separate entries
+fn quote_value(value: &str) -> String {
+    if !value.is_empty() && !value.contains([',', '{', '}', '=', '"', '\'', ' ']) {
+        return value.to_string();
+    }
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn map_script_args(scan: &NmapScan, map: impl FnOnce(&str) -> String) -> NmapScan {
+    let mut scan = scan.clone();
+    if let Some(args) = scan.script_scan.script_args.take() {
+        scan.script_scan.script_args = Some(map(&args));
+    }
+    scan
+}
+
+/// The scan with the secrets typed into --script-args hidden, for showing its command
+pub fn masked(scan: &NmapScan) -> NmapScan {
+    map_script_args(scan, mask_args)
+}
+
+/// The arguments with the secrets typed into them hidden
+pub fn mask_args(args: &str) -> String {
+    replace_secrets(args, |_, source| {
+        matches!(source, Source::Literal(_)).then(|| MASK.to_string())
+    })
+}
+
+/// The arguments edited from masked ones, with the secrets still hidden taken back from the
+/// original arguments
+pub fn unmask_args(args: &str, original: &str) -> String {
+    let hidden: HashMap<&str, &str> = entries(original)
+        .into_iter()
+        .filter_map(|entry| entry.split_once('='))
+        .filter(|(key, _)| is_secret_key(key))
+        .map(|(key, value)| (key.trim(), value))
+        .collect();
+    entries(args)
+        .into_iter()
+        .map(|entry| {
+            if let Some((key, value)) = entry.split_once('=')
+                && value.trim() == MASK
+                && let Some(original) = hidden.get(key.trim())
+            {
+                format!("{}={}", key, original)
+            } else {
+                entry.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The scan edited from a masked one, with the secrets still hidden taken back from the original
+pub fn unmasked(scan: &NmapScan, original: &NmapScan) -> NmapScan {
+    match &original.script_scan.script_args {
+        Some(original) => map_script_args(scan, |args| unmask_args(args, original)),
+        None => scan.clone(),
+    }
+}
+
+/// The scan with the secrets typed into --script-args asked for at run time instead, for
+/// storing it
+pub fn stripped(scan: &NmapScan) -> NmapScan {
+    map_script_args(scan, strip_args)
+}
+
+/// The arguments with the secrets typed into them asked for at run time instead
+pub fn strip_args(args: &str) -> String {
+    replace_secrets(args, |_, source| {
+        matches!(source, Source::Literal(_)).then(|| PROMPT.to_string())
+    })
+}
+
+/// An FTP bounce relay without the user and password written before its server, as in
+/// user:pass@ftp.example.com
+pub fn strip_userinfo(relay: &str) -> String {
+    relay
+        .rsplit_once('@')
+        .map_or(relay, |(_, server)| server)
+        .to_string()
+}
+
+/// Keys of the secrets to ask for before the scan runs: those marked with ?, and those read
+/// from environment variables that are not set, unless they were already entered
+pub fn missing(
+    scan: &NmapScan,
+    env: impl Fn(&str) -> Option<String>,
+    entered: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut keys = Vec::new();
+    if let Some(args) = &scan.script_scan.script_args {
+        replace_secrets(args, |key, source| {
+            let needed = match source {
+                Source::Literal(_) => false,
+                Source::Env(name) => env(name).is_none(),
+                Source::Prompt => true,
+            };
+            if needed && !entered.contains_key(key) && !keys.iter().any(|known| known == key) {
+                keys.push(key.to_string());
+            }
+            None
+        });
+    }
+    keys
+}
+
+/// The scan with the secrets of --script-args filled in from the environment or the values
+/// entered at the prompt, which take precedence
+pub fn resolved(
+    scan: &NmapScan,
+    env: impl Fn(&str) -> Option<String>,
+    entered: &HashMap<String, String>,
+) -> NmapScan {
+    map_script_args(scan, |args| {
+        replace_secrets(args, |key, source| {
+            let value = match source {
+                Source::Literal(_) => return None,
+                Source::Env(name) => entered.get(key).cloned().or_else(|| env(name)),
+                Source::Prompt => entered.get(key).cloned(),
+            };
+            value.map(|value| quote_value(&value))
+        })
+    })
+}
+
+/// Environment variable a secret is read from outside lazynmap: the one it names, or one named
+/// after its key, such as LDAP_PASSWORD for ldap.password
+fn variable(key: &str, source: Source) -> String {
+    match source {
+        Source::Env(name) => name.to_string(),
+        Source::Literal(_) | Source::Prompt => key
+            .chars()
+            .map(|c| match c {
+                c if c.is_ascii_alphanumeric() => c.to_ascii_uppercase(),
+                _ => '_',
+            })
+            .collect(),
+    }
+}
+
+/// The scan with the secrets of --script-args replaced by references to the environment
+/// variables they are read from, with the names of those variables and the keys they are for
+pub fn referenced(scan: &NmapScan) -> (NmapScan, Vec<(String, String)>) {
+    let mut variables: Vec<(String, String)> = Vec::new();
+    let scan = map_script_args(scan, |args| {
+        replace_secrets(args, |key, source| {
+            let name = variable(key, source);
+            let reference = format!("${{{}}}", name);
+            if !variables.iter().any(|(known, _)| *known == name) {
+                variables.push((name, key.to_string()));
+            }
+            Some(reference)
+        })
+    });
+    (scan, variables)
+}
+
+/// Command line of the scan for a shell to run, reading the secrets of --script-args from the
+/// environment variables named with the keys they are for
+pub fn shell_command(scan: &NmapScan) -> (String, Vec<(String, String)>) {
+    let (scan, variables) = referenced(scan);
+    // The builder escapes `$` so values reach nmap as typed; the references have to expand
+    let command = variables
+        .iter()
+        .fold(NmapCommandBuilder::build(&scan), |command, (name, _)| {
+            let reference = format!("${{{}}}", name);
+            command.replace(&format!("\\{}", reference), &reference)
+        });
+    (command, variables)
+}
+
+/// Values of the variables `shell_command` reads the secrets from: typed into the arguments, set
+/// in the environment or entered at the prompt, which take precedence
+pub fn variables(
+    scan: &NmapScan,
+    env: impl Fn(&str) -> Option<String>,
+    entered: &HashMap<String, String>,
+) -> Vec<(String, String)> {
+    let mut values: Vec<(String, String)> = Vec::new();
+    if let Some(args) = &scan.script_scan.script_args {
+        replace_secrets(args, |key, source| {
+            let value = match source {
+                Source::Literal(value) => Some(value.to_string()),
+                Source::Env(name) => entered.get(key).cloned().or_else(|| env(name)),
+                Source::Prompt => entered.get(key).cloned(),
+            };
+            let name = variable(key, source);
+            if let Some(value) = value
+                && !values.iter().any(|(known, _)| *known == name)
+            {
+                let value = match source {
+                    Source::Literal(_) => value,
+                    Source::Env(_) | Source::Prompt => quote_value(&value),
+                };
+                values.push((name, value));
+            }
+            None
+        });
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secrets() {
+        let mut scan = NmapScan::new();
+        scan.script_scan.script_args = Some(
+            "smbusername=admin,smbpassword=hunter2,ldap.password=$LDAP_PASS,smbhash=?,\
+             userdb={a,b},http.useragent=\"x,y\""
+                .to_string(),
+        );
+        assert_eq!(
+            masked(&scan).script_scan.script_args.as_deref(),
+            Some(
+                "smbusername=admin,smbpassword=****,ldap.password=$LDAP_PASS,smbhash=?,\
+                 userdb={a,b},http.useragent=\"x,y\""
+            )
+        );
+        assert_eq!(
+            strip_args("smbpassword=hunter2,brute.passonly=true"),
+            "smbpassword=?,brute.passonly=true"
+        );
+
+        let env = |name: &str| (name == "LDAP_PASS").then(|| "s3cret".to_string());
+        let mut entered = HashMap::new();
+        assert_eq!(missing(&scan, env, &entered), ["smbhash"]);
+        assert_eq!(
+            missing(&scan, |_| None, &entered),
+            ["ldap.password", "smbhash"]
+        );
+
+        entered.insert("smbhash".to_string(), "aa:bb, cc".to_string());
+        assert!(missing(&scan, env, &entered).is_empty());
+        assert_eq!(
+            resolved(&scan, env, &entered)
+                .script_scan
+                .script_args
+                .as_deref(),
+            Some(
+                "smbusername=admin,smbpassword=hunter2,ldap.password=s3cret,\
+                 smbhash=\"aa:bb, cc\",userdb={a,b},http.useragent=\"x,y\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_shell_command() {
+        let mut scan = NmapScan::new();
+        scan.script_scan.script_args =
+            Some("smbusername=admin,smbpassword=hunter2,ldap.password=$LDAP_PASS".to_string());
+        let (command, names) = shell_command(&scan);
+        assert!(command.contains(
+            "\"smbusername=admin,smbpassword=${SMBPASSWORD},ldap.password=${LDAP_PASS}\""
+        ));
+        assert!(!command.contains("hunter2"));
+        assert_eq!(
+            names,
+            [
+                ("SMBPASSWORD".to_string(), "smbpassword".to_string()),
+                ("LDAP_PASS".to_string(), "ldap.password".to_string())
+            ]
+        );
+
+        let entered = HashMap::from([("ldap.password".to_string(), "a,b".to_string())]);
+        assert_eq!(
+            variables(&scan, |_| None, &entered),
+            [
+                ("SMBPASSWORD".to_string(), "hunter2".to_string()),
+                ("LDAP_PASS".to_string(), "\"a,b\"".to_string())
+            ]
+        );
+
+        let edited = "smbusername=root,smbpassword=****";
+        assert_eq!(
+            unmask_args(edited, "smbusername=admin,smbpassword=hunter2"),
+            "smbusername=root,smbpassword=hunter2"
+        );
+    }
+}
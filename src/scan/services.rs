@@ -0,0 +1,84 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Directories nmap looks in for its data files when neither --datadir nor NMAPDIR is set
+const DATA_DIRS: [&str; 3] = [
+    "/usr/local/share/nmap",
+    "/usr/share/nmap",
+    "/opt/homebrew/share/nmap",
+];
+
+/// How often each port was found open, from the nmap-services file that --port-ratio and
+/// --top-ports choose ports from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PortRatios {
+    /// Open frequencies of the TCP ports, highest first
+    tcp: Vec<f32>,
+    /// Open frequencies of the UDP ports, highest first
+    udp: Vec<f32>,
+}
+
+impl PortRatios {
+    /// Read the nmap-services file from the data directory nmap would use
+    pub fn load(datadir: Option<&Path>) -> Option<Self> {
+        let dirs = datadir
+            .map(Path::to_path_buf)
+            .into_iter()
+            .chain(env::var_os("NMAPDIR").map(PathBuf::from))
+            .chain(DATA_DIRS.iter().map(PathBuf::from));
+        dirs.into_iter()
+            .find_map(|dir| fs::read_to_string(dir.join("nmap-services")).ok())
+            .map(|content| Self::parse(&content))
+    }
+
+    /// Entries such as "http 80/tcp 0.484143 # World Wide Web HTTP", separated by tabs
+    pub fn parse(content: &str) -> Self {
+        let mut ratios = Self::default();
+        for line in content.lines().filter(|line| !line.starts_with('#')) {
+            let mut fields = line.split_whitespace().skip(1);
+            let (Some(port), Some(ratio)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let Ok(ratio) = ratio.parse::<f32>() else {
+                continue;
+            };
+            match port.split_once('/') {
+                Some((_, "tcp")) => ratios.tcp.push(ratio),
+                Some((_, "udp")) => ratios.udp.push(ratio),
+                _ => {}
+            }
+        }
+        ratios.tcp.sort_by(|a, b| b.total_cmp(a));
+        ratios.udp.sort_by(|a, b| b.total_cmp(a));
+        ratios
+    }
+
+    /// Number of ports --port-ratio picks, those open at least that often
+    pub fn count(&self, ratio: f32, udp: bool) -> usize {
+        let ratios = if udp { &self.udp } else { &self.tcp };
+        ratios.partition_point(|&open| open >= ratio)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let ratios = PortRatios::parse(
+            "# Fields: service port/proto open-frequency comments\n\
+             http\t80/tcp\t0.484143\t# World Wide Web HTTP\n\
+             telnet\t23/tcp\t0.221265\n\
+             tcpmux\t1/tcp\t0.001995\n\
+             snmp\t161/udp\t0.433467\n\
+             unknown\t5/udp\n",
+        );
+        assert_eq!(ratios.count(0.2, false), 2);
+        assert_eq!(ratios.count(0.0, false), 3);
+        assert_eq!(ratios.count(0.5, false), 0);
+        assert_eq!(ratios.count(0.4, true), 1);
+    }
+}
@@ -0,0 +1,223 @@
+use std::fs;
+use std::io;
+use std::path::{self, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::scan::{builder::NmapCommandBuilder, model::NmapScan, secrets};
+
+/// Maximum number of sessions kept in the log
+const MAX_SESSIONS: usize = 50;
+
+/// Outcome of a scan session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStatus {
+    Running,
+    Completed,
+    Failed,
+    Interrupted,
+}
+
+impl SessionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionStatus::Running => "running",
+            SessionStatus::Completed => "completed",
+            SessionStatus::Failed => "failed",
+            SessionStatus::Interrupted => "interrupted",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "running" => Some(SessionStatus::Running),
+            "completed" => Some(SessionStatus::Completed),
+            "failed" => Some(SessionStatus::Failed),
+            "interrupted" => Some(SessionStatus::Interrupted),
+            _ => None,
+        }
+    }
+}
+
+/// A scan launched from the TUI and the output file it can be resumed from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanSession {
+    pub started: u64,
+    pub status: SessionStatus,
+    pub resume_file: Option<PathBuf>,
+    pub command: String,
+}
+
+impl ScanSession {
+    pub fn new(scan: &NmapScan) -> Self {
+        Self {
+            started: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            status: SessionStatus::Running,
+            resume_file: Self::resume_file(scan),
+            command: NmapCommandBuilder::build(&secrets::masked(scan)),
+        }
+    }
+
+    /// The output file nmap can resume from (-oN or -oG, or the -oA normal output)
+    fn resume_file(scan: &NmapScan) -> Option<PathBuf> {
+        scan.output
+            .resume
+            .clone()
+            .or_else(|| scan.output.normal.clone())
+            .or_else(|| scan.output.grepable.clone())
+            .or_else(|| {
+                scan.output
+                    .all_formats
+                    .as_ref()
+                    .map(|base| PathBuf::from(format!("{}.nmap", base)))
+            })
+            .map(|file| path::absolute(&file).unwrap_or(file))
+    }
+
+    /// Whether this session was interrupted and left a resumable output file behind
+    pub fn is_resumable(&self) -> bool {
+        self.status == SessionStatus::Interrupted
+            && self.resume_file.as_ref().is_some_and(|file| file.exists())
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.started,
+            self.status.as_str(),
+            self.resume_file
+                .as_ref()
+                .map(|file| file.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            self.command.replace(['\t', '\n'], " "),
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, '\t');
+        let started = fields.next()?.parse().ok()?;
+        let status = SessionStatus::from_str(fields.next()?)?;
+        let resume_file = Some(fields.next()?)
+            .filter(|file| !file.is_empty())
+            .map(PathBuf::from);
+        let command = fields.next()?.to_string();
+        Some(Self {
+            started,
+            status,
+            resume_file,
+            command,
+        })
+    }
+}
+
+/// Persistent log of scan sessions, one tab-separated session per line
+pub struct SessionLog {
+    path: Option<PathBuf>,
+    sessions: Vec<ScanSession>,
+}
+
+impl SessionLog {
+    /// Load the log, marking sessions that were still running as interrupted
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let mut sessions: Vec<ScanSession> = path
+            .as_deref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|content| content.lines().filter_map(ScanSession::from_line).collect())
+            .unwrap_or_default();
+
+        for session in sessions.iter_mut() {
+            if session.status == SessionStatus::Running {
+                session.status = SessionStatus::Interrupted;
+            }
+        }
+
+        Self { path, sessions }
+    }
+
+    pub fn start(&mut self, session: ScanSession) {
+        self.sessions.push(session);
+        if self.sessions.len() > MAX_SESSIONS {
+            self.sessions.remove(0);
+        }
+        self.save().ok();
+    }
+
+    /// Record the outcome of the most recently started session
+    pub fn finish(&mut self, status: SessionStatus) {
+        if let Some(session) = self.sessions.last_mut() {
+            session.status = status;
+        }
+        self.save().ok();
+    }
+
+    /// The most recent session that can be resumed, if any
+    pub fn last_resumable(&self) -> Option<&ScanSession> {
+        self.sessions
+            .last()
+            .filter(|session| session.is_resumable())
+    }
+
+    pub fn sessions(&self) -> &[ScanSession] {
+        &self.sessions
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = self
+            .sessions
+            .iter()
+            .map(|session| session.to_line() + "\n")
+            .collect::<String>();
+        fs::write(path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_round_trip() {
+        let session = ScanSession {
+            started: 1700000000,
+            status: SessionStatus::Interrupted,
+            resume_file: Some(PathBuf::from("scan.nmap")),
+            command: "nmap -oN scan.nmap 10.0.0.0/24".to_string(),
+        };
+        assert_eq!(ScanSession::from_line(&session.to_line()), Some(session));
+    }
+
+    #[test]
+    fn test_resume_file() {
+        let mut scan = NmapScan::new();
+        assert_eq!(ScanSession::new(&scan).resume_file, None);
+
+        scan.output.all_formats = Some("full".to_string());
+        let resume_file = ScanSession::new(&scan).resume_file.unwrap();
+        assert!(resume_file.is_absolute());
+        assert!(resume_file.ends_with("full.nmap"));
+
+        scan.output.grepable = Some(PathBuf::from("scan.gnmap"));
+        let resume_file = ScanSession::new(&scan).resume_file.unwrap();
+        assert!(resume_file.ends_with("scan.gnmap"));
+    }
+
+    #[test]
+    fn test_running_sessions_become_interrupted() {
+        let dir = std::env::temp_dir().join(format!("lazynmap-session-{}", std::process::id()));
+        let path = dir.join("sessions");
+        let mut log = SessionLog::load(Some(path.clone()));
+        log.start(ScanSession::new(&NmapScan::new()));
+
+        let log = SessionLog::load(Some(path));
+        assert_eq!(log.sessions()[0].status, SessionStatus::Interrupted);
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,121 @@
+/// Live statistics collected from the output of a running scan
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunStats {
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    pub hosts_completed: Option<u32>,
+    pub hosts_up: Option<u32>,
+    pub phase: Option<String>,
+    pub percent_done: Option<f32>,
+    pub remaining: Option<String>,
+    pub host_group: Option<String>,
+}
+
+impl RunStats {
+    /// Update the statistics from a single line of nmap output
+    pub fn update(&mut self, line: &str) {
+        let line = line.trim();
+
+        if line.starts_with("SENT (") {
+            self.packets_sent += 1;
+        } else if line.starts_with("RCVD (") {
+            self.packets_received += 1;
+        } else if let Some(rest) = line.strip_prefix("Raw packets sent: ") {
+            // Raw packets sent: 2000 (88.000KB) | Rcvd: 1003 (40.148KB)
+            if let Some(sent) = Self::leading_number(rest) {
+                self.packets_sent = sent;
+            }
+            if let Some(received) = rest
+                .split_once("Rcvd: ")
+                .and_then(|(_, rcvd)| Self::leading_number(rcvd))
+            {
+                self.packets_received = received;
+            }
+        } else if let Some(rest) = line.strip_prefix("Stats: ") {
+            // Stats: 0:00:05 elapsed; 0 hosts completed (1 up), 1 undergoing SYN Stealth Scan
+            if let Some((_, progress)) = rest.split_once("; ") {
+                self.hosts_completed = progress
+                    .split_once(" hosts completed")
+                    .and_then(|(completed, _)| completed.trim().parse().ok());
+                self.hosts_up = progress
+                    .split_once('(')
+                    .and_then(|(_, up)| up.split_once(" up)"))
+                    .and_then(|(up, _)| up.trim().parse().ok());
+                if let Some((_, phase)) = progress.split_once(" undergoing ") {
+                    self.phase = Some(phase.to_string());
+                }
+            }
+        } else if let Some((phase, progress)) = line.split_once(" Timing: About ") {
+            // SYN Stealth Scan Timing: About 12.50% done; ETC: 12:00 (0:00:35 remaining)
+            self.phase = Some(phase.to_string());
+            self.percent_done = progress
+                .split_once('%')
+                .and_then(|(percent, _)| percent.parse().ok());
+            self.remaining = progress
+                .rsplit_once('(')
+                .and_then(|(_, remaining)| remaining.split_once(" remaining"))
+                .map(|(remaining, _)| remaining.to_string());
+        } else if let Some(rest) = line.strip_prefix("Initiating ") {
+            // Initiating SYN Stealth Scan at 12:00
+            let phase = rest.rsplit_once(" at ").map_or(rest, |(phase, _)| phase);
+            self.phase = Some(phase.to_string());
+            self.percent_done = None;
+            self.remaining = None;
+        } else if let Some(rest) = line.strip_prefix("Scanning ") {
+            // Scanning 4 hosts [1000 ports/host]
+            let group = rest.split_once(" [").map_or(rest, |(group, _)| group);
+            self.host_group = Some(group.to_string());
+        }
+    }
+
+    /// Average packet send rate over the given number of seconds
+    pub fn send_rate(&self, elapsed_secs: f64) -> Option<f64> {
+        if elapsed_secs > 0.0 && self.packets_sent > 0 {
+            Some(self.packets_sent as f64 / elapsed_secs)
+        } else {
+            None
+        }
+    }
+
+    fn leading_number(s: &str) -> Option<u64> {
+        s.split_whitespace().next()?.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packet_trace() {
+        let mut stats = RunStats::default();
+        stats.update("SENT (0.0450s) TCP 10.0.0.1:54321 > 10.0.0.2:80 S ttl=42");
+        stats.update("SENT (0.0451s) TCP 10.0.0.1:54321 > 10.0.0.2:443 S ttl=42");
+        stats.update("RCVD (0.0470s) TCP 10.0.0.2:80 > 10.0.0.1:54321 SA ttl=64");
+        assert_eq!(stats.packets_sent, 2);
+        assert_eq!(stats.packets_received, 1);
+
+        stats.update("Raw packets sent: 2000 (88.000KB) | Rcvd: 1003 (40.148KB)");
+        assert_eq!(stats.packets_sent, 2000);
+        assert_eq!(stats.packets_received, 1003);
+        assert_eq!(stats.send_rate(10.0), Some(200.0));
+    }
+
+    #[test]
+    fn test_stats_lines() {
+        let mut stats = RunStats::default();
+        stats.update("Initiating SYN Stealth Scan at 12:00");
+        stats.update("Scanning 4 hosts [1000 ports/host]");
+        stats.update(
+            "Stats: 0:00:05 elapsed; 2 hosts completed (3 up), 1 undergoing SYN Stealth Scan",
+        );
+        stats.update("SYN Stealth Scan Timing: About 12.50% done; ETC: 12:00 (0:00:35 remaining)");
+
+        assert_eq!(stats.phase.as_deref(), Some("SYN Stealth Scan"));
+        assert_eq!(stats.host_group.as_deref(), Some("4 hosts"));
+        assert_eq!(stats.hosts_completed, Some(2));
+        assert_eq!(stats.hosts_up, Some(3));
+        assert_eq!(stats.percent_done, Some(12.5));
+        assert_eq!(stats.remaining.as_deref(), Some("0:00:35"));
+    }
+}
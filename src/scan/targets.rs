@@ -0,0 +1,332 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use crate::scan::sampling::expand_octet;
+
+/// Entries of a target list that add no hosts: repeats, and entries inside a CIDR block of the list
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TargetOverlaps {
+    pub duplicates: Vec<String>,
+    /// Entries with the CIDR block that contains them
+    pub covered: Vec<(String, String)>,
+}
+
+impl TargetOverlaps {
+    pub fn is_empty(&self) -> bool {
+        self.duplicates.is_empty() && self.covered.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.duplicates.len() + self.covered.len()
+    }
+}
+
+impl fmt::Display for TargetOverlaps {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        match self.duplicates.len() {
+            0 => {}
+            1 => parts.push("1 duplicate target".to_string()),
+            count => parts.push(format!("{} duplicate targets", count)),
+        }
+        match self.covered.as_slice() {
+            [] => {}
+            [(target, block)] => parts.push(format!("{} is inside {}", target, block)),
+            covered => parts.push(format!("{} targets inside CIDR blocks", covered.len())),
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Why an entry of a target list adds no hosts
+enum Redundant<'a> {
+    Duplicate,
+    Within(&'a str),
+}
+
+/// Find the duplicates of a target list and the entries that one of its CIDR blocks contains
+pub fn find_overlaps(targets: &[String]) -> TargetOverlaps {
+    let mut overlaps = TargetOverlaps::default();
+    for (target, redundant) in targets.iter().zip(redundant(targets)) {
+        match redundant {
+            Some(Redundant::Duplicate) => overlaps.duplicates.push(target.clone()),
+            Some(Redundant::Within(block)) => {
+                overlaps.covered.push((target.clone(), block.to_string()))
+            }
+            None => {}
+        }
+    }
+    overlaps
+}
+
+/// The target list without its duplicates and the entries its CIDR blocks already contain
+pub fn clean_up(targets: &[String]) -> Vec<String> {
+    targets
+        .iter()
+        .zip(redundant(targets))
+        .filter(|(_, redundant)| redundant.is_none())
+        .map(|(target, _)| target.clone())
+        .collect()
+}
+
+/// Sort a target list in address order, IPv4 before IPv6, with hostnames last in name order
+pub fn sort_targets(targets: &[String]) -> Vec<String> {
+    let mut sorted: Vec<(Option<Range>, &String)> = targets
+        .iter()
+        .map(|target| (target_range(target), target))
+        .collect();
+    sorted.sort_by(
+        |(range, target), (other_range, other)| match (range, other_range) {
+            (Some(range), Some(other_range)) => range.cmp(other_range),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => target.to_lowercase().cmp(&other.to_lowercase()),
+        },
+    );
+    sorted
+        .into_iter()
+        .map(|(_, target)| target.clone())
+        .collect()
+}
+
+/// Merge the IPv4 addresses and CIDR blocks of a target list into as few CIDR blocks as cover
+/// exactly the same hosts, in address order; other targets follow unchanged
+pub fn group_targets(targets: &[String]) -> Vec<String> {
+    let mut intervals = Vec::new();
+    let mut others = Vec::new();
+    for target in targets {
+        match target_range(target) {
+            Some(Range::V4(start, end)) if is_block(target) => {
+                intervals.push((u64::from(start), u64::from(end)))
+            }
+            _ => others.push(target.clone()),
+        }
+    }
+    intervals.sort();
+
+    let mut merged: Vec<(u64, u64)> = Vec::new();
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end + 1 => *last_end = (*last_end).max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let mut grouped = Vec::new();
+    for (mut start, end) in merged {
+        while start <= end {
+            // The largest aligned block starting here that doesn't run past the end
+            let mut size = 1u64 << start.trailing_zeros().min(32);
+            while start + size - 1 > end {
+                size /= 2;
+            }
+            let address = Ipv4Addr::from(start as u32);
+            if size == 1 {
+                grouped.push(address.to_string());
+            } else {
+                grouped.push(format!("{}/{}", address, 32 - size.trailing_zeros()));
+            }
+            start += size;
+        }
+    }
+    grouped.extend(others);
+    grouped
+}
+
+/// Whether a target is a single address or a CIDR block, rather than an octet range
+fn is_block(target: &str) -> bool {
+    let address = target.trim().split('/').next().unwrap_or_default();
+    address.parse::<Ipv4Addr>().is_ok()
+}
+
+fn redundant(targets: &[String]) -> Vec<Option<Redundant<'_>>> {
+    let keys: Vec<String> = targets
+        .iter()
+        .map(|target| target.trim().to_lowercase())
+        .collect();
+    let ranges: Vec<Option<Range>> = targets.iter().map(|target| target_range(target)).collect();
+
+    (0..targets.len())
+        .map(|index| {
+            if keys[..index].contains(&keys[index]) {
+                return Some(Redundant::Duplicate);
+            }
+            let range = ranges[index]?;
+            // Of two blocks with the same hosts, the first one listed stays
+            let block = (0..targets.len()).find(|&other| {
+                other != index
+                    && keys[other] != keys[index]
+                    && targets[other].contains('/')
+                    && ranges[other].is_some_and(|block| {
+                        block.contains(range) && (block != range || other < index)
+                    })
+            })?;
+            Some(Redundant::Within(targets[block].as_str()))
+        })
+        .collect()
+}
+
+/// First and last address of a target, for IP addresses, CIDR blocks and octet ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Range {
+    V4(u32, u32),
+    V6(u128, u128),
+}
+
+impl Range {
+    pub fn contains(self, other: Range) -> bool {
+        match (self, other) {
+            (Range::V4(start, end), Range::V4(other_start, other_end)) => {
+                start <= other_start && other_end <= end
+            }
+            (Range::V6(start, end), Range::V6(other_start, other_end)) => {
+                start <= other_start && other_end <= end
+            }
+            _ => false,
+        }
+    }
+}
+
+pub fn target_range(target: &str) -> Option<Range> {
+    let target = target.trim();
+    if let Some((address, prefix)) = target.split_once('/') {
+        let prefix = prefix.parse::<u32>().ok()?;
+        if let Ok(ip) = address.parse::<Ipv4Addr>() {
+            let mask = u32::MAX
+                .checked_shl(32u32.checked_sub(prefix)?)
+                .unwrap_or(0);
+            let start = u32::from(ip) & mask;
+            return Some(Range::V4(start, start | !mask));
+        }
+        let ip = address.parse::<Ipv6Addr>().ok()?;
+        let mask = u128::MAX
+            .checked_shl(128u32.checked_sub(prefix)?)
+            .unwrap_or(0);
+        let start = u128::from(ip) & mask;
+        return Some(Range::V6(start, start | !mask));
+    }
+    if let Ok(ip) = target.parse::<Ipv6Addr>() {
+        return Some(Range::V6(ip.into(), ip.into()));
+    }
+
+    // Octet ranges such as 10.0.0.1-20; only their bounds matter for containment
+    let octets: Vec<Vec<u8>> = target.split('.').map(expand_octet).collect();
+    if octets.len() != 4 || octets.iter().any(Vec::is_empty) {
+        return None;
+    }
+    let bound = |pick: fn(&Vec<u8>) -> u8| {
+        u32::from(Ipv4Addr::new(
+            pick(&octets[0]),
+            pick(&octets[1]),
+            pick(&octets[2]),
+            pick(&octets[3]),
+        ))
+    };
+    Some(Range::V4(
+        bound(|values| values.iter().copied().min().unwrap_or_default()),
+        bound(|values| values.iter().copied().max().unwrap_or_default()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(targets: &[&str]) -> Vec<String> {
+        targets.iter().map(|target| target.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_overlaps() {
+        let targets = list(&[
+            "10.0.0.0/24",
+            "10.0.0.5",
+            "scanme.nmap.org",
+            "10.0.1.1",
+            "ScanMe.nmap.org",
+            "10.0.0.1-20",
+            "10.0.0.7/24",
+            "10.0.0.0/16",
+            "fe80::1",
+            "fe80::/64",
+        ]);
+        let overlaps = find_overlaps(&targets);
+        assert_eq!(overlaps.duplicates, vec!["ScanMe.nmap.org"]);
+        assert_eq!(
+            overlaps.covered,
+            vec![
+                ("10.0.0.0/24".to_string(), "10.0.0.0/16".to_string()),
+                ("10.0.0.5".to_string(), "10.0.0.0/24".to_string()),
+                ("10.0.1.1".to_string(), "10.0.0.0/16".to_string()),
+                ("10.0.0.1-20".to_string(), "10.0.0.0/24".to_string()),
+                ("10.0.0.7/24".to_string(), "10.0.0.0/24".to_string()),
+                ("fe80::1".to_string(), "fe80::/64".to_string()),
+            ]
+        );
+        assert_eq!(
+            clean_up(&targets),
+            vec!["scanme.nmap.org", "10.0.0.0/16", "fe80::/64"]
+        );
+        assert!(find_overlaps(&list(&["10.0.0.1", "10.0.0.2/31"])).is_empty());
+    }
+
+    #[test]
+    fn test_sort_targets() {
+        let targets = list(&[
+            "scanme.nmap.org",
+            "10.0.0.10",
+            "fe80::1",
+            "10.0.0.9",
+            "Example.com",
+            "9.255.0.0/16",
+            "10.0.0.1-5",
+        ]);
+        assert_eq!(
+            sort_targets(&targets),
+            vec![
+                "9.255.0.0/16",
+                "10.0.0.1-5",
+                "10.0.0.9",
+                "10.0.0.10",
+                "fe80::1",
+                "Example.com",
+                "scanme.nmap.org",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_targets() {
+        let mut targets: Vec<String> = (0..4).map(|i| format!("10.0.0.{}", i)).collect();
+        targets.extend(list(&[
+            "10.0.0.4",
+            "10.0.1.0/24",
+            "10.0.0.5/32",
+            "10.0.0.6-7",
+            "scanme.nmap.org",
+            "10.0.2.0",
+            "10.0.2.255",
+            "10.0.0.128/25",
+        ]));
+        assert_eq!(
+            group_targets(&targets),
+            vec![
+                "10.0.0.0/30",
+                "10.0.0.4/31",
+                "10.0.0.128/25",
+                "10.0.1.0/24",
+                "10.0.2.0",
+                "10.0.2.255",
+                "10.0.0.6-7",
+                "scanme.nmap.org",
+            ]
+        );
+        assert_eq!(
+            group_targets(&list(&["0.0.0.0/1", "128.0.0.0/1"])),
+            vec!["0.0.0.0/0"]
+        );
+    }
+}
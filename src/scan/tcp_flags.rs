@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// TCP flags set in the probes of a --scanflags scan
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TcpFlags(u8);
+
+impl TcpFlags {
+    /// Names nmap accepts, with their bit, in the order nmap lists them
+    pub const NAMES: [(&str, u8); 8] = [
+        ("URG", 0x20),
+        ("ACK", 0x10),
+        ("PSH", 0x08),
+        ("RST", 0x04),
+        ("SYN", 0x02),
+        ("FIN", 0x01),
+        ("ECE", 0x40),
+        ("CWR", 0x80),
+    ];
+
+    /// Flags offered as checkboxes, the six of the original TCP header
+    pub const CHECKBOXES: usize = 6;
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether the flag at an index of `NAMES` is set
+    pub fn contains(self, index: usize) -> bool {
+        Self::NAMES
+            .get(index)
+            .is_some_and(|(_, bit)| self.0 & bit != 0)
+    }
+
+    /// Set or clear the flag at an index of `NAMES`
+    pub fn toggle(&mut self, index: usize) {
+        if let Some((_, bit)) = Self::NAMES.get(index) {
+            self.0 ^= bit;
+        }
+    }
+}
+
+/// Accepts flag names in any order, such as "URGACKPSH", the numeric value, such as 9 or 0x29,
+/// or the ALL and NONE keywords
+impl FromStr for TcpFlags {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let number = match value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+            Some(hex) => u8::from_str_radix(hex, 16).ok(),
+            None if value.starts_with(|c: char| c.is_ascii_digit()) => value.parse().ok(),
+            None => None,
+        };
+        if let Some(number) = number {
+            return Ok(TcpFlags(number));
+        }
+
+        let upper = value.to_ascii_uppercase();
+        match upper.as_str() {
+            "ALL" => return Ok(TcpFlags(u8::MAX)),
+            "NONE" => return Ok(TcpFlags(0)),
+            _ => {}
+        }
+        let mut rest = upper.as_str();
+        let mut bits = 0;
+        while !rest.is_empty() {
+            let (_, bit) = Self::NAMES
+                .iter()
+                .find(|(name, _)| rest.starts_with(name))
+                .ok_or_else(|| format!("Invalid TCP flags: {}", value))?;
+            bits |= bit;
+            rest = &rest[3..];
+        }
+        if bits == 0 {
+            return Err("Set at least one TCP flag, or 0 for none".to_string());
+        }
+        Ok(TcpFlags(bits))
+    }
+}
+
+impl fmt::Display for TcpFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "0");
+        }
+        for (index, (name, _)) in Self::NAMES.iter().enumerate() {
+            if self.contains(index) {
+                write!(f, "{}", name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for TcpFlags {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<TcpFlags> for String {
+    fn from(flags: TcpFlags) -> Self {
+        flags.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("synfin".parse::<TcpFlags>().unwrap().bits(), 0x03);
+        assert_eq!("FINSYNURG".parse::<TcpFlags>().unwrap().bits(), 0x23);
+        assert_eq!("9".parse::<TcpFlags>().unwrap().bits(), 0x09);
+        assert_eq!("0x29".parse::<TcpFlags>().unwrap().bits(), 0x29);
+        assert_eq!("0".parse::<TcpFlags>().unwrap(), TcpFlags::default());
+        assert!("256".parse::<TcpFlags>().is_err());
+        assert!("SYNX".parse::<TcpFlags>().is_err());
+        assert!("".parse::<TcpFlags>().is_err());
+        assert_eq!("ALL".parse::<TcpFlags>().unwrap().bits(), 0xff);
+        assert_eq!("all".parse::<TcpFlags>().unwrap().bits(), 0xff);
+        assert_eq!("None".parse::<TcpFlags>().unwrap(), TcpFlags::default());
+        assert!("ALLSYN".parse::<TcpFlags>().is_err());
+    }
+
+    #[test]
+    fn test_compose() {
+        let mut flags = TcpFlags::default();
+        flags.toggle(2);
+        flags.toggle(5);
+        flags.toggle(0);
+        assert_eq!(flags.to_string(), "URGPSHFIN");
+        assert!(flags.contains(2));
+        flags.toggle(2);
+        assert_eq!(flags.to_string(), "URGFIN");
+        assert_eq!(TcpFlags(0xc0).to_string(), "ECECWR");
+    }
+}
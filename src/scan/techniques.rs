@@ -0,0 +1,198 @@
+use std::{fmt, mem};
+
+use crate::scan::model::ScanTechnique;
+
+/// How well a scan technique does something
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Support {
+    Yes,
+    Partly,
+    No,
+}
+
+impl fmt::Display for Support {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Support::Yes => write!(f, "yes"),
+            Support::Partly => write!(f, "partly"),
+            Support::No => write!(f, "no"),
+        }
+    }
+}
+
+/// What a scan technique needs and what it can tell, for choosing between them
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    pub option: &'static str,
+    pub name: &'static str,
+    /// The technique to switch to; -sI and -b also need a host, given in their own options
+    pub technique: ScanTechnique,
+    /// Sends raw packets, which takes root or --privileged
+    pub needs_root: bool,
+    /// Gets answers through firewalls that track connections and drop stray packets
+    pub stateful_firewalls: Support,
+    /// Tells open ports from filtered ones, instead of reporting open|filtered
+    pub open_vs_filtered: Support,
+    pub note: &'static str,
+}
+
+/// Every scan technique but --scanflags, whose abilities depend on the flags chosen
+pub static CAPABILITIES: [Capabilities; 14] = [
+    Capabilities {
+        option: "-sS",
+        name: "SYN",
+        technique: ScanTechnique::Syn,
+        needs_root: true,
+        stateful_firewalls: Support::Yes,
+        open_vs_filtered: Support::Yes,
+        note: "Fast default, never completes connections",
+    },
+    Capabilities {
+        option: "-sT",
+        name: "Connect",
+        technique: ScanTechnique::Connect,
+        needs_root: false,
+        stateful_firewalls: Support::Yes,
+        open_vs_filtered: Support::Yes,
+        note: "Full connections, logged by services",
+    },
+    Capabilities {
+        option: "-sA",
+        name: "ACK",
+        technique: ScanTechnique::Ack,
+        needs_root: true,
+        stateful_firewalls: Support::No,
+        open_vs_filtered: Support::No,
+        note: "Maps firewall rules: filtered or unfiltered",
+    },
+    Capabilities {
+        option: "-sW",
+        name: "Window",
+        technique: ScanTechnique::Window,
+        needs_root: true,
+        stateful_firewalls: Support::No,
+        open_vs_filtered: Support::Partly,
+        note: "ACK scan reading the TCP window of some systems",
+    },
+    Capabilities {
+        option: "-sM",
+        name: "Maimon",
+        technique: ScanTechnique::Maimon,
+        needs_root: true,
+        stateful_firewalls: Support::No,
+        open_vs_filtered: Support::No,
+        note: "FIN/ACK, answered only by some BSD systems",
+    },
+    Capabilities {
+        option: "-sU",
+        name: "UDP",
+        technique: ScanTechnique::Udp,
+        needs_root: true,
+        stateful_firewalls: Support::Yes,
+        open_vs_filtered: Support::Partly,
+        note: "Slow; silent ports stay open|filtered",
+    },
+    Capabilities {
+        option: "-sN",
+        name: "Null",
+        technique: ScanTechnique::TcpNull,
+        needs_root: true,
+        stateful_firewalls: Support::No,
+        open_vs_filtered: Support::No,
+        note: "Slips past stateless filters, not Windows",
+    },
+    Capabilities {
+        option: "-sF",
+        name: "FIN",
+        technique: ScanTechnique::Fin,
+        needs_root: true,
+        stateful_firewalls: Support::No,
+        open_vs_filtered: Support::No,
+        note: "Slips past stateless filters, not Windows",
+    },
+    Capabilities {
+        option: "-sX",
+        name: "Xmas",
+        technique: ScanTechnique::Xmas,
+        needs_root: true,
+        stateful_firewalls: Support::No,
+        open_vs_filtered: Support::No,
+        note: "Slips past stateless filters, not Windows",
+    },
+    Capabilities {
+        option: "-sY",
+        name: "SCTP INIT",
+        technique: ScanTechnique::SctpInit,
+        needs_root: true,
+        stateful_firewalls: Support::Yes,
+        open_vs_filtered: Support::Yes,
+        note: "SYN scan of SCTP, for telecom services",
+    },
+    Capabilities {
+        option: "-sZ",
+        name: "SCTP COOKIE ECHO",
+        technique: ScanTechnique::SctpCookie,
+        needs_root: true,
+        stateful_firewalls: Support::No,
+        open_vs_filtered: Support::No,
+        note: "Stealthier than -sY but less conclusive",
+    },
+    Capabilities {
+        option: "-sO",
+        name: "IP protocol",
+        technique: ScanTechnique::IpProtocol,
+        needs_root: true,
+        stateful_firewalls: Support::Partly,
+        open_vs_filtered: Support::Partly,
+        note: "Lists IP protocols instead of ports",
+    },
+    Capabilities {
+        option: "-sI",
+        name: "Idle",
+        technique: ScanTechnique::Idle(String::new()),
+        needs_root: true,
+        stateful_firewalls: Support::Yes,
+        open_vs_filtered: Support::Partly,
+        note: "Blind scan through an idle zombie host",
+    },
+    Capabilities {
+        option: "-b",
+        name: "FTP bounce",
+        technique: ScanTechnique::Ftp(String::new()),
+        needs_root: false,
+        stateful_firewalls: Support::Yes,
+        open_vs_filtered: Support::Partly,
+        note: "Relays through an FTP server, rarely works",
+    },
+];
+
+/// Index in `CAPABILITIES` of a scan technique
+pub fn capabilities_index(technique: &ScanTechnique) -> Option<usize> {
+    CAPABILITIES
+        .iter()
+        .position(|row| mem::discriminant(&row.technique) == mem::discriminant(technique))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::{builder::NmapCommandBuilder, model::NmapScan, tcp_flags::TcpFlags};
+
+    #[test]
+    fn test_capabilities() {
+        for (index, row) in CAPABILITIES.iter().enumerate() {
+            assert_eq!(capabilities_index(&row.technique), Some(index));
+            let mut scan = NmapScan::new();
+            scan.scan_technique = row.technique.clone();
+            let command = NmapCommandBuilder::build(&scan);
+            assert!(
+                index == 0 || command.contains(row.option),
+                "{}: {}",
+                row.option,
+                command
+            );
+        }
+        let scanflags = ScanTechnique::Scanflags(TcpFlags::default());
+        assert_eq!(capabilities_index(&scanflags), None);
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::ConfigError,
+    scan::{
+        model::NmapScan,
+        queue::scan_targets,
+        targets::{Range, target_range},
+    },
+};
+
+/// File in the working directory listing the networks a project may scan
+pub const SCOPE_FILE: &str = ".lazynmap-scope";
+
+/// Networks and hosts a project is allowed to scan; an empty scope allows anything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scope {
+    ranges: Vec<Range>,
+    /// Hostnames allowed by name, since they can't be checked against networks
+    hostnames: Vec<String>,
+    path: Option<PathBuf>,
+}
+
+impl Scope {
+    /// Load the scope file of the project in `dir`, if it has one
+    pub fn load(dir: &Path) -> Result<Self, ConfigError> {
+        let path = dir.join(SCOPE_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(Self {
+                path: Some(path),
+                ..Self::parse(&content)
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(ConfigError::Io(err)),
+        }
+    }
+
+    /// Parse addresses, CIDR blocks, octet ranges and hostnames, separated by whitespace or lines
+    /// and with # comments
+    pub fn parse(content: &str) -> Self {
+        let mut scope = Self::default();
+        for entry in content
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default())
+            .flat_map(str::split_whitespace)
+        {
+            match target_range(entry) {
+                Some(range) => scope.ranges.push(range),
+                None => scope.hostnames.push(entry.to_lowercase()),
+            }
+        }
+        scope
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty() && self.hostnames.is_empty()
+    }
+
+    /// The scope file, when the scope was loaded from one
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Whether every host of a target is in scope
+    pub fn allows(&self, target: &str) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        match target_range(target) {
+            Some(range) => self.ranges.iter().any(|allowed| allowed.contains(range)),
+            None => self.hostnames.contains(&target.to_lowercase()),
+        }
+    }
+
+    /// Targets with hosts outside the scope
+    pub fn outside(&self, targets: &[String]) -> Vec<String> {
+        targets
+            .iter()
+            .filter(|target| !self.allows(target))
+            .cloned()
+            .collect()
+    }
+
+    /// Targets of a scan and its -iL file outside the scope, and its -iR option, since random
+    /// hosts can't be kept within a scope
+    pub fn outside_scan(&self, scan: &NmapScan) -> Vec<String> {
+        let ts = &scan.target_specification;
+        let targets = scan_targets(scan).unwrap_or_else(|_| ts.targets.clone());
+        let mut outside = self.outside(&targets);
+        if let Some(count) = ts.random_targets
+            && !self.is_empty()
+        {
+            outside.push(format!("-iR {}", count));
+        }
+        outside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope() {
+        let scope = Scope::parse("# lab\n10.0.0.0/16 192.168.1.1-50\nScanme.nmap.org\nfd00::/8\n");
+        let targets: Vec<String> = [
+            "10.0.5.0/24",
+            "10.1.0.1",
+            "10.0.0.0/8",
+            "192.168.1.20",
+            "192.168.1.40-60",
+            "scanme.NMAP.org",
+            "example.com",
+            "fd00::1",
+        ]
+        .iter()
+        .map(|target| target.to_string())
+        .collect();
+        assert_eq!(
+            scope.outside(&targets),
+            vec!["10.1.0.1", "10.0.0.0/8", "192.168.1.40-60", "example.com"]
+        );
+        assert!(Scope::default().allows("example.com"));
+    }
+
+    #[test]
+    fn test_random_targets_outside_scope() {
+        let mut scan = NmapScan::new();
+        scan.target_specification.targets = vec!["10.0.0.1".to_string()];
+        scan.target_specification.random_targets = Some(1000);
+        assert_eq!(
+            Scope::parse("10.0.0.0/16").outside_scan(&scan),
+            vec!["-iR 1000"]
+        );
+        assert!(Scope::default().outside_scan(&scan).is_empty());
+    }
+}
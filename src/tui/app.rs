@@ -1,66 +1,416 @@
 use ratatui::{
     DefaultTerminal,
-    crossterm::event::{self, Event, KeyCode},
+    crossterm::{
+        event::{
+            self, DisableFocusChange, EnableFocusChange, Event, KeyCode, KeyEvent, KeyModifiers,
+        },
+        execute, terminal,
+    },
     prelude::*,
-    widgets::{Block, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    widgets::{Block, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
 };
-use std::{collections::HashMap, error::Error};
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use strum::IntoEnumIterator;
 
 use crate::{
+    asn::{AsnLookup, AsnSource, target_address},
+    config::{Config, Pane, UiMode},
+    engagement::Engagement,
+    error::{Error, Result},
+    external::{
+        expand_placeholders, held_command, multiplexer_pane_command, open_url, spawn_shell,
+    },
+    hooks::run_hooks,
+    logging::LogBuffer,
+    monitor::Monitor,
+    notifications::notify,
+    paths,
+    policy::Policy,
+    profiles::Profile,
+    results::{
+        self,
+        baseline::{self, BaselineStore},
+        diff::{PortChange, diff},
+        export::{ExportFormat, export},
+        follow_up::service_kinds,
+        model::{ScanResults, nvd_search_url},
+        parser::ResultsError,
+        snapshots::SnapshotStore,
+    },
     scan::{
+        advisor::{count_targets, estimate},
         builder::NmapCommandBuilder,
-        flags::{FlagValue, NmapFlag},
-        model::{NmapScan, TimingTemplate},
+        bundles::OptionBundle,
+        diagnostics::{Diagnosis, diagnose},
+        discovery::DiscoveryStrategy,
+        duration::{format_duration, format_file_stamp},
+        exclusions::exclude_hosts,
+        flags::{FlagValue, NmapFlag, next_preset},
+        iflist::{IflistFetch, Interface},
+        length::{HostList, check_length, move_list_to_file},
+        link_local::{add_zone, check_link_local},
+        macros::{EditValue, Macro},
+        model::{MAX_LEVEL, NmapScan, PacketLayer, ScanTechnique, TimingTemplate},
+        ports::{check_ports, keep_explicit_ports},
+        queue::{QueuedScan, ScanQueue, batch_scans, chunk_scans, scan_targets},
+        rate_presets::{self, RatePreset},
+        rdns::use_annotated_targets,
+        runner::{NmapRunner, RunStatus},
+        sampling::{random_seed, sample_targets},
+        scripts::{CategorySelection, ScriptCategory},
+        secrets,
+        services::PortRatios,
+        session::{ScanSession, SessionLog, SessionStatus},
+        stats::RunStats,
+        targets::{TargetOverlaps, clean_up, find_overlaps, group_targets, sort_targets},
+        tcp_flags::TcpFlags,
+        techniques::{CAPABILITIES, capabilities_index},
     },
+    scope::{SCOPE_FILE, Scope},
     tui::{
+        chords::{CHORDS, ChordAction, ChordResult, ChordState},
+        clipboard,
+        history::InputHistory,
+        locks,
+        popups::{
+            ExistingOutputs, Popup, PopupAction, arp::ArpPopup, banner::BannerPopup,
+            command::CommandPopup, discovery::DiscoveryPopup, export::ExportPopup,
+            follow_up::FollowUpPopup, ip_options::IpOptionsPopup, notes::NotesPopup,
+            port_actions::PortActionsPopup, profiles::ProfilesPopup,
+            rate_advisor::RateAdvisorPopup, rdns::ReverseDnsPopup, sample::SamplePopup,
+            save_results::SaveResultsPopup, script::ScriptPopup, script_args::ScriptArgsPopup,
+            script_help::ScriptHelpPopup, search::SearchPopup, secrets::SecretsPopup,
+            techniques::TechniquesPopup, which_key::render_which_key,
+        },
         sections::{
-            host_discovery::render_host_discovery,
+            evasion_spoofing::render_evasion_spoofing, host_discovery::render_host_discovery,
+            output::render_output_options, port_specification::render_port_specification,
+            scan_technique::render_scan_technique, script_scan::render_script_scan,
             target_specification::render_target_specification, timing::render_timing,
         },
-        utils::initialize_text_inputs,
-        widgets::text_input::{EventResult, InputValue, InputWidget},
+        theme::Theme,
+        utils::{flag_label, initialize_text_inputs},
+        views::{
+            compare::CompareView, debug_console::render_debug_console,
+            engagement::render_engagement, monitor::MonitorView, output::render_output,
+            queue::render_queue, results::ResultsView,
+        },
+        widgets::{
+            progress::Progress,
+            text_input::{EventResult, InputValue, InputWidget, VecIntParser},
+        },
     },
+    webhook::{self, WebhookEvent},
 };
 
-const SECTIONS: [(&str, u16); 10] = [
+const TICK_RATE: Duration = Duration::from_millis(100);
+/// Narrowest terminal the split view is shown on, leaving room for the editor and the results
+const MIN_SPLIT_WIDTH: u16 = 140;
+const DEBUG_CONSOLE_HEIGHT: u16 = 10;
+const STATUS_TIMEOUT: Duration = Duration::from_secs(3);
+const MACRO_FILE: &str = "macro.json";
+const HISTORY_FILE: &str = "history.json";
+const LOCKS_FILE: &str = "locks.json";
+const ENGAGEMENT_FILE: &str = "engagement.json";
+/// Interval of the progress reports asked for when the scan sets none
+const DEFAULT_STATS_EVERY: &str = "5s";
+
+const SECTIONS: [(&str, u16); 11] = [
     ("Target Specification", 11),
     ("Host Discovery", 11),
     ("Scan Technique", 10),
     ("Port Specification", 10),
     ("Service Detection", 10),
+    ("Script Scan", 10),
     ("OS Detection", 10),
     ("Timing", 10),
-    ("Evasion and Spoofing", 10),
+    ("Evasion and Spoofing", 13),
     ("Output", 10),
     ("Miscellaneous", 10),
 ];
 
+/// Seconds since the unix epoch
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Index in SECTIONS of the section showing a flag
+fn section_of(flag: NmapFlag) -> usize {
+    match flag {
+        NmapFlag::Targets
+        | NmapFlag::InputFile
+        | NmapFlag::Exclude
+        | NmapFlag::ExcludeFile
+        | NmapFlag::RandomTargets => 0,
+        NmapFlag::Scanflags | NmapFlag::FtpRelay => 2,
+        NmapFlag::PortRatio => 3,
+        NmapFlag::ScriptDefault
+        | NmapFlag::Scripts
+        | NmapFlag::ScriptArgs
+        | NmapFlag::ScriptCategories => 5,
+        NmapFlag::TimingTemplate
+        | NmapFlag::RatePreset
+        | NmapFlag::HostTimeout
+        | NmapFlag::ScriptTimeout => 7,
+        NmapFlag::SourcePort
+        | NmapFlag::SpoofMac
+        | NmapFlag::IpOptions
+        | NmapFlag::Interface
+        | NmapFlag::Proxies
+        | NmapFlag::Data => 8,
+        NmapFlag::Verbosity | NmapFlag::DebugLevel | NmapFlag::StatsEvery => 9,
+        NmapFlag::ListScan
+        | NmapFlag::PingScan
+        | NmapFlag::SkipPortScan
+        | NmapFlag::Traceroute
+        | NmapFlag::SynDiscovery
+        | NmapFlag::AckDiscovery
+        | NmapFlag::UdpDiscovery
+        | NmapFlag::SctpDiscovery
+        | NmapFlag::IcmpEcho
+        | NmapFlag::DisableArpPing
+        | NmapFlag::IcmpTimestamp
+        | NmapFlag::IcmpNetmask
+        | NmapFlag::IpProtocolPing
+        | NmapFlag::SystemDns
+        | NmapFlag::DnsResolution
+        | NmapFlag::DnsServers => 1,
+    }
+}
+
+/// Number of options of each section set differently than in a new scan
+fn changed_options(scan: &mut NmapScan) -> [usize; SECTIONS.len()] {
+    let mut defaults = NmapScan::new();
+    // The environment preset stands for the timing options without a flag of their own
+    let value = |flag, scan: &mut NmapScan| match EditValue::capture(flag, scan) {
+        EditValue::Timing(mut timing) => {
+            timing.template = None;
+            timing.host_timeout = None;
+            timing.script_timeout = None;
+            EditValue::Timing(timing)
+        }
+        value => value,
+    };
+    let mut counts = [0; SECTIONS.len()];
+    for flag in NmapFlag::iter() {
+        if value(flag, scan) != value(flag, &mut defaults) {
+            counts[section_of(flag)] += 1;
+        }
+    }
+    counts
+}
+
+/// Top-level screens of the app
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    Editor,
+    Results,
+    Compare,
+    Monitor,
+    /// Level of effort spent on the engagement
+    Engagement,
+}
+
+/// A pane shown over the whole terminal until zoomed out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zoom {
+    /// The editor, results, comparison or monitor, whichever is shown
+    View,
+    /// The output of the running scan
+    Output,
+}
+
+/// A run that ended, waiting for its XML output to be parsed in the background before it is
+/// recorded, compared, reported and shown
+struct FinishedRun {
+    status: SessionStatus,
+    run_status: RunStatus,
+    command: String,
+    elapsed: Duration,
+    stats: RunStats,
+    xml: Option<PathBuf>,
+    temp_xml: bool,
+    monitor_run: bool,
+    queued_run: bool,
+    /// The run failed in a way shown in a popup instead of its results
+    diagnosed: bool,
+    results: Option<Receiver<ParsedRun>>,
+}
+
+/// What the background thread read from the XML output of a finished run
+struct ParsedRun {
+    results: std::result::Result<ScanResults, ResultsError>,
+    /// Ports that changed since the baseline of the profile, when there is one to compare with
+    baseline_changes: Option<Vec<PortChange>>,
+}
+
 pub struct App<'a> {
     pub scan: &'a mut NmapScan,
+    pub config: Config,
+    pub policy: Policy,
+    pub scope: Scope,
+    /// Targets outside the scope were allowed for the rest of the session
+    pub scope_overridden: bool,
+    /// Secrets of script arguments entered at the prompt for the rest of the session, by key
+    secrets: HashMap<String, String>,
     pub input_map: HashMap<NmapFlag, InputWidget>,
     pub focused_section: usize,
     pub focused_flag: NmapFlag,
     pub editing_flag: Option<NmapFlag>,
     pub focused_radio_index: Option<usize>,
+    pub runner: Option<NmapRunner>,
+    pub popup: Option<Popup>,
+    pub sessions: SessionLog,
+    pub view: View,
+    pub results: ResultsView,
+    pub compare: Option<CompareView>,
+    pub monitor: Option<Monitor>,
+    pub monitor_view: MonitorView,
+    pub queue: Option<ScanQueue>,
+    pub recording: Option<Macro>,
+    pub recorded_macro: Option<Macro>,
+    /// Scans added up since the engagement clock started, when it runs
+    pub engagement: Option<Engagement>,
+    pub locked_flags: HashSet<NmapFlag>,
+    pub history: InputHistory,
+    /// Owners of public target and result addresses, when enrichment is on
+    pub asn: Option<AsnLookup>,
+    /// Interfaces of the scanning host, fetched once there are targets to compare them with
+    pub iflist: Option<IflistFetch>,
+    /// Open frequencies of ports from nmap-services, for the number of ports --port-ratio picks
+    pub port_ratios: Option<PortRatios>,
+    pub zoom: Option<Zoom>,
+    /// Progress bar of the running scan
+    pub run_progress: Progress,
+    /// Runs that ended and whose results are still being parsed, oldest first
+    finished_runs: Vec<FinishedRun>,
+    /// Hooks of the last finished run being started, reporting those that failed to
+    hooks: Option<Receiver<Vec<String>>>,
+    /// Recent log events, shown in the debug console
+    pub logs: LogBuffer,
+    pub debug_console: bool,
 
+    state_dir: Option<PathBuf>,
+    /// Name of the last loaded profile, whose baseline finished scans are compared against
+    profile: Option<String>,
+    /// XML output of the scan in the editor and the timestamped name its last run wrote to
+    stamped_xml: Option<(PathBuf, PathBuf)>,
+    status: Option<(String, Instant)>,
+    chords: ChordState,
+    theme: Theme,
+    focused: bool,
+    /// Options changed in each section, with the scan they were counted for
+    changed_options: Option<(NmapScan, [usize; SECTIONS.len()])>,
     scroll_state: ScrollbarState,
     scroll: u16,
     running: bool,
 }
 
 impl<'a> App<'a> {
-    pub fn new(scan: &'a mut NmapScan) -> Self {
+    pub fn new(scan: &'a mut NmapScan, config: Config, policy: Policy, scope: Scope) -> Self {
+        let port_ratios = PortRatios::load(scan.misc.datadir.as_deref());
+        let mut app = Self::with_state_dir(scan, config, policy, scope, paths::state_dir());
+        app.port_ratios = port_ratios;
+        app
+    }
+
+    /// An app keeping its input history, sessions, macro and locked flags in `state_dir`, or
+    /// nowhere without one, so that it can be rendered without touching the user's files
+    pub fn with_state_dir(
+        scan: &'a mut NmapScan,
+        config: Config,
+        policy: Policy,
+        scope: Scope,
+        state_dir: Option<PathBuf>,
+    ) -> Self {
         let total_height: u16 = SECTIONS.iter().map(|(_, height)| height).sum();
         let mut input_map = HashMap::new();
         initialize_text_inputs(scan, &mut input_map);
+        let history = state_dir
+            .as_ref()
+            .map(|dir| InputHistory::load(&dir.join(HISTORY_FILE)))
+            .unwrap_or_default();
+        for (flag, input) in input_map.iter_mut() {
+            input.set_history(history.entries(*flag).to_vec());
+        }
+        let locked_flags = state_dir
+            .as_ref()
+            .map(|dir| locks::load(&dir.join(LOCKS_FILE)))
+            .unwrap_or_default();
+        for flag in &locked_flags {
+            if let Some(input) = input_map.get_mut(flag) {
+                input.set_label(flag_label(*flag, true));
+            }
+        }
+        let theme = Theme::from_env(config.ui.theme);
+        let results = ResultsView::new()
+            .with_subnet_prefixes(
+                config.results.subnet_prefix,
+                config.results.ipv6_subnet_prefix,
+            )
+            .with_mac_vendor_lookup(config.results.mac_vendor_lookup);
+        let asn = AsnSource::from_config(&config.enrichment).map(AsnLookup::new);
+        let chords = ChordState::new(CHORDS, Duration::from_millis(config.ui.chord_timeout_ms));
 
         Self {
             scan,
+            config,
+            policy,
+            scope,
+            scope_overridden: false,
+            secrets: HashMap::new(),
             input_map,
             focused_section: 0,
             focused_flag: NmapFlag::first(),
             editing_flag: None,
             focused_radio_index: None,
+            runner: None,
+            popup: None,
+            sessions: SessionLog::load(state_dir.as_ref().map(|dir| dir.join("sessions"))),
+            view: View::Editor,
+            results,
+            compare: None,
+            monitor: None,
+            monitor_view: MonitorView::default(),
+            queue: None,
+            recording: None,
+            locked_flags,
+            history,
+            recorded_macro: state_dir
+                .as_ref()
+                .and_then(|dir| Macro::load(&dir.join(MACRO_FILE))),
+            engagement: state_dir
+                .as_ref()
+                .and_then(|dir| Engagement::load(&dir.join(ENGAGEMENT_FILE))),
+            asn,
+            iflist: None,
+            port_ratios: None,
+            zoom: None,
+            run_progress: Progress::new(),
+            finished_runs: Vec::new(),
+            hooks: None,
+            logs: LogBuffer::new(),
+            debug_console: false,
+
+            state_dir,
+            profile: None,
+            stamped_xml: None,
+            status: None,
+            chords,
+            theme,
+            focused: true,
+            changed_options: None,
 
             scroll_state: ScrollbarState::new(total_height.into()),
             scroll: 0,
@@ -68,12 +418,21 @@ impl<'a> App<'a> {
         }
     }
 
-    pub fn start(self) -> Result<(), Box<dyn Error>> {
-        color_eyre::install()?;
+    /// Show the events recorded in `logs` in the debug console
+    pub fn with_logs(mut self, logs: LogBuffer) -> Self {
+        self.logs = logs;
+        self
+    }
+
+    pub fn start(self) -> Result<()> {
+        color_eyre::install().map_err(|err| Error::Terminal(io::Error::other(err.to_string())))?;
         let terminal = ratatui::init();
+        // Focus reporting lets notifications fire only when the terminal is in the background
+        execute!(io::stdout(), EnableFocusChange).map_err(Error::Terminal)?;
 
         let res = self.run(terminal);
 
+        execute!(io::stdout(), DisableFocusChange).map_err(Error::Terminal)?;
         ratatui::restore();
         if let Err(err) = &res {
             println!("{err:?}");
@@ -81,13 +440,19 @@ impl<'a> App<'a> {
         res
     }
 
-    fn run(mut self, mut terminal: DefaultTerminal) -> Result<(), Box<dyn Error>> {
+    fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
         loop {
-            terminal.draw(|frame| self.draw(frame))?;
+            terminal
+                .draw(|frame| self.draw(frame))
+                .map_err(Error::Terminal)?;
 
-            if let Ok(event) = event::read() {
+            if event::poll(TICK_RATE).map_err(Error::Terminal)?
+                && let Ok(event) = event::read()
+            {
                 self.handle_event(event)?
             }
+            self.tick();
+            self.expire_chord()?;
             if !self.running {
                 return Ok(());
             }
@@ -95,32 +460,289 @@ impl<'a> App<'a> {
     }
 
     fn draw(&mut self, frame: &mut Frame) {
+        match self.zoom {
+            Some(zoom) => self.draw_zoomed(frame, zoom),
+            None => self.draw_layout(frame),
+        }
+
+        if let Some(flag) = self.editing_flag
+            && let Some(input) = self.input_map.get(&flag)
+        {
+            input.render_dropdown_overlay(frame.buffer_mut());
+        }
+
+        if let Some(popup) = self.popup.as_mut() {
+            popup.render(frame);
+        }
+        if self.chords.is_pending() {
+            render_which_key(frame, self.chords.pending(), &self.chords.continuations());
+        }
+        self.theme.apply(frame.buffer_mut());
+    }
+
+    /// One pane over the whole terminal, above a line telling how to zoom out
+    fn draw_zoomed(&mut self, frame: &mut Frame, zoom: Zoom) {
+        let chunks =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(frame.area());
+        let key = match zoom {
+            Zoom::View => {
+                self.draw_view(frame, chunks[0]);
+                'z'
+            }
+            Zoom::Output => {
+                self.draw_output(frame, chunks[0]);
+                'Z'
+            }
+        };
+        let mut spans = Vec::new();
+        if let Some((message, _)) = &self.status {
+            spans.push(Span::styled(
+                format!("{}  ", message),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        spans.push(Span::styled(
+            format!("zoomed, press {} to restore the layout", key),
+            Style::default().fg(Color::DarkGray),
+        ));
+        frame.render_widget(Paragraph::new(Line::from(spans).right_aligned()), chunks[1]);
+    }
+
+    /// The screen of the current view, the output of a running scan and the command
+    fn draw_layout(&mut self, frame: &mut Frame) {
+        let layout = &self.config.layout;
+        let split =
+            self.view == View::Editor && layout.split_view && frame.area().width >= MIN_SPLIT_WIDTH;
+        let output_height = if self.runner.is_some() && !split {
+            layout.output_height
+        } else {
+            0
+        };
+        let console_height = if self.debug_console {
+            DEBUG_CONSOLE_HEIGHT
+        } else {
+            0
+        };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(15), Constraint::Length(3)])
+            .constraints([
+                Constraint::Min(15),
+                Constraint::Length(output_height),
+                Constraint::Length(console_height),
+                Constraint::Length(layout.footer_height),
+            ])
             .split(frame.area());
 
+        match self.view {
+            View::Editor if split => {
+                let halves = Layout::horizontal([Constraint::Percentage(50); 2]).split(chunks[0]);
+                self.draw_editor(frame, halves[0]);
+                let side_output = if self.runner.is_some() {
+                    Constraint::Percentage(50)
+                } else {
+                    Constraint::Length(0)
+                };
+                let side = Layout::vertical([side_output, Constraint::Min(0)]).split(halves[1]);
+                self.draw_output(frame, side[0]);
+                self.results.render(frame, side[1]);
+            }
+            _ => self.draw_view(frame, chunks[0]),
+        }
+
+        let footer_title = if self.sessions.last_resumable().is_some() {
+            "Nmap command (interrupted scan found, press R to resume)"
+        } else {
+            "Nmap command"
+        };
+        let mut footer_block = Block::bordered().title(Line::from(footer_title).centered());
+        if self.recording.is_some() {
+            footer_block = footer_block.title(
+                Line::from("recording macro")
+                    .style(Style::default().fg(Color::Red))
+                    .left_aligned(),
+            );
+        }
+        if let Some(warning) = check_length(self.scan) {
+            footer_block = footer_block.title_bottom(
+                Line::from(format!("{}, press W", warning))
+                    .style(Style::default().fg(Color::Red))
+                    .left_aligned(),
+            );
+        }
+        if let Some(warning) = check_link_local(self.scan) {
+            footer_block = footer_block.title_bottom(
+                Line::from(format!("{}, press I", warning))
+                    .style(Style::default().fg(Color::Red))
+                    .left_aligned(),
+            );
+        }
+        if let Some(conflict) = check_ports(self.scan) {
+            footer_block = footer_block.title_bottom(
+                Line::from(format!("{}, press P to keep -p", conflict))
+                    .style(Style::default().fg(Color::Red))
+                    .left_aligned(),
+            );
+        }
+        if let Some((message, _)) = &self.status {
+            footer_block = footer_block.title_bottom(
+                Line::from(message.as_str())
+                    .style(Style::default().fg(Color::Cyan))
+                    .right_aligned(),
+            );
+        }
+        let nmap_command = Paragraph::new(NmapCommandBuilder::build(&secrets::masked(self.scan)))
+            .centered()
+            .wrap(Wrap { trim: true })
+            .block(footer_block);
+        frame.render_widget(nmap_command, chunks[3]);
+        if !split {
+            self.draw_output(frame, chunks[1]);
+        }
+        if self.debug_console {
+            render_debug_console(&self.logs, frame, chunks[2]);
+        }
+    }
+
+    fn draw_view(&mut self, frame: &mut Frame, area: Rect) {
+        match self.view {
+            View::Editor => self.draw_editor(frame, area),
+            View::Results => self.results.render(frame, area),
+            View::Compare => {
+                if let Some(compare) = &self.compare {
+                    compare.render(frame, area);
+                }
+            }
+            View::Monitor => self.monitor_view.render(self.monitor.as_ref(), frame, area),
+            View::Engagement => {
+                render_engagement(self.engagement.as_ref(), unix_now(), frame, area)
+            }
+        }
+    }
+
+    /// Output of the running scan, next to the progress of its queue
+    fn draw_output(&self, frame: &mut Frame, area: Rect) {
+        match &self.queue {
+            Some(queue) => {
+                let output_chunks = Layout::horizontal([
+                    Constraint::Min(0),
+                    Constraint::Length(self.config.layout.queue_width),
+                ])
+                .split(area);
+                render_output(self, frame, output_chunks[0]);
+                render_queue(queue, frame, output_chunks[1]);
+            }
+            None => render_output(self, frame, area),
+        }
+    }
+
+    fn draw_editor(&mut self, frame: &mut Frame, area: Rect) {
         let top_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(25), Constraint::Min(0)])
-            .split(chunks[0]);
+            .constraints([
+                Constraint::Length(self.config.layout.sidebar_width),
+                Constraint::Min(0),
+            ])
+            .split(area);
 
         let left_block = Block::bordered().title("Sections");
+        // Options set differently than in a new scan, counted at the right of each section
+        let changed = match &self.changed_options {
+            Some((scan, counts)) if scan == &*self.scan => *counts,
+            _ => {
+                let counts = changed_options(self.scan);
+                self.changed_options = Some((self.scan.clone(), counts));
+                counts
+            }
+        };
+        let width = left_block.inner(top_chunks[0]).width as usize;
         let sections = SECTIONS
             .iter()
+            .zip(changed)
             .enumerate()
-            .map(|(index, (title, _))| {
+            .map(|(index, ((title, _), count))| {
+                let mut spans = vec![Span::raw(*title)];
+                if count > 0 {
+                    let badge = count.to_string();
+                    let padding = width.saturating_sub(title.len() + badge.len()).max(1);
+                    spans.push(Span::raw(" ".repeat(padding)));
+                    spans.push(Span::styled(badge, Style::default().fg(Color::Cyan)));
+                }
                 if index == self.focused_section {
-                    Line::from(*title).style(Style::default().fg(Color::Yellow))
+                    Line::from(spans).style(Style::default().fg(Color::Yellow))
                 } else {
-                    Line::from(*title)
+                    Line::from(spans)
                 }
             })
             .collect::<Vec<_>>();
         let section_paragraph = Paragraph::new(sections).block(left_block);
         frame.render_widget(section_paragraph, top_chunks[0]);
 
-        let right_block = Block::bordered().title("Options");
+        let mut right_block = if self.locked_flags.is_empty() {
+            Block::bordered().title("Options")
+        } else {
+            Block::bordered().title(format!(
+                "Options ({} locked, L to toggle)",
+                self.locked_flags.len()
+            ))
+        };
+        if self.config.ui.mode == UiMode::Beginner {
+            right_block = right_block.title(
+                Line::from("beginner mode, M for all options")
+                    .style(Style::default().fg(Color::DarkGray))
+                    .right_aligned(),
+            );
+        }
+        let description = match self.config.ui.mode {
+            UiMode::Beginner => self.focused_flag.description(),
+            UiMode::Expert => None,
+        };
+        let overlaps = match self.focused_flag {
+            NmapFlag::Targets => find_overlaps(&self.scan.target_specification.targets),
+            _ => TargetOverlaps::default(),
+        };
+        let overlaps_note =
+            (!overlaps.is_empty()).then(|| format!("{}, press C to clean up", overlaps));
+        let arp_note = (section_of(self.focused_flag) <= 1
+            && !self.scan.host_discovery.disable_arp_ping
+            && self.scan.misc.packet_layer != PacketLayer::Ip)
+            .then(|| self.local_interface())
+            .flatten()
+            .map(|interface| {
+                format!(
+                    "All targets are on the subnet of {}, where nmap finds hosts by ARP rather \
+                     than the -P probes, press A",
+                    interface.device
+                )
+            });
+        let outside = match self.focused_flag {
+            NmapFlag::Targets => self.targets_outside_scope(),
+            _ => Vec::new(),
+        };
+        let scope_note = (!outside.is_empty()).then(|| {
+            format!(
+                "{} outside the scope in {}",
+                outside.join(", "),
+                self.scope_name()
+            )
+        });
+        if let Some(note) = self
+            .focused_flag
+            .unmet_dependency(self.scan)
+            .or(self.focused_flag.tip())
+            .map(String::from)
+            .or(scope_note)
+            .or(overlaps_note)
+            .or(arp_note)
+            .or(description.map(String::from))
+        {
+            right_block = right_block.title_bottom(
+                Line::from(format!(" {}: {} ", self.focused_flag, note))
+                    .style(Style::default().fg(Color::DarkGray)),
+            );
+        }
+        for (flag, input) in self.input_map.iter_mut() {
+            input.set_dimmed(flag.unmet_dependency(self.scan).is_some());
+        }
         let right_area = right_block.inner(top_chunks[1]);
         frame.render_widget(right_block, top_chunks[1]);
 
@@ -183,7 +805,47 @@ impl<'a> App<'a> {
                             horizontal: 1,
                         }),
                     ),
-                    2 => render_timing(
+                    2 => render_scan_technique(
+                        self,
+                        frame,
+                        visible_area.inner(Margin {
+                            vertical: 1,
+                            horizontal: 1,
+                        }),
+                    ),
+                    3 => render_port_specification(
+                        self,
+                        frame,
+                        visible_area.inner(Margin {
+                            vertical: 1,
+                            horizontal: 1,
+                        }),
+                    ),
+                    5 => render_script_scan(
+                        self,
+                        frame,
+                        visible_area.inner(Margin {
+                            vertical: 1,
+                            horizontal: 1,
+                        }),
+                    ),
+                    7 => render_timing(
+                        self,
+                        frame,
+                        visible_area.inner(Margin {
+                            vertical: 1,
+                            horizontal: 1,
+                        }),
+                    ),
+                    8 => render_evasion_spoofing(
+                        self,
+                        frame,
+                        visible_area.inner(Margin {
+                            vertical: 1,
+                            horizontal: 1,
+                        }),
+                    ),
+                    9 => render_output_options(
                         self,
                         frame,
                         visible_area.inner(Margin {
@@ -201,24 +863,128 @@ impl<'a> App<'a> {
             top_chunks[1],
             &mut self.scroll_state,
         );
+    }
 
-        let footer_block = Block::bordered().title(Line::from("Nmap command").centered());
-        let nmap_command = Paragraph::new(NmapCommandBuilder::build(self.scan))
-            .centered()
-            .block(footer_block);
-        frame.render_widget(nmap_command, chunks[1]);
-
-        if let Some(flag) = self.editing_flag
-            && let Some(input) = self.input_map.get(&flag)
+    fn handle_event(&mut self, event: Event) -> Result<()> {
+        if let Event::Key(key) = event
+            && self.takes_chords()
+            && !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
         {
-            input.render_dropdown_overlay(frame.buffer_mut());
+            match self.chords.press(key.code) {
+                ChordResult::Pending | ChordResult::Cancelled => return Ok(()),
+                ChordResult::Matched(action) => {
+                    self.run_chord(action);
+                    return Ok(());
+                }
+                // The last key is this one, handled below
+                ChordResult::Unmatched(keys) => self.replay_keys(&keys[..keys.len() - 1])?,
+            }
+        }
+        self.dispatch_event(event)
+    }
+
+    /// Chords apply in the editor when no popup or input takes the keys
+    fn takes_chords(&self) -> bool {
+        self.view == View::Editor && self.popup.is_none() && self.editing_flag.is_none()
+    }
+
+    /// Handle the keys of an unfinished chord one by one, as if there were no chords
+    fn replay_keys(&mut self, keys: &[KeyCode]) -> Result<()> {
+        for key in keys {
+            self.dispatch_event(Event::Key(KeyEvent::new(*key, KeyModifiers::NONE)))?;
+        }
+        Ok(())
+    }
+
+    /// Give up on a chord whose next key took too long
+    fn expire_chord(&mut self) -> Result<()> {
+        match self.chords.expire() {
+            Some(keys) => self.replay_keys(&keys),
+            None => Ok(()),
+        }
+    }
+
+    fn run_chord(&mut self, action: ChordAction) {
+        match action {
+            ChordAction::FirstSection => self.focus_section(0),
+            ChordAction::LastSection => self.focus_section(SECTIONS.len() - 1),
+            ChordAction::Results => self.show_results(),
+            ChordAction::Monitor => self.view = View::Monitor,
+            ChordAction::Engagement => self.view = View::Engagement,
+            ChordAction::Run => self.run_current_scan(),
+            ChordAction::Profiles => {
+                self.popup = Some(Popup::Profiles(Box::new(ProfilesPopup::new(
+                    self.recorded_macro.clone(),
+                ))));
+            }
+            ChordAction::EditCommand => {
+                self.popup = Some(Popup::Command(Box::new(CommandPopup::new(self.scan))));
+            }
+            ChordAction::Notes => {
+                self.popup = Some(Popup::Notes(Box::new(NotesPopup::new(&self.scan.notes))));
+            }
+            ChordAction::CopyCommand => self.copy_command(),
+            ChordAction::UdpEssentials => self.toggle_udp_essentials(),
+            ChordAction::Techniques => {
+                self.popup = Some(Popup::Techniques(Box::new(TechniquesPopup::new(self.scan))));
+            }
         }
     }
 
-    fn handle_event(&mut self, event: Event) -> Result<(), Box<dyn Error>> {
+    /// Turn the UDP essentials bundle on or off, showing how it changes the estimated duration
+    fn toggle_udp_essentials(&mut self) {
+        let options = self.config.bundles.udp_essentials.clone();
+        // Validated when the config loaded
+        let Ok(bundle) = OptionBundle::parse(&options) else {
+            return;
+        };
+        // Compared with a UDP scan keeping the other options, which is what the bundle speeds up
+        let mut plain_udp = self.scan.clone();
+        plain_udp.scan_technique = ScanTechnique::Udp;
+        let plain_udp = estimate(&plain_udp).duration();
+        let applied = bundle.is_applied(self.scan);
+        self.preserve_locked(|scan| {
+            if applied {
+                bundle.remove(scan);
+            } else {
+                bundle.apply(scan);
+            }
+        });
+        let duration = format_duration(estimate(self.scan).duration());
+        self.set_status(if applied {
+            format!("UDP essentials off, estimated duration {}", duration)
+        } else {
+            format!(
+                "UDP essentials on ({}), estimated duration {} instead of {} with UDP defaults",
+                options,
+                duration,
+                format_duration(plain_udp)
+            )
+        });
+    }
+
+    fn dispatch_event(&mut self, event: Event) -> Result<()> {
+        match event {
+            Event::FocusGained => self.focused = true,
+            Event::FocusLost => self.focused = false,
+            _ => {}
+        }
+
+        let mut edited = None;
         let flag_value = self.focused_flag.get_flag_value(self.scan);
         if let Event::Key(key) = event {
-            if self.editing_flag.is_some() {
+            if let Some(popup) = self.popup.as_mut() {
+                match popup.handle_event(&event, self.scan) {
+                    EventResult::Submit(action) => {
+                        self.popup = None;
+                        self.handle_popup_action(action);
+                    }
+                    EventResult::Cancel => self.popup = None,
+                    _ => {}
+                }
+            } else if self.editing_flag.is_some() {
                 match self
                     .input_map
                     .get_mut(&self.focused_flag)
@@ -230,8 +996,25 @@ impl<'a> App<'a> {
                             (InputValue::Int(value), FlagValue::Int(flag_value)) => {
                                 *flag_value = Some(value);
                             }
+                            (InputValue::Float(value), FlagValue::Float(flag_value)) => {
+                                *flag_value = Some(value);
+                            }
+                            (InputValue::String(value), FlagValue::String(flag_value)) => {
+                                *flag_value = (!value.trim().is_empty()).then_some(value);
+                            }
                             (InputValue::VecInt(value), FlagValue::VecInt(flag_value)) => {
                                 *flag_value = value;
+                                let duplicates = self
+                                    .input_map
+                                    .get(&self.focused_flag)
+                                    .map_or(0, |input| VecIntParser::duplicates(input.content()));
+                                if duplicates > 0 {
+                                    self.set_status(format!(
+                                        "Removed {} duplicate {}",
+                                        duplicates,
+                                        if duplicates == 1 { "entry" } else { "entries" }
+                                    ));
+                                }
                             }
                             (InputValue::VecString(value), FlagValue::VecString(flag_value)) => {
                                 *flag_value = value;
@@ -239,18 +1022,279 @@ impl<'a> App<'a> {
                             (InputValue::Path(value), FlagValue::Path(flag_value)) => {
                                 *flag_value = Some(value);
                             }
+                            (InputValue::String(value), FlagValue::FtpRelay(technique)) => {
+                                if !value.trim().is_empty() {
+                                    *technique = ScanTechnique::Ftp(value);
+                                } else if matches!(technique, ScanTechnique::Ftp(_)) {
+                                    *technique = ScanTechnique::default();
+                                }
+                            }
                             _ => {}
                         }
-                        self.editing_flag = None
+                        edited = self.editing_flag.take();
+                        self.remember_input(self.focused_flag);
                     }
                     EventResult::Cancel => self.editing_flag = None,
                     _ => {}
                 };
+            } else if key.code == KeyCode::Char('f') && key.modifiers == KeyModifiers::CONTROL {
+                self.popup = Some(Popup::Search(Box::new(SearchPopup::new(
+                    self.scan.xml_output(),
+                ))));
+            } else if key.code == KeyCode::Char('z') {
+                self.toggle_zoom(Zoom::View);
+            } else if key.code == KeyCode::Char('Z') {
+                self.toggle_zoom(Zoom::Output);
+            } else if key.code == KeyCode::Char('`') {
+                self.debug_console = !self.debug_console;
+            } else if self.view == View::Compare {
+                let consumed = self.compare.as_mut().is_some_and(|compare| {
+                    !matches!(compare.handle_event(&event), EventResult::Ignored)
+                });
+                if !consumed {
+                    match key.code {
+                        KeyCode::Char('q') => {
+                            self.running = false;
+                        }
+                        KeyCode::Char('v') | KeyCode::Esc => {
+                            self.view = View::Editor;
+                        }
+                        KeyCode::Char('1') | KeyCode::Char('2') => {
+                            if let Some(compare) = self.compare.take() {
+                                let profile = if key.code == KeyCode::Char('1') {
+                                    compare.left().clone()
+                                } else {
+                                    compare.right().clone()
+                                };
+                                self.load_profile(profile);
+                                self.view = View::Editor;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            } else if self.view == View::Monitor {
+                let consumed = self.monitor.as_ref().is_some_and(|monitor| {
+                    !matches!(
+                        self.monitor_view.handle_event(&event, monitor),
+                        EventResult::Ignored
+                    )
+                });
+                if !consumed {
+                    match key.code {
+                        KeyCode::Char('q') => {
+                            self.running = false;
+                        }
+                        KeyCode::Char('v') | KeyCode::Esc => {
+                            self.view = View::Editor;
+                        }
+                        KeyCode::Char('s') => {
+                            self.stop_monitor();
+                        }
+                        _ => {}
+                    }
+                }
+            } else if self.view == View::Engagement {
+                match key.code {
+                    KeyCode::Char('q') => {
+                        self.running = false;
+                    }
+                    KeyCode::Char('v') | KeyCode::Esc => {
+                        self.view = View::Editor;
+                    }
+                    KeyCode::Char('n') => {
+                        self.engagement = Some(Engagement::new(unix_now()));
+                        self.save_engagement();
+                        self.set_status("Started the engagement clock".to_string());
+                    }
+                    KeyCode::Char('x') => {
+                        self.stop_engagement();
+                    }
+                    _ => {}
+                }
+            } else if self.view == View::Results {
+                if matches!(self.results.handle_event(&event), EventResult::Ignored) {
+                    match key.code {
+                        KeyCode::Char('q') => {
+                            self.running = false;
+                        }
+                        KeyCode::Char('v') | KeyCode::Esc => {
+                            self.view = View::Editor;
+                        }
+                        KeyCode::Char('c') => {
+                            if let Some(cpe) = self.results.selected_cpe().map(str::to_string) {
+                                self.copy_to_clipboard(&cpe, "CPE");
+                            }
+                        }
+                        KeyCode::Char('e') => {
+                            if let Some(results) = self.results.results() {
+                                let mut popup =
+                                    ExportPopup::new(results.clone(), self.results.source());
+                                if self.results.shows_web_inventory() {
+                                    popup = popup.with_format(ExportFormat::WebInventory);
+                                }
+                                self.popup = Some(Popup::Export(Box::new(popup)));
+                            }
+                        }
+                        KeyCode::Char('w') => {
+                            self.send_web_targets();
+                        }
+                        KeyCode::Char('s') => {
+                            if let Some(source) = self.results.source() {
+                                let now = SystemTime::now()
+                                    .duration_since(UNIX_EPOCH)
+                                    .map_or(0, |elapsed| elapsed.as_secs());
+                                let name = format!("nmap-{}.xml", format_file_stamp(now));
+                                self.popup = Some(Popup::SaveResults(Box::new(
+                                    SaveResultsPopup::new(source.to_path_buf(), &name),
+                                )));
+                            }
+                        }
+                        KeyCode::Char('B') => {
+                            self.mark_baseline();
+                        }
+                        KeyCode::Char('x') => {
+                            self.exclude_selected_hosts();
+                        }
+                        KeyCode::Char('F') => {
+                            if let Some((host, port)) = self.results.selected_open_port() {
+                                let kinds = service_kinds(host, port);
+                                if kinds.is_empty() {
+                                    self.set_status(format!(
+                                        "No follow-up scripts for port {}/{}",
+                                        port.port, port.protocol
+                                    ));
+                                } else {
+                                    self.popup = Some(Popup::FollowUp(Box::new(
+                                        FollowUpPopup::new(host, port, &kinds),
+                                    )));
+                                }
+                            }
+                        }
+                        KeyCode::Char('a') => {
+                            if let Some((host, port)) = self.results.selected_open_port() {
+                                self.popup = Some(Popup::PortActions(Box::new(
+                                    PortActionsPopup::new(host, port),
+                                )));
+                            }
+                        }
+                        KeyCode::Char('b') => {
+                            if let Some((_, banner)) = self.results.selected_banner() {
+                                self.copy_to_clipboard(&banner, "banner");
+                            }
+                        }
+                        KeyCode::Enter => {
+                            if let Some((port, banner)) = self.results.selected_banner() {
+                                self.popup =
+                                    Some(Popup::Banner(Box::new(BannerPopup::new(port, banner))));
+                            }
+                        }
+                        KeyCode::Char('u') => {
+                            if let Some(cpe) = self.results.selected_cpe() {
+                                let url = nvd_search_url(cpe);
+                                self.copy_to_clipboard(&url, "NVD search link");
+                            }
+                        }
+                        _ => {}
+                    }
+                }
             } else {
                 match key.code {
                     KeyCode::Char('q') => {
                         self.running = false;
                     }
+                    KeyCode::Char('r') => {
+                        self.run_current_scan();
+                    }
+                    KeyCode::Char('y') => {
+                        self.copy_command();
+                    }
+                    KeyCode::Char('J') => {
+                        self.copy_json();
+                    }
+                    KeyCode::Char('?') => {
+                        self.show_script_help();
+                    }
+                    KeyCode::Char('R') => {
+                        self.resume_scan();
+                    }
+                    KeyCode::Char('a') => {
+                        self.popup = Some(Popup::RateAdvisor(Box::new(RateAdvisorPopup::new(
+                            self.scan,
+                        ))));
+                    }
+                    KeyCode::Char('S') => {
+                        self.popup = Some(Popup::Split(Box::default()));
+                    }
+                    KeyCode::Char('W') => {
+                        self.move_long_list();
+                    }
+                    KeyCode::Char('I') => {
+                        self.fix_link_local();
+                    }
+                    KeyCode::Char('P') => {
+                        self.fix_port_conflict();
+                    }
+                    // 1 to 9 jump to the first nine sections and 0 to the tenth, like the
+                    // number row of a keyboard
+                    KeyCode::Char(digit @ '0'..='9') => {
+                        let number = digit.to_digit(10).unwrap_or_default() as usize;
+                        self.focus_section(if number == 0 { 9 } else { number - 1 });
+                    }
+                    KeyCode::Char('A') => {
+                        let local = self
+                            .local_interface()
+                            .map(|interface| (interface.device.clone(), interface.address.clone()));
+                        self.popup = Some(Popup::Arp(Box::new(ArpPopup::new(
+                            local,
+                            self.scan.host_discovery.disable_arp_ping,
+                            self.scan.misc.packet_layer == PacketLayer::Ip,
+                        ))));
+                    }
+                    KeyCode::Char('T') => {
+                        self.run_in_pane();
+                    }
+                    KeyCode::Char('H') => {
+                        self.look_up_hostnames();
+                    }
+                    KeyCode::Char('e') => {
+                        self.popup = Some(Popup::Command(Box::new(CommandPopup::new(self.scan))));
+                    }
+                    KeyCode::Char('n') => {
+                        self.popup =
+                            Some(Popup::Notes(Box::new(NotesPopup::new(&self.scan.notes))));
+                    }
+                    KeyCode::Char('D') => {
+                        self.popup =
+                            Some(Popup::Discovery(Box::new(DiscoveryPopup::new(self.scan))));
+                    }
+                    KeyCode::Char('E') => {
+                        self.export_script();
+                    }
+                    KeyCode::Char('N') => {
+                        self.popup = Some(Popup::Sample(Box::new(SamplePopup::new(
+                            count_targets(self.scan),
+                        ))));
+                    }
+                    KeyCode::Char('v') => {
+                        self.show_results();
+                    }
+                    KeyCode::Char('t') => {
+                        self.view = View::Monitor;
+                    }
+                    KeyCode::Char('p') => {
+                        self.popup = Some(Popup::Profiles(Box::new(ProfilesPopup::new(
+                            self.recorded_macro.clone(),
+                        ))));
+                    }
+                    KeyCode::Char('x') => {
+                        if let Some(queue) = self.queue.as_mut() {
+                            queue.cancel();
+                        }
+                        if let Some(runner) = self.runner.as_mut() {
+                            runner.kill();
+                        }
+                    }
                     KeyCode::Char('j') | KeyCode::Down => {
                         self.scroll_down();
                     }
@@ -267,6 +1311,9 @@ impl<'a> App<'a> {
                             }
                             _ => {
                                 self.focused_flag = self.focused_flag.next();
+                                while !self.shows(self.focused_flag) {
+                                    self.focused_flag = self.focused_flag.next();
+                                }
                                 if self.focused_flag.get_variant_count().is_some() {
                                     self.focused_radio_index = Some(0);
                                 } else {
@@ -281,6 +1328,9 @@ impl<'a> App<'a> {
                         }
                         _ => {
                             self.focused_flag = self.focused_flag.prev();
+                            while !self.shows(self.focused_flag) {
+                                self.focused_flag = self.focused_flag.prev();
+                            }
                             if let Some(count) = self.focused_flag.get_variant_count() {
                                 self.focused_radio_index = Some(count.saturating_sub(1));
                             } else {
@@ -288,32 +1338,1651 @@ impl<'a> App<'a> {
                             }
                         }
                     },
-                    KeyCode::Enter | KeyCode::Char(' ') => match flag_value {
-                        FlagValue::Bool(flag_value) => *flag_value = !*flag_value,
-                        FlagValue::VecString(_)
-                        | FlagValue::Path(_)
-                        | FlagValue::Int(_)
-                        | FlagValue::VecInt(_) => self.editing_flag = Some(self.focused_flag),
-                        FlagValue::TimingTemplate(flag_value) => {
-                            *flag_value = self
-                                .focused_radio_index
-                                .and_then(TimingTemplate::from_index)
-                                .and_then(|new_val| {
+                    KeyCode::Char('m') => {
+                        self.toggle_recording();
+                    }
+                    KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-')
+                        if matches!(flag_value, FlagValue::Level(_)) =>
+                    {
+                        if self.locked_flags.contains(&self.focused_flag) {
+                            self.set_status(format!(
+                                "{} is locked, press L to unlock it",
+                                self.focused_flag
+                            ));
+                        } else if let FlagValue::Level(level) = flag_value {
+                            *level = if key.code == KeyCode::Char('-') {
+                                level.saturating_sub(1)
+                            } else {
+                                (*level + 1).min(MAX_LEVEL)
+                            };
+                            edited = Some(self.focused_flag);
+                        }
+                    }
+                    KeyCode::Char('<') => self.resize(Pane::Sidebar, -1),
+                    KeyCode::Char('>') => self.resize(Pane::Sidebar, 1),
+                    KeyCode::Char('-') => self.resize(Pane::Output, -1),
+                    KeyCode::Char('+') | KeyCode::Char('=') => self.resize(Pane::Output, 1),
+                    KeyCode::Char('[') => self.resize(Pane::Footer, -1),
+                    KeyCode::Char(']') => self.resize(Pane::Footer, 1),
+                    KeyCode::Char('{') => self.resize(Pane::Queue, -1),
+                    KeyCode::Char('}') => self.resize(Pane::Queue, 1),
+                    KeyCode::Char('|') => self.toggle_split_view(),
+                    KeyCode::Char('@') => {
+                        self.replay_macro();
+                    }
+                    KeyCode::Char('L') => {
+                        self.toggle_lock(self.focused_flag);
+                    }
+                    KeyCode::Char('M') => {
+                        self.toggle_mode();
+                    }
+                    KeyCode::Char('C') => {
+                        self.clean_up_targets();
+                    }
+                    KeyCode::Char('O') => {
+                        self.reorder_targets(false);
+                    }
+                    KeyCode::Char('G') => {
+                        self.reorder_targets(true);
+                    }
+                    KeyCode::Enter | KeyCode::Char(' ')
+                        if self.locked_flags.contains(&self.focused_flag) =>
+                    {
+                        self.set_status(format!(
+                            "{} is locked, press L to unlock it",
+                            self.focused_flag
+                        ));
+                    }
+                    KeyCode::Char(' ') if self.focused_flag == NmapFlag::SpoofMac => {
+                        self.popup = Some(Popup::SpoofMac(Box::default()));
+                    }
+                    KeyCode::Char(' ') if self.focused_flag == NmapFlag::Interface => {
+                        self.popup = Some(Popup::Interfaces(Box::default()));
+                    }
+                    KeyCode::Char(' ') if self.focused_flag == NmapFlag::IpOptions => {
+                        let popup = IpOptionsPopup::new(self.scan.evasion.ip_options.as_deref());
+                        self.popup = Some(Popup::IpOptions(Box::new(popup)));
+                    }
+                    KeyCode::Char(' ') if self.focused_flag == NmapFlag::ScriptArgs => {
+                        let popup =
+                            ScriptArgsPopup::new(self.scan.script_scan.script_args.as_deref());
+                        self.popup = Some(Popup::ScriptArgs(Box::new(popup)));
+                    }
+                    KeyCode::Char(' ') if self.focused_flag.presets().is_some() => {
+                        let presets = self.focused_flag.presets().unwrap_or_default();
+                        let content = match flag_value {
+                            FlagValue::String(flag_value) => {
+                                *flag_value =
+                                    next_preset(presets, flag_value.as_deref()).map(str::to_string);
+                                flag_value.clone()
+                            }
+                            FlagValue::Int(flag_value) => {
+                                let current = flag_value.map(|value| value.to_string());
+                                *flag_value = next_preset(presets, current.as_deref())
+                                    .and_then(|preset| preset.parse().ok());
+                                flag_value.map(|value| value.to_string())
+                            }
+                            _ => None,
+                        };
+                        if let Some(input) = self.input_map.get_mut(&self.focused_flag) {
+                            input.set_content(content.unwrap_or_default());
+                        }
+                        edited = Some(self.focused_flag);
+                    }
+                    KeyCode::Enter | KeyCode::Char(' ') => match flag_value {
+                        FlagValue::Bool(flag_value) => {
+                            *flag_value = !*flag_value;
+                            edited = Some(self.focused_flag);
+                        }
+                        FlagValue::DnsResolution(resolution) => {
+                            *resolution = resolution.next();
+                            edited = Some(self.focused_flag);
+                        }
+                        // Steps up like +, starting over from 0 after the highest level
+                        FlagValue::Level(level) => {
+                            *level = if *level >= MAX_LEVEL { 0 } else { *level + 1 };
+                            edited = Some(self.focused_flag);
+                        }
+                        FlagValue::VecString(_)
+                        | FlagValue::Path(_)
+                        | FlagValue::Int(_)
+                        | FlagValue::Float(_)
+                        | FlagValue::String(_)
+                        | FlagValue::VecInt(_)
+                        | FlagValue::FtpRelay(_) => self.editing_flag = Some(self.focused_flag),
+                        FlagValue::TimingTemplate(flag_value) => {
+                            *flag_value = self
+                                .focused_radio_index
+                                .and_then(TimingTemplate::from_index)
+                                .and_then(|new_val| {
                                     if Some(new_val) == *flag_value {
                                         None
                                     } else {
                                         Some(new_val)
                                     }
                                 });
+                            edited = Some(self.focused_flag);
+                        }
+                        FlagValue::RatePreset(timing) => {
+                            if let Some(preset) =
+                                self.focused_radio_index.and_then(RatePreset::from_index)
+                            {
+                                let preset_timing = self.config.rate_presets.timing(preset);
+                                let message = if rate_presets::matches(timing, &preset_timing) {
+                                    rate_presets::clear(timing, &preset_timing);
+                                    format!("Cleared the {} preset", preset)
+                                } else {
+                                    rate_presets::apply(timing, &preset_timing);
+                                    format!(
+                                        "Applied the {} preset: {}",
+                                        preset,
+                                        self.config.rate_presets.options(preset)
+                                    )
+                                };
+                                self.status = Some((message, Instant::now()));
+                                self.refresh_input(NmapFlag::HostTimeout);
+                                self.refresh_input(NmapFlag::ScriptTimeout);
+                                edited = Some(self.focused_flag);
+                            }
+                        }
+                        FlagValue::ScriptCategories(scripts) => {
+                            let mut selection = CategorySelection::from_scripts(scripts);
+                            match self
+                                .focused_radio_index
+                                .and_then(ScriptCategory::from_index)
+                            {
+                                Some(category) => selection.cycle(category),
+                                None if selection.included.len() < 2 => {
+                                    self.status = Some((
+                                        "Select two or more categories to combine them".to_string(),
+                                        Instant::now(),
+                                    ));
+                                }
+                                None => selection.match_all = !selection.match_all,
+                            }
+                            selection.apply(scripts);
+                            self.refresh_input(NmapFlag::Scripts);
+                            edited = Some(self.focused_flag);
+                        }
+                        FlagValue::Scanflags(technique) => {
+                            if let Some(index) = self.focused_radio_index {
+                                let mut flags = match technique {
+                                    ScanTechnique::Scanflags(flags) => *flags,
+                                    _ => TcpFlags::default(),
+                                };
+                                flags.toggle(index);
+                                *technique = if flags.is_empty() {
+                                    ScanTechnique::default()
+                                } else {
+                                    ScanTechnique::Scanflags(flags)
+                                };
+                                // Only one technique applies, so composing flags drops -b
+                                if let Some(input) = self.input_map.get_mut(&NmapFlag::FtpRelay) {
+                                    input.set_content(String::new());
+                                }
+                                edited = Some(self.focused_flag);
+                            }
                         }
                     },
                     _ => {}
                 }
             }
         }
+
+        if let Some(flag) = edited
+            && let Some(recording) = self.recording.as_mut()
+        {
+            recording.record(flag, self.scan);
+        }
         Ok(())
     }
 
+    /// Show the current value of a flag in its input after it was changed from elsewhere
+    fn refresh_input(&mut self, flag: NmapFlag) {
+        let Some(input) = self.input_map.get_mut(&flag) else {
+            return;
+        };
+        match flag.get_flag_value(self.scan) {
+            FlagValue::VecString(value) => {
+                input.set_typed_value(InputValue::VecString(value.clone()))
+            }
+            FlagValue::String(value) => input.set_content(value.clone().unwrap_or_default()),
+            _ => {}
+        }
+    }
+
+    /// Whether the editor shows a flag in the current UI mode
+    pub fn shows(&self, flag: NmapFlag) -> bool {
+        self.config.ui.mode == UiMode::Expert || flag.is_common()
+    }
+
+    /// The flags of a row that the editor shows in the current UI mode
+    pub fn shown(&self, flags: &[NmapFlag]) -> Vec<NmapFlag> {
+        flags
+            .iter()
+            .copied()
+            .filter(|flag| self.shows(*flag))
+            .collect()
+    }
+
+    /// Switch between beginner and expert mode and remember the choice in the config file
+    fn toggle_mode(&mut self) {
+        self.config.ui.mode = self.config.ui.mode.toggle();
+        if !self.shows(self.focused_flag) {
+            self.focused_flag = NmapFlag::first();
+            self.focused_radio_index = None;
+        }
+        match Config::save_ui_mode(self.config.ui.mode) {
+            Ok(()) => self.set_status(format!("Switched to {} mode", self.config.ui.mode)),
+            Err(err) => self.set_status(err.to_string()),
+        }
+    }
+
+    /// Change the size of a pane and remember it for the next sessions
+    fn resize(&mut self, pane: Pane, delta: i16) {
+        self.config.layout.resize(pane, delta);
+        if let Err(err) = Config::save_layout(&self.config.layout) {
+            self.set_status(err.to_string());
+        }
+    }
+
+    /// Show a pane over the whole terminal, or restore the layout if it already is
+    fn toggle_zoom(&mut self, zoom: Zoom) {
+        if self.zoom == Some(zoom) {
+            self.zoom = None;
+        } else if zoom == Zoom::Output && self.runner.is_none() {
+            self.set_status("No scan output to zoom, run a scan first".to_string());
+        } else {
+            self.zoom = Some(zoom);
+        }
+    }
+
+    /// Show or hide the output and results next to the editor
+    fn toggle_split_view(&mut self) {
+        let layout = &mut self.config.layout;
+        layout.split_view = !layout.split_view;
+        if let Err(err) = Config::save_layout(layout) {
+            self.set_status(err.to_string());
+        } else if self.config.layout.split_view {
+            self.load_results();
+            let width = terminal::size().map_or(0, |(width, _)| width);
+            if width < MIN_SPLIT_WIDTH {
+                self.set_status(format!(
+                    "The split view shows once the terminal is {} columns wide",
+                    MIN_SPLIT_WIDTH
+                ));
+            }
+        }
+    }
+
+    fn toggle_lock(&mut self, flag: NmapFlag) {
+        let locked = !self.locked_flags.remove(&flag);
+        if locked {
+            self.locked_flags.insert(flag);
+            self.set_status(format!("Locked {}", flag));
+        } else {
+            self.set_status(format!("Unlocked {}", flag));
+        }
+        if let Some(input) = self.input_map.get_mut(&flag) {
+            input.set_label(flag_label(flag, locked));
+        }
+        if let Some(dir) = &self.state_dir
+            && let Err(err) = locks::save(&self.locked_flags, &dir.join(LOCKS_FILE))
+        {
+            self.set_status(format!("Failed to save locked flags: {}", err));
+        }
+    }
+
+    /// Apply a bulk change to the scan without touching locked flags, then refresh the inputs
+    fn preserve_locked(&mut self, change: impl FnOnce(&mut NmapScan)) {
+        let locked: Vec<(NmapFlag, EditValue)> = self
+            .locked_flags
+            .iter()
+            .map(|&flag| (flag, EditValue::capture(flag, self.scan)))
+            .collect();
+        change(self.scan);
+        for (flag, value) in &locked {
+            value.apply(*flag, self.scan);
+        }
+
+        self.input_map.clear();
+        initialize_text_inputs(self.scan, &mut self.input_map);
+        for (flag, input) in self.input_map.iter_mut() {
+            input.set_history(self.history.entries(*flag).to_vec());
+        }
+        for flag in &self.locked_flags {
+            if let Some(input) = self.input_map.get_mut(flag) {
+                input.set_label(flag_label(*flag, true));
+            }
+        }
+    }
+
+    fn toggle_recording(&mut self) {
+        match self.recording.take() {
+            None => {
+                self.recording = Some(Macro::new());
+                self.set_status("Recording macro, press m to stop".to_string());
+            }
+            Some(recorded) if recorded.is_empty() => {
+                self.set_status("Macro recording cancelled, no edits made".to_string());
+            }
+            Some(recorded) => {
+                let message = match &self.state_dir {
+                    Some(dir) => match recorded.save(&dir.join(MACRO_FILE)) {
+                        Ok(()) => format!("Recorded macro with {} edits", recorded.edits().len()),
+                        Err(err) => format!("Recorded macro, but failed to save it: {}", err),
+                    },
+                    None => format!("Recorded macro with {} edits", recorded.edits().len()),
+                };
+                self.recorded_macro = Some(recorded);
+                self.set_status(message);
+            }
+        }
+    }
+
+    fn replay_macro(&mut self) {
+        let Some(recorded) = &self.recorded_macro else {
+            self.set_status("No macro recorded, press m to start recording".to_string());
+            return;
+        };
+        let recorded = recorded.clone();
+        self.preserve_locked(|scan| recorded.apply(scan));
+        let count = recorded.edits().len();
+        self.set_status(format!("Replayed macro with {} edits", count));
+    }
+
+    fn run_current_scan(&mut self) {
+        if self.check_policy()
+            && self.check_secrets(&self.scan.clone(), true)
+            && self.check_outputs()
+        {
+            self.start_current_scan();
+        }
+    }
+
+    /// Ask for the secrets of the script arguments that are neither set in the environment nor
+    /// entered yet, returning whether the scan has them all. With `run`, the scan in the editor
+    /// runs once they are entered
+    fn check_secrets(&mut self, scan: &NmapScan, run: bool) -> bool {
+        let missing = secrets::missing(scan, |name| env::var(name).ok(), &self.secrets);
+        if missing.is_empty() {
+            return true;
+        }
+        if self.popup.is_none() {
+            self.popup = Some(Popup::Secrets(Box::new(SecretsPopup::new(missing, run))));
+        }
+        false
+    }
+
+    fn start_current_scan(&mut self) {
+        self.stamped_xml = None;
+        let scan = self.scan.clone();
+        self.start_scan(&scan);
+    }
+
+    /// Run a scan in lazynmap or in a new pane, as configured
+    fn start_scan(&mut self, scan: &NmapScan) {
+        if self.config.integrations.run_in_pane {
+            self.run_scan_in_pane(scan);
+        } else {
+            self.run_scan(scan);
+        }
+    }
+
+    /// Ask what to do with output files an earlier run left behind, returning whether the scan
+    /// may start right away
+    fn check_outputs(&mut self) -> bool {
+        if self.scan.output.append_output {
+            return true;
+        }
+        let existing = self
+            .scan
+            .output_files()
+            .into_iter()
+            .filter(|file| file.exists())
+            .collect::<Vec<_>>();
+        if existing.is_empty() {
+            return true;
+        }
+        self.popup = Some(Popup::ExistingOutputs(existing));
+        false
+    }
+
+    /// Run the scan in a new terminal pane, for watching nmap's raw output outside lazynmap
+    fn run_in_pane(&mut self) {
+        let scan = self.scan.clone();
+        self.run_scan_in_pane(&scan);
+    }
+
+    fn run_scan_in_pane(&mut self, scan: &NmapScan) {
+        if !self.check_policy() || !self.check_secrets(scan, true) {
+            return;
+        }
+        // Secrets reach the pane through the environment, leaving the command free of them
+        let (command, variables) = secrets::shell_command(scan);
+        let names = variables
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        let Some(template) = self
+            .config
+            .integrations
+            .pane_command
+            .clone()
+            .or_else(|| multiplexer_pane_command(&names))
+        else {
+            self.set_status(
+                "Set integrations.pane_command or run lazynmap inside tmux or screen".to_string(),
+            );
+            return;
+        };
+        let values = secrets::variables(scan, |name| env::var(name).ok(), &self.secrets);
+        let command = expand_placeholders(&template, &[("command", &held_command(&command))]);
+        let message = match spawn_shell(&command, None, &values) {
+            Ok(()) => "Started nmap in a new pane".to_string(),
+            Err(err) => format!("Failed to run {}: {}", command, err),
+        };
+        self.set_status(message);
+    }
+
+    fn copy_command(&mut self) {
+        if self.check_policy() {
+            let command = NmapCommandBuilder::build(self.scan);
+            self.copy_to_clipboard(&command, "nmap command");
+        }
+    }
+
+    fn export_script(&mut self) {
+        if self.check_policy() {
+            self.popup = Some(Popup::Script(Box::new(ScriptPopup::new(self.scan.clone()))));
+        }
+    }
+
+    /// Show nmap's help for the --script entries, starting with the focused category if any
+    fn show_script_help(&mut self) {
+        let mut scripts = self.scan.script_scan.scripts.clone();
+        let mut selected = 0;
+        if self.focused_flag == NmapFlag::ScriptCategories
+            && let Some(category) = self
+                .focused_radio_index
+                .and_then(ScriptCategory::from_index)
+        {
+            let category = category.to_string();
+            selected = scripts
+                .iter()
+                .position(|script| *script == category)
+                .unwrap_or_else(|| {
+                    scripts.insert(0, category);
+                    0
+                });
+        }
+        if scripts.is_empty() {
+            self.set_status("Add scripts to --script to see their help".to_string());
+            return;
+        }
+        self.popup = Some(Popup::ScriptHelp(Box::new(ScriptHelpPopup::new(
+            scripts, selected,
+        ))));
+    }
+
+    fn copy_json(&mut self) {
+        if !self.check_policy() {
+            return;
+        }
+        match serde_json::to_string_pretty(&*self.scan) {
+            Ok(json) => self.copy_to_clipboard(&json, "scan as JSON"),
+            Err(err) => self.set_status(format!("Failed to serialize scan: {}", err)),
+        }
+    }
+
+    /// Show why the current scan may not run, returning whether it is allowed
+    fn check_policy(&mut self) -> bool {
+        match self.blocking_popup(self.scan) {
+            Some(popup) => {
+                self.popup = Some(popup);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// The policy violations or out of scope targets that keep a scan from running
+    fn blocking_popup(&self, scan: &NmapScan) -> Option<Popup> {
+        let violations = self.policy.check(scan);
+        if !violations.is_empty() {
+            return Some(Popup::PolicyViolation(violations));
+        }
+        if self.scope_overridden {
+            return None;
+        }
+        let outside = self.scope.outside_scan(scan);
+        (!outside.is_empty()).then(|| Popup::OutOfScope(outside, self.scope_name()))
+    }
+
+    /// Command line targets outside the project scope, unless the scope was overridden
+    pub fn targets_outside_scope(&self) -> Vec<String> {
+        if self.scope_overridden {
+            return Vec::new();
+        }
+        self.scope.outside(&self.scan.target_specification.targets)
+    }
+
+    /// The interface whose subnet holds every target, on which nmap discovers hosts by ARP
+    fn local_interface(&self) -> Option<&Interface> {
+        let Some(Ok(list)) = self.iflist.as_ref().and_then(IflistFetch::result) else {
+            return None;
+        };
+        list.local_interface(&self.scan.target_specification.targets)
+    }
+
+    fn scope_name(&self) -> String {
+        self.scope
+            .path()
+            .map_or(SCOPE_FILE.to_string(), |path| path.display().to_string())
+    }
+
+    fn run_scan(&mut self, scan: &NmapScan) {
+        if self
+            .runner
+            .as_ref()
+            .is_some_and(|runner| runner.is_running())
+        {
+            return;
+        }
+        if let Some(xml) = scan.xml_output()
+            && let Err(err) = results::notes::save(&xml, &scan.notes)
+        {
+            self.set_status(format!("Failed to save notes with the results: {}", err));
+        }
+        let mut resolved = secrets::resolved(scan, |name| env::var(name).ok(), &self.secrets);
+        // The progress bar is drawn from the reports of --stats-every
+        resolved
+            .output
+            .stats_every
+            .get_or_insert_with(|| DEFAULT_STATS_EVERY.to_string());
+        match NmapRunner::spawn(&resolved) {
+            Ok(runner) => {
+                self.runner = Some(runner);
+                self.sessions.start(ScanSession::new(scan));
+            }
+            Err(err) => {
+                tracing::error!(%err, transient = err.is_transient(), "Failed to start nmap");
+                self.runner = None;
+                let diagnosis = Diagnosis::from_spawn_error(&err);
+                self.popup = Some(if err.is_transient() {
+                    Popup::Retry(diagnosis, Box::new(scan.clone()))
+                } else {
+                    Popup::Failure(vec![diagnosis])
+                });
+            }
+        }
+    }
+
+    fn resume_scan(&mut self) {
+        let Some(session) = self.sessions.last_resumable() else {
+            return;
+        };
+        let mut scan = NmapScan::new();
+        scan.output.resume = session.resume_file.clone();
+        self.run_scan(&scan);
+    }
+
+    fn handle_popup_action(&mut self, action: PopupAction) {
+        match action {
+            PopupAction::None => {}
+            PopupAction::Status(message) => self.set_status(message),
+            PopupAction::LoadProfile(profile) => self.load_profile(*profile),
+            PopupAction::Compare(left, right) => {
+                self.compare = Some(CompareView::new(*left, *right));
+                self.view = View::Compare;
+            }
+            PopupAction::Monitor(profile, interval) => self.start_monitor(*profile, interval),
+            PopupAction::Batch(profile, dir) => self.start_batch(*profile, &dir),
+            PopupAction::Split(chunks, delay) => self.start_split(chunks, delay),
+            PopupAction::SpoofMac(value) => {
+                if let Some(input) = self.input_map.get_mut(&NmapFlag::SpoofMac) {
+                    input.set_content(value.clone());
+                }
+                self.scan.evasion.spoof_mac = Some(value);
+                if let Some(recording) = self.recording.as_mut() {
+                    recording.record(NmapFlag::SpoofMac, self.scan);
+                }
+            }
+            PopupAction::Technique(technique) => {
+                self.preserve_locked(|scan| scan.scan_technique = technique);
+                let option = capabilities_index(&self.scan.scan_technique)
+                    .map_or("", |index| CAPABILITIES[index].option);
+                self.set_status(format!("Switched to the {} scan", option));
+            }
+            PopupAction::Discovery(strategy) => {
+                self.preserve_locked(|scan| strategy.apply(scan));
+                if let Some(recording) = self.recording.as_mut() {
+                    for flag in DiscoveryStrategy::FLAGS {
+                        recording.record(flag, self.scan);
+                    }
+                }
+                let status = match strategy.flags() {
+                    flags if flags.is_empty() => "Cleared host discovery probes".to_string(),
+                    flags => format!("Host discovery set to {}", flags),
+                };
+                self.set_status(status);
+            }
+            PopupAction::AnnotateTargets(mappings) => {
+                if [NmapFlag::Targets, NmapFlag::InputFile]
+                    .iter()
+                    .any(|flag| self.locked_flags.contains(flag))
+                {
+                    self.set_status("Unlock the targets to replace them".to_string());
+                    return;
+                }
+                let Some(dir) = paths::state_dir() else {
+                    self.set_status("No state directory to write the target list to".to_string());
+                    return;
+                };
+                let mut written = None;
+                self.preserve_locked(|scan| {
+                    written = Some(use_annotated_targets(scan, &mappings, &dir.join("targets")));
+                });
+                match written {
+                    Some(Ok(path)) => self.set_status(format!(
+                        "Targets read from {}, annotated with their names",
+                        path.display()
+                    )),
+                    Some(Err(err)) => {
+                        self.set_status(format!("Failed to write the target list: {}", err))
+                    }
+                    None => {}
+                }
+            }
+            PopupAction::Arp {
+                disable_arp_ping,
+                send_ip,
+            } => {
+                self.preserve_locked(|scan| {
+                    scan.host_discovery.disable_arp_ping = disable_arp_ping;
+                    scan.misc.packet_layer = match scan.misc.packet_layer {
+                        _ if send_ip => PacketLayer::Ip,
+                        PacketLayer::Ip => PacketLayer::Default,
+                        layer => layer,
+                    };
+                });
+                self.set_status(match (disable_arp_ping, send_ip) {
+                    (false, false) => "Hosts on the local subnet are discovered by ARP".to_string(),
+                    _ => "ARP discovery of hosts on the local subnet is off".to_string(),
+                });
+            }
+            PopupAction::Interface(device) => {
+                if let Some(input) = self.input_map.get_mut(&NmapFlag::Interface) {
+                    input.set_content(device.clone());
+                }
+                self.scan.evasion.interface = Some(device);
+                if let Some(recording) = self.recording.as_mut() {
+                    recording.record(NmapFlag::Interface, self.scan);
+                }
+            }
+            PopupAction::IpOptions(value) => {
+                if let Some(input) = self.input_map.get_mut(&NmapFlag::IpOptions) {
+                    input.set_content(value.clone());
+                }
+                self.scan.evasion.ip_options = Some(value);
+                if let Some(recording) = self.recording.as_mut() {
+                    recording.record(NmapFlag::IpOptions, self.scan);
+                }
+            }
+            PopupAction::ScriptArgs(value) => {
+                if let Some(input) = self.input_map.get_mut(&NmapFlag::ScriptArgs) {
+                    input.set_content(value.clone());
+                }
+                self.scan.script_scan.script_args = (!value.is_empty()).then_some(value);
+                if let Some(recording) = self.recording.as_mut() {
+                    recording.record(NmapFlag::ScriptArgs, self.scan);
+                }
+            }
+            PopupAction::Sample(count) => self.sample_targets(count as usize),
+            PopupAction::Notes(notes) => {
+                self.scan.notes = notes;
+                self.set_status("Saved scan notes".to_string());
+            }
+            PopupAction::Command(scan, legacy) => {
+                let mut scan = *scan;
+                scan.notes = std::mem::take(&mut self.scan.notes);
+                self.preserve_locked(|current| *current = scan);
+                let message = if legacy.is_empty() {
+                    "Applied the edited command".to_string()
+                } else {
+                    let replaced = legacy
+                        .iter()
+                        .map(|(legacy, modern)| {
+                            format!("{} is deprecated, using {}", legacy, modern)
+                        })
+                        .collect::<Vec<_>>();
+                    format!("Applied the edited command; {}", replaced.join(", "))
+                };
+                self.set_status(message);
+            }
+            PopupAction::Retry(scan) => self.run_scan(&scan),
+            PopupAction::Secrets(values, run) => {
+                self.secrets.extend(values);
+                if run {
+                    self.run_current_scan();
+                }
+            }
+            PopupAction::SkipSecrets => {
+                if let Some(queue) = self.queue.as_mut() {
+                    queue.cancel();
+                }
+                self.stop_monitor();
+                self.set_status("Stopped the scans waiting for secrets".to_string());
+            }
+            PopupAction::OverrideScope => {
+                self.scope_overridden = true;
+                self.set_status("Scanning outside the scope until lazynmap exits".to_string());
+            }
+            PopupAction::RunWithOutputs(choice) => {
+                // Only this run is changed, the scan in the editor keeps its outputs
+                let mut scan = self.scan.clone();
+                self.stamped_xml = None;
+                match choice {
+                    ExistingOutputs::Append => {
+                        scan.output.append_output = true;
+                        self.set_status("Appending to the existing output files".to_string());
+                    }
+                    ExistingOutputs::Overwrite => {}
+                    ExistingOutputs::Timestamp => {
+                        let now = SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map_or(0, |elapsed| elapsed.as_secs());
+                        scan.stamp_outputs(&format_file_stamp(now));
+                        self.stamped_xml = self.scan.xml_output().zip(scan.xml_output());
+                    }
+                }
+                self.start_scan(&scan);
+            }
+            PopupAction::ShowHost(path, host) => {
+                self.results.load(&path);
+                self.refresh_asn_owners();
+                self.results.select_host(host);
+                self.view = View::Results;
+            }
+            PopupAction::FocusFlag(flag) => self.focus_flag(flag),
+            PopupAction::Copy(text, what) => self.copy_to_clipboard(&text, what),
+            PopupAction::OpenUrl(url) => {
+                if let Err(err) = open_url(&url) {
+                    self.set_status(format!("Failed to open {}: {}", url, err));
+                }
+            }
+            PopupAction::Queue(scan) => self.queue_scan(*scan),
+            PopupAction::LoadScan(scan) => {
+                let mut scan = *scan;
+                scan.notes = std::mem::take(&mut self.scan.notes);
+                self.preserve_locked(|current| *current = scan);
+                self.view = View::Editor;
+                self.set_status("Loaded the follow-up scan into the editor".to_string());
+            }
+        }
+    }
+
+    /// Focus the field of a flag in the editor, scrolling to its section
+    fn focus_flag(&mut self, flag: NmapFlag) {
+        if !self.shows(flag) {
+            self.set_status(format!("{} is only shown in expert mode, press M", flag));
+            return;
+        }
+        self.view = View::Editor;
+        self.focused_flag = flag;
+        self.focused_radio_index = flag.get_variant_count().map(|_| 0);
+        self.focus_section(section_of(flag));
+    }
+
+    /// Scroll section by section until the given one is focused
+    fn focus_section(&mut self, section: usize) {
+        while self.focused_section > section {
+            self.scroll_up();
+        }
+        while self.focused_section < section.min(SECTIONS.len() - 1) {
+            self.scroll_down();
+        }
+    }
+
+    /// Replace the current options with a profile, keeping the targets if the profile has none
+    fn load_profile(&mut self, profile: Profile) {
+        let mut scan = profile.scan;
+        let ts = &scan.target_specification;
+        if ts.targets.is_empty() && ts.input_file.is_none() && ts.random_targets.is_none() {
+            scan.target_specification = self.scan.target_specification.clone();
+        }
+        self.preserve_locked(|current| *current = scan);
+        self.set_status(format!("Loaded profile {}", profile.name));
+        self.profile = Some(profile.name);
+    }
+
+    /// Monitor a profile, scanning the current targets if the profile has none
+    fn start_monitor(&mut self, mut profile: Profile, interval: Duration) {
+        let ts = &profile.scan.target_specification;
+        if ts.targets.is_empty() && ts.input_file.is_none() && ts.random_targets.is_none() {
+            profile.scan.target_specification = self.scan.target_specification.clone();
+        }
+        self.stop_monitor();
+        self.set_status(format!(
+            "Monitoring {} every {}",
+            profile.name,
+            format_duration(interval)
+        ));
+        self.monitor = Some(Monitor::new(profile, interval));
+        self.monitor_view = MonitorView::default();
+        self.view = View::Monitor;
+    }
+
+    /// Replace the targets, including those of the -iL file, with a random sample of their hosts
+    fn sample_targets(&mut self, count: usize) {
+        if [NmapFlag::Targets, NmapFlag::InputFile]
+            .iter()
+            .any(|flag| self.locked_flags.contains(flag))
+        {
+            self.set_status("Unlock the targets to replace them with a sample".to_string());
+            return;
+        }
+        let targets = match scan_targets(self.scan) {
+            Ok(targets) if targets.is_empty() => {
+                self.set_status("Add targets or a target list file to sample".to_string());
+                return;
+            }
+            Ok(targets) => targets,
+            Err(err) => {
+                self.set_status(format!("Failed to read the target list: {}", err));
+                return;
+            }
+        };
+        let sample = sample_targets(&targets, count, random_seed());
+        let message = format!("Replaced the targets with {} sampled hosts", sample.len());
+        self.preserve_locked(|scan| {
+            scan.target_specification.targets = sample;
+            scan.target_specification.input_file = None;
+        });
+        self.set_status(message);
+    }
+
+    /// Resolve the names of the targets with a list scan, which sends them no packets
+    fn look_up_hostnames(&mut self) {
+        let ts = &self.scan.target_specification;
+        if ts.targets.is_empty() && ts.input_file.is_none() {
+            self.set_status("Add targets to look up their names".to_string());
+            return;
+        }
+        self.popup = Some(Popup::ReverseDns(Box::new(ReverseDnsPopup::new(self.scan))));
+    }
+
+    /// Drop duplicate targets and those inside a CIDR block of the list
+    fn clean_up_targets(&mut self) {
+        if self.locked_flags.contains(&NmapFlag::Targets) {
+            self.set_status("Unlock the targets to clean them up".to_string());
+            return;
+        }
+        let targets = &self.scan.target_specification.targets;
+        let overlaps = find_overlaps(targets);
+        if overlaps.is_empty() {
+            self.set_status("No duplicate or overlapping targets".to_string());
+            return;
+        }
+        let cleaned = clean_up(targets);
+        self.preserve_locked(|scan| scan.target_specification.targets = cleaned);
+        self.set_status(format!(
+            "Removed {} redundant {}",
+            overlaps.len(),
+            if overlaps.len() == 1 {
+                "target"
+            } else {
+                "targets"
+            }
+        ));
+    }
+
+    /// Sort the targets in address order, or also merge their addresses into CIDR blocks
+    fn reorder_targets(&mut self, group: bool) {
+        if self.locked_flags.contains(&NmapFlag::Targets) {
+            self.set_status("Unlock the targets to reorder them".to_string());
+            return;
+        }
+        let targets = &self.scan.target_specification.targets;
+        let reordered = if group {
+            group_targets(targets)
+        } else {
+            sort_targets(targets)
+        };
+        let message = if group {
+            format!("Grouped {} targets into {}", targets.len(), reordered.len())
+        } else {
+            format!("Sorted {} targets", targets.len())
+        };
+        self.preserve_locked(|scan| scan.target_specification.targets = reordered);
+        self.set_status(message);
+    }
+
+    /// Add -6 and the zone of the chosen interface to link-local targets, which need both
+    fn fix_link_local(&mut self) {
+        let Some(warning) = check_link_local(self.scan) else {
+            self.set_status("No link-local targets missing a zone or -6".to_string());
+            return;
+        };
+        let interface = self.scan.evasion.interface.clone();
+        if !warning.unzoned.is_empty() {
+            if self.locked_flags.contains(&NmapFlag::Targets) {
+                self.set_status("Unlock the targets to add the zone".to_string());
+                return;
+            }
+            if interface.is_none() {
+                self.focus_flag(NmapFlag::Interface);
+                self.set_status(
+                    "Choose the interface (-e) to add as the zone of the link-local targets"
+                        .to_string(),
+                );
+                return;
+            }
+        }
+        let zoned = warning.unzoned.len();
+        self.preserve_locked(|scan| {
+            if let Some(interface) = &interface {
+                scan.target_specification.targets =
+                    add_zone(&scan.target_specification.targets, interface);
+            }
+            scan.misc.ipv6 = true;
+        });
+        self.set_status(match interface {
+            Some(interface) if zoned > 0 => format!(
+                "Added -6 and the zone %{} to {} link-local {}",
+                interface,
+                zoned,
+                if zoned == 1 { "target" } else { "targets" }
+            ),
+            _ => "Added -6 for the link-local targets".to_string(),
+        });
+    }
+
+    /// Clear -F and --top-ports when -p lists the ports explicitly
+    fn fix_port_conflict(&mut self) {
+        let Some(conflict) = check_ports(self.scan) else {
+            self.set_status("No port selections conflict with -p".to_string());
+            return;
+        };
+        self.preserve_locked(|scan| keep_explicit_ports(&mut scan.ports));
+        self.set_status(format!("Cleared {}, keeping -p", conflict.options()));
+    }
+
+    /// Shorten a command that is too long by moving its longest host list to a file
+    fn move_long_list(&mut self) {
+        let Some(warning) = check_length(self.scan) else {
+            self.set_status("The command is short enough to run anywhere".to_string());
+            return;
+        };
+        let flags = match warning.list {
+            HostList::Targets => [NmapFlag::Targets, NmapFlag::InputFile],
+            HostList::Exclude => [NmapFlag::Exclude, NmapFlag::ExcludeFile],
+        };
+        if flags.iter().any(|flag| self.locked_flags.contains(flag)) {
+            self.set_status(format!(
+                "Unlock the {} to move them to a file",
+                warning.list
+            ));
+            return;
+        }
+        let mut moved = None;
+        self.preserve_locked(|scan| {
+            moved = Some(move_list_to_file(scan, warning.list, &env::temp_dir()));
+        });
+        match moved {
+            Some(Ok(path)) => {
+                self.set_status(format!("Moved the {} to {}", warning.list, path.display()))
+            }
+            Some(Err(err)) => {
+                self.set_status(format!("Failed to write the {}: {}", warning.list, err))
+            }
+            None => {}
+        }
+    }
+
+    fn queue_busy(&mut self) -> bool {
+        let busy = self.queue.as_ref().is_some_and(|queue| !queue.is_done());
+        if busy {
+            self.set_status("A queue is already running, press x to stop it".to_string());
+        }
+        busy
+    }
+
+    /// Queue one run of a profile per target list file in a directory
+    fn start_batch(&mut self, profile: Profile, dir: &Path) {
+        if self.queue_busy() {
+            return;
+        }
+        match batch_scans(&profile.scan, dir) {
+            Ok(scans) if scans.is_empty() => {
+                self.set_status(format!("No target list files in {}", dir.display()));
+            }
+            Ok(scans) => {
+                self.set_status(format!("Queued {} scans of {}", scans.len(), profile.name));
+                self.queue = Some(ScanQueue::new(profile.name, scans));
+            }
+            Err(err) => self.set_status(format!("Failed to read {}: {}", dir.display(), err)),
+        }
+    }
+
+    /// Queue the current scan as several scans over chunks of its targets
+    fn start_split(&mut self, chunks: u32, delay: Duration) {
+        if self.queue_busy() {
+            return;
+        }
+        let dir = env::temp_dir().join(format!("lazynmap-chunks-{}", std::process::id()));
+        match chunk_scans(self.scan, chunks as usize, &dir) {
+            Ok(scans) if scans.is_empty() => {
+                self.set_status("Add targets or a target list file to split".to_string());
+            }
+            Ok(scans) => {
+                let mut message = format!("Queued {} chunks", scans.len());
+                if !delay.is_zero() {
+                    message.push_str(&format!(", {} apart", format_duration(delay)));
+                }
+                self.set_status(message);
+                self.queue = Some(ScanQueue::new("split".to_string(), scans).with_delay(delay));
+            }
+            Err(err) => self.set_status(format!("Failed to split targets: {}", err)),
+        }
+    }
+
+    /// Run a scan after those already queued, or start a queue of follow-up scans with it
+    fn queue_scan(&mut self, scan: QueuedScan) {
+        self.set_status(format!("Queued {}", scan.label));
+        match self.queue.as_mut() {
+            Some(queue) if !queue.is_done() => queue.push(scan),
+            _ => self.queue = Some(ScanQueue::new("follow-up".to_string(), vec![scan])),
+        }
+    }
+
+    /// Start the next scan of the queue once no other scan is running
+    fn run_queue(&mut self) {
+        if self
+            .runner
+            .as_ref()
+            .is_some_and(|runner| runner.is_running())
+        {
+            return;
+        }
+        if let Some(next) = self.queue.as_ref().and_then(|queue| queue.pending().next())
+            && !self.check_secrets(&next.scan.clone(), false)
+        {
+            return;
+        }
+        let Some(scan) = self.queue.as_mut().and_then(ScanQueue::start_next) else {
+            return;
+        };
+        if let Some(popup) = self.blocking_popup(&scan) {
+            self.queue = None;
+            self.popup = Some(popup);
+            return;
+        }
+        self.run_scan(&scan);
+        if let Some(queue) = self.queue.as_mut()
+            && !self
+                .runner
+                .as_ref()
+                .is_some_and(|runner| runner.is_running())
+        {
+            queue.finish(false);
+        }
+    }
+
+    /// Record the end of a queued scan, reporting the queue once every scan has run
+    fn finish_queued_scan(&mut self, completed: bool) {
+        let Some(queue) = self.queue.as_mut() else {
+            return;
+        };
+        queue.finish(completed);
+        if queue.is_done() {
+            let message = format!(
+                "Queue {} finished: {} completed, {} failed",
+                queue.name(),
+                queue.completed(),
+                queue.failed()
+            );
+            self.set_status(message);
+        }
+    }
+
+    fn stop_monitor(&mut self) {
+        let Some(monitor) = self.monitor.take() else {
+            return;
+        };
+        if monitor.is_running()
+            && let Some(runner) = self.runner.as_mut()
+        {
+            runner.kill();
+        }
+        self.set_status(format!("Stopped monitoring {}", monitor.name()));
+    }
+
+    /// Start the next monitor run once it is due and no other scan is running
+    fn run_monitor(&mut self) {
+        let Some(monitor) = self.monitor.as_ref() else {
+            return;
+        };
+        if !monitor.is_due()
+            || self
+                .runner
+                .as_ref()
+                .is_some_and(|runner| runner.is_running())
+        {
+            return;
+        }
+        let scan = monitor.scan().clone();
+        if let Some(popup) = self.blocking_popup(&scan) {
+            self.monitor = None;
+            self.popup = Some(popup);
+            return;
+        }
+        if !self.check_secrets(&scan, false) {
+            return;
+        }
+        if let Some(monitor) = self.monitor.as_mut() {
+            monitor.start_run();
+        }
+        self.run_scan(&scan);
+        if self.runner.is_none() {
+            self.monitor = None;
+        }
+    }
+
+    /// Store the results of a finished monitor run and report the ports that changed
+    fn finish_monitor_run(&mut self, completed: bool) {
+        let Some(monitor) = self.monitor.as_mut() else {
+            return;
+        };
+        let run = monitor.runs();
+        match monitor.finish_run(completed) {
+            Ok(changes) => {
+                if !changes.is_empty() {
+                    let event = WebhookEvent {
+                        event: "changes_detected",
+                        title: format!(
+                            "Monitor {} detected {} changes",
+                            monitor.name(),
+                            changes.len()
+                        ),
+                        command: NmapCommandBuilder::build(&secrets::masked(monitor.scan())),
+                        results: None,
+                        changes: changes.clone(),
+                    };
+                    webhook::send(&self.config.webhook, &event);
+                }
+                self.set_status(format!("Monitor run {}: {} changes", run, changes.len()));
+            }
+            Err(err) => self.set_status(format!("Monitor run {} failed: {}", run, err)),
+        }
+    }
+
+    /// Make the results shown the baseline of the last loaded profile
+    fn mark_baseline(&mut self) {
+        let Some(source) = self.results.source() else {
+            return;
+        };
+        let Some(profile) = self.profile.clone() else {
+            self.set_status("Load a profile to mark a baseline for it".to_string());
+            return;
+        };
+        let Some(dir) = paths::data_dir() else {
+            return;
+        };
+        let message =
+            match BaselineStore::new(dir.join("results").join("baselines")).set(&profile, source) {
+                Ok(_) => format!("Marked these results as the baseline of {}", profile),
+                Err(err) => format!("Failed to mark baseline: {}", err),
+            };
+        self.set_status(message);
+    }
+
+    /// Leave the hosts selected in the results out of the next runs
+    fn exclude_selected_hosts(&mut self) {
+        let hosts = self.results.selected_addresses();
+        if hosts.is_empty() {
+            return;
+        }
+        // Hosts go to the exclude file when there is one, so its lock guards the file too
+        let (flag, list) = match self.scan.target_specification.exclude_file {
+            Some(_) => (NmapFlag::ExcludeFile, "exclude file"),
+            None => (NmapFlag::Exclude, "exclude list"),
+        };
+        if self.locked_flags.contains(&flag) {
+            self.set_status(format!("Unlock the {} to add hosts to it", list));
+            return;
+        }
+        let mut result = Ok(Vec::new());
+        self.preserve_locked(|scan| result = exclude_hosts(scan, &hosts));
+        let message = match result {
+            Ok(added) if added.is_empty() => "Already excluded".to_string(),
+            Ok(added) => match &self.scan.target_specification.exclude_file {
+                Some(file) => format!("Added {} to {}", added.join(", "), file.display()),
+                None => format!("Excluding {} from the next runs", added.join(", ")),
+            },
+            Err(err) => format!("Failed to update the exclude file: {}", err),
+        };
+        self.set_status(message);
+    }
+
+    fn show_results(&mut self) {
+        self.load_results();
+        self.view = View::Results;
+    }
+
+    /// Load the XML output of the current scan into the results view, as named by its last run,
+    /// or that of the last run when the scan sets none and the runner wrote it to a temporary
+    /// file
+    fn load_results(&mut self) {
+        let temp_xml = self
+            .runner
+            .as_ref()
+            .and_then(NmapRunner::temp_xml)
+            .map(Path::to_path_buf);
+        let xml = self.scan.xml_output().map(|path| match &self.stamped_xml {
+            Some((editor, run)) if *editor == path => run.clone(),
+            _ => path,
+        });
+        if let Some(path) = xml.or(temp_xml) {
+            self.results.load(&path);
+            self.refresh_asn_owners();
+        }
+    }
+
+    /// Write the URLs of open web ports to a file and hand them to the configured web command
+    fn send_web_targets(&mut self) {
+        let Some(results) = self.results.results() else {
+            return;
+        };
+        let urls = export(results, ExportFormat::WebUrls);
+        if urls.is_empty() {
+            self.set_status("No open web ports found".to_string());
+            return;
+        }
+
+        let path = self
+            .results
+            .source()
+            .and_then(Path::parent)
+            .unwrap_or(Path::new(""))
+            .join(ExportFormat::WebUrls.file_name());
+        let mut content = urls.join("\n");
+        content.push('\n');
+        if let Err(err) = fs::write(&path, &content) {
+            self.set_status(format!("Failed to write {}: {}", path.display(), err));
+            return;
+        }
+
+        let message = match &self.config.integrations.web_command {
+            Some(command) => {
+                let command = expand_placeholders(command, &[("file", &path.to_string_lossy())]);
+                match spawn_shell(&command, Some(content), &[]) {
+                    Ok(()) => format!("Sent {} URLs to {}", urls.len(), command),
+                    Err(err) => format!("Failed to run {}: {}", command, err),
+                }
+            }
+            None => format!("Wrote {} URLs to {}", urls.len(), path.display()),
+        };
+        self.set_status(message);
+    }
+
+    fn save_engagement(&mut self) {
+        if let Some(engagement) = &self.engagement
+            && let Some(dir) = &self.state_dir
+            && let Err(err) = engagement.save(&dir.join(ENGAGEMENT_FILE))
+        {
+            self.set_status(format!("Failed to save the engagement clock: {}", err));
+        }
+    }
+
+    /// Stop adding up scans, forgetting those of the engagement
+    fn stop_engagement(&mut self) {
+        if self.engagement.take().is_none() {
+            return;
+        }
+        if let Some(dir) = &self.state_dir {
+            let path = dir.join(ENGAGEMENT_FILE);
+            if path.exists()
+                && let Err(err) = fs::remove_file(&path)
+            {
+                self.set_status(format!("Failed to remove {}: {}", path.display(), err));
+                return;
+            }
+        }
+        self.set_status("Stopped the engagement clock".to_string());
+    }
+
+    /// Add the submitted text of a field to its history and save the history
+    fn remember_input(&mut self, flag: NmapFlag) {
+        let Some(input) = self.input_map.get_mut(&flag) else {
+            return;
+        };
+        self.history.record(flag, input.content());
+        input.set_history(self.history.entries(flag).to_vec());
+        if let Some(dir) = &self.state_dir
+            && let Err(err) = self.history.save(&dir.join(HISTORY_FILE))
+        {
+            self.set_status(format!("Failed to save input history: {}", err));
+        }
+    }
+
+    fn copy_to_clipboard(&mut self, text: &str, what: &str) {
+        let message = match clipboard::copy(text) {
+            Ok(()) => format!("Copied {} to clipboard", what),
+            Err(err) => format!("Failed to copy {}: {}", what, err),
+        };
+        self.set_status(message);
+    }
+
+    fn set_status(&mut self, message: String) {
+        self.status = Some((message, Instant::now()));
+    }
+
+    /// Owners of the targets given as addresses, each AS with the number of its targets
+    pub fn target_owners(&self) -> Vec<(String, usize)> {
+        let Some(asn) = &self.asn else {
+            return Vec::new();
+        };
+        let mut owners: Vec<(String, usize)> = Vec::new();
+        for target in &self.scan.target_specification.targets {
+            let Some(info) = target_address(target).and_then(|address| asn.get(address)) else {
+                continue;
+            };
+            let owner = info.to_string();
+            match owners.iter_mut().find(|(known, _)| *known == owner) {
+                Some((_, count)) => *count += 1,
+                None => owners.push((owner, 1)),
+            }
+        }
+        owners
+    }
+
+    fn refresh_asn_owners(&mut self) {
+        let Some(asn) = &self.asn else {
+            return;
+        };
+        let owners = self
+            .results
+            .results()
+            .map(|results| {
+                results
+                    .hosts
+                    .iter()
+                    .filter_map(|host| {
+                        let info = asn.get(host.address().parse().ok()?)?;
+                        Some((host.address().to_string(), info.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.results.set_asn_owners(owners);
+    }
+
+    fn poll_asn(&mut self) {
+        let Some(asn) = self.asn.as_mut() else {
+            return;
+        };
+        let targets = self
+            .scan
+            .target_specification
+            .targets
+            .iter()
+            .filter_map(|target| target_address(target));
+        let hosts = self
+            .results
+            .results()
+            .into_iter()
+            .flat_map(|results| &results.hosts)
+            .filter_map(|host| host.address().parse().ok());
+        asn.request(targets.chain(hosts));
+        if !asn.poll() {
+            return;
+        }
+        if let Some(err) = asn.error() {
+            let message = format!("ASN lookup failed: {}", err);
+            self.set_status(message);
+        }
+        self.refresh_asn_owners();
+    }
+
+    fn tick(&mut self) {
+        if self
+            .status
+            .as_ref()
+            .is_some_and(|(_, since)| since.elapsed() >= STATUS_TIMEOUT)
+        {
+            self.status = None;
+        }
+        if let Some(popup) = self.popup.as_mut() {
+            popup.tick();
+        }
+        if let Some(Popup::Interfaces(popup)) = self.popup.as_mut()
+            && let Some(devices) = popup.take_devices()
+            && let Some(InputWidget::Choice(input)) = self.input_map.get_mut(&NmapFlag::Interface)
+        {
+            input.set_choices(devices);
+        }
+        if self.results.poll() {
+            self.refresh_asn_owners();
+        }
+        self.poll_asn();
+        match self.iflist.as_mut() {
+            Some(iflist) => {
+                iflist.poll();
+            }
+            None if !self.scan.target_specification.targets.is_empty() => {
+                self.iflist = Some(IflistFetch::start());
+            }
+            None => {}
+        }
+
+        if let Some(runner) = self.runner.as_mut()
+            && runner.is_running()
+        {
+            self.run_progress.tick();
+            runner.poll();
+            if !runner.is_running() {
+                let status = match runner.status() {
+                    RunStatus::Exited(0) => SessionStatus::Completed,
+                    RunStatus::Exited(_) => SessionStatus::Failed,
+                    RunStatus::Running | RunStatus::Killed => SessionStatus::Interrupted,
+                };
+                self.sessions.finish(status);
+                if let Some(hooks) = run_hooks(&self.config.hooks, runner.scan(), status) {
+                    self.hooks = Some(hooks);
+                }
+                let monitor_run = self.monitor.as_ref().is_some_and(Monitor::is_running);
+                let queued_run = self.queue.as_ref().is_some_and(ScanQueue::is_running);
+                let xml = runner.scan().xml_output();
+                let history = paths::data_dir()
+                    .filter(|_| status == SessionStatus::Completed && !monitor_run)
+                    .map(|dir| SnapshotStore::new(dir.join("results").join("history")));
+                let history_limit = self.config.results.history_limit;
+                let baseline = paths::data_dir()
+                    .zip(self.profile.clone())
+                    .filter(|_| status == SessionStatus::Completed && !monitor_run && !queued_run)
+                    .map(|(dir, profile)| {
+                        let store = BaselineStore::new(dir.join("results").join("baselines"));
+                        (store, profile)
+                    });
+                // Parsed once and compared with the baseline, off the UI thread, for everything
+                // that needs the results. The file is opened here so that a temporary one removed
+                // by the next run can still be read
+                let results = xml.as_deref().map(|path| {
+                    let file = File::open(path);
+                    let (sender, receiver) = mpsc::channel();
+                    thread::spawn(move || {
+                        let results = file.map_err(ResultsError::from).and_then(|file| {
+                            let xml = io::read_to_string(file)?;
+                            let results = results::parser::parse_xml(&xml)?;
+                            // Keep a copy so the results stay searchable after the file is
+                            // overwritten
+                            if let Some(history) = history {
+                                history.keep(&xml, &results, history_limit).ok();
+                            }
+                            Ok(results)
+                        });
+                        let baseline_changes = results.as_ref().ok().zip(baseline).and_then(
+                            |(results, (store, profile))| {
+                                Some(diff(&store.get(&profile)?, results))
+                            },
+                        );
+                        sender
+                            .send(ParsedRun {
+                                results,
+                                baseline_changes,
+                            })
+                            .ok()
+                    });
+                    receiver
+                });
+                let diagnoses = diagnose(runner.status(), runner.stderr());
+                self.finished_runs.push(FinishedRun {
+                    status,
+                    run_status: runner.status(),
+                    command: runner.command().to_string(),
+                    elapsed: runner.elapsed(),
+                    stats: runner.stats().clone(),
+                    xml,
+                    temp_xml: runner.temp_xml().is_some(),
+                    monitor_run,
+                    queued_run,
+                    diagnosed: !diagnoses.is_empty(),
+                    results,
+                });
+                if monitor_run {
+                    self.finish_monitor_run(status == SessionStatus::Completed);
+                }
+                if queued_run {
+                    self.finish_queued_scan(status == SessionStatus::Completed);
+                }
+                if !diagnoses.is_empty() {
+                    self.popup = Some(Popup::Failure(diagnoses));
+                }
+            }
+        }
+        self.poll_finished_runs();
+        self.poll_hooks();
+        self.run_queue();
+        self.run_monitor();
+    }
+
+    fn poll_hooks(&mut self) {
+        let Some(receiver) = &self.hooks else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(failed) => {
+                self.hooks = None;
+                if let Some(command) = failed.first() {
+                    self.set_status(format!("Failed to run hook: {}", command));
+                }
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.hooks = None,
+        }
+    }
+
+    /// Report the finished runs whose results are parsed, in the order they ended
+    fn poll_finished_runs(&mut self) {
+        while let Some(run) = self.finished_runs.first() {
+            let parsed = match &run.results {
+                Some(receiver) => match receiver.try_recv() {
+                    Ok(parsed) => Some(parsed),
+                    Err(TryRecvError::Empty) => return,
+                    Err(TryRecvError::Disconnected) => Some(ParsedRun {
+                        results: Err(ResultsError::InvalidXml(
+                            "the parser stopped unexpectedly".to_string(),
+                        )),
+                        baseline_changes: None,
+                    }),
+                },
+                None => None,
+            };
+            let run = self.finished_runs.remove(0);
+            self.report_run(run, parsed);
+        }
+    }
+
+    /// Record a finished run in the engagement, notify about it and show its results
+    fn report_run(&mut self, run: FinishedRun, parsed: Option<ParsedRun>) {
+        let completed = run.status == SessionStatus::Completed;
+        let (results, baseline_changes) = match parsed {
+            Some(parsed) => (Some(parsed.results), parsed.baseline_changes),
+            None => (None, None),
+        };
+        let parsed = results.as_ref().and_then(|result| result.as_ref().ok());
+        if let Some(engagement) = self.engagement.as_mut() {
+            engagement.record(completed, run.elapsed, &run.stats, parsed);
+            if let Some(dir) = &self.state_dir
+                && let Err(err) = engagement.save(&dir.join(ENGAGEMENT_FILE))
+            {
+                self.status = Some((
+                    format!("Failed to save the engagement clock: {}", err),
+                    Instant::now(),
+                ));
+            }
+        }
+        let notifications = &self.config.notifications;
+        if (!self.focused || notifications.when_focused)
+            && let Some(summary) = match run.run_status {
+                RunStatus::Exited(0) => Some("Scan completed"),
+                RunStatus::Exited(_) => Some("Scan failed"),
+                RunStatus::Running | RunStatus::Killed => None,
+            }
+        {
+            let mut body = format!(
+                "{}\nFinished after {}",
+                run.command,
+                format_duration(run.elapsed)
+            );
+            if let Some(changes) = &baseline_changes {
+                body.push('\n');
+                body.push_str(&baseline::summarize(changes));
+            }
+            notify(notifications, summary, &body);
+        }
+        if completed && self.config.webhook.url.is_some() && !run.monitor_run {
+            let event = WebhookEvent {
+                event: "scan_completed",
+                title: format!("Scan completed in {}", format_duration(run.elapsed)),
+                command: run.command.clone(),
+                results: parsed,
+                changes: baseline_changes.clone().unwrap_or_default(),
+            };
+            webhook::send(&self.config.webhook, &event);
+        }
+        if run.diagnosed {
+            return;
+        }
+        if let Some(xml) = &run.xml
+            && let Some(results) = results
+            && completed
+            && !run.monitor_run
+            && !run.queued_run
+        {
+            self.results.show(xml, results);
+            self.refresh_asn_owners();
+            if let Some(changes) = &baseline_changes {
+                self.set_status(baseline::summarize(changes));
+            } else if run.temp_xml {
+                self.set_status(
+                    "Results are kept until the next scan, press s in the results view to save \
+                     them"
+                        .to_string(),
+                );
+            }
+            if self.config.results.auto_switch
+                && self.view == View::Editor
+                && !self.config.layout.split_view
+            {
+                self.view = View::Results;
+            }
+        } else if self.view == View::Results || self.config.layout.split_view {
+            self.load_results();
+        }
+    }
+
     fn scroll_up(&mut self) {
         self.focused_section = self.focused_section.saturating_sub(1);
         self.scroll = self.scroll.saturating_sub(SECTIONS[self.focused_section].1);
@@ -0,0 +1,217 @@
+use ratatui::crossterm::event::KeyCode;
+use std::time::{Duration, Instant};
+
+/// Editor commands reached by a sequence of keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordAction {
+    FirstSection,
+    LastSection,
+    Results,
+    Monitor,
+    Engagement,
+    Run,
+    Profiles,
+    EditCommand,
+    Notes,
+    CopyCommand,
+    UdpEssentials,
+    Techniques,
+}
+
+/// A sequence of keys and the command it runs
+#[derive(Debug, Clone, Copy)]
+pub struct Chord {
+    pub keys: &'static [KeyCode],
+    pub action: ChordAction,
+    pub description: &'static str,
+}
+
+/// Chords of the editor. Space still toggles an option when no key follows it in time
+pub const CHORDS: &[Chord] = &[
+    Chord {
+        keys: &[KeyCode::Char('g'), KeyCode::Char('g')],
+        action: ChordAction::FirstSection,
+        description: "first section",
+    },
+    Chord {
+        keys: &[KeyCode::Char('g'), KeyCode::Char('e')],
+        action: ChordAction::LastSection,
+        description: "last section",
+    },
+    Chord {
+        keys: &[KeyCode::Char('g'), KeyCode::Char('r')],
+        action: ChordAction::Results,
+        description: "results",
+    },
+    Chord {
+        keys: &[KeyCode::Char('g'), KeyCode::Char('m')],
+        action: ChordAction::Monitor,
+        description: "monitor",
+    },
+    Chord {
+        keys: &[KeyCode::Char('g'), KeyCode::Char('s')],
+        action: ChordAction::Engagement,
+        description: "engagement stats",
+    },
+    Chord {
+        keys: &[KeyCode::Char(' '), KeyCode::Char('r')],
+        action: ChordAction::Run,
+        description: "run the scan",
+    },
+    Chord {
+        keys: &[KeyCode::Char(' '), KeyCode::Char('p')],
+        action: ChordAction::Profiles,
+        description: "profiles",
+    },
+    Chord {
+        keys: &[KeyCode::Char(' '), KeyCode::Char('e')],
+        action: ChordAction::EditCommand,
+        description: "edit the command",
+    },
+    Chord {
+        keys: &[KeyCode::Char(' '), KeyCode::Char('n')],
+        action: ChordAction::Notes,
+        description: "notes",
+    },
+    Chord {
+        keys: &[KeyCode::Char(' '), KeyCode::Char('y')],
+        action: ChordAction::CopyCommand,
+        description: "copy the command",
+    },
+    Chord {
+        keys: &[KeyCode::Char(' '), KeyCode::Char('u')],
+        action: ChordAction::UdpEssentials,
+        description: "UDP essentials",
+    },
+    Chord {
+        keys: &[KeyCode::Char(' '), KeyCode::Char('t')],
+        action: ChordAction::Techniques,
+        description: "scan techniques",
+    },
+];
+
+/// What a key did to the chord being typed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChordResult {
+    /// The keys so far start a chord; wait for the next one
+    Pending,
+    Matched(ChordAction),
+    /// Esc dropped the keys typed so far
+    Cancelled,
+    /// The keys start no chord, so they are handled one by one as usual
+    Unmatched(Vec<KeyCode>),
+}
+
+/// Keys of a chord typed so far, dropped when the next key takes too long
+#[derive(Debug, Clone)]
+pub struct ChordState {
+    chords: &'static [Chord],
+    timeout: Duration,
+    pending: Vec<KeyCode>,
+    since: Instant,
+}
+
+impl ChordState {
+    pub fn new(chords: &'static [Chord], timeout: Duration) -> Self {
+        Self {
+            chords,
+            timeout,
+            pending: Vec::new(),
+            since: Instant::now(),
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    pub fn pending(&self) -> &[KeyCode] {
+        &self.pending
+    }
+
+    pub fn press(&mut self, key: KeyCode) -> ChordResult {
+        if key == KeyCode::Esc && self.is_pending() {
+            self.pending.clear();
+            return ChordResult::Cancelled;
+        }
+        let mut keys = std::mem::take(&mut self.pending);
+        keys.push(key);
+        let mut starts_chord = false;
+        for chord in self.chords {
+            if chord.keys == keys.as_slice() {
+                return ChordResult::Matched(chord.action);
+            }
+            starts_chord |= chord.keys.starts_with(&keys);
+        }
+        if !starts_chord {
+            return ChordResult::Unmatched(keys);
+        }
+        self.pending = keys;
+        self.since = Instant::now();
+        ChordResult::Pending
+    }
+
+    /// Keys typed so far once the next key has taken too long, to be handled as usual
+    pub fn expire(&mut self) -> Option<Vec<KeyCode>> {
+        (self.is_pending() && self.since.elapsed() >= self.timeout)
+            .then(|| std::mem::take(&mut self.pending))
+    }
+
+    /// Keys that continue the chord being typed, with what each does
+    pub fn continuations(&self) -> Vec<(KeyCode, &'static str)> {
+        self.chords
+            .iter()
+            .filter(|chord| chord.keys.len() > self.pending.len())
+            .filter(|chord| chord.keys.starts_with(&self.pending))
+            .map(|chord| (chord.keys[self.pending.len()], chord.description))
+            .collect()
+    }
+}
+
+/// Name of a key as the hints show it
+pub fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        key => key.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chords() {
+        let mut chords = ChordState::new(CHORDS, Duration::from_secs(60));
+        assert_eq!(chords.press(KeyCode::Char('g')), ChordResult::Pending);
+        assert!(
+            chords
+                .continuations()
+                .contains(&(KeyCode::Char('g'), "first section"))
+        );
+        assert_eq!(
+            chords.press(KeyCode::Char('g')),
+            ChordResult::Matched(ChordAction::FirstSection)
+        );
+        assert!(!chords.is_pending());
+
+        chords.press(KeyCode::Char(' '));
+        assert_eq!(
+            chords.press(KeyCode::Char('j')),
+            ChordResult::Unmatched(vec![KeyCode::Char(' '), KeyCode::Char('j')])
+        );
+        assert_eq!(
+            chords.press(KeyCode::Char('q')),
+            ChordResult::Unmatched(vec![KeyCode::Char('q')])
+        );
+        chords.press(KeyCode::Char(' '));
+        assert_eq!(chords.press(KeyCode::Esc), ChordResult::Cancelled);
+        assert_eq!(chords.expire(), None);
+
+        let mut quick = ChordState::new(CHORDS, Duration::ZERO);
+        quick.press(KeyCode::Char(' '));
+        assert_eq!(quick.expire(), Some(vec![KeyCode::Char(' ')]));
+        assert_eq!(key_name(KeyCode::Char(' ')), "Space");
+    }
+}
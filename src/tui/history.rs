@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, io, path::Path};
+
+use crate::scan::{flags::NmapFlag, secrets};
+
+/// Submitted values kept per field
+const MAX_ENTRIES: usize = 50;
+
+/// Values previously submitted in each text field, oldest first, kept between sessions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputHistory {
+    fields: HashMap<NmapFlag, Vec<String>>,
+}
+
+impl InputHistory {
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn entries(&self, flag: NmapFlag) -> &[String] {
+        self.fields.get(&flag).map_or(&[], Vec::as_slice)
+    }
+
+    /// Add a submitted value as the newest entry, moving it up if it was already there. Secrets
+    /// typed into script arguments are kept as prompts instead
+    pub fn record(&mut self, flag: NmapFlag, value: &str) {
+        if value.trim().is_empty() {
+            return;
+        }
+        let value = &match flag {
+            NmapFlag::ScriptArgs => secrets::strip_args(value),
+            NmapFlag::FtpRelay => secrets::strip_userinfo(value),
+            _ => value.to_string(),
+        };
+        let entries = self.fields.entry(flag).or_default();
+        entries.retain(|entry| entry != value);
+        entries.push(value.to_string());
+        if entries.len() > MAX_ENTRIES {
+            entries.drain(..entries.len() - MAX_ENTRIES);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut history = InputHistory::default();
+        history.record(NmapFlag::Exclude, "10.0.0.1");
+        history.record(NmapFlag::Exclude, "10.0.0.0/8");
+        history.record(NmapFlag::Exclude, "10.0.0.1");
+        history.record(NmapFlag::Exclude, " ");
+        assert_eq!(
+            history.entries(NmapFlag::Exclude),
+            ["10.0.0.0/8", "10.0.0.1"]
+        );
+        assert!(history.entries(NmapFlag::Targets).is_empty());
+
+        history.record(NmapFlag::FtpRelay, "anon:hunter2@ftp.example.com:2121");
+        assert_eq!(
+            history.entries(NmapFlag::FtpRelay),
+            ["ftp.example.com:2121"]
+        );
+
+        for port in 0..60 {
+            history.record(NmapFlag::Targets, &port.to_string());
+        }
+        assert_eq!(history.entries(NmapFlag::Targets).len(), MAX_ENTRIES);
+        assert_eq!(history.entries(NmapFlag::Targets)[0], "10");
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("lazynmap-history-{}", std::process::id()))
+            .join("history.json");
+        let mut history = InputHistory::default();
+        history.record(NmapFlag::Targets, "10.0.0.0/24");
+        history.save(&path).unwrap();
+        let loaded = InputHistory::load(&path);
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+
+        assert_eq!(loaded, history);
+        assert_eq!(InputHistory::load(&path), InputHistory::default());
+    }
+}
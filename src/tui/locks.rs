@@ -0,0 +1,37 @@
+use std::{collections::HashSet, fs, io, path::Path};
+
+use crate::scan::flags::NmapFlag;
+
+/// Locked flags saved in a previous session, or none when the file is missing or unreadable
+pub fn load(path: &Path) -> HashSet<NmapFlag> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(flags: &HashSet<NmapFlag>, path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(flags)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("lazynmap-locks-{}", std::process::id()))
+            .join("locks.json");
+        let flags = HashSet::from([NmapFlag::Targets, NmapFlag::TimingTemplate]);
+        save(&flags, &path).unwrap();
+        let loaded = load(&path);
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+
+        assert_eq!(loaded, flags);
+        assert!(load(&path).is_empty());
+    }
+}
@@ -1,4 +1,11 @@
 pub mod app;
+pub mod chords;
+pub mod clipboard;
+pub mod history;
+pub mod locks;
+pub mod popups;
 pub mod sections;
+pub mod theme;
 pub mod utils;
+pub mod views;
 pub mod widgets;
@@ -0,0 +1,105 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Wrap},
+};
+
+use crate::tui::{
+    popups::{PopupAction, centered_rect},
+    widgets::text_input::EventResult,
+};
+
+/// Explains how nmap finds hosts on a local subnet by ARP, with the options that change it
+pub struct ArpPopup {
+    /// Interface and address of the subnet holding every target, if known
+    local: Option<(String, String)>,
+    disable_arp_ping: bool,
+    send_ip: bool,
+}
+
+impl ArpPopup {
+    pub fn new(local: Option<(String, String)>, disable_arp_ping: bool, send_ip: bool) -> Self {
+        Self {
+            local,
+            disable_arp_ping,
+            send_ip,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        match key.code {
+            KeyCode::Char('d') => self.disable_arp_ping = !self.disable_arp_ping,
+            KeyCode::Char('s') => self.send_ip = !self.send_ip,
+            KeyCode::Enter => {
+                return EventResult::Submit(PopupAction::Arp {
+                    disable_arp_ping: self.disable_arp_ping,
+                    send_ip: self.send_ip,
+                });
+            }
+            KeyCode::Esc => return EventResult::Cancel,
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = centered_rect(90, 15, frame.area());
+        let block = Block::bordered()
+            .title("ARP discovery")
+            .border_style(Style::default().fg(Color::Yellow));
+        frame.render_widget(Clear, area);
+
+        let hint = Style::default().fg(Color::DarkGray);
+        let checkbox = |checked: bool| if checked { "[x]" } else { "[ ]" };
+        let subnet = match &self.local {
+            Some((device, address)) => format!(
+                "All targets are on the subnet of {} ({}), which nmap scans over ethernet.",
+                device, address
+            ),
+            None => {
+                "Targets on a subnet of the scanning host are scanned over ethernet.".to_string()
+            }
+        };
+        let lines = vec![
+            Line::from(subnet),
+            Line::from(
+                "When run as root, nmap finds the hosts that are up with ARP requests (neighbor \
+                 discovery for IPv6) instead of the -P probes, which it ignores. ARP is fast and \
+                 every host answers it, even those whose firewall drops pings.",
+            ),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw(format!(
+                    "d {} --disable-arp-ping  ",
+                    checkbox(self.disable_arp_ping)
+                )),
+                Span::styled(
+                    "use the -P probes instead, e.g. to see what a firewall lets through or \
+                     when a proxy ARP device answers for every address",
+                    hint,
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw(format!("s {} --send-ip           ", checkbox(self.send_ip))),
+                Span::styled(
+                    "send raw IP packets rather than ethernet frames, which also skips ARP \
+                     discovery",
+                    hint,
+                ),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("Enter: apply  Esc: cancel", hint)),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(block),
+            area,
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use ratatui::{
+    Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Wrap},
+};
+
+use crate::tui::popups::centered_rect;
+
+/// The full service banner of a port, for banners too long for the port table
+pub struct BannerPopup {
+    port: String,
+    banner: String,
+}
+
+impl BannerPopup {
+    pub fn new(port: String, banner: String) -> Self {
+        Self { port, banner }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let width = 80;
+        // Border, blank line and hint around the wrapped banner
+        let height = (self.banner.chars().count() as u16).div_ceil(width - 2) + 4;
+        let area = centered_rect(width, height, frame.area());
+        let block = Block::bordered()
+            .title(format!("Banner of {}", self.port))
+            .border_style(Style::default().fg(Color::Yellow));
+        let lines = vec![
+            Line::from(self.banner.as_str()),
+            Line::default(),
+            Line::from(Span::styled(
+                "Esc: close",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(block),
+            area,
+        );
+    }
+}
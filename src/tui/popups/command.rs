@@ -0,0 +1,82 @@
+use ratatui::{
+    Frame,
+    crossterm::event::Event,
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Clear, Paragraph},
+};
+
+use crate::{
+    scan::{builder::NmapCommandBuilder, model::NmapScan, parser::NmapParser, secrets},
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::{text_area::TextArea, text_input::EventResult},
+    },
+};
+
+/// The whole nmap command as editable text, parsed back into the scan on save. Secret script
+/// arguments are shown masked and kept as they were unless retyped
+pub struct CommandPopup {
+    text: TextArea,
+    error: Option<String>,
+    original: NmapScan,
+}
+
+impl CommandPopup {
+    pub fn new(scan: &NmapScan) -> Self {
+        let mut text = TextArea::new().with_placeholder("nmap options and targets");
+        text.set_content(&NmapCommandBuilder::build(&secrets::masked(scan)));
+        Self {
+            text,
+            error: None,
+            original: scan.clone(),
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        match self.text.handle_event(event) {
+            // Shell line continuations are allowed, as in commands pasted from scripts
+            EventResult::Submit(command) => {
+                let command = command.replace("\\\n", " ");
+                match NmapParser::parse(&command) {
+                    Ok(scan) => EventResult::Submit(PopupAction::Command(
+                        Box::new(secrets::unmasked(&scan, &self.original)),
+                        NmapParser::legacy_flags(&command),
+                    )),
+                    Err(err) => {
+                        self.error = Some(err.to_string());
+                        EventResult::Consumed
+                    }
+                }
+            }
+            EventResult::Consumed => {
+                self.error = None;
+                EventResult::Consumed
+            }
+            EventResult::Ignored => EventResult::Ignored,
+            EventResult::Cancel => EventResult::Cancel,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(90, 12, frame.area());
+        let block = Block::bordered()
+            .title("Edit nmap command")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+        self.text.render(rows[0], frame.buffer_mut(), true);
+        let footer = match &self.error {
+            Some(error) => Line::styled(error.as_str(), Style::default().fg(Color::Red)),
+            None => Line::styled(
+                "Ctrl-s: apply  Esc: cancel",
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        frame.render_widget(Paragraph::new(footer), rows[1]);
+    }
+}
@@ -0,0 +1,188 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Wrap},
+};
+
+use crate::{
+    scan::{
+        discovery::{DiscoveryAnswers, DiscoveryStrategy, Icmp, Network, recommend},
+        model::{NmapScan, Privileges},
+    },
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::EventResult,
+    },
+};
+
+/// A question of the wizard, with a label and an explanation for each answer
+struct Question {
+    title: &'static str,
+    answers: &'static [(&'static str, &'static str)],
+}
+
+const QUESTIONS: [Question; 3] = [
+    Question {
+        title: "Are the targets on your local network?",
+        answers: &[
+            (
+                "Local network",
+                "Same subnet as this machine, with no router in between",
+            ),
+            ("Remote", "Reached through a router, a VPN or the internet"),
+        ],
+    },
+    Question {
+        title: "Do firewalls block ICMP (ping)?",
+        answers: &[
+            ("No", "Hosts answer ping, as most internal networks allow"),
+            (
+                "Yes",
+                "Common for internet-facing hosts and the Windows firewall",
+            ),
+            (
+                "Hosts answer nothing",
+                "Every probe is dropped, yet the hosts are known to be up",
+            ),
+        ],
+    },
+    Question {
+        title: "Will nmap run as root?",
+        answers: &[
+            ("Yes", "With sudo or as root: ARP, ICMP and raw TCP probes"),
+            (
+                "No",
+                "As a normal user: nmap can only try to connect to ports",
+            ),
+        ],
+    },
+];
+
+/// Asks about the network, firewall and privileges, then sets host discovery probes to match
+pub struct DiscoveryPopup {
+    /// Question being asked, the summary after the last one
+    step: usize,
+    choices: [usize; QUESTIONS.len()],
+}
+
+impl DiscoveryPopup {
+    pub fn new(scan: &NmapScan) -> Self {
+        Self {
+            step: 0,
+            choices: [
+                0,
+                0,
+                usize::from(scan.misc.privileges == Privileges::Unprivileged),
+            ],
+        }
+    }
+
+    fn strategy(&self) -> DiscoveryStrategy {
+        recommend(DiscoveryAnswers {
+            network: if self.choices[0] == 0 {
+                Network::Local
+            } else {
+                Network::Remote
+            },
+            icmp: match self.choices[1] {
+                0 => Icmp::Allowed,
+                1 => Icmp::Blocked,
+                _ => Icmp::Filtered,
+            },
+            privileged: self.choices[2] == 0,
+        })
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        match key.code {
+            KeyCode::Esc => return EventResult::Cancel,
+            KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') => {
+                self.step = self.step.saturating_sub(1);
+            }
+            KeyCode::Enter if self.step == QUESTIONS.len() => {
+                return EventResult::Submit(PopupAction::Discovery(Box::new(self.strategy())));
+            }
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => {
+                self.step = (self.step + 1).min(QUESTIONS.len());
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.step < QUESTIONS.len() => {
+                let count = QUESTIONS[self.step].answers.len();
+                self.choices[self.step] = (self.choices[self.step] + 1).min(count - 1);
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.step < QUESTIONS.len() => {
+                self.choices[self.step] = self.choices[self.step].saturating_sub(1);
+            }
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = centered_rect(80, 14, frame.area());
+        let block = Block::bordered()
+            .title(format!(
+                "Host discovery wizard ({}/{})",
+                (self.step + 1).min(QUESTIONS.len()),
+                QUESTIONS.len()
+            ))
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+        let hint = Style::default().fg(Color::DarkGray);
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+
+        let (lines, footer) = match QUESTIONS.get(self.step) {
+            Some(question) => {
+                let mut lines = vec![Line::styled(question.title, bold), Line::default()];
+                for (index, (label, explanation)) in question.answers.iter().enumerate() {
+                    let selected = index == self.choices[self.step];
+                    let style = if selected {
+                        Style::default().add_modifier(Modifier::REVERSED)
+                    } else {
+                        Style::default()
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled(format!(" {} ", label), style),
+                        Span::styled(format!("  {}", explanation), hint),
+                    ]));
+                }
+                (
+                    lines,
+                    "Up/Down: answer  Enter: next  Left: back  Esc: cancel",
+                )
+            }
+            None => {
+                let strategy = self.strategy();
+                let flags = match strategy.flags() {
+                    flags if flags.is_empty() => "none, nmap's defaults apply".to_string(),
+                    flags => flags,
+                };
+                let mut lines = vec![
+                    Line::from(vec![
+                        Span::styled("Discovery flags: ", bold),
+                        Span::raw(flags),
+                    ]),
+                    Line::default(),
+                ];
+                lines.extend(
+                    strategy
+                        .reasons
+                        .iter()
+                        .map(|reason| Line::from(format!("  {}", reason))),
+                );
+                (lines, "Enter: apply  Left: back  Esc: cancel")
+            }
+        };
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), rows[0]);
+        frame.render_widget(Paragraph::new(Line::styled(footer, hint)), rows[1]);
+    }
+}
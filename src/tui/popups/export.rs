@@ -0,0 +1,168 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    results::{
+        export::{ExportFormat, export},
+        model::ScanResults,
+    },
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::{CompletingInput, EventResult},
+    },
+};
+
+/// Writes scan results to a file in a format other tools can ingest
+pub struct ExportPopup {
+    results: ScanResults,
+    directory: PathBuf,
+    selected: usize,
+    path: Option<CompletingInput>,
+    error: Option<String>,
+}
+
+impl ExportPopup {
+    /// Create the popup, suggesting files next to `source`
+    pub fn new(results: ScanResults, source: Option<&Path>) -> Self {
+        Self {
+            results,
+            directory: source
+                .and_then(Path::parent)
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+            selected: 0,
+            path: None,
+            error: None,
+        }
+    }
+
+    /// Start with a format selected
+    pub fn with_format(mut self, format: ExportFormat) -> Self {
+        self.selected = ExportFormat::ALL
+            .iter()
+            .position(|candidate| *candidate == format)
+            .unwrap_or_default();
+        self
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        let Some(path) = self.path.as_mut() else {
+            let Event::Key(key) = event else {
+                return EventResult::Ignored;
+            };
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.selected = (self.selected + 1).min(ExportFormat::ALL.len() - 1);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.selected = self.selected.saturating_sub(1);
+                }
+                KeyCode::Enter => {
+                    let format = ExportFormat::ALL[self.selected];
+                    let mut input = CompletingInput::new().with_label("Export to");
+                    input.set_typed_value(self.directory.join(format.file_name()));
+                    self.path = Some(input);
+                }
+                KeyCode::Esc => return EventResult::Cancel,
+                _ => return EventResult::Ignored,
+            }
+            return EventResult::Consumed;
+        };
+
+        match path.handle_event(event) {
+            EventResult::Submit(path) => {
+                let format = ExportFormat::ALL[self.selected];
+                let lines = export(&self.results, format);
+                let mut content = lines.join("\n");
+                content.push('\n');
+                match fs::write(&path, content) {
+                    Ok(()) => EventResult::Submit(PopupAction::Status(format!(
+                        "Exported {} lines to {}",
+                        lines.len(),
+                        path.display()
+                    ))),
+                    Err(err) => {
+                        self.error = Some(format!("Failed to write {}: {}", path.display(), err));
+                        EventResult::Consumed
+                    }
+                }
+            }
+            EventResult::Cancel => {
+                self.path = None;
+                self.error = None;
+                EventResult::Consumed
+            }
+            EventResult::Consumed => {
+                self.error = None;
+                EventResult::Consumed
+            }
+            EventResult::Ignored => EventResult::Ignored,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(70, 11, frame.area());
+        let block = Block::bordered()
+            .title("Export results")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(ExportFormat::ALL.len() as u16),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+        let formats = ExportFormat::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, format)| {
+                let count = export(&self.results, *format).len();
+                let line = format!(" {} ({})", format.label(), count);
+                if index == self.selected {
+                    Line::from(line).style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    Line::from(line)
+                }
+            })
+            .collect::<Vec<_>>();
+        frame.render_widget(Paragraph::new(formats), chunks[0]);
+
+        let hint = Style::default().fg(Color::DarkGray);
+        let footer = if let Some(error) = &self.error {
+            Line::from(Span::styled(
+                error.as_str(),
+                Style::default().fg(Color::Red),
+            ))
+        } else if self.path.is_some() {
+            Line::from(Span::styled(
+                "Enter to write the file, Esc to go back",
+                hint,
+            ))
+        } else {
+            Line::from(Span::styled(
+                "j/k to choose a format, Enter to continue, Esc to close",
+                hint,
+            ))
+        };
+        frame.render_widget(Paragraph::new(footer), chunks[2]);
+
+        if let Some(path) = self.path.as_mut() {
+            path.render(chunks[1], frame.buffer_mut(), true, true);
+            path.render_dropdown_overlay(frame.buffer_mut());
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use ratatui::{
+    Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Wrap},
+};
+
+use std::path::PathBuf;
+
+use crate::{policy::Violation, scan::diagnostics::Diagnosis, tui::popups::centered_rect};
+
+pub fn render_failure_summary(diagnoses: &[Diagnosis], frame: &mut Frame) {
+    let problems = diagnoses.iter().map(|diagnosis| {
+        (
+            diagnosis.problem.as_str(),
+            Some(diagnosis.suggestion.as_str()),
+        )
+    });
+    render_problems("Scan failed", problems, "Press Esc to dismiss", frame);
+}
+
+/// A scan that failed to start for a reason that may pass, offering to start it again
+pub fn render_retry(diagnosis: &Diagnosis, frame: &mut Frame) {
+    let problems = std::iter::once((
+        diagnosis.problem.as_str(),
+        Some(diagnosis.suggestion.as_str()),
+    ));
+    render_problems(
+        "Scan failed to start",
+        problems,
+        "Press r to try again, Esc to dismiss",
+        frame,
+    );
+}
+
+pub fn render_policy_violations(violations: &[Violation], frame: &mut Frame) {
+    let problems = violations
+        .iter()
+        .map(|violation| (violation.message.as_str(), violation.reason.as_deref()));
+    render_problems("Blocked by policy", problems, "Press Esc to dismiss", frame);
+}
+
+pub fn render_out_of_scope(targets: &[String], scope: &str, frame: &mut Frame) {
+    let explanation = format!("not allowed by {}", scope);
+    let problems = targets
+        .iter()
+        .map(|target| (target.as_str(), Some(explanation.as_str())));
+    render_problems(
+        "Targets outside the project scope",
+        problems,
+        "Press o to scan them anyway until lazynmap exits, Esc to dismiss",
+        frame,
+    );
+}
+
+/// Output files an earlier run left behind, offering what to do with them before scanning
+pub fn render_existing_outputs(files: &[PathBuf], frame: &mut Frame) {
+    let names = files
+        .iter()
+        .map(|file| file.display().to_string())
+        .collect::<Vec<_>>();
+    let problems = names.iter().map(|name| (name.as_str(), None));
+    render_problems(
+        "Output files already exist",
+        problems,
+        "Press a to append, o to overwrite, t to add a timestamp, Esc to cancel",
+        frame,
+    );
+}
+
+fn render_problems<'a>(
+    title: &str,
+    problems: impl Iterator<Item = (&'a str, Option<&'a str>)>,
+    footer: &str,
+    frame: &mut Frame,
+) {
+    let mut lines = Vec::new();
+    for (problem, explanation) in problems {
+        lines.push(Line::from(Span::styled(
+            problem,
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        if let Some(explanation) = explanation {
+            lines.push(Line::from(Span::styled(
+                format!("  -> {}", explanation),
+                Style::default().fg(Color::Cyan),
+            )));
+        }
+        lines.push(Line::default());
+    }
+    lines.push(Line::from(Span::styled(
+        footer,
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let area = centered_rect(80, lines.len() as u16 + 2, frame.area());
+    let block = Block::bordered()
+        .title(title)
+        .border_style(Style::default().fg(Color::Red));
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(block),
+        area,
+    );
+}
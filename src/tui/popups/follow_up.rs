@@ -0,0 +1,144 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+
+use crate::{
+    results::{
+        follow_up::{ScriptSuggestion, ServiceKind},
+        model::{Host, Port},
+    },
+    scan::queue::{QueuedScan, script_rescan},
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::EventResult,
+    },
+};
+
+/// NSE scripts suggested for the service of a port, picked to build a drill-down scan of it
+pub struct FollowUpPopup {
+    address: String,
+    port: u16,
+    protocol: String,
+    scripts: Vec<(ServiceKind, ScriptSuggestion)>,
+    picked: Vec<bool>,
+    selected: usize,
+}
+
+impl FollowUpPopup {
+    /// Create the popup with every script suggested for the kinds of service picked
+    pub fn new(host: &Host, port: &Port, kinds: &[ServiceKind]) -> Self {
+        let scripts: Vec<(ServiceKind, ScriptSuggestion)> = kinds
+            .iter()
+            .flat_map(|kind| kind.scripts().iter().map(|script| (*kind, *script)))
+            .collect();
+        Self {
+            address: host.address().to_string(),
+            port: port.port,
+            protocol: port.protocol.clone(),
+            picked: vec![true; scripts.len()],
+            scripts,
+            selected: 0,
+        }
+    }
+
+    /// Scan of the port running the picked scripts, if any are
+    fn scan(&self) -> Option<QueuedScan> {
+        let scripts: Vec<&str> = self
+            .scripts
+            .iter()
+            .zip(&self.picked)
+            .filter(|(_, picked)| **picked)
+            .map(|((_, suggestion), _)| suggestion.script)
+            .collect();
+        (!scripts.is_empty())
+            .then(|| script_rescan(&self.address, self.port, &self.protocol, &scripts))
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        match key.code {
+            KeyCode::Esc => return EventResult::Cancel,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.scripts.len().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Char(' ') => {
+                if let Some(picked) = self.picked.get_mut(self.selected) {
+                    *picked = !*picked;
+                }
+            }
+            KeyCode::Char('a') => {
+                let all = self.picked.iter().all(|picked| *picked);
+                self.picked.fill(!all);
+            }
+            KeyCode::Enter => {
+                if let Some(rescan) = self.scan() {
+                    return EventResult::Submit(PopupAction::LoadScan(Box::new(rescan.scan)));
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(rescan) = self.scan() {
+                    return EventResult::Submit(PopupAction::Queue(Box::new(rescan)));
+                }
+            }
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let hint = Style::default().fg(Color::DarkGray);
+        let mut lines = Vec::new();
+        let mut kind = None;
+        for (index, ((script_kind, suggestion), picked)) in
+            self.scripts.iter().zip(&self.picked).enumerate()
+        {
+            if kind != Some(*script_kind) {
+                kind = Some(*script_kind);
+                lines.push(Line::styled(
+                    script_kind.as_str(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ));
+            }
+            let style = if index == self.selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(
+                        " [{}] {:<20}",
+                        if *picked { 'x' } else { ' ' },
+                        suggestion.script
+                    ),
+                    style,
+                ),
+                Span::styled(format!("  {}", suggestion.description), hint),
+            ]));
+        }
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            "Space: pick  a: all  Enter: edit the scan  r: queue it  Esc: cancel",
+            hint,
+        ));
+
+        let area = centered_rect(84, lines.len() as u16 + 2, frame.area());
+        let block = Block::bordered()
+            .title(format!(
+                "Follow-up scripts for {} {}/{}",
+                self.address, self.port, self.protocol
+            ))
+            .border_style(Style::default().fg(Color::Yellow));
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
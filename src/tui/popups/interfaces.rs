@@ -0,0 +1,199 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    scan::iflist::{IflistFetch, InterfaceList},
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::EventResult,
+    },
+};
+
+/// Shows the interfaces and routes of `nmap --iflist` and picks the interface for -e
+pub struct InterfacesPopup {
+    fetch: IflistFetch,
+    selected: usize,
+    /// Interface names found by nmap, until the app takes them for the -e dropdown
+    devices: Option<Vec<String>>,
+}
+
+impl InterfacesPopup {
+    /// Create the popup and start running `nmap --iflist`
+    pub fn new() -> Self {
+        Self {
+            fetch: IflistFetch::start(),
+            selected: 0,
+            devices: None,
+        }
+    }
+
+    /// Pick up the interface list once nmap has finished
+    pub fn tick(&mut self) {
+        if self.fetch.poll()
+            && let Some(Ok(list)) = self.fetch.result()
+        {
+            self.devices = Some(list.devices());
+        }
+    }
+
+    /// Interface names found by nmap, once, after it has finished
+    pub fn take_devices(&mut self) -> Option<Vec<String>> {
+        self.devices.take()
+    }
+
+    fn list(&self) -> Option<&InterfaceList> {
+        self.fetch.result().and_then(|result| result.as_ref().ok())
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        let count = self.list().map_or(0, |list| list.interfaces.len());
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => EventResult::Cancel,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(count.saturating_sub(1));
+                EventResult::Consumed
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                EventResult::Consumed
+            }
+            KeyCode::Enter => match self
+                .list()
+                .and_then(|list| list.interfaces.get(self.selected))
+            {
+                Some(interface) => {
+                    EventResult::Submit(PopupAction::Interface(interface.device.clone()))
+                }
+                None => EventResult::Consumed,
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = centered_rect(100, 28, frame.area());
+        let block = Block::bordered()
+            .title("Interfaces (nmap --iflist)")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+        let list = match self.fetch.result() {
+            None => {
+                let message = Span::styled(
+                    "Running nmap --iflist...",
+                    Style::default().fg(Color::DarkGray),
+                );
+                frame.render_widget(Paragraph::new(Line::from(message)), rows[0]);
+                return;
+            }
+            Some(Err(err)) => {
+                let message = Span::styled(err.as_str(), Style::default().fg(Color::Red));
+                frame.render_widget(Paragraph::new(Line::from(message)), rows[0]);
+                return;
+            }
+            Some(Ok(list)) => list,
+        };
+        let default_device = list.default_device();
+        let header_style = Style::default().add_modifier(Modifier::BOLD);
+
+        let interfaces = list.interfaces.iter().map(|interface| {
+            let route = if Some(interface.device.as_str()) == default_device {
+                "default route"
+            } else {
+                ""
+            };
+            let row = Row::new(vec![
+                interface.device.clone(),
+                interface.address.clone(),
+                interface.kind.clone(),
+                if interface.up { "up" } else { "down" }.to_string(),
+                interface.mac.clone().unwrap_or_default(),
+                route.to_string(),
+            ]);
+            if interface.up {
+                row
+            } else {
+                row.style(Style::default().fg(Color::DarkGray))
+            }
+        });
+        let table = Table::new(
+            interfaces,
+            [
+                Constraint::Length(12),
+                Constraint::Length(30),
+                Constraint::Length(10),
+                Constraint::Length(5),
+                Constraint::Length(18),
+                Constraint::Min(0),
+            ],
+        )
+        .header(Row::new(vec!["Device", "Address", "Type", "State", "MAC", ""]).style(header_style))
+        .block(Block::bordered().title("Interfaces"))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, rows[0], &mut state);
+
+        let routes = list.routes.iter().map(|route| {
+            Row::new(vec![
+                route.destination.clone(),
+                route.device.clone(),
+                route
+                    .metric
+                    .map(|metric| metric.to_string())
+                    .unwrap_or_default(),
+                route.gateway.clone().unwrap_or_default(),
+            ])
+        });
+        let table = Table::new(
+            routes,
+            [
+                Constraint::Length(30),
+                Constraint::Length(12),
+                Constraint::Length(8),
+                Constraint::Min(0),
+            ],
+        )
+        .header(Row::new(vec!["Destination", "Device", "Metric", "Gateway"]).style(header_style))
+        .block(Block::bordered().title("Routes"));
+        frame.render_widget(table, rows[1]);
+
+        let footer = match default_device {
+            Some(device) => format!(
+                "Without -e nmap uses {}.  j/k: select  Enter: use for -e  Esc: close",
+                device
+            ),
+            None => "j/k: select  Enter: use for -e  Esc: close".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                footer,
+                Style::default().fg(Color::DarkGray),
+            ))),
+            rows[2],
+        );
+    }
+}
+
+impl Default for InterfacesPopup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
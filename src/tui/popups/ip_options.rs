@@ -0,0 +1,158 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListState, Paragraph},
+};
+
+use crate::{
+    scan::ip_options::{IpOptions, MAX_ROUTE_HOPS},
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::{EventResult, StringParser, TextInput},
+    },
+};
+
+/// Builds --ip-options from a kind of option and, for source routing, a list of hops
+pub struct IpOptionsPopup {
+    /// Index into `IpOptions::KINDS`
+    kind: usize,
+    hops: TextInput<String>,
+    message: Option<String>,
+}
+
+impl IpOptionsPopup {
+    pub fn new(current: Option<&str>) -> Self {
+        let mut hops = TextInput::new(StringParser)
+            .with_label("Hops")
+            .with_placeholder(format!(
+                "Up to {} addresses separated by spaces",
+                MAX_ROUTE_HOPS
+            ));
+        let kind = match current.map(IpOptions::parse) {
+            Some(Ok(IpOptions::Timestamp)) => 1,
+            Some(Ok(IpOptions::RouteAndTimestamp)) => 2,
+            Some(Ok(IpOptions::LooseRoute(route))) => {
+                hops.set_content(join_hops(&route));
+                3
+            }
+            Some(Ok(IpOptions::StrictRoute(route))) => {
+                hops.set_content(join_hops(&route));
+                4
+            }
+            _ => 0,
+        };
+        Self {
+            kind,
+            hops,
+            message: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        self.message = None;
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Down => {
+                    self.kind = (self.kind + 1).min(IpOptions::KINDS.len() - 1);
+                    return EventResult::Consumed;
+                }
+                KeyCode::Up => {
+                    self.kind = self.kind.saturating_sub(1);
+                    return EventResult::Consumed;
+                }
+                KeyCode::Esc => return EventResult::Cancel,
+                _ if !IpOptions::kind_needs_hops(self.kind) => {
+                    return match key.code {
+                        KeyCode::Enter => self.submit(),
+                        _ => EventResult::Consumed,
+                    };
+                }
+                _ => {}
+            }
+        }
+
+        match self.hops.handle_event(event) {
+            EventResult::Submit(_) => self.submit(),
+            EventResult::Consumed => EventResult::Consumed,
+            EventResult::Ignored => EventResult::Ignored,
+            EventResult::Cancel => EventResult::Cancel,
+        }
+    }
+
+    /// The options as --ip-options takes them, or why they can't be built
+    fn options(&self) -> Result<IpOptions, String> {
+        let letter = &IpOptions::KINDS[self.kind][..1];
+        let hops = if IpOptions::kind_needs_hops(self.kind) {
+            self.hops.content().replace(',', " ")
+        } else {
+            String::new()
+        };
+        IpOptions::parse(&format!("{} {}", letter, hops))
+    }
+
+    fn submit(&mut self) -> EventResult<PopupAction> {
+        match self.options() {
+            Ok(options) => EventResult::Submit(PopupAction::IpOptions(options.to_string())),
+            Err(message) => {
+                self.message = Some(message);
+                EventResult::Consumed
+            }
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(60, 13, frame.area());
+        let block = Block::bordered()
+            .title("IP options (--ip-options)")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(IpOptions::KINDS.len() as u16),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+        let list = List::new(IpOptions::KINDS)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.kind));
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        let needs_hops = IpOptions::kind_needs_hops(self.kind);
+        self.hops.set_dimmed(!needs_hops);
+        self.hops
+            .render(chunks[1], frame.buffer_mut(), needs_hops, needs_hops);
+
+        let preview = match self.options() {
+            Ok(options) => Line::from(vec![
+                Span::styled("--ip-options ", Style::default().fg(Color::DarkGray)),
+                Span::raw(options.to_string()),
+            ]),
+            Err(_) => Line::default(),
+        };
+        frame.render_widget(Paragraph::new(preview), chunks[2]);
+
+        let footer = match &self.message {
+            Some(message) => Span::styled(message.as_str(), Style::default().fg(Color::Red)),
+            None => Span::styled(
+                "Up/Down: kind  Enter: use  Esc: cancel",
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        frame.render_widget(Paragraph::new(Line::from(footer)), chunks[3]);
+    }
+}
+
+fn join_hops(hops: &[std::net::Ipv4Addr]) -> String {
+    hops.iter()
+        .map(|hop| hop.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,313 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::Rect,
+};
+
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+use crate::{
+    policy::Violation,
+    profiles::Profile,
+    scan::{
+        diagnostics::Diagnosis,
+        discovery::DiscoveryStrategy,
+        flags::NmapFlag,
+        model::{NmapScan, ScanTechnique},
+        queue::QueuedScan,
+        rdns::HostnameMapping,
+    },
+    tui::{
+        popups::{
+            arp::ArpPopup,
+            banner::BannerPopup,
+            command::CommandPopup,
+            discovery::DiscoveryPopup,
+            export::ExportPopup,
+            failure::{
+                render_existing_outputs, render_failure_summary, render_out_of_scope,
+                render_policy_violations, render_retry,
+            },
+            follow_up::FollowUpPopup,
+            interfaces::InterfacesPopup,
+            ip_options::IpOptionsPopup,
+            notes::NotesPopup,
+            port_actions::PortActionsPopup,
+            profiles::ProfilesPopup,
+            rate_advisor::RateAdvisorPopup,
+            rdns::ReverseDnsPopup,
+            sample::SamplePopup,
+            save_results::SaveResultsPopup,
+            script::ScriptPopup,
+            script_args::ScriptArgsPopup,
+            script_help::ScriptHelpPopup,
+            search::SearchPopup,
+            secrets::SecretsPopup,
+            split::SplitPopup,
+            spoof_mac::SpoofMacPopup,
+            techniques::TechniquesPopup,
+        },
+        widgets::text_input::EventResult,
+    },
+};
+
+pub mod arp;
+pub mod banner;
+pub mod command;
+pub mod discovery;
+pub mod export;
+pub mod failure;
+pub mod follow_up;
+pub mod interfaces;
+pub mod ip_options;
+pub mod notes;
+pub mod port_actions;
+pub mod profiles;
+pub mod rate_advisor;
+pub mod rdns;
+pub mod sample;
+pub mod save_results;
+pub mod script;
+pub mod script_args;
+pub mod script_help;
+pub mod search;
+pub mod secrets;
+pub mod split;
+pub mod spoof_mac;
+pub mod techniques;
+pub mod which_key;
+
+/// Modal dialogs drawn on top of the main layout
+pub enum Popup {
+    Failure(Vec<Diagnosis>),
+    /// A scan that failed to start for a reason that may pass, and the scan to start again
+    Retry(Diagnosis, Box<NmapScan>),
+    PolicyViolation(Vec<Violation>),
+    /// Targets outside the project scope, and the file defining the scope
+    OutOfScope(Vec<String>, String),
+    /// Output files of the scan that already exist
+    ExistingOutputs(Vec<PathBuf>),
+    RateAdvisor(Box<RateAdvisorPopup>),
+    Export(Box<ExportPopup>),
+    SaveResults(Box<SaveResultsPopup>),
+    Secrets(Box<SecretsPopup>),
+    Profiles(Box<ProfilesPopup>),
+    ScriptHelp(Box<ScriptHelpPopup>),
+    Search(Box<SearchPopup>),
+    Banner(Box<BannerPopup>),
+    PortActions(Box<PortActionsPopup>),
+    FollowUp(Box<FollowUpPopup>),
+    Split(Box<SplitPopup>),
+    Sample(Box<SamplePopup>),
+    Script(Box<ScriptPopup>),
+    SpoofMac(Box<SpoofMacPopup>),
+    IpOptions(Box<IpOptionsPopup>),
+    ScriptArgs(Box<ScriptArgsPopup>),
+    Interfaces(Box<InterfacesPopup>),
+    Arp(Box<ArpPopup>),
+    Discovery(Box<DiscoveryPopup>),
+    ReverseDns(Box<ReverseDnsPopup>),
+    Notes(Box<NotesPopup>),
+    Command(Box<CommandPopup>),
+    Techniques(Box<TechniquesPopup>),
+}
+
+/// Follow-up for the app to perform when a popup is submitted
+pub enum PopupAction {
+    None,
+    Status(String),
+    LoadProfile(Box<Profile>),
+    Compare(Box<Profile>, Box<Profile>),
+    Monitor(Box<Profile>, Duration),
+    /// Run the profile once per target list file in the directory
+    Batch(Box<Profile>, PathBuf),
+    /// Split the targets of the current scan into this many queued scans, waiting between them
+    Split(u32, Duration),
+    /// Replace the targets with this many randomly chosen hosts
+    Sample(u32),
+    /// Set --spoof-mac to this value
+    SpoofMac(String),
+    /// Set --ip-options to this value
+    IpOptions(String),
+    /// Set --script-args to this value
+    ScriptArgs(String),
+    /// Set -e to this interface
+    Interface(String),
+    /// Set --disable-arp-ping and --send-ip
+    Arp {
+        disable_arp_ping: bool,
+        send_ip: bool,
+    },
+    /// Switch to this scan technique
+    Technique(ScanTechnique),
+    /// Replace the host discovery probes with those of the strategy
+    Discovery(Box<DiscoveryStrategy>),
+    /// Scan the hosts from a target list annotated with their names
+    AnnotateTargets(Vec<HostnameMapping>),
+    /// Replace the notes of the scan
+    Notes(String),
+    /// Replace the scan with one parsed from an edited command, and the deprecated options it
+    /// used with their current names
+    Command(Box<NmapScan>, Vec<(String, String)>),
+    /// Start a scan that failed to start again
+    Retry(Box<NmapScan>),
+    /// Allow targets outside the project scope until lazynmap exits
+    OverrideScope,
+    /// Start the scan, doing this with the output files that already exist
+    RunWithOutputs(ExistingOutputs),
+    /// Open the results in the file and select the host with the given index
+    ShowHost(PathBuf, usize),
+    /// Focus the field of a flag in the editor
+    FocusFlag(NmapFlag),
+    /// Copy the text to the clipboard, naming what it is in the status
+    Copy(String, &'static str),
+    /// Open the URL in the browser
+    OpenUrl(String),
+    /// Run the scan after the scans already queued
+    Queue(Box<QueuedScan>),
+    /// Replace the scan in the editor with a new one to adjust before running it
+    LoadScan(Box<NmapScan>),
+    /// Keep the secrets entered for script arguments until lazynmap exits, running the scan
+    /// in the editor when set
+    Secrets(HashMap<String, String>, bool),
+    /// Stop the queue and monitor waiting for secrets that were not entered
+    SkipSecrets,
+}
+
+/// What to do with output files an earlier run left behind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExistingOutputs {
+    /// Add to them with --append-output
+    Append,
+    Overwrite,
+    /// Write new files named with the time of the run
+    Timestamp,
+}
+
+impl Popup {
+    pub fn render(&mut self, frame: &mut Frame) {
+        match self {
+            Popup::Failure(diagnoses) => render_failure_summary(diagnoses, frame),
+            Popup::Retry(diagnosis, _) => render_retry(diagnosis, frame),
+            Popup::PolicyViolation(violations) => render_policy_violations(violations, frame),
+            Popup::OutOfScope(targets, scope) => render_out_of_scope(targets, scope, frame),
+            Popup::ExistingOutputs(files) => render_existing_outputs(files, frame),
+            Popup::RateAdvisor(popup) => popup.render(frame),
+            Popup::Export(popup) => popup.render(frame),
+            Popup::SaveResults(popup) => popup.render(frame),
+            Popup::Secrets(popup) => popup.render(frame),
+            Popup::PortActions(popup) => popup.render(frame),
+            Popup::FollowUp(popup) => popup.render(frame),
+            Popup::Profiles(popup) => popup.render(frame),
+            Popup::ScriptHelp(popup) => popup.render(frame),
+            Popup::Search(popup) => popup.render(frame),
+            Popup::Banner(popup) => popup.render(frame),
+            Popup::Split(popup) => popup.render(frame),
+            Popup::Sample(popup) => popup.render(frame),
+            Popup::Arp(popup) => popup.render(frame),
+            Popup::Script(popup) => popup.render(frame),
+            Popup::SpoofMac(popup) => popup.render(frame),
+            Popup::IpOptions(popup) => popup.render(frame),
+            Popup::ScriptArgs(popup) => popup.render(frame),
+            Popup::Interfaces(popup) => popup.render(frame),
+            Popup::Discovery(popup) => popup.render(frame),
+            Popup::ReverseDns(popup) => popup.render(frame),
+            Popup::Notes(popup) => popup.render(frame),
+            Popup::Command(popup) => popup.render(frame),
+            Popup::Techniques(popup) => popup.render(frame),
+        }
+    }
+
+    /// Update popups that wait on background work
+    pub fn tick(&mut self) {
+        match self {
+            Popup::ScriptHelp(popup) => popup.tick(),
+            Popup::Interfaces(popup) => popup.tick(),
+            Popup::ReverseDns(popup) => popup.tick(),
+            Popup::Search(popup) => popup.tick(),
+            _ => {}
+        }
+    }
+
+    /// Handle an event; `Submit` and `Cancel` both close the popup
+    pub fn handle_event(&mut self, event: &Event, scan: &mut NmapScan) -> EventResult<PopupAction> {
+        match self {
+            Popup::Failure(_) | Popup::PolicyViolation(_) | Popup::Banner(_) => match event {
+                Event::Key(key) if matches!(key.code, KeyCode::Esc | KeyCode::Enter) => {
+                    EventResult::Cancel
+                }
+                _ => EventResult::Ignored,
+            },
+            Popup::Retry(_, retry) => match event {
+                Event::Key(key) if key.code == KeyCode::Char('r') => {
+                    EventResult::Submit(PopupAction::Retry(retry.clone()))
+                }
+                Event::Key(key) if matches!(key.code, KeyCode::Esc | KeyCode::Enter) => {
+                    EventResult::Cancel
+                }
+                _ => EventResult::Ignored,
+            },
+            Popup::OutOfScope(..) => match event {
+                Event::Key(key) if key.code == KeyCode::Char('o') => {
+                    EventResult::Submit(PopupAction::OverrideScope)
+                }
+                Event::Key(key) if matches!(key.code, KeyCode::Esc | KeyCode::Enter) => {
+                    EventResult::Cancel
+                }
+                _ => EventResult::Ignored,
+            },
+            Popup::ExistingOutputs(_) => {
+                let Event::Key(key) = event else {
+                    return EventResult::Ignored;
+                };
+                let choice = match key.code {
+                    KeyCode::Char('a') => ExistingOutputs::Append,
+                    KeyCode::Char('o') => ExistingOutputs::Overwrite,
+                    KeyCode::Char('t') => ExistingOutputs::Timestamp,
+                    KeyCode::Esc => return EventResult::Cancel,
+                    _ => return EventResult::Ignored,
+                };
+                EventResult::Submit(PopupAction::RunWithOutputs(choice))
+            }
+            Popup::RateAdvisor(popup) => match popup.handle_event(event, scan) {
+                EventResult::Submit(()) => EventResult::Submit(PopupAction::None),
+                EventResult::Consumed => EventResult::Consumed,
+                EventResult::Ignored => EventResult::Ignored,
+                EventResult::Cancel => EventResult::Cancel,
+            },
+            Popup::Export(popup) => popup.handle_event(event),
+            Popup::SaveResults(popup) => popup.handle_event(event),
+            Popup::Secrets(popup) => popup.handle_event(event),
+            Popup::PortActions(popup) => popup.handle_event(event),
+            Popup::FollowUp(popup) => popup.handle_event(event),
+            Popup::Profiles(popup) => popup.handle_event(event, scan),
+            Popup::ScriptHelp(popup) => popup.handle_event(event),
+            Popup::Search(popup) => popup.handle_event(event),
+            Popup::Split(popup) => popup.handle_event(event),
+            Popup::Sample(popup) => popup.handle_event(event),
+            Popup::Arp(popup) => popup.handle_event(event),
+            Popup::Script(popup) => popup.handle_event(event),
+            Popup::SpoofMac(popup) => popup.handle_event(event),
+            Popup::IpOptions(popup) => popup.handle_event(event),
+            Popup::ScriptArgs(popup) => popup.handle_event(event),
+            Popup::Interfaces(popup) => popup.handle_event(event),
+            Popup::Discovery(popup) => popup.handle_event(event),
+            Popup::ReverseDns(popup) => popup.handle_event(event),
+            Popup::Notes(popup) => popup.handle_event(event),
+            Popup::Command(popup) => popup.handle_event(event),
+            Popup::Techniques(popup) => popup.handle_event(event),
+        }
+    }
+}
+
+/// Compute a rectangle of the given size centered in the area, clamped to fit
+pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -0,0 +1,56 @@
+use ratatui::{
+    Frame,
+    crossterm::event::Event,
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Clear, Paragraph},
+};
+
+use crate::tui::{
+    popups::{PopupAction, centered_rect},
+    widgets::{text_area::TextArea, text_input::EventResult},
+};
+
+/// Free-text notes of the scan, such as the engagement or ticket it belongs to
+pub struct NotesPopup {
+    text: TextArea,
+}
+
+impl NotesPopup {
+    pub fn new(notes: &str) -> Self {
+        let mut text =
+            TextArea::new().with_placeholder("Engagement, ticket number, anything worth keeping");
+        text.set_content(notes);
+        Self { text }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        match self.text.handle_event(event) {
+            EventResult::Submit(notes) => EventResult::Submit(PopupAction::Notes(notes)),
+            EventResult::Consumed => EventResult::Consumed,
+            EventResult::Ignored => EventResult::Ignored,
+            EventResult::Cancel => EventResult::Cancel,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(80, 16, frame.area());
+        let block = Block::bordered()
+            .title("Scan notes")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+        self.text.render(rows[0], frame.buffer_mut(), true);
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                "Saved with profiles and next to the XML results  Ctrl-s: save  Esc: cancel",
+                Style::default().fg(Color::DarkGray),
+            )),
+            rows[1],
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+
+use crate::{
+    results::{
+        export::{host_part, web_url},
+        model::{Host, Port},
+    },
+    scan::queue::{QueuedScan, port_rescan},
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::EventResult,
+    },
+};
+
+/// Quick follow-ups for an open port of the results
+pub struct PortActionsPopup {
+    /// The port as host:port, e.g. "[fe80::1]:80"
+    endpoint: String,
+    url: String,
+    ncat: String,
+    rescan: QueuedScan,
+}
+
+impl PortActionsPopup {
+    pub fn new(host: &Host, port: &Port) -> Self {
+        let endpoint = format!("{}:{}", host_part(host), port.port);
+        let url = web_url(host, port).unwrap_or_else(|| format!("http://{}", endpoint));
+        let mut ncat = String::from("ncat");
+        if port.protocol == "udp" {
+            ncat.push_str(" -u");
+        } else if url.starts_with("https") {
+            ncat.push_str(" --ssl");
+        }
+        ncat.push_str(&format!(" {} {}", host.address(), port.port));
+        Self {
+            endpoint,
+            url,
+            ncat,
+            rescan: port_rescan(host.address(), port.port, &port.protocol),
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        let action = match key.code {
+            KeyCode::Char('c') => PopupAction::Copy(self.endpoint.clone(), "host:port"),
+            KeyCode::Char('o') => PopupAction::OpenUrl(self.url.clone()),
+            KeyCode::Char('n') => PopupAction::Copy(self.ncat.clone(), "ncat command"),
+            KeyCode::Char('r') => PopupAction::Queue(Box::new(self.rescan.clone())),
+            KeyCode::Esc => return EventResult::Cancel,
+            _ => return EventResult::Ignored,
+        };
+        EventResult::Submit(action)
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let key = Style::default().fg(Color::Yellow);
+        let hint = Style::default().fg(Color::DarkGray);
+        let rescan = format!("Queue a version and script scan: {}", self.rescan.label);
+        let actions = [
+            ('c', format!("Copy {}", self.endpoint)),
+            ('o', format!("Open {}", self.url)),
+            ('n', format!("Copy {}", self.ncat)),
+            ('r', rescan),
+        ];
+        let mut lines: Vec<Line> = actions
+            .into_iter()
+            .map(|(letter, action)| {
+                Line::from(vec![
+                    Span::styled(format!(" {}  ", letter), key),
+                    Span::raw(action),
+                ])
+            })
+            .collect();
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(" Esc: close", hint)));
+
+        let area = centered_rect(70, lines.len() as u16 + 2, frame.area());
+        let block = Block::bordered()
+            .title(format!("Port {}", self.endpoint))
+            .border_style(Style::default().fg(Color::Yellow));
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
@@ -0,0 +1,334 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListState, Paragraph},
+};
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    paths,
+    profiles::ProfileStore,
+    scan::{builder::NmapCommandBuilder, macros::Macro, model::NmapScan},
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::{
+            DurationParser, EventResult, PathBufParser, StringParser, TextInput,
+        },
+    },
+};
+
+/// Lists saved profiles to load, save over, delete, compare, monitor or batch run
+pub struct ProfilesPopup {
+    store: ProfileStore,
+    recorded_macro: Option<Macro>,
+    selected: usize,
+    marked: Vec<usize>,
+    name: Option<TextInput<String>>,
+    interval: Option<TextInput<Duration>>,
+    batch_dir: Option<TextInput<PathBuf>>,
+    message: Option<String>,
+}
+
+impl ProfilesPopup {
+    /// Create the popup; the recorded macro, if any, can be applied to saved profiles
+    pub fn new(recorded_macro: Option<Macro>) -> Self {
+        Self {
+            store: ProfileStore::load(paths::data_dir().map(|dir| dir.join("profiles"))),
+            recorded_macro,
+            selected: 0,
+            marked: Vec::new(),
+            name: None,
+            interval: None,
+            batch_dir: None,
+            message: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event, scan: &mut NmapScan) -> EventResult<PopupAction> {
+        if let Some(name) = self.name.as_mut() {
+            return match name.handle_event(event) {
+                EventResult::Submit(name) => {
+                    let name = name.trim().to_string();
+                    self.message = Some(match self.store.save(&name, scan) {
+                        Ok(()) => format!("Saved profile {}", name),
+                        Err(err) => format!("Failed to save profile: {}", err),
+                    });
+                    self.name = None;
+                    self.marked.clear();
+                    if let Some(index) = self
+                        .store
+                        .profiles()
+                        .iter()
+                        .position(|profile| profile.name == name)
+                    {
+                        self.selected = index;
+                    }
+                    EventResult::Consumed
+                }
+                EventResult::Cancel => {
+                    self.name = None;
+                    EventResult::Consumed
+                }
+                EventResult::Consumed => EventResult::Consumed,
+                EventResult::Ignored => EventResult::Ignored,
+            };
+        }
+
+        if let Some(interval) = self.interval.as_mut() {
+            return match interval.handle_event(event) {
+                EventResult::Submit(interval) if interval.is_zero() => {
+                    self.message = Some("The interval must be longer than zero".to_string());
+                    EventResult::Consumed
+                }
+                EventResult::Submit(interval) => match self.store.profiles().get(self.selected) {
+                    Some(profile) => EventResult::Submit(PopupAction::Monitor(
+                        Box::new(profile.clone()),
+                        interval,
+                    )),
+                    None => {
+                        self.interval = None;
+                        EventResult::Consumed
+                    }
+                },
+                EventResult::Cancel => {
+                    self.interval = None;
+                    EventResult::Consumed
+                }
+                EventResult::Consumed => EventResult::Consumed,
+                EventResult::Ignored => EventResult::Ignored,
+            };
+        }
+
+        if let Some(batch_dir) = self.batch_dir.as_mut() {
+            return match batch_dir.handle_event(event) {
+                EventResult::Submit(dir) if !dir.is_dir() => {
+                    self.message = Some(format!("{} is not a directory", dir.display()));
+                    EventResult::Consumed
+                }
+                EventResult::Submit(dir) => match self.store.profiles().get(self.selected) {
+                    Some(profile) => {
+                        EventResult::Submit(PopupAction::Batch(Box::new(profile.clone()), dir))
+                    }
+                    None => {
+                        self.batch_dir = None;
+                        EventResult::Consumed
+                    }
+                },
+                EventResult::Cancel => {
+                    self.batch_dir = None;
+                    EventResult::Consumed
+                }
+                EventResult::Consumed => EventResult::Consumed,
+                EventResult::Ignored => EventResult::Ignored,
+            };
+        }
+
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        self.message = None;
+        let count = self.store.profiles().len();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                if let Some(profile) = self.store.profiles().get(self.selected) {
+                    return EventResult::Submit(PopupAction::LoadProfile(Box::new(
+                        profile.clone(),
+                    )));
+                }
+            }
+            KeyCode::Char('s') => {
+                let mut input = TextInput::new(StringParser)
+                    .with_label("Save as")
+                    .with_placeholder("Profile name");
+                if let Some(profile) = self.store.profiles().get(self.selected)
+                    && !profile.builtin
+                {
+                    input.set_typed_value(profile.name.clone());
+                }
+                self.name = Some(input);
+            }
+            KeyCode::Char('d') => {
+                if let Some(profile) = self.store.profiles().get(self.selected) {
+                    let name = profile.name.clone();
+                    self.message = Some(match self.store.delete(&name) {
+                        Ok(()) => format!("Deleted profile {}", name),
+                        Err(err) => format!("Failed to delete {}: {}", name, err),
+                    });
+                    self.marked.clear();
+                    self.selected = self
+                        .selected
+                        .min(self.store.profiles().len().saturating_sub(1));
+                }
+            }
+            KeyCode::Char('@') => {
+                self.message = Some(self.apply_macro());
+            }
+            KeyCode::Char('M') if count > 0 => {
+                self.interval = Some(
+                    TextInput::new(DurationParser)
+                        .with_label("Re-run every")
+                        .with_placeholder("e.g. 30m, 2h"),
+                );
+            }
+            KeyCode::Char('B') if count > 0 => {
+                self.batch_dir = Some(
+                    TextInput::new(PathBufParser)
+                        .with_label("Target list directory")
+                        .with_placeholder("One scan per file, e.g. targets/"),
+                );
+            }
+            KeyCode::Char(' ') => {
+                if let Some(position) = self.marked.iter().position(|&index| index == self.selected)
+                {
+                    self.marked.remove(position);
+                } else if count > 0 {
+                    self.marked.push(self.selected);
+                    if self.marked.len() > 2 {
+                        self.marked.remove(0);
+                    }
+                }
+            }
+            KeyCode::Char('c') => {
+                let mut indices = self.marked.clone();
+                if indices.len() == 1 && indices[0] != self.selected {
+                    indices.push(self.selected);
+                }
+                let profiles = self.store.profiles();
+                if let [left, right] = indices[..] {
+                    return EventResult::Submit(PopupAction::Compare(
+                        Box::new(profiles[left].clone()),
+                        Box::new(profiles[right].clone()),
+                    ));
+                }
+                self.message = Some("Mark two profiles with space to compare them".to_string());
+            }
+            KeyCode::Esc => return EventResult::Cancel,
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
+
+    /// Apply the recorded macro to the marked profiles, or the selected one, and save them
+    fn apply_macro(&mut self) -> String {
+        let Some(recorded) = &self.recorded_macro else {
+            return "No macro recorded, press m in the editor to record one".to_string();
+        };
+        let indices = if self.marked.is_empty() {
+            vec![self.selected]
+        } else {
+            self.marked.clone()
+        };
+        // Saving reorders the store, so look profiles up by name
+        let names: Vec<String> = indices
+            .iter()
+            .filter_map(|&index| self.store.profiles().get(index))
+            .map(|profile| profile.name.clone())
+            .collect();
+
+        let mut updated = Vec::new();
+        for name in names {
+            let Some(mut scan) = self.store.get(&name).map(|profile| profile.scan.clone()) else {
+                continue;
+            };
+            recorded.apply(&mut scan);
+            if let Err(err) = self.store.save(&name, &scan) {
+                return format!("Failed to save profile {}: {}", name, err);
+            }
+            updated.push(name);
+        }
+        self.marked.clear();
+        format!("Applied macro to {}", updated.join(", "))
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(90, 20, frame.area());
+        let block = Block::bordered()
+            .title("Profiles")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+        let items = self
+            .store
+            .profiles()
+            .iter()
+            .enumerate()
+            .map(|(index, profile)| {
+                let marker = if self.marked.contains(&index) {
+                    "* "
+                } else {
+                    "  "
+                };
+                let mut spans = vec![
+                    Span::styled(marker, Style::default().fg(Color::Yellow)),
+                    Span::styled(
+                        format!("{:<16}", profile.name),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(NmapCommandBuilder::build(&profile.scan)),
+                ];
+                if profile.builtin {
+                    spans.push(Span::styled(
+                        " (built-in)",
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect::<Vec<_>>();
+        let list =
+            List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        if let Some(name) = &self.name {
+            name.render(chunks[1], frame.buffer_mut(), true, true);
+        } else if let Some(interval) = &self.interval {
+            interval.render(chunks[1], frame.buffer_mut(), true, true);
+        } else if let Some(batch_dir) = &self.batch_dir {
+            batch_dir.render(chunks[1], frame.buffer_mut(), true, true);
+        }
+
+        let hint = Style::default().fg(Color::DarkGray);
+        let footer = match &self.message {
+            Some(message) => Line::from(Span::styled(
+                message.as_str(),
+                Style::default().fg(Color::Cyan),
+            )),
+            None if self.name.is_some() => Line::from(Span::styled(
+                "Enter to save the current options, Esc to cancel",
+                hint,
+            )),
+            None if self.interval.is_some() => Line::from(Span::styled(
+                "Enter to start monitoring the selected profile, Esc to cancel",
+                hint,
+            )),
+            None if self.batch_dir.is_some() => Line::from(Span::styled(
+                "Enter to run the selected profile against each file, Esc to cancel",
+                hint,
+            )),
+            None => Line::from(Span::styled(
+                "Enter: load  s: save current  d: delete  space: mark  c: compare marked  @: apply macro  M: monitor  B: batch  Esc: close",
+                hint,
+            )),
+        };
+        frame.render_widget(Paragraph::new(footer), chunks[2]);
+    }
+}
@@ -0,0 +1,145 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Wrap},
+};
+use std::time::Duration;
+
+use crate::{
+    scan::{
+        advisor::{RateAdvice, ScanEstimate, advise, estimate},
+        duration::format_duration,
+        model::NmapScan,
+    },
+    tui::{
+        popups::centered_rect,
+        widgets::text_input::{DurationParser, EventResult, TextInput},
+    },
+};
+
+/// Estimates scan duration and suggests rate settings for a time budget
+pub struct RateAdvisorPopup {
+    /// Estimate of the scan as it is, made once since counting an -iL file reads it
+    current: ScanEstimate,
+    budget: TextInput<Duration>,
+    advice: Option<RateAdvice>,
+}
+
+impl RateAdvisorPopup {
+    pub fn new(scan: &NmapScan) -> Self {
+        Self {
+            current: estimate(scan),
+            budget: TextInput::new(DurationParser)
+                .with_label("Time budget")
+                .with_placeholder("e.g. 30m, 2h"),
+            advice: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event, scan: &mut NmapScan) -> EventResult<()> {
+        if let (Event::Key(key), Some(advice)) = (event, &self.advice)
+            && key.code == KeyCode::Enter
+        {
+            if advice.min_rate.is_some() {
+                scan.timing.min_rate = advice.min_rate;
+            }
+            if advice.max_retries.is_some() {
+                scan.timing.max_retries = advice.max_retries;
+            }
+            return EventResult::Submit(());
+        }
+
+        match self.budget.handle_event(event) {
+            EventResult::Submit(budget) => {
+                self.advice = Some(advise(scan, budget));
+                EventResult::Consumed
+            }
+            EventResult::Consumed => {
+                self.advice = None;
+                EventResult::Consumed
+            }
+            EventResult::Cancel => EventResult::Cancel,
+            EventResult::Ignored => EventResult::Ignored,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(80, 18, frame.area());
+        let block = Block::bordered()
+            .title("Rate advisor")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+        let mut current = vec![Line::from("Current settings")];
+        current.extend(estimate_lines(&self.current));
+        frame.render_widget(Paragraph::new(current), chunks[0]);
+
+        self.budget
+            .render(chunks[1], frame.buffer_mut(), true, true);
+
+        let hint = Style::default().fg(Color::DarkGray);
+        let lines = match &self.advice {
+            None => vec![Line::from(Span::styled(
+                "Enter a budget and press Enter for suggestions, Esc to close",
+                hint,
+            ))],
+            Some(advice) if advice.min_rate.is_none() => vec![
+                Line::from("The scan already fits in this budget."),
+                Line::from(Span::styled("Press Enter or Esc to close", hint)),
+            ],
+            Some(advice) => {
+                let mut lines = vec![Line::from("Suggested settings")];
+                if let Some(min_rate) = advice.min_rate {
+                    lines.push(Line::from(format!("  --min-rate {}", min_rate)));
+                }
+                if let Some(max_retries) = advice.max_retries {
+                    lines.push(Line::from(format!("  --max-retries {}", max_retries)));
+                }
+                lines.extend(estimate_lines(&advice.estimate));
+                if let Some(warning) = &advice.warning {
+                    lines.push(Line::from(Span::styled(
+                        warning.as_str(),
+                        Style::default().fg(Color::Red),
+                    )));
+                }
+                lines.push(Line::from(Span::styled(
+                    "Press Enter to apply, Esc to close",
+                    hint,
+                )));
+                lines
+            }
+        };
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[2]);
+    }
+}
+
+fn estimate_lines(estimate: &ScanEstimate) -> Vec<Line<'static>> {
+    let duration = estimate.duration();
+    let duration = if duration == Duration::MAX {
+        "unknown".to_string()
+    } else {
+        format_duration(duration)
+    };
+    vec![
+        Line::from(format!(
+            "  {} hosts x {} ports, ~{} probes",
+            estimate.targets, estimate.ports, estimate.probes
+        )),
+        Line::from(format!(
+            "  ~{:.1} packets/s, estimated duration {}",
+            estimate.rate, duration
+        )),
+    ]
+}
@@ -0,0 +1,158 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    scan::{
+        model::NmapScan,
+        rdns::{HostnameMapping, ReverseDnsFetch},
+    },
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::{progress::Progress, text_input::EventResult},
+    },
+};
+
+/// Shows the names the targets resolve to, found by a list scan, without scanning them
+pub struct ReverseDnsPopup {
+    fetch: ReverseDnsFetch,
+    progress: Progress,
+    selected: usize,
+    /// Hide the addresses without a name
+    named_only: bool,
+}
+
+impl ReverseDnsPopup {
+    /// Create the popup and start the list scan of the targets
+    pub fn new(scan: &NmapScan) -> Self {
+        Self {
+            fetch: ReverseDnsFetch::start(scan),
+            progress: Progress::new(),
+            selected: 0,
+            named_only: false,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.fetch.poll();
+        self.progress.tick();
+    }
+
+    fn mappings(&self) -> &[HostnameMapping] {
+        match self.fetch.result() {
+            Some(Ok(mappings)) => mappings,
+            _ => &[],
+        }
+    }
+
+    fn shown(&self) -> Vec<&HostnameMapping> {
+        self.mappings()
+            .iter()
+            .filter(|mapping| !self.named_only || mapping.hostname.is_some())
+            .collect()
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        let count = self.shown().len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => EventResult::Cancel,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(count.saturating_sub(1));
+                EventResult::Consumed
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                EventResult::Consumed
+            }
+            KeyCode::Char('f') => {
+                self.named_only = !self.named_only;
+                self.selected = 0;
+                EventResult::Consumed
+            }
+            KeyCode::Enter if !self.mappings().is_empty() => {
+                EventResult::Submit(PopupAction::AnnotateTargets(self.mappings().to_vec()))
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = centered_rect(90, 30, frame.area());
+        let mappings = self.mappings();
+        let named = mappings
+            .iter()
+            .filter(|mapping| mapping.hostname.is_some())
+            .count();
+        let title = match self.fetch.result() {
+            Some(Ok(_)) => format!(
+                "Reverse DNS (nmap -sL): {} of {} hosts named",
+                named,
+                mappings.len()
+            ),
+            _ => "Reverse DNS (nmap -sL)".to_string(),
+        };
+        let block = Block::bordered()
+            .title(title)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+        match self.fetch.result() {
+            None => {
+                let status = Layout::vertical([Constraint::Length(1)]).split(rows[0]);
+                self.progress
+                    .render(frame, status[0], None, "Looking up the targets...");
+                return;
+            }
+            Some(Err(err)) => {
+                let message = Span::styled(err.as_str(), Style::default().fg(Color::Red));
+                frame.render_widget(Paragraph::new(Line::from(message)), rows[0]);
+                return;
+            }
+            Some(Ok(_)) => {}
+        }
+
+        let table_rows = self
+            .shown()
+            .into_iter()
+            .map(|mapping| match &mapping.hostname {
+                Some(hostname) => Row::new(vec![mapping.address.clone(), hostname.clone()]),
+                None => Row::new(vec![mapping.address.clone(), "-".to_string()])
+                    .style(Style::default().fg(Color::DarkGray)),
+            });
+        let table = Table::new(table_rows, [Constraint::Length(40), Constraint::Min(0)])
+            .header(
+                Row::new(vec!["Address", "Hostname"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, rows[0], &mut state);
+
+        let filter = if self.named_only {
+            "all hosts"
+        } else {
+            "named only"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                format!(
+                    "j/k: select  f: {}  Enter: scan from a target list annotated with the names  Esc: close",
+                    filter
+                ),
+                Style::default().fg(Color::DarkGray),
+            ))),
+            rows[1],
+        );
+    }
+}
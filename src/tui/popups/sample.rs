@@ -0,0 +1,64 @@
+use ratatui::{
+    Frame,
+    crossterm::event::Event,
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+
+use crate::tui::{
+    popups::{PopupAction, centered_rect},
+    widgets::text_input::{EventResult, IntParser, TextInput},
+};
+
+/// Asks how many hosts to keep when replacing the targets with a random sample
+pub struct SamplePopup {
+    total: u64,
+    count: TextInput<u32>,
+}
+
+impl SamplePopup {
+    /// Create the popup for a target list with the given number of hosts
+    pub fn new(total: u64) -> Self {
+        Self {
+            total,
+            count: TextInput::new(IntParser)
+                .with_label("Hosts")
+                .with_placeholder("Number of hosts to keep"),
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        match self.count.handle_event(event) {
+            EventResult::Submit(0) => EventResult::Consumed,
+            EventResult::Submit(count) => EventResult::Submit(PopupAction::Sample(count)),
+            EventResult::Consumed => EventResult::Consumed,
+            EventResult::Ignored => EventResult::Ignored,
+            EventResult::Cancel => EventResult::Cancel,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = centered_rect(60, 6, frame.area());
+        let block = Block::bordered()
+            .title("Sample targets")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([Constraint::Length(3), Constraint::Length(1)]).split(inner);
+        self.count.render(chunks[0], frame.buffer_mut(), true, true);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                format!(
+                    "Replace the targets with a random sample of their {} hosts  Esc: cancel",
+                    self.total
+                ),
+                Style::default().fg(Color::DarkGray),
+            ))),
+            chunks[1],
+        );
+    }
+}
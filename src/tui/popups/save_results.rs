@@ -0,0 +1,77 @@
+use ratatui::{
+    Frame,
+    crossterm::event::Event,
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+use std::{fs, path::PathBuf};
+
+use crate::tui::{
+    popups::{PopupAction, centered_rect},
+    widgets::text_input::{CompletingInput, EventResult},
+};
+
+/// Copies the XML results shown, such as those of a scan that wrote them to a temporary file
+pub struct SaveResultsPopup {
+    source: PathBuf,
+    path: CompletingInput,
+    error: Option<String>,
+}
+
+impl SaveResultsPopup {
+    /// Create the popup, suggesting `name` in the current directory
+    pub fn new(source: PathBuf, name: &str) -> Self {
+        let mut path = CompletingInput::new().with_label("Save to");
+        path.set_typed_value(PathBuf::from(name));
+        Self {
+            source,
+            path,
+            error: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        match self.path.handle_event(event) {
+            EventResult::Submit(path) => match fs::copy(&self.source, &path) {
+                Ok(_) => EventResult::Submit(PopupAction::Status(format!(
+                    "Saved results to {}",
+                    path.display()
+                ))),
+                Err(err) => {
+                    self.error = Some(format!("Failed to write {}: {}", path.display(), err));
+                    EventResult::Consumed
+                }
+            },
+            EventResult::Consumed => {
+                self.error = None;
+                EventResult::Consumed
+            }
+            EventResult::Ignored => EventResult::Ignored,
+            EventResult::Cancel => EventResult::Cancel,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(70, 6, frame.area());
+        let block = Block::bordered()
+            .title("Save results")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).split(inner);
+        let footer = match &self.error {
+            Some(error) => Span::styled(error.as_str(), Style::default().fg(Color::Red)),
+            None => Span::styled(
+                "Enter to write the XML file, Esc to cancel",
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        frame.render_widget(Paragraph::new(Line::from(footer)), chunks[1]);
+        self.path.render(chunks[0], frame.buffer_mut(), true, true);
+        self.path.render_dropdown_overlay(frame.buffer_mut());
+    }
+}
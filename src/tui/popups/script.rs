@@ -0,0 +1,80 @@
+use ratatui::{
+    Frame,
+    crossterm::event::Event,
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+use std::path::PathBuf;
+
+use crate::{
+    scan::{model::NmapScan, script::write_script},
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::{CompletingInput, EventResult},
+    },
+};
+
+/// Writes the current command to an executable shell script
+pub struct ScriptPopup {
+    scan: NmapScan,
+    path: CompletingInput,
+    error: Option<String>,
+}
+
+impl ScriptPopup {
+    pub fn new(scan: NmapScan) -> Self {
+        let mut path = CompletingInput::new().with_label("Export as script");
+        path.set_typed_value(PathBuf::from("nmap-scan.sh"));
+        Self {
+            scan,
+            path,
+            error: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        match self.path.handle_event(event) {
+            EventResult::Submit(path) => match write_script(&self.scan, &path) {
+                Ok(()) => EventResult::Submit(PopupAction::Status(format!(
+                    "Wrote the scan script to {}",
+                    path.display()
+                ))),
+                Err(err) => {
+                    self.error = Some(format!("Failed to write {}: {}", path.display(), err));
+                    EventResult::Consumed
+                }
+            },
+            EventResult::Consumed => {
+                self.error = None;
+                EventResult::Consumed
+            }
+            EventResult::Ignored => EventResult::Ignored,
+            EventResult::Cancel => EventResult::Cancel,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(70, 6, frame.area());
+        let block = Block::bordered()
+            .title("Export as script")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([Constraint::Length(3), Constraint::Length(1)]).split(inner);
+        let footer = match &self.error {
+            Some(error) => Span::styled(error.as_str(), Style::default().fg(Color::Red)),
+            None => Span::styled(
+                "Outputs get a timestamp per run. Enter: write  Esc: cancel",
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        frame.render_widget(Paragraph::new(Line::from(footer)), chunks[1]);
+
+        self.path.render(chunks[0], frame.buffer_mut(), true, true);
+        self.path.render_dropdown_overlay(frame.buffer_mut());
+    }
+}
@@ -0,0 +1,60 @@
+use ratatui::{
+    Frame,
+    crossterm::event::Event,
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Clear, Paragraph},
+};
+
+use crate::{
+    scan::script_args::{from_lines, to_lines},
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::{text_area::TextArea, text_input::EventResult},
+    },
+};
+
+/// Edits --script-args one key=value entry per line, for argument lists too long for one row
+pub struct ScriptArgsPopup {
+    text: TextArea,
+}
+
+impl ScriptArgsPopup {
+    pub fn new(args: Option<&str>) -> Self {
+        let mut text = TextArea::new().with_placeholder("key=value, one per line");
+        text.set_content(&to_lines(args.unwrap_or_default()));
+        Self { text }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        match self.text.handle_event(event) {
+            EventResult::Submit(text) => {
+                EventResult::Submit(PopupAction::ScriptArgs(from_lines(&text)))
+            }
+            EventResult::Consumed => EventResult::Consumed,
+            EventResult::Ignored => EventResult::Ignored,
+            EventResult::Cancel => EventResult::Cancel,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(80, 16, frame.area());
+        let block = Block::bordered()
+            .title("Script args")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+        self.text.render(rows[0], frame.buffer_mut(), true);
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                "Joined with commas for --script-args  Ctrl-s: save  Esc: cancel",
+                Style::default().fg(Color::DarkGray),
+            )),
+            rows[1],
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListState, Paragraph, Wrap},
+};
+use std::collections::HashMap;
+
+use crate::{
+    scan::script_help::ScriptHelp,
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::EventResult,
+    },
+};
+
+/// Shows `nmap --script-help` output for each entry of --script
+pub struct ScriptHelpPopup {
+    scripts: Vec<String>,
+    selected: usize,
+    help: HashMap<String, ScriptHelp>,
+    scroll: u16,
+}
+
+impl ScriptHelpPopup {
+    /// Create the popup and start fetching help for the selected script
+    pub fn new(scripts: Vec<String>, selected: usize) -> Self {
+        let mut popup = Self {
+            scripts,
+            selected,
+            help: HashMap::new(),
+            scroll: 0,
+        };
+        popup.fetch_selected();
+        popup
+    }
+
+    fn fetch_selected(&mut self) {
+        if let Some(script) = self.scripts.get(self.selected) {
+            self.help
+                .entry(script.clone())
+                .or_insert_with(|| ScriptHelp::fetch(script));
+        }
+        self.scroll = 0;
+    }
+
+    /// Pick up help text from nmap processes that have finished
+    pub fn tick(&mut self) {
+        for help in self.help.values_mut() {
+            help.poll();
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => EventResult::Cancel,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.scroll = self.scroll.saturating_add(1);
+                EventResult::Consumed
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.scroll = self.scroll.saturating_sub(1);
+                EventResult::Consumed
+            }
+            KeyCode::Tab if !self.scripts.is_empty() => {
+                self.selected = (self.selected + 1) % self.scripts.len();
+                self.fetch_selected();
+                EventResult::Consumed
+            }
+            KeyCode::BackTab if !self.scripts.is_empty() => {
+                self.selected = (self.selected + self.scripts.len() - 1) % self.scripts.len();
+                self.fetch_selected();
+                EventResult::Consumed
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(110, 30, frame.area());
+        let block = Block::bordered()
+            .title("Script help")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+        let columns =
+            Layout::horizontal([Constraint::Length(30), Constraint::Min(0)]).split(rows[0]);
+
+        let items = self
+            .scripts
+            .iter()
+            .map(|script| Line::from(script.as_str()))
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(Block::bordered().title("Scripts"))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, columns[0], &mut state);
+
+        let help = self
+            .scripts
+            .get(self.selected)
+            .and_then(|script| self.help.get(script))
+            .and_then(ScriptHelp::result);
+        let lines = match help {
+            None => vec![Line::from(Span::styled(
+                "Running nmap --script-help...",
+                Style::default().fg(Color::DarkGray),
+            ))],
+            Some(Ok(lines)) if lines.is_empty() => vec![Line::from("No matching scripts")],
+            Some(Ok(lines)) => lines.iter().map(|line| Line::from(line.as_str())).collect(),
+            Some(Err(err)) => vec![Line::from(Span::styled(
+                err.as_str(),
+                Style::default().fg(Color::Red),
+            ))],
+        };
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0))
+                .block(Block::bordered()),
+            columns[1],
+        );
+
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "j/k: scroll  Tab: next script  Esc: close",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            rows[1],
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListState, Paragraph},
+};
+use std::{
+    path::PathBuf,
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+use crate::{
+    paths,
+    results::{
+        model::ScanResults,
+        parser,
+        search::{SearchMatch, search},
+        snapshots::stored_scans,
+    },
+    scan::{duration::format_timestamp, flags::NmapFlag},
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::{EventResult, StringParser, TextInput},
+    },
+};
+
+/// Matches listed at most, so that a short query doesn't flood the list
+const MAX_MATCHES: usize = 500;
+
+/// A scan that can be searched, labelled with where it came from
+struct SearchedScan {
+    label: String,
+    path: PathBuf,
+    results: ScanResults,
+}
+
+/// Searches hosts, services and script output across the current and all stored scans, or
+/// finds the field of an nmap option typed as on the command line
+pub struct SearchPopup {
+    scans: Vec<SearchedScan>,
+    /// Scans still being parsed in the background
+    loading: Option<Receiver<Vec<SearchedScan>>>,
+    query: TextInput<String>,
+    /// Flag of the option typed, which Enter jumps to
+    flag: Option<NmapFlag>,
+    matches: Vec<(usize, SearchMatch)>,
+    selected: usize,
+}
+
+impl SearchPopup {
+    /// Start loading the stored scans in the background, plus the results of the current scan if
+    /// it isn't stored
+    pub fn new(current: Option<PathBuf>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || sender.send(load_scans(current)).ok());
+
+        Self {
+            scans: Vec::new(),
+            loading: Some(receiver),
+            query: TextInput::new(StringParser)
+                .with_label("Search")
+                .with_placeholder("Hostname, IP, banner, script output or an option like -sC"),
+            flag: None,
+            matches: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Down => {
+                    self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1));
+                    return EventResult::Consumed;
+                }
+                KeyCode::Up => {
+                    self.selected = self.selected.saturating_sub(1);
+                    return EventResult::Consumed;
+                }
+                _ => {}
+            }
+        }
+
+        match self.query.handle_event(event) {
+            EventResult::Submit(_) if let Some(flag) = self.flag => {
+                EventResult::Submit(PopupAction::FocusFlag(flag))
+            }
+            EventResult::Submit(_) => match self.matches.get(self.selected) {
+                Some((scan, found)) => EventResult::Submit(PopupAction::ShowHost(
+                    self.scans[*scan].path.clone(),
+                    found.host,
+                )),
+                None => EventResult::Consumed,
+            },
+            EventResult::Consumed => {
+                self.update_matches();
+                EventResult::Consumed
+            }
+            EventResult::Cancel => EventResult::Cancel,
+            EventResult::Ignored => EventResult::Ignored,
+        }
+    }
+
+    /// Take the scans once they are loaded, searching them for what was typed meanwhile
+    pub fn tick(&mut self) {
+        if let Some(loading) = &self.loading
+            && let Ok(scans) = loading.try_recv()
+        {
+            self.scans = scans;
+            self.loading = None;
+            self.update_matches();
+        }
+    }
+
+    fn update_matches(&mut self) {
+        let query = self.query.content();
+        self.flag = NmapFlag::from_option(query);
+        self.matches = self
+            .scans
+            .iter()
+            .enumerate()
+            .flat_map(|(index, scan)| {
+                search(&scan.results, query)
+                    .into_iter()
+                    .map(move |found| (index, found))
+            })
+            .take(MAX_MATCHES)
+            .collect();
+        self.selected = 0;
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = centered_rect(120, 30, frame.area());
+        let block = Block::bordered()
+            .title(match self.loading {
+                Some(_) => "Search (loading stored scans...)".to_string(),
+                None => format!("Search {} scans", self.scans.len()),
+            })
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let query = self.query.content().trim();
+        let option_typed = query.starts_with('-');
+        let chunks = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(option_typed.into()),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+        self.query.render(chunks[0], frame.buffer_mut(), true, true);
+
+        let label = Style::default().fg(Color::DarkGray);
+        if option_typed {
+            let line = match self.flag {
+                Some(flag) => Line::from(vec![
+                    Span::styled("Enter: jump to ", label),
+                    Span::styled(flag.to_string(), Style::default().fg(Color::Yellow)),
+                ]),
+                None => Line::from(Span::styled(
+                    format!("No field for {}, edit the command with e instead", query),
+                    label,
+                )),
+            };
+            frame.render_widget(Paragraph::new(line), chunks[1]);
+        }
+
+        let items = self
+            .matches
+            .iter()
+            .map(|(scan, found)| {
+                Line::from(vec![
+                    Span::styled(format!("{:<32}", self.scans[*scan].label), label),
+                    Span::styled(
+                        format!("{:<18}", found.address),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:<24}", found.field),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(found.text.as_str()),
+                ])
+            })
+            .collect::<Vec<_>>();
+        let list =
+            List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state =
+            ListState::default().with_selected((!self.matches.is_empty()).then_some(self.selected));
+        frame.render_stateful_widget(list, chunks[2], &mut state);
+
+        let footer = if self.matches.len() >= MAX_MATCHES {
+            format!(
+                "Showing the first {} matches  Up/Down: select  Enter: show host  Esc: close",
+                MAX_MATCHES
+            )
+        } else {
+            format!(
+                "{} matches  Up/Down: select  Enter: show host  Esc: close",
+                self.matches.len()
+            )
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(footer, label))),
+            chunks[3],
+        );
+    }
+}
+
+fn load_scans(current: Option<PathBuf>) -> Vec<SearchedScan> {
+    let stored = paths::data_dir()
+        .map(|dir| stored_scans(&dir.join("results")))
+        .unwrap_or_default();
+    let mut scans = Vec::new();
+    if let Some(path) = current
+        && let Ok(results) = parser::load(&path)
+        && !stored.iter().any(|scan| results.start == Some(scan.taken))
+    {
+        scans.push(SearchedScan {
+            label: "current".to_string(),
+            path,
+            results,
+        });
+    }
+    scans.extend(stored.into_iter().filter_map(|scan| {
+        Some(SearchedScan {
+            label: format!("{} {}", scan.store, format_timestamp(scan.taken)),
+            results: parser::load(&scan.path).ok()?,
+            path: scan.path,
+        })
+    }));
+    scans
+}
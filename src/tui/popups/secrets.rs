@@ -0,0 +1,104 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+use std::collections::HashMap;
+
+use crate::tui::{
+    popups::{PopupAction, centered_rect},
+    widgets::text_input::{EventResult, StringParser, TextInput},
+};
+
+/// Asks for the secret script arguments a scan reads from the environment or marks with ?
+pub struct SecretsPopup {
+    keys: Vec<String>,
+    values: HashMap<String, String>,
+    input: TextInput<String>,
+    /// Whether to run the scan in the editor once the secrets are entered, rather than leave
+    /// them to the queue or monitor waiting for them
+    run: bool,
+}
+
+impl SecretsPopup {
+    pub fn new(keys: Vec<String>, run: bool) -> Self {
+        let mut popup = Self {
+            keys,
+            values: HashMap::new(),
+            input: TextInput::new(StringParser).with_masked(true),
+            run,
+        };
+        popup.label_input();
+        popup
+    }
+
+    fn label_input(&mut self) {
+        if let Some(key) = self.keys.get(self.values.len()) {
+            self.input.set_label(key.clone());
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        if let Event::Key(key) = event
+            && key.code == KeyCode::Esc
+        {
+            return if self.run {
+                EventResult::Cancel
+            } else {
+                EventResult::Submit(PopupAction::SkipSecrets)
+            };
+        }
+        match self.input.handle_event(event) {
+            EventResult::Submit(value) => {
+                let Some(key) = self.keys.get(self.values.len()) else {
+                    return EventResult::Cancel;
+                };
+                self.values.insert(key.clone(), value);
+                if self.values.len() == self.keys.len() {
+                    return EventResult::Submit(PopupAction::Secrets(
+                        std::mem::take(&mut self.values),
+                        self.run,
+                    ));
+                }
+                self.input.clear();
+                self.label_input();
+                EventResult::Consumed
+            }
+            EventResult::Consumed => EventResult::Consumed,
+            EventResult::Ignored => EventResult::Ignored,
+            EventResult::Cancel => EventResult::Cancel,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = centered_rect(90, 6, frame.area());
+        let block = Block::bordered()
+            .title(format!(
+                "Script argument secrets ({}/{})",
+                (self.values.len() + 1).min(self.keys.len()),
+                self.keys.len()
+            ))
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).split(inner);
+        self.input.render(chunks[0], frame.buffer_mut(), true, true);
+        let hint = if self.run {
+            "Ctrl+R: show  Enter: next, kept until lazynmap exits  Esc: cancel the run"
+        } else {
+            "Ctrl+R: show  Enter: next, kept until lazynmap exits  Esc: stop the waiting scans"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                hint,
+                Style::default().fg(Color::DarkGray),
+            ))),
+            chunks[1],
+        );
+    }
+}
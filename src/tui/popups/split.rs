@@ -0,0 +1,111 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+use std::time::Duration;
+
+use crate::tui::{
+    popups::{PopupAction, centered_rect},
+    widgets::text_input::{DurationParser, EventResult, IntParser, TextInput},
+};
+
+/// Asks how many chunks to split the targets into and how long to wait between them
+pub struct SplitPopup {
+    chunks: TextInput<u32>,
+    delay: TextInput<Duration>,
+    /// Chunk count entered, waiting for the delay
+    count: Option<u32>,
+    message: Option<String>,
+}
+
+impl SplitPopup {
+    pub fn new() -> Self {
+        Self {
+            chunks: TextInput::new(IntParser)
+                .with_label("Chunks")
+                .with_placeholder("Number of scans to split the targets into"),
+            delay: TextInput::new(DurationParser)
+                .with_label("Wait between chunks")
+                .with_placeholder("e.g. 10m, empty to run them back to back"),
+            count: None,
+            message: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        self.message = None;
+        let Some(count) = self.count else {
+            return match self.chunks.handle_event(event) {
+                EventResult::Submit(count) if count < 2 => {
+                    self.message = Some("Split into at least 2 chunks".to_string());
+                    EventResult::Consumed
+                }
+                EventResult::Submit(count) => {
+                    self.count = Some(count);
+                    EventResult::Consumed
+                }
+                EventResult::Consumed => EventResult::Consumed,
+                EventResult::Ignored => EventResult::Ignored,
+                EventResult::Cancel => EventResult::Cancel,
+            };
+        };
+
+        if let Event::Key(key) = event
+            && key.code == KeyCode::Enter
+            && self.delay.content().trim().is_empty()
+        {
+            return EventResult::Submit(PopupAction::Split(count, Duration::ZERO));
+        }
+        match self.delay.handle_event(event) {
+            EventResult::Submit(delay) => EventResult::Submit(PopupAction::Split(count, delay)),
+            EventResult::Consumed => EventResult::Consumed,
+            EventResult::Ignored => EventResult::Ignored,
+            EventResult::Cancel => EventResult::Cancel,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = centered_rect(70, 9, frame.area());
+        let block = Block::bordered()
+            .title("Split targets into chunks")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+        let editing_delay = self.count.is_some();
+        self.chunks.render(
+            chunks[0],
+            frame.buffer_mut(),
+            !editing_delay,
+            !editing_delay,
+        );
+        self.delay
+            .render(chunks[1], frame.buffer_mut(), editing_delay, editing_delay);
+
+        let footer = match &self.message {
+            Some(message) => Span::styled(message.as_str(), Style::default().fg(Color::Red)),
+            None => Span::styled(
+                "Enter: next  Esc: cancel",
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        frame.render_widget(Paragraph::new(Line::from(footer)), chunks[2]);
+    }
+}
+
+impl Default for SplitPopup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,134 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListState, Paragraph},
+};
+
+use crate::{
+    results::oui::search_vendors,
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::{EventResult, StringParser, TextInput},
+    },
+};
+
+/// Value of --spoof-mac that makes nmap pick a random MAC
+const RANDOM_MAC: &str = "0";
+
+/// Picks a vendor whose prefix --spoof-mac should use, or a random MAC
+pub struct SpoofMacPopup {
+    query: TextInput<String>,
+    /// --spoof-mac values offered, with a description of each
+    choices: Vec<(&'static str, String)>,
+    selected: usize,
+}
+
+impl SpoofMacPopup {
+    pub fn new() -> Self {
+        let mut popup = Self {
+            query: TextInput::new(StringParser)
+                .with_label("Vendor")
+                .with_placeholder("e.g. Cisco or Apple"),
+            choices: Vec::new(),
+            selected: 0,
+        };
+        popup.update_choices();
+        popup
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Down => {
+                    self.selected = (self.selected + 1).min(self.choices.len().saturating_sub(1));
+                    return EventResult::Consumed;
+                }
+                KeyCode::Up => {
+                    self.selected = self.selected.saturating_sub(1);
+                    return EventResult::Consumed;
+                }
+                _ => {}
+            }
+        }
+
+        match self.query.handle_event(event) {
+            EventResult::Submit(_) => match self.choices.get(self.selected) {
+                Some((value, _)) => EventResult::Submit(PopupAction::SpoofMac(value.to_string())),
+                None => EventResult::Consumed,
+            },
+            EventResult::Consumed => {
+                self.update_choices();
+                EventResult::Consumed
+            }
+            EventResult::Cancel => EventResult::Cancel,
+            EventResult::Ignored => EventResult::Ignored,
+        }
+    }
+
+    fn update_choices(&mut self) {
+        let query = self.query.content().trim();
+        self.choices = Vec::new();
+        if query.is_empty() {
+            self.choices
+                .push((RANDOM_MAC, "Random MAC from any vendor".to_string()));
+        }
+        self.choices.extend(
+            search_vendors(query)
+                .into_iter()
+                .map(|(prefix, vendor)| (prefix, vendor.to_string())),
+        );
+        self.selected = 0;
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = centered_rect(60, 20, frame.area());
+        let block = Block::bordered()
+            .title("Spoof MAC (--spoof-mac)")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+        self.query.render(chunks[0], frame.buffer_mut(), true, true);
+
+        let items = self
+            .choices
+            .iter()
+            .map(|(value, description)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:<8}", value),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(description.as_str()),
+                ])
+            })
+            .collect::<Vec<_>>();
+        let list =
+            List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state =
+            ListState::default().with_selected((!self.choices.is_empty()).then_some(self.selected));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+
+        let footer = Span::styled(
+            "Up/Down: select  Enter: use prefix  Esc: close",
+            Style::default().fg(Color::DarkGray),
+        );
+        frame.render_widget(Paragraph::new(Line::from(footer)), chunks[2]);
+    }
+}
+
+impl Default for SpoofMacPopup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
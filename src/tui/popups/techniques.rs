@@ -0,0 +1,137 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Cell, Clear, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    scan::{
+        model::{NmapScan, Privileges, ScanTechnique},
+        techniques::{CAPABILITIES, Support, capabilities_index},
+    },
+    tui::{
+        popups::{PopupAction, centered_rect},
+        widgets::text_input::EventResult,
+    },
+};
+
+/// Compares what the scan techniques need and can tell, and switches to the one picked
+pub struct TechniquesPopup {
+    selected: usize,
+    /// Whether nmap is told to run without root, so raw packet scans would fail
+    unprivileged: bool,
+}
+
+impl TechniquesPopup {
+    pub fn new(scan: &NmapScan) -> Self {
+        Self {
+            selected: capabilities_index(&scan.scan_technique).unwrap_or_default(),
+            unprivileged: scan.misc.privileges == Privileges::Unprivileged,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<PopupAction> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => EventResult::Cancel,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(CAPABILITIES.len() - 1);
+                EventResult::Consumed
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                EventResult::Consumed
+            }
+            KeyCode::Enter => {
+                let row = &CAPABILITIES[self.selected];
+                EventResult::Submit(match row.technique {
+                    ScanTechnique::Idle(_) => PopupAction::Status(
+                        "-sI needs a zombie host, add it with Space e".to_string(),
+                    ),
+                    ScanTechnique::Ftp(_) => PopupAction::Status(
+                        "-b needs an FTP server, enter it in FTP bounce (-b)".to_string(),
+                    ),
+                    _ => PopupAction::Technique(row.technique.clone()),
+                })
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = centered_rect(116, 19, frame.area());
+        let block = Block::bordered()
+            .title("Scan techniques")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+        let support_cell = |support: Support| {
+            let color = match support {
+                Support::Yes => Color::Green,
+                Support::Partly => Color::Yellow,
+                Support::No => Color::Red,
+            };
+            Cell::from(support.to_string()).style(Style::default().fg(color))
+        };
+        let techniques = CAPABILITIES.iter().map(|row| {
+            let root = match (row.needs_root, self.unprivileged) {
+                (true, true) => Cell::from("yes").style(Style::default().fg(Color::Red)),
+                (true, false) => Cell::from("yes"),
+                (false, _) => Cell::from("no").style(Style::default().fg(Color::Green)),
+            };
+            Row::new(vec![
+                Cell::from(row.option),
+                Cell::from(row.name),
+                root,
+                support_cell(row.stateful_firewalls),
+                support_cell(row.open_vs_filtered),
+                Cell::from(row.note),
+            ])
+        });
+        let header = Row::new(vec![
+            "Option",
+            "Scan",
+            "Root",
+            "Stateful firewalls",
+            "Open vs filtered",
+            "",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+        let table = Table::new(
+            techniques,
+            [
+                Constraint::Length(6),
+                Constraint::Length(16),
+                Constraint::Length(5),
+                Constraint::Length(18),
+                Constraint::Length(16),
+                Constraint::Min(0),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, rows[0], &mut state);
+
+        let footer = if self.unprivileged {
+            "Red: needs root, which --unprivileged rules out.  j/k: select  Enter: use  Esc: close"
+        } else {
+            "j/k: select  Enter: use  Esc: close"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                footer,
+                Style::default().fg(Color::DarkGray),
+            ))),
+            rows[1],
+        );
+    }
+}
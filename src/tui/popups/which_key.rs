@@ -0,0 +1,57 @@
+use ratatui::{
+    Frame,
+    crossterm::event::KeyCode,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+
+use crate::tui::chords::key_name;
+
+const WIDTH: u16 = 36;
+
+/// Hint in the bottom right corner listing the keys that continue a chord
+pub fn render_which_key(
+    frame: &mut Frame,
+    pending: &[KeyCode],
+    continuations: &[(KeyCode, &'static str)],
+) {
+    let typed = pending
+        .iter()
+        .map(|key| key_name(*key))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let hint = Style::default().fg(Color::DarkGray);
+    let mut lines = continuations
+        .iter()
+        .map(|(key, description)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:>5} ", key_name(*key)),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(*description),
+            ])
+        })
+        .collect::<Vec<_>>();
+    lines.push(Line::from(Span::styled(
+        format!("  Esc cancel, wait for {} alone", typed),
+        hint,
+    )));
+
+    let area = frame.area();
+    let width = WIDTH.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let area = Rect {
+        x: area.right() - width,
+        y: area.bottom().saturating_sub(height + 3).max(area.y),
+        width,
+        height,
+    };
+    let block = Block::bordered()
+        .title(typed)
+        .border_style(Style::default().fg(Color::Yellow));
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
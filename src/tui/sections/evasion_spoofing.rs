@@ -0,0 +1,40 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Flex, Layout, Rect},
+};
+
+use crate::{scan::flags::NmapFlag, tui::app::App};
+
+pub fn render_evasion_spoofing(app: &mut App, frame: &mut Frame, area: Rect) {
+    let row_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+        ])
+        .split(area);
+
+    let flags = [
+        vec![NmapFlag::SourcePort, NmapFlag::SpoofMac],
+        vec![NmapFlag::IpOptions, NmapFlag::Interface],
+        vec![NmapFlag::Proxies, NmapFlag::Data],
+    ];
+
+    for (index, &chunk) in row_chunks.iter().enumerate() {
+        let row_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .flex(Flex::SpaceBetween)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunk);
+
+        for (index, flag) in app.shown(&flags[index]).into_iter().enumerate() {
+            app.input_map.get_mut(&flag).unwrap().render(
+                row_chunks[index],
+                frame.buffer_mut(),
+                app.focused_flag == flag,
+                app.editing_flag == Some(flag),
+            );
+        }
+    }
+}
@@ -31,14 +31,15 @@ pub fn render_host_discovery(app: &mut App, frame: &mut Frame, area: Rect) {
         ])
         .split(row_chunks[0]);
 
-    for (index, &flag) in [
-        NmapFlag::ListScan,
-        NmapFlag::PingScan,
-        NmapFlag::SkipPortScan,
-        NmapFlag::Traceroute,
-    ]
-    .iter()
-    .enumerate()
+    for (index, flag) in app
+        .shown(&[
+            NmapFlag::ListScan,
+            NmapFlag::PingScan,
+            NmapFlag::SkipPortScan,
+            NmapFlag::Traceroute,
+        ])
+        .into_iter()
+        .enumerate()
     {
         render_checkbox(app, flag, frame, row_0_col_chunks[index]);
     }
@@ -55,14 +56,15 @@ pub fn render_host_discovery(app: &mut App, frame: &mut Frame, area: Rect) {
         ])
         .split(row_chunks[1]);
 
-    for (index, &flag) in [
-        NmapFlag::SynDiscovery,
-        NmapFlag::AckDiscovery,
-        NmapFlag::UdpDiscovery,
-        NmapFlag::SctpDiscovery,
-    ]
-    .iter()
-    .enumerate()
+    for (index, flag) in app
+        .shown(&[
+            NmapFlag::SynDiscovery,
+            NmapFlag::AckDiscovery,
+            NmapFlag::UdpDiscovery,
+            NmapFlag::SctpDiscovery,
+        ])
+        .into_iter()
+        .enumerate()
     {
         app.input_map.get_mut(&flag).unwrap().render(
             row_1_col_chunks[index],
@@ -84,25 +86,28 @@ pub fn render_host_discovery(app: &mut App, frame: &mut Frame, area: Rect) {
         ])
         .split(row_chunks[2]);
 
-    for (index, &flag) in [
-        NmapFlag::IcmpEcho,
-        NmapFlag::IcmpTimestamp,
-        NmapFlag::IcmpNetmask,
-    ]
-    .iter()
-    .enumerate()
+    for (index, flag) in app
+        .shown(&[
+            NmapFlag::IcmpEcho,
+            NmapFlag::IcmpTimestamp,
+            NmapFlag::IcmpNetmask,
+        ])
+        .into_iter()
+        .enumerate()
     {
         render_checkbox(app, flag, frame, row_2_col_chunks[index]);
     }
-    app.input_map
-        .get_mut(&NmapFlag::IpProtocolPing)
-        .unwrap()
-        .render(
-            row_2_col_chunks[3],
-            frame.buffer_mut(),
-            app.focused_flag == NmapFlag::IpProtocolPing,
-            app.editing_flag == Some(NmapFlag::IpProtocolPing),
-        );
+    if app.shows(NmapFlag::IpProtocolPing) {
+        app.input_map
+            .get_mut(&NmapFlag::IpProtocolPing)
+            .unwrap()
+            .render(
+                row_2_col_chunks[3],
+                frame.buffer_mut(),
+                app.focused_flag == NmapFlag::IpProtocolPing,
+                app.editing_flag == Some(NmapFlag::IpProtocolPing),
+            );
+    }
 
     // Row 3
     let row_3_col_chunks = Layout::default()
@@ -111,28 +116,31 @@ pub fn render_host_discovery(app: &mut App, frame: &mut Frame, area: Rect) {
         .constraints([
             Constraint::Length(30),
             Constraint::Length(30),
-            Constraint::Length(30),
+            Constraint::Length(32),
             Constraint::Length(60),
         ])
         .split(row_chunks[3]);
-    for (index, &flag) in [
-        NmapFlag::SystemDns,
-        NmapFlag::NoResolve,
-        NmapFlag::AlwaysResolve,
-    ]
-    .iter()
-    .enumerate()
+    for (index, flag) in app
+        .shown(&[
+            NmapFlag::SystemDns,
+            NmapFlag::DnsResolution,
+            NmapFlag::DisableArpPing,
+        ])
+        .into_iter()
+        .enumerate()
     {
         render_checkbox(app, flag, frame, row_3_col_chunks[index]);
     }
 
-    app.input_map
-        .get_mut(&NmapFlag::DnsServers)
-        .unwrap()
-        .render(
-            row_3_col_chunks[3],
-            frame.buffer_mut(),
-            app.focused_flag == NmapFlag::DnsServers,
-            app.editing_flag == Some(NmapFlag::DnsServers),
-        );
+    if app.shows(NmapFlag::DnsServers) {
+        app.input_map
+            .get_mut(&NmapFlag::DnsServers)
+            .unwrap()
+            .render(
+                row_3_col_chunks[3],
+                frame.buffer_mut(),
+                app.focused_flag == NmapFlag::DnsServers,
+                app.editing_flag == Some(NmapFlag::DnsServers),
+            );
+    }
 }
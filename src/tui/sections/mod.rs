@@ -1,3 +1,211 @@
+pub mod evasion_spoofing;
 pub mod host_discovery;
+pub mod output;
+pub mod port_specification;
+pub mod scan_technique;
+pub mod script_scan;
 pub mod target_specification;
 pub mod timing;
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use ratatui::{
+        Frame, Terminal,
+        backend::TestBackend,
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        layout::Rect,
+    };
+
+    use super::{
+        evasion_spoofing::render_evasion_spoofing, host_discovery::render_host_discovery,
+        output::render_output_options, port_specification::render_port_specification,
+        scan_technique::render_scan_technique, script_scan::render_script_scan,
+        target_specification::render_target_specification, timing::render_timing,
+    };
+    use crate::{
+        config::Config,
+        policy::Policy,
+        scan::{
+            flags::NmapFlag,
+            model::{NmapScan, TimingTemplate},
+            services::PortRatios,
+        },
+        scope::Scope,
+        tui::app::App,
+    };
+
+    /// Width of the options pane on a 160 column terminal
+    const WIDTH: u16 = 130;
+
+    type Section = fn(&mut App, &mut Frame, Rect);
+
+    /// Render a section of an app without state, after `setup`, with the text and styles of
+    /// every cell
+    fn render(section: Section, height: u16, setup: impl FnOnce(&mut App)) -> String {
+        let mut scan = NmapScan::new();
+        let mut app = App::with_state_dir(
+            &mut scan,
+            Config::default(),
+            Policy::default(),
+            Scope::default(),
+            None,
+        );
+        setup(&mut app);
+        let mut terminal = Terminal::new(TestBackend::new(WIDTH, height)).unwrap();
+        terminal
+            .draw(|frame| section(&mut app, frame, frame.area()))
+            .unwrap();
+        format!("{:?}", terminal.backend().buffer())
+    }
+
+    /// Focus a field and type into it, as if editing it
+    fn type_into(app: &mut App, flag: NmapFlag, text: &str) {
+        app.focused_flag = flag;
+        app.editing_flag = Some(flag);
+        let input = app.input_map.get_mut(&flag).unwrap();
+        for c in text.chars() {
+            input.handle_event(&Event::Key(KeyEvent::new(
+                KeyCode::Char(c),
+                KeyModifiers::NONE,
+            )));
+        }
+    }
+
+    #[test]
+    fn test_target_specification() {
+        let section: Section = render_target_specification;
+        assert_snapshot!("target_specification", render(section, 9, |_| {}));
+        assert_snapshot!(
+            "target_specification_focused",
+            render(section, 9, |app| app.focused_flag = NmapFlag::Exclude)
+        );
+        assert_snapshot!(
+            "target_specification_editing",
+            render(section, 9, |app| type_into(
+                app,
+                NmapFlag::Targets,
+                "10.0.0.0/24"
+            ))
+        );
+        assert_snapshot!(
+            "target_specification_error",
+            render(section, 9, |app| type_into(
+                app,
+                NmapFlag::RandomTargets,
+                "8x"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_host_discovery() {
+        let section: Section = render_host_discovery;
+        assert_snapshot!("host_discovery", render(section, 9, |_| {}));
+        // Probes are dimmed while -Pn turns host discovery off
+        assert_snapshot!(
+            "host_discovery_focused",
+            render(section, 9, |app| {
+                app.scan.host_discovery.skip_port_scan = true;
+                app.focused_flag = NmapFlag::SkipPortScan;
+            })
+        );
+    }
+
+    #[test]
+    fn test_scan_technique() {
+        let section: Section = render_scan_technique;
+        assert_snapshot!("scan_technique", render(section, 8, |_| {}));
+        assert_snapshot!(
+            "scan_technique_focused",
+            render(section, 8, |app| {
+                app.focused_flag = NmapFlag::Scanflags;
+                app.focused_radio_index = Some(1);
+            })
+        );
+    }
+
+    #[test]
+    fn test_port_specification() {
+        let section: Section = render_port_specification;
+        assert_snapshot!(
+            "port_specification",
+            render(section, 3, |app| {
+                app.port_ratios = Some(PortRatios::parse(
+                    "http\t80/tcp\t0.484143\nssh\t22/tcp\t0.182286\nident\t113/tcp\t0.003\n",
+                ));
+                type_into(app, NmapFlag::PortRatio, "0.1");
+            })
+        );
+        assert_snapshot!(
+            "port_specification_error",
+            render(section, 3, |app| type_into(app, NmapFlag::PortRatio, "1.5"))
+        );
+    }
+
+    #[test]
+    fn test_script_scan() {
+        let section: Section = render_script_scan;
+        assert_snapshot!("script_scan", render(section, 8, |_| {}));
+        assert_snapshot!(
+            "script_scan_editing",
+            render(section, 8, |app| type_into(app, NmapFlag::Scripts, "vuln"))
+        );
+    }
+
+    #[test]
+    fn test_timing() {
+        let section: Section = render_timing;
+        assert_snapshot!("timing", render(section, 8, |_| {}));
+        assert_snapshot!(
+            "timing_focused",
+            render(section, 8, |app| {
+                app.scan.timing.template = Some(TimingTemplate::Aggressive);
+                app.focused_flag = NmapFlag::TimingTemplate;
+                app.focused_radio_index = Some(2);
+            })
+        );
+    }
+
+    #[test]
+    fn test_output_options() {
+        let section: Section = render_output_options;
+        assert_snapshot!(
+            "output_options",
+            render(section, 5, |app| {
+                app.scan.output.verbose = 2;
+                app.focused_flag = NmapFlag::DebugLevel;
+            })
+        );
+        assert_snapshot!(
+            "output_options_error",
+            render(section, 5, |app| type_into(app, NmapFlag::StatsEvery, "5x"))
+        );
+    }
+
+    #[test]
+    fn test_evasion_spoofing() {
+        let section: Section = render_evasion_spoofing;
+        assert_snapshot!("evasion_spoofing", render(section, 9, |_| {}));
+        assert_snapshot!(
+            "evasion_spoofing_error",
+            render(section, 9, |app| type_into(app, NmapFlag::SourcePort, "-1"))
+        );
+        assert_snapshot!(
+            "evasion_spoofing_proxies_error",
+            render(section, 9, |app| type_into(
+                app,
+                NmapFlag::Proxies,
+                "socks5://10.0.0.1:1080"
+            ))
+        );
+        assert_snapshot!(
+            "evasion_spoofing_payload",
+            render(section, 9, |app| type_into(
+                app,
+                NmapFlag::Data,
+                "47 45 54 0d 0a"
+            ))
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Flex, Layout, Rect},
+};
+
+use crate::{
+    scan::flags::NmapFlag,
+    tui::{app::App, utils::render_stepper},
+};
+
+pub fn render_output_options(app: &mut App, frame: &mut Frame, area: Rect) {
+    let row_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+        ])
+        .split(area);
+
+    // Row 0
+    let row_0_col_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .flex(Flex::SpaceBetween)
+        .constraints([
+            Constraint::Length(30),
+            Constraint::Length(30),
+            Constraint::Length(30),
+            Constraint::Length(30),
+        ])
+        .split(row_chunks[0]);
+    for (index, flag) in app
+        .shown(&[NmapFlag::Verbosity, NmapFlag::DebugLevel])
+        .into_iter()
+        .enumerate()
+    {
+        render_stepper(app, flag, frame, row_0_col_chunks[index]);
+    }
+
+    // Row 2
+    let row_2_col_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .flex(Flex::SpaceBetween)
+        .constraints([Constraint::Length(60), Constraint::Length(60)])
+        .split(row_chunks[2]);
+    if app.shows(NmapFlag::StatsEvery) {
+        app.input_map
+            .get_mut(&NmapFlag::StatsEvery)
+            .unwrap()
+            .render(
+                row_2_col_chunks[0],
+                frame.buffer_mut(),
+                app.focused_flag == NmapFlag::StatsEvery,
+                app.editing_flag == Some(NmapFlag::StatsEvery),
+            );
+    }
+}
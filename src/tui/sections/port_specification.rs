@@ -0,0 +1,49 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Flex, Layout, Rect},
+};
+
+use crate::{
+    scan::{flags::NmapFlag, model::ScanTechnique},
+    tui::{
+        app::App,
+        widgets::text_input::{InputWidget, Parser, RatioParser},
+    },
+};
+
+pub fn render_port_specification(app: &mut App, frame: &mut Frame, area: Rect) {
+    let row_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3)])
+        .split(area);
+
+    // Row 0
+    let row_0_col_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .flex(Flex::SpaceBetween)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(row_chunks[0]);
+
+    // How many ports the typed ratio picks, counted in the services nmap would read
+    let udp = matches!(app.scan.scan_technique, ScanTechnique::Udp);
+    if let Some(InputWidget::Float(input)) = app.input_map.get_mut(&NmapFlag::PortRatio) {
+        let hint = app
+            .port_ratios
+            .as_ref()
+            .zip(RatioParser.parse(input.content()).ok())
+            .map(|(ratios, ratio)| {
+                let protocol = if udp { "UDP" } else { "TCP" };
+                format!("~{} {} ports", ratios.count(ratio, udp), protocol)
+            });
+        input.set_hint(hint);
+    }
+
+    for (index, flag) in app.shown(&[NmapFlag::PortRatio]).into_iter().enumerate() {
+        app.input_map.get_mut(&flag).unwrap().render(
+            row_0_col_chunks[index],
+            frame.buffer_mut(),
+            app.focused_flag == flag,
+            app.editing_flag == Some(flag),
+        );
+    }
+}
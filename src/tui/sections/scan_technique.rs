@@ -0,0 +1,74 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Flex, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+};
+
+use crate::{
+    scan::{flags::NmapFlag, model::ScanTechnique, tcp_flags::TcpFlags},
+    tui::{app::App, widgets::checkbox::Checkbox},
+};
+
+pub fn render_scan_technique(app: &mut App, frame: &mut Frame, area: Rect) {
+    if !app.shows(NmapFlag::Scanflags) {
+        frame.render_widget(
+            Line::from("Custom TCP flags and FTP bounce scans are expert options, press M")
+                .style(Style::default().fg(Color::DarkGray)),
+            area,
+        );
+        return;
+    }
+
+    let row_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+        ])
+        .split(area);
+
+    // Row 0: TCP flags composing --scanflags
+    let label = format!("{}: ", NmapFlag::Scanflags);
+    let mut constraints = vec![Constraint::Length(label.len() as u16)];
+    constraints.extend([Constraint::Length(10); TcpFlags::CHECKBOXES]);
+    let row_0_col_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .flex(Flex::Start)
+        .constraints(constraints)
+        .split(row_chunks[0]);
+    frame.render_widget(Line::from(label), row_0_col_chunks[0]);
+
+    let flags = match app.scan.scan_technique {
+        ScanTechnique::Scanflags(flags) => flags,
+        _ => TcpFlags::default(),
+    };
+    let focused_index = match (app.focused_flag, app.focused_radio_index) {
+        (NmapFlag::Scanflags, Some(index)) => Some(index),
+        _ => None,
+    };
+    for (index, (name, _)) in TcpFlags::NAMES
+        .iter()
+        .take(TcpFlags::CHECKBOXES)
+        .enumerate()
+    {
+        Checkbox::new(*name)
+            .with_checked(flags.contains(index))
+            .with_focused(focused_index == Some(index))
+            .render(row_0_col_chunks[index + 1], frame.buffer_mut());
+    }
+
+    // Row 2
+    let row_2_col_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(60)])
+        .split(row_chunks[2]);
+    let flag = NmapFlag::FtpRelay;
+    app.input_map.get_mut(&flag).unwrap().render(
+        row_2_col_chunks[0],
+        frame.buffer_mut(),
+        app.focused_flag == flag,
+        app.editing_flag == Some(flag),
+    );
+}
@@ -0,0 +1,88 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Flex, Layout, Rect},
+};
+
+use crate::{
+    scan::{
+        flags::NmapFlag,
+        script_args,
+        scripts::{CategorySelection, CategoryState, ScriptCategory},
+    },
+    tui::{
+        app::App,
+        utils::render_checkbox,
+        widgets::{checkbox::Checkbox, text_input::InputWidget},
+    },
+};
+
+/// Categories shown per row of the category grid
+const CATEGORY_COLUMNS: usize = 5;
+
+pub fn render_script_scan(app: &mut App, frame: &mut Frame, area: Rect) {
+    let row_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    // Row 0
+    let row_0_col_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(30)])
+        .split(row_chunks[0]);
+    render_checkbox(app, NmapFlag::ScriptDefault, frame, row_0_col_chunks[0]);
+
+    // Row 1
+    let row_1_col_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .flex(Flex::SpaceBetween)
+        .constraints([Constraint::Length(70), Constraint::Length(80)])
+        .split(row_chunks[1]);
+
+    let keys = script_args::args_for(&app.scan.script_scan.scripts);
+    if let Some(InputWidget::Args(input)) = app.input_map.get_mut(&NmapFlag::ScriptArgs) {
+        input.set_keys(keys.into_iter().map(String::from).collect());
+    }
+    for (index, &flag) in [NmapFlag::Scripts, NmapFlag::ScriptArgs].iter().enumerate() {
+        app.input_map.get_mut(&flag).unwrap().render(
+            row_1_col_chunks[index],
+            frame.buffer_mut(),
+            app.focused_flag == flag,
+            app.editing_flag == Some(flag),
+        );
+    }
+
+    // Rows 2-4: category grid, followed by the and/or toggle
+    let selection = CategorySelection::from_scripts(&app.scan.script_scan.scripts);
+    let focused_index = match (app.focused_flag, app.focused_radio_index) {
+        (NmapFlag::ScriptCategories, Some(index)) => Some(index),
+        _ => None,
+    };
+    for index in 0..CategorySelection::ITEMS {
+        let checkbox = match ScriptCategory::from_index(index) {
+            Some(category) => match selection.state(category) {
+                CategoryState::Off => Checkbox::new(category.to_string()),
+                CategoryState::Included => Checkbox::new(category.to_string()).with_checked(true),
+                CategoryState::Excluded => {
+                    Checkbox::new(format!("not {}", category)).with_checked(true)
+                }
+            },
+            None => Checkbox::new("match all (and)").with_checked(selection.match_all),
+        };
+
+        let row = Layout::default()
+            .direction(Direction::Horizontal)
+            .flex(Flex::Start)
+            .constraints([Constraint::Length(24); CATEGORY_COLUMNS])
+            .split(row_chunks[2 + index / CATEGORY_COLUMNS]);
+        checkbox
+            .with_focused(focused_index == Some(index))
+            .render(row[index % CATEGORY_COLUMNS], frame.buffer_mut());
+    }
+}
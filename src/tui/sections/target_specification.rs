@@ -1,6 +1,9 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Flex, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
 };
 
 use crate::{scan::flags::NmapFlag, tui::app::App};
@@ -28,7 +31,7 @@ pub fn render_target_specification(app: &mut App, frame: &mut Frame, area: Rect)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
             .split(chunk);
 
-        for (index, &flag) in flags[index].iter().enumerate() {
+        for (index, flag) in app.shown(&flags[index]).into_iter().enumerate() {
             app.input_map.get_mut(&flag).unwrap().render(
                 row_chunks[index],
                 frame.buffer_mut(),
@@ -36,5 +39,38 @@ pub fn render_target_specification(app: &mut App, frame: &mut Frame, area: Rect)
                 app.editing_flag == Some(flag),
             );
         }
+        if index == 0 && !app.targets_outside_scope().is_empty() {
+            frame
+                .buffer_mut()
+                .set_style(row_chunks[0], Style::default().fg(Color::Red));
+        }
+        if index == 2 && app.asn.is_some() {
+            render_owners(app, frame, row_chunks[1]);
+        }
     }
 }
+
+/// The autonomous systems the target addresses belong to
+fn render_owners(app: &App, frame: &mut Frame, area: Rect) {
+    let hint = Style::default().fg(Color::DarkGray);
+    let owners = app.target_owners();
+    let line = if owners.is_empty() {
+        Line::styled("No public target addresses looked up", hint)
+    } else {
+        Line::from(
+            owners
+                .into_iter()
+                .enumerate()
+                .flat_map(|(index, (owner, count))| {
+                    [
+                        Span::styled(if index == 0 { "" } else { ", " }, hint),
+                        Span::raw(owner),
+                        Span::styled(format!(" ×{}", count), hint),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        )
+    };
+    let block = Block::bordered().title("Owners").border_style(hint);
+    frame.render_widget(Paragraph::new(line).block(block), area);
+}
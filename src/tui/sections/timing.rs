@@ -1,10 +1,10 @@
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Flex, Layout, Rect},
 };
 
 use crate::{
-    scan::{flags::NmapFlag, model::TimingTemplate},
+    scan::{flags::NmapFlag, model::TimingTemplate, rate_presets::RatePreset},
     tui::{app::App, widgets::radio::RadioGroup},
 };
 
@@ -14,7 +14,7 @@ pub fn render_timing(app: &mut App, frame: &mut Frame, area: Rect) {
         .constraints([
             Constraint::Length(1),
             Constraint::Length(1),
-            Constraint::Length(1),
+            Constraint::Length(3),
         ])
         .split(area);
 
@@ -31,4 +31,37 @@ pub fn render_timing(app: &mut App, frame: &mut Frame, area: Rect) {
         });
 
     timing_radios.render(row_chunks[0], frame.buffer_mut());
+
+    // Row 1
+    let preset_radios = RadioGroup::new(RatePreset::all_labels())
+        .with_selected(
+            app.config
+                .rate_presets
+                .matching(&app.scan.timing)
+                .map(RatePreset::as_index),
+        )
+        .with_focused(match (app.focused_flag, app.focused_radio_index) {
+            (NmapFlag::RatePreset, Some(index)) => Some(index),
+            _ => None,
+        });
+    preset_radios.render(row_chunks[1], frame.buffer_mut());
+
+    // Row 2
+    let row_2_col_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .flex(Flex::SpaceBetween)
+        .constraints([Constraint::Length(60), Constraint::Length(60)])
+        .split(row_chunks[2]);
+    for (index, flag) in app
+        .shown(&[NmapFlag::HostTimeout, NmapFlag::ScriptTimeout])
+        .into_iter()
+        .enumerate()
+    {
+        app.input_map.get_mut(&flag).unwrap().render(
+            row_2_col_chunks[index],
+            frame.buffer_mut(),
+            app.focused_flag == flag,
+            app.editing_flag == Some(flag),
+        );
+    }
 }
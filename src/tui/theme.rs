@@ -0,0 +1,128 @@
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use serde::Deserialize;
+use std::{env, ffi::OsString};
+
+/// Color scheme applied to every frame after it is drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Default,
+    /// Okabe-Ito colors, with focus in bold orange and selection in blue
+    Colorblind,
+    /// No colors; focus is reversed and highlights are bold
+    Monochrome,
+}
+
+impl Theme {
+    /// The configured theme, or monochrome when NO_COLOR is set
+    pub fn from_env(configured: Theme) -> Theme {
+        Self::with_no_color(configured, env::var_os("NO_COLOR"))
+    }
+
+    /// NO_COLOR only counts when it is set to a non-empty value
+    fn with_no_color(configured: Theme, no_color: Option<OsString>) -> Theme {
+        match no_color {
+            Some(value) if !value.is_empty() => Theme::Monochrome,
+            _ => configured,
+        }
+    }
+
+    /// Restyle every cell of a drawn frame
+    pub fn apply(self, buf: &mut Buffer) {
+        if self == Theme::Default {
+            return;
+        }
+        for cell in buf.content.iter_mut() {
+            let (fg, bg, modifier) = self.restyle(cell.fg, cell.bg);
+            cell.fg = fg;
+            cell.bg = bg;
+            cell.modifier |= modifier;
+        }
+    }
+
+    /// The colors and extra modifiers that replace a foreground and background color
+    fn restyle(self, fg: Color, bg: Color) -> (Color, Color, Modifier) {
+        match self {
+            Theme::Default => (fg, bg, Modifier::empty()),
+            Theme::Colorblind => {
+                // Focus is yellow everywhere, so it also gets bold to not rely on hue alone
+                let modifier = if fg == Color::Yellow {
+                    Modifier::BOLD
+                } else {
+                    Modifier::empty()
+                };
+                (colorblind(fg), colorblind(bg), modifier)
+            }
+            Theme::Monochrome => {
+                let modifier = match (fg, bg) {
+                    (_, bg) if bg != Color::Reset => Modifier::REVERSED,
+                    (Color::Yellow, _) => Modifier::REVERSED,
+                    (Color::DarkGray, _) => Modifier::DIM,
+                    (Color::Reset | Color::Gray | Color::White, _) => Modifier::empty(),
+                    _ => Modifier::BOLD,
+                };
+                (Color::Reset, Color::Reset, modifier)
+            }
+        }
+    }
+}
+
+/// Map the named colors used by the views to the Okabe-Ito palette, which stays distinct
+/// for red-green and blue-yellow color blindness
+fn colorblind(color: Color) -> Color {
+    match color {
+        Color::Yellow => Color::Rgb(230, 159, 0),
+        Color::Green => Color::Rgb(0, 114, 178),
+        Color::LightGreen => Color::Rgb(86, 180, 233),
+        Color::Cyan => Color::Rgb(0, 158, 115),
+        Color::Blue => Color::Rgb(204, 121, 167),
+        Color::Red => Color::Rgb(213, 94, 0),
+        Color::LightRed => Color::Rgb(240, 228, 66),
+        color => color,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{layout::Rect, style::Style};
+
+    #[test]
+    fn test_from_env() {
+        assert_eq!(
+            Theme::with_no_color(Theme::Colorblind, Some("1".into())),
+            Theme::Monochrome
+        );
+        assert_eq!(
+            Theme::with_no_color(Theme::Colorblind, Some("".into())),
+            Theme::Colorblind
+        );
+        assert_eq!(Theme::with_no_color(Theme::Default, None), Theme::Default);
+    }
+
+    #[test]
+    fn test_apply() {
+        let area = Rect::new(0, 0, 3, 1);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 0, "a", Style::default().fg(Color::Yellow));
+        buf.set_string(1, 0, "b", Style::default().fg(Color::Green));
+        buf.set_string(2, 0, "c", Style::default().bg(Color::Blue).fg(Color::White));
+
+        let mut colorblind = buf.clone();
+        Theme::Colorblind.apply(&mut colorblind);
+        assert_eq!(colorblind[(0, 0)].fg, Color::Rgb(230, 159, 0));
+        assert!(colorblind[(0, 0)].modifier.contains(Modifier::BOLD));
+        assert_eq!(colorblind[(1, 0)].fg, Color::Rgb(0, 114, 178));
+
+        Theme::Monochrome.apply(&mut buf);
+        assert!(buf.content.iter().all(|cell| cell.fg == Color::Reset));
+        assert!(buf.content.iter().all(|cell| cell.bg == Color::Reset));
+        assert_eq!(buf[(0, 0)].modifier, Modifier::REVERSED);
+        assert_eq!(buf[(1, 0)].modifier, Modifier::BOLD);
+        assert_eq!(buf[(2, 0)].modifier, Modifier::REVERSED);
+    }
+}
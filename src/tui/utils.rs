@@ -1,26 +1,65 @@
-use ratatui::{Frame, layout::Rect};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+};
 use std::collections::HashMap;
 use strum::EnumMessage;
 
 use crate::{
+    results::model::PortState,
     scan::{
         flags::{FlagValue, NmapFlag},
-        model::NmapScan,
+        model::{DnsResolution, MAX_LEVEL, NmapScan, ScanTechnique},
     },
     tui::{
         app::App,
         widgets::{
             checkbox::Checkbox,
+            stepper::Stepper,
             text_input::{
-                CompletingInput, InputWidget, IntParser, TextInput, VecIntParser, VecStringParser,
+                ArgsInput, ChoiceInput, CompletingInput, FtpRelayParser, HexInput, InputWidget,
+                IntParser, IpOptionsParser, ProxiesParser, RatioParser, SpoofMacParser, TextInput,
+                TimeSpecParser, VecIntParser, VecStringParser,
             },
         },
     },
 };
 
+/// Color of a port state, shared by every view that shows port states
+pub fn port_state_color(state: PortState) -> Color {
+    match state {
+        PortState::Open => Color::Green,
+        PortState::OpenFiltered => Color::LightGreen,
+        PortState::Unfiltered => Color::Blue,
+        PortState::Filtered => Color::Yellow,
+        PortState::ClosedFiltered => Color::LightRed,
+        PortState::Closed => Color::Red,
+    }
+}
+
+/// Cut text to at most `width` characters, ending with an ellipsis when it was cut
+pub fn truncate_with_ellipsis(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    if width > 0 {
+        truncated.push('…');
+    }
+    truncated
+}
+
 pub fn initialize_text_inputs(scan: &mut NmapScan, input_map: &mut HashMap<NmapFlag, InputWidget>) {
     // VecString inputs
-    for flag in [NmapFlag::Targets, NmapFlag::Exclude, NmapFlag::DnsServers].iter() {
+    for flag in [
+        NmapFlag::Targets,
+        NmapFlag::Exclude,
+        NmapFlag::DnsServers,
+        NmapFlag::Scripts,
+    ]
+    .iter()
+    {
         let mut input = TextInput::new(VecStringParser)
             .with_label(flag.to_string())
             .with_placeholder(flag.get_message().unwrap());
@@ -41,8 +80,89 @@ pub fn initialize_text_inputs(scan: &mut NmapScan, input_map: &mut HashMap<NmapF
         input_map.insert(*flag, InputWidget::Path(input));
     }
 
+    // Key=value inputs
+    let flag = NmapFlag::ScriptArgs;
+    let mut input = ArgsInput::new()
+        .with_label(flag.to_string())
+        .with_placeholder(flag.get_message().unwrap());
+    if let FlagValue::String(Some(flag_value)) = flag.get_flag_value(scan) {
+        input.set_typed_value(flag_value.clone());
+    }
+    input_map.insert(flag, InputWidget::Args(input));
+
+    // Time inputs
+    for flag in [
+        NmapFlag::HostTimeout,
+        NmapFlag::ScriptTimeout,
+        NmapFlag::StatsEvery,
+    ]
+    .iter()
+    {
+        let mut input = TextInput::new(TimeSpecParser)
+            .with_label(flag.to_string())
+            .with_placeholder(flag.get_message().unwrap());
+        if let FlagValue::String(Some(flag_value)) = flag.get_flag_value(scan) {
+            input.set_typed_value(flag_value.clone());
+        }
+        input_map.insert(*flag, InputWidget::String(input));
+    }
+
+    let flag = NmapFlag::SpoofMac;
+    let mut input = TextInput::new(SpoofMacParser)
+        .with_label(flag.to_string())
+        .with_placeholder(flag.get_message().unwrap());
+    if let FlagValue::String(Some(flag_value)) = flag.get_flag_value(scan) {
+        input.set_typed_value(flag_value.clone());
+    }
+    input_map.insert(flag, InputWidget::String(input));
+
+    let flag = NmapFlag::Proxies;
+    let mut input = TextInput::new(ProxiesParser)
+        .with_label(flag.to_string())
+        .with_placeholder(flag.get_message().unwrap());
+    if let FlagValue::VecString(flag_value) = flag.get_flag_value(scan) {
+        input.set_typed_value(flag_value.to_vec());
+    }
+    input_map.insert(flag, InputWidget::VecString(input));
+
+    let flag = NmapFlag::Data;
+    let mut input = HexInput::new()
+        .with_label(flag.to_string())
+        .with_placeholder(flag.get_message().unwrap());
+    if let FlagValue::String(Some(flag_value)) = flag.get_flag_value(scan) {
+        input.set_typed_value(flag_value.clone());
+    }
+    input_map.insert(flag, InputWidget::Hex(input));
+
+    let flag = NmapFlag::FtpRelay;
+    let mut input = TextInput::new(FtpRelayParser)
+        .with_label(flag.to_string())
+        .with_placeholder(flag.get_message().unwrap());
+    if let ScanTechnique::Ftp(relay) = &scan.scan_technique {
+        input.set_typed_value(relay.clone());
+    }
+    input_map.insert(flag, InputWidget::String(input));
+
+    let flag = NmapFlag::Interface;
+    let mut input = ChoiceInput::new("Interfaces")
+        .with_label(flag.to_string())
+        .with_placeholder(flag.get_message().unwrap());
+    if let FlagValue::String(Some(flag_value)) = flag.get_flag_value(scan) {
+        input.set_typed_value(flag_value.clone());
+    }
+    input_map.insert(flag, InputWidget::Choice(input));
+
+    let flag = NmapFlag::IpOptions;
+    let mut input = TextInput::new(IpOptionsParser)
+        .with_label(flag.to_string())
+        .with_placeholder(flag.get_message().unwrap());
+    if let FlagValue::String(Some(flag_value)) = flag.get_flag_value(scan) {
+        input.set_typed_value(flag_value.clone());
+    }
+    input_map.insert(flag, InputWidget::String(input));
+
     // Int inputs
-    for flag in [NmapFlag::RandomTargets].iter() {
+    for flag in [NmapFlag::RandomTargets, NmapFlag::SourcePort].iter() {
         let mut input = TextInput::new(IntParser)
             .with_label(flag.to_string())
             .with_placeholder(flag.get_message().unwrap());
@@ -52,6 +172,15 @@ pub fn initialize_text_inputs(scan: &mut NmapScan, input_map: &mut HashMap<NmapF
         input_map.insert(*flag, InputWidget::Int(input));
     }
 
+    let flag = NmapFlag::PortRatio;
+    let mut input = TextInput::new(RatioParser)
+        .with_label(flag.to_string())
+        .with_placeholder(flag.get_message().unwrap());
+    if let FlagValue::Float(Some(flag_value)) = flag.get_flag_value(scan) {
+        input.set_typed_value(*flag_value);
+    }
+    input_map.insert(flag, InputWidget::Float(input));
+
     // VecInt inputs
     for flag in [
         NmapFlag::SynDiscovery,
@@ -72,13 +201,60 @@ pub fn initialize_text_inputs(scan: &mut NmapScan, input_map: &mut HashMap<NmapF
     }
 }
 
+/// Label of a flag, marked when the flag is locked against edits
+pub fn flag_label(flag: NmapFlag, locked: bool) -> String {
+    if locked {
+        format!("{} [locked]", flag)
+    } else {
+        flag.to_string()
+    }
+}
+
+/// Render a boolean flag, or a pair of mutually exclusive options as a checkbox marked with the
+/// one that is set
 pub fn render_checkbox(app: &mut App, flag: NmapFlag, frame: &mut Frame, area: Rect) {
-    let FlagValue::Bool(flag_value) = flag.get_flag_value(app.scan) else {
-        panic!()
+    let locked = app.locked_flags.contains(&flag);
+    let dimmed = flag.unmet_dependency(app.scan).is_some();
+    let (checked, mark, label) = match flag.get_flag_value(app.scan) {
+        FlagValue::Bool(flag_value) => (*flag_value, 'X', flag_label(flag, locked)),
+        FlagValue::DnsResolution(resolution) => {
+            let (mark, name) = match resolution {
+                DnsResolution::Default => ('X', flag.to_string()),
+                DnsResolution::Never => ('n', "No resolve (-n)".to_string()),
+                DnsResolution::Always => ('R', "Always resolve (-R)".to_string()),
+            };
+            let label = if locked {
+                format!("{} [locked]", name)
+            } else {
+                name
+            };
+            (*resolution != DnsResolution::Default, mark, label)
+        }
+        _ => panic!(),
     };
-    let label = flag.to_string();
-    let checkbox = Checkbox::new(label)
-        .with_checked(*flag_value)
+    let mut checkbox = Checkbox::new(label)
+        .with_checked(checked)
+        .with_mark(mark)
         .with_focused(app.focused_flag == flag);
+    if dimmed {
+        let style = Style::default().fg(Color::DarkGray);
+        checkbox = checkbox
+            .with_checked_style(style)
+            .with_unchecked_style(style)
+            .with_label_style(style);
+    }
     checkbox.render(area, frame.buffer_mut());
 }
+
+pub fn render_stepper(app: &mut App, flag: NmapFlag, frame: &mut Frame, area: Rect) {
+    let label = flag_label(flag, app.locked_flags.contains(&flag));
+    let dimmed = flag.unmet_dependency(app.scan).is_some();
+    let FlagValue::Level(level) = flag.get_flag_value(app.scan) else {
+        panic!()
+    };
+    Stepper::new(label, MAX_LEVEL)
+        .with_value(*level)
+        .with_focused(app.focused_flag == flag)
+        .with_dimmed(dimmed)
+        .render(area, frame.buffer_mut());
+}
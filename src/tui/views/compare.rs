@@ -0,0 +1,119 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Row, Table, TableState},
+};
+
+use crate::{
+    profiles::Profile,
+    scan::compare::{OptionDiff, compare},
+    tui::widgets::text_input::EventResult,
+};
+
+/// Side-by-side comparison of the options of two profiles
+pub struct CompareView {
+    left: Profile,
+    right: Profile,
+    diffs: Vec<OptionDiff>,
+    only_differences: bool,
+    selected: usize,
+}
+
+impl CompareView {
+    pub fn new(left: Profile, right: Profile) -> Self {
+        let diffs = compare(&left.scan, &right.scan);
+        Self {
+            left,
+            right,
+            diffs,
+            only_differences: false,
+            selected: 0,
+        }
+    }
+
+    pub fn left(&self) -> &Profile {
+        &self.left
+    }
+
+    pub fn right(&self) -> &Profile {
+        &self.right
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<()> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected =
+                    (self.selected + 1).min(self.visible_diffs().count().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Char('d') => {
+                self.only_differences = !self.only_differences;
+                self.selected = 0;
+            }
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let different = self.diffs.iter().filter(|diff| diff.is_different()).count();
+        let rows = self.visible_diffs().map(|diff| {
+            let cell = |value: &Option<String>| match value {
+                Some(value) if value.is_empty() => "on".to_string(),
+                Some(value) => value.clone(),
+                None => "-".to_string(),
+            };
+            let row = Row::new(vec![diff.flag.clone(), cell(&diff.left), cell(&diff.right)]);
+            if diff.is_different() {
+                row.style(Style::default().fg(Color::Yellow))
+            } else {
+                row
+            }
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(22),
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Option".to_string(),
+                self.left.name.clone(),
+                self.right.name.clone(),
+            ])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(
+            Block::bordered()
+                .title(format!(
+                    "Compare {} and {} ({} differing options)",
+                    self.left.name, self.right.name, different
+                ))
+                .title_bottom(
+                    Line::from("d: toggle differences only  1/2: load left/right profile  v: back")
+                        .style(Style::default().fg(Color::DarkGray)),
+                ),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    fn visible_diffs(&self) -> impl Iterator<Item = &OptionDiff> {
+        self.diffs
+            .iter()
+            .filter(|diff| !self.only_differences || diff.is_different())
+    }
+}
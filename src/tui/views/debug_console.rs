@@ -0,0 +1,46 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+use tracing::Level;
+
+use crate::logging::LogBuffer;
+
+/// The most recent log events, newest at the bottom
+pub fn render_debug_console(logs: &LogBuffer, frame: &mut Frame, area: Rect) {
+    let block = Block::bordered().title("Debug console (press ` to hide)");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let hint = Style::default().fg(Color::DarkGray);
+    let entries = logs.recent(inner.height.into());
+    if entries.is_empty() {
+        frame.render_widget(Paragraph::new(Span::styled("No log events", hint)), inner);
+        return;
+    }
+    let lines = entries
+        .into_iter()
+        .map(|entry| {
+            Line::from(vec![
+                Span::styled(format!("{:>8.1}s ", entry.elapsed.as_secs_f64()), hint),
+                Span::styled(format!("{:<5} ", entry.level), level_style(entry.level)),
+                Span::styled(format!("{} ", entry.target), hint),
+                Span::raw(entry.message),
+            ])
+        })
+        .collect::<Vec<_>>();
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn level_style(level: Level) -> Style {
+    match level {
+        Level::ERROR => Style::default().fg(Color::Red),
+        Level::WARN => Style::default().fg(Color::Yellow),
+        Level::INFO => Style::default().fg(Color::Green),
+        Level::DEBUG => Style::default().fg(Color::Cyan),
+        Level::TRACE => Style::default().fg(Color::Magenta),
+    }
+}
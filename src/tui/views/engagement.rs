@@ -0,0 +1,67 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+use std::time::Duration;
+
+use crate::{
+    engagement::Engagement,
+    scan::duration::{format_duration, format_timestamp},
+};
+
+/// Time, scans, packets and hosts spent on the engagement, for reporting the level of effort
+pub fn render_engagement(engagement: Option<&Engagement>, now: u64, frame: &mut Frame, area: Rect) {
+    let hint = Style::default().fg(Color::DarkGray);
+    let block = Block::bordered().title("Engagement").title_bottom(
+        Line::from("n: start a new engagement  x: stop the clock  v: back to the editor")
+            .style(hint),
+    );
+    let Some(engagement) = engagement else {
+        let message = Line::from(Span::styled(
+            "No engagement clock running, press n to start adding up the scans from now on",
+            hint,
+        ));
+        frame.render_widget(Paragraph::new(message).block(block), area);
+        return;
+    };
+
+    let row = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<18}", label), hint),
+            Span::raw(value),
+        ])
+    };
+    let mut scans = engagement.scans.to_string();
+    if engagement.failed > 0 {
+        scans.push_str(&format!(" ({} failed or stopped)", engagement.failed));
+    }
+    let packets = if engagement.packets_sent == 0 && engagement.packets_received == 0 {
+        Span::styled("not reported, run scans with -v to count them", hint)
+    } else {
+        Span::raw(format!(
+            "{} sent, {} received",
+            engagement.packets_sent, engagement.packets_received
+        ))
+    };
+    let lines = vec![
+        row(
+            "Started",
+            format!(
+                "{} UTC, {} ago",
+                format_timestamp(engagement.started),
+                format_duration(Duration::from_secs(now.saturating_sub(engagement.started)))
+            ),
+        ),
+        row("Time scanning", format_duration(engagement.scanning())),
+        row("Scans run", scans),
+        Line::from(vec![
+            Span::styled(format!("{:<18}", "Packets"), hint),
+            packets,
+        ]),
+        row("Hosts found up", engagement.hosts.len().to_string()),
+    ];
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
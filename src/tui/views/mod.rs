@@ -0,0 +1,7 @@
+pub mod compare;
+pub mod debug_console;
+pub mod engagement;
+pub mod monitor;
+pub mod output;
+pub mod queue;
+pub mod results;
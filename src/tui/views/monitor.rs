@@ -0,0 +1,137 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    monitor::Monitor,
+    results::{diff::ChangeKind, model::PortState},
+    scan::{
+        builder::NmapCommandBuilder,
+        duration::{format_duration, format_timestamp},
+        secrets,
+    },
+    tui::{utils::port_state_color, widgets::text_input::EventResult},
+};
+
+/// Status of the running monitor and the per-host timeline of port changes
+#[derive(Default)]
+pub struct MonitorView {
+    selected: usize,
+}
+
+impl MonitorView {
+    pub fn handle_event(&mut self, event: &Event, monitor: &Monitor) -> EventResult<()> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                let count: usize = monitor.timeline().values().map(Vec::len).sum();
+                self.selected = (self.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
+
+    pub fn render(&self, monitor: Option<&Monitor>, frame: &mut Frame, area: Rect) {
+        let hint = Style::default().fg(Color::DarkGray);
+        let Some(monitor) = monitor else {
+            let block = Block::bordered()
+                .title("Monitor")
+                .title_bottom(Line::from("v: back").style(hint));
+            frame.render_widget(
+                Paragraph::new(
+                    "Not monitoring, press M on a profile in the profiles popup (p) to start",
+                )
+                .block(block),
+                area,
+            );
+            return;
+        };
+
+        let chunks = Layout::vertical([Constraint::Length(5), Constraint::Min(0)]).split(area);
+
+        let next_run = match monitor.next_run_in() {
+            Some(next_run) => format!("next run in {}", format_duration(next_run)),
+            None => "running".to_string(),
+        };
+        let mut lines = vec![
+            Line::from(NmapCommandBuilder::build(&secrets::masked(monitor.scan()))),
+            Line::from(format!(
+                "Every {}, {} runs this session, {} stored, {}",
+                format_duration(monitor.interval()),
+                monitor.runs(),
+                monitor.snapshot_count(),
+                next_run
+            )),
+        ];
+        lines.push(match monitor.last_error() {
+            Some(err) => Line::from(Span::styled(
+                format!("Last run failed: {}", err),
+                Style::default().fg(Color::Red),
+            )),
+            None if monitor.last_changes().is_empty() => {
+                Line::from(Span::styled("No changes in the last run", hint))
+            }
+            None => Line::from(Span::styled(
+                format!("{} changes in the last run", monitor.last_changes().len()),
+                Style::default().fg(Color::Yellow),
+            )),
+        });
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(Block::bordered().title(format!("Monitoring {}", monitor.name()))),
+            chunks[0],
+        );
+
+        let rows = monitor.timeline().into_iter().flat_map(|(host, events)| {
+            events.into_iter().enumerate().map(move |(index, event)| {
+                let color = port_state_color(match event.change.kind {
+                    ChangeKind::Opened => PortState::Open,
+                    ChangeKind::Closed => PortState::Closed,
+                });
+                Row::new(vec![
+                    if index == 0 {
+                        host.clone()
+                    } else {
+                        String::new()
+                    },
+                    format_timestamp(event.taken),
+                    format!("{}/{}", event.change.port, event.change.protocol),
+                    event.change.kind.as_str().to_string(),
+                ])
+                .style(Style::default().fg(color))
+            })
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(40),
+                Constraint::Length(18),
+                Constraint::Length(12),
+                Constraint::Min(0),
+            ],
+        )
+        .header(
+            Row::new(vec!["Host", "Time (UTC)", "Port", "Change"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(
+            Block::bordered()
+                .title("Timeline")
+                .title_bottom(Line::from("s: stop monitoring  v: back").style(hint)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, chunks[1], &mut state);
+    }
+}
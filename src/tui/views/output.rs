@@ -0,0 +1,120 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+use std::time::Duration;
+
+use crate::{
+    scan::{
+        duration::format_duration,
+        runner::{RunEvent, RunStatus},
+        stats::RunStats,
+    },
+    tui::app::App,
+};
+
+pub fn render_output(app: &App, frame: &mut Frame, area: Rect) {
+    let Some(runner) = &app.runner else {
+        return;
+    };
+
+    let status = match runner.status() {
+        RunStatus::Running => format!("running for {}", format_duration(runner.elapsed())),
+        RunStatus::Exited(0) => format!("finished in {}", format_duration(runner.elapsed())),
+        RunStatus::Exited(code) => format!("failed with exit code {}", code),
+        RunStatus::Killed if app.sessions.last_resumable().is_some() => {
+            "killed, press R to resume".to_string()
+        }
+        RunStatus::Killed => "killed".to_string(),
+    };
+    let block = Block::bordered().title(format!("Output ({})", status));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let running = runner.is_running();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(running.into()),
+        Constraint::Min(0),
+    ])
+    .split(inner);
+    frame.render_widget(
+        Paragraph::new(render_stats(runner.stats(), runner.elapsed(), !running)),
+        chunks[0],
+    );
+    if running {
+        let stats = runner.stats();
+        app.run_progress.render(
+            frame,
+            chunks[1],
+            stats.percent_done.map(|percent| f64::from(percent) / 100.0),
+            &progress_label(stats),
+        );
+    }
+
+    let visible = chunks[2].height as usize;
+    let output = runner.output();
+    let lines = output[output.len().saturating_sub(visible)..]
+        .iter()
+        .map(|event| match event {
+            RunEvent::Stdout(line) => Line::from(line.as_str()),
+            RunEvent::Stderr(line) => {
+                Line::from(line.as_str()).style(Style::default().fg(Color::Red))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    frame.render_widget(Paragraph::new(lines), chunks[2]);
+}
+
+/// The phase nmap is in and the time it expects to take, shown on the progress bar
+fn progress_label(stats: &RunStats) -> String {
+    let mut label = stats
+        .phase
+        .clone()
+        .unwrap_or_else(|| "Scanning".to_string());
+    if let Some(remaining) = &stats.remaining {
+        label.push_str(&format!(" ({} left)", remaining));
+    }
+    label
+}
+
+/// Stats of the run on one line; the phase is left to the progress bar while nmap runs
+fn render_stats(stats: &RunStats, elapsed: Duration, with_phase: bool) -> Line<'static> {
+    let label = Style::default().fg(Color::DarkGray);
+    let mut spans = vec![
+        Span::styled("Elapsed ", label),
+        Span::raw(format_duration(elapsed)),
+        Span::styled("  Sent ", label),
+        Span::raw(stats.packets_sent.to_string()),
+        Span::styled("  Rcvd ", label),
+        Span::raw(stats.packets_received.to_string()),
+    ];
+    if let Some(rate) = stats.send_rate(elapsed.as_secs_f64()) {
+        spans.push(Span::styled("  Rate ", label));
+        spans.push(Span::raw(format!("{:.1} pkt/s", rate)));
+    }
+    if let Some(group) = &stats.host_group {
+        spans.push(Span::styled("  Group ", label));
+        spans.push(Span::raw(group.clone()));
+    }
+    if let (Some(completed), Some(up)) = (stats.hosts_completed, stats.hosts_up) {
+        spans.push(Span::styled("  Hosts ", label));
+        spans.push(Span::raw(format!("{} done, {} up", completed, up)));
+    }
+    if with_phase && let Some(phase) = &stats.phase {
+        spans.push(Span::styled("  Phase ", label));
+        let mut text = phase.clone();
+        if let Some(percent) = stats.percent_done {
+            text.push_str(&format!(" {:.1}%", percent));
+        }
+        if let Some(remaining) = &stats.remaining {
+            text.push_str(&format!(" ({} left)", remaining));
+        }
+        spans.push(Span::raw(text));
+    }
+    Line::from(spans)
+}
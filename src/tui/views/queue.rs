@@ -0,0 +1,58 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Gauge, Paragraph},
+};
+
+use crate::scan::{duration::format_duration, queue::ScanQueue};
+
+/// Overall progress of a scan queue, next to the output of the running scan
+pub fn render_queue(queue: &ScanQueue, frame: &mut Frame, area: Rect) {
+    let block = Block::bordered().title(format!("Queue {}", queue.name()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).split(inner);
+    let finished = queue.completed() + queue.failed();
+    let ratio = if queue.total() == 0 {
+        1.0
+    } else {
+        finished as f64 / queue.total() as f64
+    };
+    let mut label = format!("{}/{} done", finished, queue.total());
+    if queue.failed() > 0 {
+        label.push_str(&format!(", {} failed", queue.failed()));
+    }
+    frame.render_widget(
+        Gauge::default()
+            .gauge_style(Style::default().fg(Color::Green))
+            .ratio(ratio)
+            .label(label),
+        chunks[0],
+    );
+
+    let hint = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![match queue.current() {
+        Some(current) => Line::from(vec![
+            Span::styled("Running ", hint),
+            Span::raw(current.to_string()),
+        ]),
+        None if queue.is_done() => Line::from(Span::styled("Finished", hint)),
+        None => match queue.next_start_in() {
+            Some(wait) => Line::from(Span::styled(
+                format!("Next scan in {}", format_duration(wait)),
+                hint,
+            )),
+            None => Line::from(Span::styled("Waiting", hint)),
+        },
+    }];
+    lines.extend(queue.pending().map(|scan| {
+        Line::from(vec![
+            Span::styled("Next ", hint),
+            Span::raw(scan.label.clone()),
+        ])
+    }));
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+}
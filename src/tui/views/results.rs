@@ -0,0 +1,1351 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Gauge, List, ListState, Paragraph, Row},
+};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    results::{
+        certs::{Certificate, collect_certificates},
+        model::{
+            AddressType, Host, HostStatus, Port, PortState, ScanResults, Service, nvd_search_url,
+        },
+        notes, oui,
+        parser::{ResultsError, load},
+        smb::{Signing, SmbIdentity, collect_smb_identities},
+        subnets::{SubnetGroup, group_by_subnet},
+        summary::{Summary, summarize},
+        topology::{HopNode, hop_tree, tree_lines},
+        vulns::{Finding, FindingSort, collect_findings, compare_hosts, sort_findings},
+        web::{WebService, collect_web_services},
+    },
+    scan::duration::{format_duration, format_iso_timestamp},
+    tui::{
+        utils::{port_state_color, truncate_with_ellipsis},
+        widgets::{
+            progress::Progress,
+            table::{Column, SortableTable, TableEvent},
+            text_input::EventResult,
+        },
+    },
+};
+
+const HOSTS_WIDTH: u16 = 32;
+const DETAILS_HEIGHT: u16 = 9;
+/// Open ports shown in the summary bar chart
+const TOP_PORTS: usize = 10;
+const PORT_REASON_COLUMN: usize = 2;
+const FINDING_CVSS_COLUMN: usize = 1;
+/// Lines of scan notes shown above the summary charts
+const MAX_NOTES_LINES: usize = 5;
+/// Result files at least this large are parsed in the background
+const LARGE_RESULTS_BYTES: u64 = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Hosts,
+    Ports,
+}
+
+/// A line of the host list: a subnet header or a host, by index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HostRow {
+    Group(usize),
+    Host(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Hosts,
+    Findings,
+    Summary,
+    /// Routes found by --traceroute, merged into a tree
+    Routes,
+    /// TLS certificates reported by ssl-cert
+    Certificates,
+    /// Web ports with their page titles and servers
+    Web,
+    /// Names, domains and SMB signing of Windows hosts
+    Smb,
+}
+
+/// Browser for the hosts, ports and services found by a finished scan
+pub struct ResultsView {
+    results: Option<ScanResults>,
+    source: Option<PathBuf>,
+    error: Option<String>,
+    /// Results of a large file being parsed in the background
+    loading: Option<Receiver<Result<ScanResults, ResultsError>>>,
+    /// Host to select once the results being parsed are in
+    pending_host: Option<usize>,
+    progress: Progress,
+    findings: Vec<Finding>,
+    finding_table: SortableTable,
+    certificates: Vec<Certificate>,
+    certificate_table: SortableTable,
+    web_services: Vec<WebService>,
+    web_table: SortableTable,
+    smb_identities: Vec<SmbIdentity>,
+    smb_table: SortableTable,
+    summary: Summary,
+    hop_tree: Vec<HopNode>,
+    groups: Vec<SubnetGroup>,
+    grouped: bool,
+    collapsed: HashSet<String>,
+    subnet_prefixes: (u8, u8),
+    /// Fall back to the embedded OUI table for MAC vendors nmap didn't report
+    mac_vendor_lookup: bool,
+    /// AS and owner of each host address, filled in by ASN lookups
+    asn_owners: HashMap<String, String>,
+    /// Notes saved next to the results when the scan was run
+    notes: Option<String>,
+    tab: Tab,
+    focus: Focus,
+    selected_row: usize,
+    port_table: SortableTable,
+    /// Leave closed and filtered ports out of the port table
+    hide_closed: bool,
+    /// Show why nmap gave each port its state
+    show_reason: bool,
+    selected_cpe: usize,
+    selected_hop: usize,
+}
+
+impl Default for ResultsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResultsView {
+    pub fn new() -> Self {
+        Self {
+            results: None,
+            source: None,
+            error: None,
+            loading: None,
+            pending_host: None,
+            progress: Progress::new(),
+            findings: Vec::new(),
+            finding_table: SortableTable::new(vec![
+                Column::new("ID", Constraint::Length(28)),
+                Column::new("CVSS", Constraint::Length(6)),
+                Column::new("Host", Constraint::Length(20)),
+                Column::new("Port", Constraint::Length(10)),
+                Column::new("Script", Constraint::Min(0)),
+                Column::new("Exploit", Constraint::Length(8)).with_sortable(false),
+            ])
+            .with_sort(FINDING_CVSS_COLUMN),
+            certificates: Vec::new(),
+            certificate_table: SortableTable::new(vec![
+                Column::new("Host", Constraint::Length(20)),
+                Column::new("Port", Constraint::Length(10)),
+                Column::new("Subject", Constraint::Min(0)),
+                Column::new("Issuer", Constraint::Length(24)),
+                Column::new("Alternative names", Constraint::Min(0)).with_sortable(false),
+                Column::new("Expires", Constraint::Length(12)),
+            ]),
+            web_services: Vec::new(),
+            web_table: SortableTable::new(vec![
+                Column::new("Host", Constraint::Length(20)),
+                Column::new("Port", Constraint::Length(8)),
+                Column::new("Title", Constraint::Min(0)),
+                Column::new("Server", Constraint::Length(32)),
+            ]),
+            smb_identities: Vec::new(),
+            smb_table: SortableTable::new(vec![
+                Column::new("Host", Constraint::Length(20)),
+                Column::new("Computer name", Constraint::Min(0)),
+                Column::new("Domain", Constraint::Length(24)),
+                Column::new("Workgroup", Constraint::Length(16)),
+                Column::new("OS", Constraint::Min(0)),
+                Column::new("Signing", Constraint::Length(14)),
+            ]),
+            summary: Summary::default(),
+            hop_tree: Vec::new(),
+            groups: Vec::new(),
+            grouped: true,
+            collapsed: HashSet::new(),
+            subnet_prefixes: (24, 64),
+            mac_vendor_lookup: true,
+            asn_owners: HashMap::new(),
+            notes: None,
+            tab: Tab::Hosts,
+            focus: Focus::Hosts,
+            selected_row: 0,
+            port_table: port_table(),
+            hide_closed: false,
+            show_reason: false,
+            selected_cpe: 0,
+            selected_hop: 0,
+        }
+    }
+
+    /// Group IPv4 and IPv6 hosts into subnets with the given prefix lengths
+    pub fn with_subnet_prefixes(mut self, ipv4_prefix: u8, ipv6_prefix: u8) -> Self {
+        self.subnet_prefixes = (ipv4_prefix, ipv6_prefix);
+        self
+    }
+
+    pub fn with_mac_vendor_lookup(mut self, enabled: bool) -> Self {
+        self.mac_vendor_lookup = enabled;
+        self
+    }
+
+    pub fn set_asn_owners(&mut self, owners: HashMap<String, String>) {
+        self.asn_owners = owners;
+    }
+
+    /// Load results from an nmap XML file, keeping the selection when reloading the same file
+    pub fn load(&mut self, path: &Path) {
+        self.set_source(path);
+        let size = fs::metadata(path).map_or(0, |metadata| metadata.len());
+        if size >= LARGE_RESULTS_BYTES {
+            let (sender, receiver) = mpsc::channel();
+            let path = path.to_path_buf();
+            thread::spawn(move || sender.send(load(&path)).ok());
+            self.loading = Some(receiver);
+            self.results = None;
+            self.error = None;
+            return;
+        }
+        self.loading = None;
+        self.apply(load(path));
+    }
+
+    /// Show the results of an nmap XML file that were parsed elsewhere
+    pub fn show(&mut self, path: &Path, result: Result<ScanResults, ResultsError>) {
+        self.set_source(path);
+        self.loading = None;
+        self.apply(result);
+    }
+
+    fn set_source(&mut self, path: &Path) {
+        if self.source.as_deref() != Some(path) {
+            self.selected_row = 0;
+            self.collapsed.clear();
+            self.port_table.select(0);
+            self.selected_cpe = 0;
+            self.finding_table.select(0);
+            self.selected_hop = 0;
+            self.focus = Focus::Hosts;
+        }
+        self.source = Some(path.to_path_buf());
+        self.notes = notes::load(path);
+        self.pending_host = None;
+    }
+
+    /// Take the results parsed in the background once they are in; true when they changed
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = &self.loading else {
+            return false;
+        };
+        self.progress.tick();
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => Err(ResultsError::InvalidXml(
+                "the parser stopped unexpectedly".to_string(),
+            )),
+        };
+        self.loading = None;
+        self.apply(result);
+        if let Some(index) = self.pending_host.take() {
+            self.select_host(index);
+        }
+        true
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading.is_some()
+    }
+
+    fn apply(&mut self, result: Result<ScanResults, ResultsError>) {
+        match result {
+            Ok(results) => {
+                self.findings = collect_findings(&results);
+                self.sort_findings();
+                self.certificates = collect_certificates(&results);
+                self.sort_certificates();
+                self.web_services = collect_web_services(&results);
+                self.sort_web_services();
+                self.smb_identities = collect_smb_identities(&results);
+                self.sort_smb_identities();
+                self.summary = summarize(&results, TOP_PORTS);
+                self.hop_tree = hop_tree(&results);
+                let (ipv4_prefix, ipv6_prefix) = self.subnet_prefixes;
+                self.groups = group_by_subnet(&results, ipv4_prefix, ipv6_prefix);
+                self.results = Some(results);
+                self.error = None;
+                self.clamp_selection();
+            }
+            Err(err) => {
+                self.results = None;
+                self.findings.clear();
+                self.certificates.clear();
+                self.web_services.clear();
+                self.smb_identities.clear();
+                self.hop_tree.clear();
+                self.groups.clear();
+                self.error = Some(err.to_string());
+            }
+        }
+    }
+
+    /// Show the ports of the host with the given index
+    pub fn select_host(&mut self, index: usize) {
+        if self.is_loading() {
+            self.pending_host = Some(index);
+            return;
+        }
+        self.tab = Tab::Hosts;
+        self.focus = Focus::Hosts;
+        if let Some(group) = self
+            .groups
+            .iter()
+            .find(|group| group.hosts.contains(&index))
+        {
+            self.collapsed.remove(&group.subnet);
+        }
+        self.selected_row = self
+            .host_rows()
+            .iter()
+            .position(|&row| row == HostRow::Host(index))
+            .unwrap_or(0);
+        self.port_table.select(0);
+        self.selected_cpe = 0;
+        self.clamp_selection();
+    }
+
+    pub fn results(&self) -> Option<&ScanResults> {
+        self.results.as_ref()
+    }
+
+    /// Whether the web inventory is shown instead of the hosts
+    pub fn shows_web_inventory(&self) -> bool {
+        self.tab == Tab::Web
+    }
+
+    pub fn source(&self) -> Option<&Path> {
+        self.source.as_deref()
+    }
+
+    /// Addresses of the selected host, or of every host in the selected subnet
+    pub fn selected_addresses(&self) -> Vec<String> {
+        let Some(results) = &self.results else {
+            return Vec::new();
+        };
+        let hosts = match self.host_rows().get(self.selected_row) {
+            Some(HostRow::Host(index)) => vec![*index],
+            Some(HostRow::Group(index)) => self.groups[*index].hosts.clone(),
+            None => Vec::new(),
+        };
+        hosts
+            .into_iter()
+            .filter_map(|index| Some(results.hosts.get(index)?.address().to_string()))
+            .collect()
+    }
+
+    /// The selected port with its host, when the port is open
+    pub fn selected_open_port(&self) -> Option<(&Host, &Port)> {
+        let port = self.selected_port()?;
+        (port.state == PortState::Open).then_some((self.selected_host()?, port))
+    }
+
+    pub fn selected_service(&self) -> Option<&Service> {
+        self.selected_port()?.service.as_ref()
+    }
+
+    /// Port label and full banner of the selected service, e.g. ("22/tcp", "OpenSSH 8.9p1 (protocol 2.0)")
+    pub fn selected_banner(&self) -> Option<(String, String)> {
+        let port = self.selected_port()?;
+        let banner = port.service.as_ref()?.banner();
+        (!banner.is_empty()).then(|| (format!("{}/{}", port.port, port.protocol), banner))
+    }
+
+    pub fn selected_cpe(&self) -> Option<&str> {
+        self.selected_service()?
+            .cpes
+            .get(self.selected_cpe)
+            .map(String::as_str)
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<()> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        let tab = match key.code {
+            KeyCode::Char('f') => Some(Tab::Findings),
+            KeyCode::Char('d') => Some(Tab::Summary),
+            KeyCode::Char('t') => Some(Tab::Routes),
+            KeyCode::Char('C') => Some(Tab::Certificates),
+            KeyCode::Char('W') => Some(Tab::Web),
+            KeyCode::Char('I') => Some(Tab::Smb),
+            _ => None,
+        };
+        match tab {
+            // Pressing the key of the open tab goes back to the hosts
+            Some(tab) => {
+                self.tab = if self.tab == tab { Tab::Hosts } else { tab };
+                return EventResult::Consumed;
+            }
+            None if self.tab == Tab::Summary => return EventResult::Ignored,
+            None => {}
+        }
+        if self.tab == Tab::Smb {
+            return match self
+                .smb_table
+                .handle_event(event, self.smb_identities.len())
+            {
+                EventResult::Submit(TableEvent::Sorted(_)) => {
+                    self.sort_smb_identities();
+                    EventResult::Consumed
+                }
+                EventResult::Submit(TableEvent::Activated(_)) => EventResult::Ignored,
+                EventResult::Submit(TableEvent::Selected(_)) | EventResult::Consumed => {
+                    EventResult::Consumed
+                }
+                EventResult::Ignored => EventResult::Ignored,
+                EventResult::Cancel => EventResult::Cancel,
+            };
+        }
+        if self.tab == Tab::Web {
+            return match self.web_table.handle_event(event, self.web_services.len()) {
+                EventResult::Submit(TableEvent::Sorted(_)) => {
+                    self.sort_web_services();
+                    EventResult::Consumed
+                }
+                EventResult::Submit(TableEvent::Activated(_)) => EventResult::Ignored,
+                EventResult::Submit(TableEvent::Selected(_)) | EventResult::Consumed => {
+                    EventResult::Consumed
+                }
+                EventResult::Ignored => EventResult::Ignored,
+                EventResult::Cancel => EventResult::Cancel,
+            };
+        }
+        if self.tab == Tab::Certificates {
+            return match self
+                .certificate_table
+                .handle_event(event, self.certificates.len())
+            {
+                EventResult::Submit(TableEvent::Sorted(_)) => {
+                    self.sort_certificates();
+                    EventResult::Consumed
+                }
+                EventResult::Submit(TableEvent::Activated(_)) => EventResult::Ignored,
+                EventResult::Submit(TableEvent::Selected(_)) | EventResult::Consumed => {
+                    EventResult::Consumed
+                }
+                EventResult::Ignored => EventResult::Ignored,
+                EventResult::Cancel => EventResult::Cancel,
+            };
+        }
+        if self.tab == Tab::Routes {
+            let count = tree_lines(&self.hop_tree).len();
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.selected_hop = (self.selected_hop + 1).min(count.saturating_sub(1));
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.selected_hop = self.selected_hop.saturating_sub(1);
+                }
+                _ => return EventResult::Ignored,
+            }
+            return EventResult::Consumed;
+        }
+        if self.tab == Tab::Findings {
+            return match self.finding_table.handle_event(event, self.findings.len()) {
+                EventResult::Submit(TableEvent::Sorted(_)) => {
+                    self.sort_findings();
+                    EventResult::Consumed
+                }
+                EventResult::Submit(TableEvent::Activated(_)) => EventResult::Ignored,
+                EventResult::Submit(TableEvent::Selected(_)) | EventResult::Consumed => {
+                    EventResult::Consumed
+                }
+                EventResult::Ignored => EventResult::Ignored,
+                EventResult::Cancel => EventResult::Cancel,
+            };
+        }
+        if self.focus == Focus::Ports {
+            match self
+                .port_table
+                .handle_event(event, self.visible_ports().len())
+            {
+                // Left to the app, which opens the full banner
+                EventResult::Submit(TableEvent::Activated(_)) => return EventResult::Ignored,
+                EventResult::Submit(TableEvent::Selected(_) | TableEvent::Sorted(_)) => {
+                    self.selected_cpe = 0;
+                    return EventResult::Consumed;
+                }
+                EventResult::Consumed => return EventResult::Consumed,
+                EventResult::Ignored | EventResult::Cancel => {}
+            }
+        }
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::Char('g') => self.toggle_grouping(),
+            KeyCode::Char('o') => {
+                self.hide_closed = !self.hide_closed;
+                self.port_table.select(0);
+            }
+            KeyCode::Char('r') => {
+                self.show_reason = !self.show_reason;
+                self.port_table
+                    .set_hidden(PORT_REASON_COLUMN, !self.show_reason);
+            }
+            KeyCode::Enter if self.focus == Focus::Ports => return EventResult::Ignored,
+            KeyCode::Enter | KeyCode::Char(' ') if self.selected_group().is_some() => {
+                if let Some(subnet) = self.selected_group().map(|group| group.subnet.clone())
+                    && !self.collapsed.remove(&subnet)
+                {
+                    self.collapsed.insert(subnet);
+                }
+            }
+            KeyCode::Char('l') | KeyCode::Right if self.selected_group().is_some() => {
+                if let Some(subnet) = self.selected_group().map(|group| group.subnet.clone()) {
+                    self.collapsed.remove(&subnet);
+                }
+            }
+            KeyCode::Char('h') | KeyCode::Left
+                if self.focus == Focus::Hosts && self.selected_group().is_some() =>
+            {
+                if let Some(subnet) = self.selected_group().map(|group| group.subnet.clone()) {
+                    self.collapsed.insert(subnet);
+                }
+            }
+            KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => self.focus = Focus::Ports,
+            KeyCode::Char('h') | KeyCode::Left => self.focus = Focus::Hosts,
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Hosts => Focus::Ports,
+                    Focus::Ports => Focus::Hosts,
+                }
+            }
+            KeyCode::Char('n') => {
+                let count = self
+                    .selected_service()
+                    .map_or(0, |service| service.cpes.len());
+                if count > 0 {
+                    self.selected_cpe = (self.selected_cpe + 1) % count;
+                }
+            }
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let title = match &self.source {
+            Some(source) => format!("Results ({})", source.display()),
+            None => "Results".to_string(),
+        };
+        let block = Block::bordered().title(title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.is_loading() {
+            let status = Layout::vertical([Constraint::Length(1)]).split(inner);
+            self.progress
+                .render(frame, status[0], None, "Parsing the XML output...");
+            return;
+        }
+        let Some(results) = &self.results else {
+            let message = match &self.error {
+                Some(error) => Line::from(error.as_str()).style(Style::default().fg(Color::Red)),
+                None => {
+                    Line::from("No results loaded; add an XML output file (-oX) and run the scan")
+                }
+            };
+            frame.render_widget(Paragraph::new(message), inner);
+            return;
+        };
+
+        match self.tab {
+            Tab::Findings => return self.render_findings(frame, inner),
+            Tab::Summary => return self.render_summary(frame, inner),
+            Tab::Routes => return self.render_routes(results, frame, inner),
+            Tab::Certificates => return self.render_certificates(frame, inner),
+            Tab::Web => return self.render_web_services(frame, inner),
+            Tab::Smb => return self.render_smb_identities(frame, inner),
+            Tab::Hosts => {}
+        }
+
+        let columns =
+            Layout::horizontal([Constraint::Length(HOSTS_WIDTH), Constraint::Min(0)]).split(inner);
+        let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(DETAILS_HEIGHT)])
+            .split(columns[1]);
+
+        self.render_hosts(results, frame, columns[0]);
+        self.render_ports(frame, rows[0]);
+        self.render_details(frame, rows[1]);
+    }
+
+    fn render_hosts(&self, results: &ScanResults, frame: &mut Frame, area: Rect) {
+        let hint = Style::default().fg(Color::DarkGray);
+        let items = self
+            .host_rows()
+            .into_iter()
+            .map(|row| match row {
+                HostRow::Group(index) => {
+                    let group = &self.groups[index];
+                    let marker = if self.collapsed.contains(&group.subnet) {
+                        "▸ "
+                    } else {
+                        "▾ "
+                    };
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{}{}", marker, group.subnet),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!(" {}h {}p", group.hosts.len(), group.open_ports),
+                            hint,
+                        ),
+                    ])
+                }
+                HostRow::Host(index) => {
+                    let host = &results.hosts[index];
+                    let color = match host.status {
+                        HostStatus::Up => Color::Green,
+                        HostStatus::Down => Color::DarkGray,
+                        HostStatus::Unknown => Color::Reset,
+                    };
+                    Line::from(vec![
+                        Span::raw(if self.grouped { "  " } else { "" }),
+                        Span::styled("● ", Style::default().fg(color)),
+                        Span::raw(host.display_name()),
+                        Span::styled(format!(" {}", host.open_ports().count()), hint),
+                        Span::styled(
+                            self.asn_owners
+                                .get(host.address())
+                                .map_or(String::new(), |owner| format!(" {}", owner)),
+                            hint,
+                        ),
+                    ])
+                }
+            })
+            .collect::<Vec<_>>();
+        let block = self
+            .focus_block("Hosts", Focus::Hosts)
+            .title_bottom(Line::from("g: group  d: summary  t: routes").style(hint));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.selected_row));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_ports(&self, frame: &mut Frame, area: Rect) {
+        let ports = self.visible_ports();
+        let banner_width =
+            usize::from(area.width.saturating_sub(2 + self.port_table.fixed_width()));
+        let rows = ports.iter().map(|port| {
+            let service = port.service.as_ref();
+            let mut cells = vec![
+                format!("{}/{}", port.port, port.protocol),
+                port.state.to_string(),
+            ];
+            if self.show_reason {
+                cells.push(port_reason(port));
+            }
+            cells.push(service.map_or(String::new(), |service| service.name.clone()));
+            cells.push(service.map_or(String::new(), |service| {
+                truncate_with_ellipsis(&service.banner(), banner_width)
+            }));
+            Row::new(cells).style(Style::default().fg(port_state_color(port.state)))
+        });
+        let block = self
+            .focus_block("Ports", Focus::Ports)
+            .title_bottom(self.legend());
+        self.port_table
+            .render(frame, area, rows, block, self.focus == Focus::Ports);
+    }
+
+    /// Port state colors, followed by the toggle for hiding closed and filtered ports
+    fn legend(&self) -> Line<'static> {
+        let mut spans = Vec::new();
+        for state in PortState::ALL {
+            spans.push(Span::styled(
+                "● ",
+                Style::default().fg(port_state_color(state)),
+            ));
+            spans.push(Span::raw(format!("{}  ", state)));
+        }
+        spans.push(Span::styled(
+            if self.hide_closed {
+                "o: show all ports"
+            } else {
+                "o: open only"
+            },
+            Style::default().fg(Color::DarkGray),
+        ));
+        spans.push(Span::styled(
+            if self.show_reason {
+                "  r: hide reason"
+            } else {
+                "  r: show reason"
+            },
+            Style::default().fg(Color::DarkGray),
+        ));
+        spans.push(Span::styled(
+            "  s/S: sort",
+            Style::default().fg(Color::DarkGray),
+        ));
+        Line::from(spans)
+    }
+
+    fn render_details(&self, frame: &mut Frame, area: Rect) {
+        if self.focus == Focus::Hosts {
+            return self.render_host_details(frame, area);
+        }
+        let block = Block::bordered().title("Service");
+        let Some(service) = self.selected_service() else {
+            frame.render_widget(Paragraph::new("No service selected").block(block), area);
+            return;
+        };
+
+        let label = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![Line::from(vec![
+            Span::styled("Service: ", label),
+            Span::raw(service.name.as_str()),
+            Span::raw(match &service.tunnel {
+                Some(tunnel) => format!(" ({})", tunnel),
+                None => String::new(),
+            }),
+        ])];
+        lines.push(Line::from(vec![
+            Span::styled("Version: ", label),
+            Span::raw(service.banner()),
+        ]));
+
+        if service.cpes.is_empty() {
+            lines.push(Line::from(Span::styled("No CPEs reported", label)));
+        } else {
+            for (index, cpe) in service.cpes.iter().enumerate() {
+                let style = if index == self.selected_cpe {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                lines.push(Line::from(vec![
+                    Span::styled("CPE: ", label),
+                    Span::styled(cpe.as_str(), style),
+                ]));
+            }
+        }
+        if let Some(cpe) = self.selected_cpe() {
+            lines.push(Line::from(vec![
+                Span::styled("NVD: ", label),
+                Span::styled(nvd_search_url(cpe), Style::default().fg(Color::Cyan)),
+            ]));
+        }
+
+        let block = block.title_bottom(
+            Line::from(
+                "n: next CPE  c: copy CPE  u: copy NVD link  b: copy banner  Enter: full banner",
+            )
+            .style(label),
+        );
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_host_details(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title("Host");
+        let Some(host) = self.selected_host() else {
+            frame.render_widget(Paragraph::new("No host selected").block(block), area);
+            return;
+        };
+
+        let label = Style::default().fg(Color::DarkGray);
+        let addresses = host
+            .addresses
+            .iter()
+            .filter(|address| address.addr_type != AddressType::Mac)
+            .map(|address| address.addr.as_str())
+            .collect::<Vec<_>>();
+        let mut lines = vec![Line::from(vec![
+            Span::styled("Address: ", label),
+            Span::raw(addresses.join(", ")),
+        ])];
+        if !host.hostnames.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Hostnames: ", label),
+                Span::raw(host.hostnames.join(", ")),
+            ]));
+        }
+        if let Some(owner) = self.asn_owners.get(host.address()) {
+            lines.push(Line::from(vec![
+                Span::styled("Owner: ", label),
+                Span::raw(owner.as_str()),
+            ]));
+        }
+        if let Some(mac) = host.mac() {
+            let vendor = match (&mac.vendor, self.mac_vendor_lookup) {
+                (Some(vendor), _) => format!(" ({})", vendor),
+                (None, true) => oui::vendor(&mac.addr).map_or(String::new(), |vendor| {
+                    format!(" ({}, from OUI table)", vendor)
+                }),
+                (None, false) => String::new(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled("MAC: ", label),
+                Span::raw(mac.addr.as_str()),
+                Span::raw(vendor),
+            ]));
+        }
+        if let Some(os) = host.os_matches.first() {
+            lines.push(Line::from(vec![
+                Span::styled("OS: ", label),
+                Span::raw(format!("{} ({}%)", os.name, os.accuracy)),
+            ]));
+        }
+        if let Some(uptime) = &host.uptime {
+            let last_boot = match &uptime.last_boot {
+                Some(last_boot) => format!(" (last boot {})", last_boot),
+                None => String::new(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled("Uptime: ", label),
+                Span::raw(format_duration(Duration::from_secs(uptime.seconds))),
+                Span::raw(last_boot),
+            ]));
+        }
+        if let Some(distance) = host.distance {
+            lines.push(Line::from(vec![
+                Span::styled("Distance: ", label),
+                Span::raw(format!(
+                    "{} {}",
+                    distance,
+                    if distance == 1 { "hop" } else { "hops" }
+                )),
+            ]));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_summary(&self, frame: &mut Frame, area: Rect) {
+        let hint = Style::default().fg(Color::DarkGray);
+        let notes_height = self.notes.as_ref().map_or(0, |notes| {
+            notes.lines().count().min(MAX_NOTES_LINES) as u16 + 2
+        });
+        let rows = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(notes_height),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+        let total = self.summary.up + self.summary.down;
+        let ratio = if total == 0 {
+            0.0
+        } else {
+            self.summary.up as f64 / total as f64
+        };
+        let gauge = Gauge::default()
+            .block(Block::bordered().title("Hosts up"))
+            .gauge_style(Style::default().fg(Color::Green).bg(Color::DarkGray))
+            .ratio(ratio)
+            .label(format!(
+                "{} up, {} down",
+                self.summary.up, self.summary.down
+            ));
+        frame.render_widget(gauge, rows[0]);
+
+        if let Some(notes) = &self.notes {
+            frame.render_widget(
+                Paragraph::new(notes.as_str()).block(Block::bordered().title("Notes")),
+                rows[1],
+            );
+        }
+
+        let columns = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(rows[2]);
+        frame.render_widget(
+            count_chart(
+                "Most common open ports",
+                &self.summary.top_ports,
+                Color::Cyan,
+            ),
+            columns[0],
+        );
+        frame.render_widget(
+            count_chart(
+                "Hosts by OS family",
+                &self.summary.os_families,
+                Color::Yellow,
+            ),
+            columns[1],
+        );
+
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "d: back to hosts  f: findings",
+                hint,
+            ))),
+            rows[3],
+        );
+    }
+
+    fn render_routes(&self, results: &ScanResults, frame: &mut Frame, area: Rect) {
+        let hint = Style::default().fg(Color::DarkGray);
+        let lines = tree_lines(&self.hop_tree);
+        let block = Block::bordered()
+            .title(format!(
+                "Routes (traced hosts: {})",
+                self.hop_tree.iter().map(HopNode::host_count).sum::<usize>()
+            ))
+            .title_bottom(Line::from("t: back to hosts").style(hint));
+        if lines.is_empty() {
+            let message = Line::from("No routes in these results; run the scan with --traceroute");
+            frame.render_widget(Paragraph::new(message).block(block), area);
+            return;
+        }
+
+        let items = lines
+            .iter()
+            .map(|line| {
+                let node = line.node;
+                let mut spans = vec![
+                    Span::styled(line.prefix.clone(), hint),
+                    Span::raw(node.address.clone().unwrap_or_else(|| "*".to_string())),
+                ];
+                if let Some(hostname) = &node.hostname {
+                    spans.push(Span::raw(format!(" ({})", hostname)));
+                }
+                for host in node
+                    .hosts
+                    .iter()
+                    .filter_map(|&index| results.hosts.get(index))
+                {
+                    if node.address.as_deref() == Some(host.address()) {
+                        spans.push(Span::styled(
+                            "  scanned host",
+                            Style::default().fg(Color::Green),
+                        ));
+                    } else {
+                        spans.push(Span::styled(
+                            format!("  last hop to {}", host.address()),
+                            Style::default().fg(Color::Yellow),
+                        ));
+                    }
+                }
+                if !node.children.is_empty() {
+                    let count = node.host_count();
+                    let noun = if count == 1 { "host" } else { "hosts" };
+                    spans.push(Span::styled(format!("  {} {}", count, noun), hint));
+                }
+                Line::from(spans)
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.selected_hop));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_findings(&self, frame: &mut Frame, area: Rect) {
+        let rows = self.findings.iter().map(|finding| {
+            Row::new(vec![
+                Span::raw(finding.id.as_str()),
+                Span::styled(
+                    finding
+                        .cvss
+                        .map_or("-".to_string(), |cvss| format!("{:.1}", cvss)),
+                    Style::default().fg(cvss_color(finding.cvss)),
+                ),
+                Span::raw(finding.host.as_str()),
+                Span::raw(finding.port_label()),
+                Span::raw(finding.script.as_str()),
+                Span::styled(
+                    if finding.exploit { "yes" } else { "" },
+                    Style::default().fg(Color::Red),
+                ),
+            ])
+        });
+        let block = Block::bordered()
+            .title(format!(
+                "Findings ({}, sorted by {})",
+                self.findings.len(),
+                self.finding_table.sort_label().unwrap_or_default()
+            ))
+            .title_bottom(
+                Line::from("s: change sort  S: reverse  f: back to hosts")
+                    .style(Style::default().fg(Color::DarkGray)),
+            );
+        self.finding_table.render(frame, area, rows, block, true);
+    }
+
+    fn render_certificates(&self, frame: &mut Frame, area: Rect) {
+        let hint = Style::default().fg(Color::DarkGray);
+        let now = format_iso_timestamp(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+        );
+        let mut expired = 0;
+        let mut self_signed = 0;
+        let rows = self
+            .certificates
+            .iter()
+            .map(|certificate| {
+                // Expiry outweighs a self-signed issuer, which is often intended
+                let style = if certificate.is_expired(&now) {
+                    expired += 1;
+                    Style::default().fg(Color::Red)
+                } else if certificate.self_signed {
+                    self_signed += 1;
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                let issuer = if certificate.self_signed {
+                    "self-signed".to_string()
+                } else {
+                    certificate.issuer.clone()
+                };
+                Row::new(vec![
+                    certificate.host.clone(),
+                    certificate.port_label(),
+                    certificate.subject.clone(),
+                    issuer,
+                    certificate.sans.join(", "),
+                    certificate
+                        .not_after
+                        .as_deref()
+                        .map_or("-", |not_after| not_after.get(..10).unwrap_or(not_after))
+                        .to_string(),
+                ])
+                .style(style)
+            })
+            .collect::<Vec<_>>();
+        let block = Block::bordered()
+            .title(format!(
+                "Certificates ({}, {} expired, {} self-signed)",
+                self.certificates.len(),
+                expired,
+                self_signed
+            ))
+            .title_bottom(Line::from("s: change sort  S: reverse  C: back to hosts").style(hint));
+        if self.certificates.is_empty() {
+            let message = Line::from(
+                "No certificates in these results; run the ssl-cert script on TLS ports",
+            );
+            frame.render_widget(Paragraph::new(message).block(block), area);
+            return;
+        }
+        self.certificate_table
+            .render(frame, area, rows, block, true);
+    }
+
+    fn render_smb_identities(&self, frame: &mut Frame, area: Rect) {
+        let hint = Style::default().fg(Color::DarkGray);
+        let unknown = || Span::styled("-", hint);
+        let rows = self.smb_identities.iter().map(|identity| {
+            let text = |value: &Option<String>| match value {
+                Some(value) => Span::raw(value.clone()),
+                None => unknown(),
+            };
+            let signing = match identity.signing {
+                Some(signing) => {
+                    let color = match signing {
+                        Signing::Required => Color::Green,
+                        Signing::Enabled => Color::Yellow,
+                        Signing::Disabled => Color::Red,
+                    };
+                    Span::styled(signing.as_str(), Style::default().fg(color))
+                }
+                None => unknown(),
+            };
+            Row::new(vec![
+                Span::raw(identity.host.clone()),
+                text(&identity.computer_name),
+                text(&identity.domain),
+                text(&identity.workgroup),
+                text(&identity.os),
+                signing,
+            ])
+        });
+        let unsigned = self
+            .smb_identities
+            .iter()
+            .filter(|identity| {
+                identity
+                    .signing
+                    .is_some_and(|signing| signing != Signing::Required)
+            })
+            .count();
+        let block = Block::bordered()
+            .title(format!(
+                "Windows hosts ({}, {} without required signing)",
+                self.smb_identities.len(),
+                unsigned
+            ))
+            .title_bottom(Line::from("s: change sort  S: reverse  I: back to hosts").style(hint));
+        if self.smb_identities.is_empty() {
+            let message = Line::from(
+                "No SMB or NetBIOS details in these results; run smb-os-discovery, nbstat and smb-security-mode",
+            );
+            frame.render_widget(Paragraph::new(message).block(block), area);
+            return;
+        }
+        self.smb_table.render(frame, area, rows, block, true);
+    }
+
+    fn sort_smb_identities(&mut self) {
+        self.smb_table
+            .sort_rows(&mut self.smb_identities, |column, a, b| match column {
+                1 => a.computer_name.cmp(&b.computer_name),
+                2 => a.domain.cmp(&b.domain),
+                3 => a.workgroup.cmp(&b.workgroup),
+                4 => a.os.cmp(&b.os),
+                5 => a.signing.cmp(&b.signing),
+                _ => compare_hosts(&a.host, &b.host),
+            });
+    }
+
+    fn render_web_services(&self, frame: &mut Frame, area: Rect) {
+        let hint = Style::default().fg(Color::DarkGray);
+        let rows = self.web_services.iter().map(|service| {
+            Row::new(vec![
+                Span::raw(service.host.as_str()),
+                Span::raw(service.port.to_string()),
+                match &service.title {
+                    Some(title) => Span::raw(title.as_str()),
+                    None => Span::styled("no title", hint),
+                },
+                Span::raw(service.server.as_deref().unwrap_or("-")),
+            ])
+        });
+        let block = Block::bordered()
+            .title(format!("Web inventory ({})", self.web_services.len()))
+            .title_bottom(
+                Line::from("s: change sort  S: reverse  e: export as CSV  W: back to hosts")
+                    .style(hint),
+            );
+        if self.web_services.is_empty() {
+            let message = Line::from("No open web ports in these results");
+            frame.render_widget(Paragraph::new(message).block(block), area);
+            return;
+        }
+        self.web_table.render(frame, area, rows, block, true);
+    }
+
+    fn sort_web_services(&mut self) {
+        self.web_table
+            .sort_rows(&mut self.web_services, |column, a, b| match column {
+                1 => a.port.cmp(&b.port),
+                2 => a.title.cmp(&b.title),
+                3 => a.server.cmp(&b.server),
+                _ => compare_hosts(&a.host, &b.host).then_with(|| a.port.cmp(&b.port)),
+            });
+    }
+
+    fn sort_certificates(&mut self) {
+        self.certificate_table
+            .sort_rows(&mut self.certificates, |column, a, b| match column {
+                1 => (a.port, &a.protocol).cmp(&(b.port, &b.protocol)),
+                2 => a.subject.cmp(&b.subject),
+                3 => a.issuer.cmp(&b.issuer),
+                5 => a.not_after.cmp(&b.not_after),
+                _ => compare_hosts(&a.host, &b.host),
+            });
+    }
+
+    fn sort_findings(&mut self) {
+        let Some(sort) = self.finding_table.sort() else {
+            return;
+        };
+        let by = match sort.column {
+            0 => FindingSort::Id,
+            2 => FindingSort::Host,
+            3 => FindingSort::Port,
+            4 => FindingSort::Script,
+            _ => FindingSort::Cvss,
+        };
+        sort_findings(&mut self.findings, by);
+        if sort.reversed {
+            self.findings.reverse();
+        }
+    }
+
+    fn focus_block(&self, title: &str, focus: Focus) -> Block<'static> {
+        let border_style = if self.focus == focus {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        Block::bordered()
+            .title(title.to_string())
+            .border_style(border_style)
+    }
+
+    /// Rows of the host list, with subnet headers when grouping and collapsed groups left out
+    fn host_rows(&self) -> Vec<HostRow> {
+        let host_count = self
+            .results
+            .as_ref()
+            .map_or(0, |results| results.hosts.len());
+        if !self.grouped {
+            return (0..host_count).map(HostRow::Host).collect();
+        }
+        let mut rows = Vec::new();
+        for (index, group) in self.groups.iter().enumerate() {
+            rows.push(HostRow::Group(index));
+            if !self.collapsed.contains(&group.subnet) {
+                rows.extend(group.hosts.iter().map(|&host| HostRow::Host(host)));
+            }
+        }
+        rows
+    }
+
+    fn selected_group(&self) -> Option<&SubnetGroup> {
+        match self.host_rows().get(self.selected_row)? {
+            HostRow::Group(index) => self.groups.get(*index),
+            HostRow::Host(_) => None,
+        }
+    }
+
+    fn selected_host(&self) -> Option<&Host> {
+        match self.host_rows().get(self.selected_row)? {
+            HostRow::Host(index) => self.results.as_ref()?.hosts.get(*index),
+            HostRow::Group(_) => None,
+        }
+    }
+
+    /// Switch between the grouped and flat host list, keeping the selected host
+    fn toggle_grouping(&mut self) {
+        let selected = match self.host_rows().get(self.selected_row) {
+            Some(HostRow::Host(index)) => *index,
+            Some(HostRow::Group(index)) => self.groups[*index].hosts[0],
+            None => 0,
+        };
+        self.grouped = !self.grouped;
+        self.select_host(selected);
+    }
+
+    fn selected_port(&self) -> Option<&Port> {
+        if self.focus != Focus::Ports {
+            return None;
+        }
+        self.visible_ports()
+            .get(self.port_table.selected())
+            .copied()
+    }
+
+    /// Ports of the selected host in the table's order, without closed and filtered ones when
+    /// they are hidden
+    fn visible_ports(&self) -> Vec<&Port> {
+        let mut ports: Vec<&Port> = self
+            .selected_host()
+            .map_or(&[][..], |host| &host.ports)
+            .iter()
+            .filter(|port| {
+                !self.hide_closed
+                    || !matches!(
+                        port.state,
+                        PortState::Closed | PortState::Filtered | PortState::ClosedFiltered
+                    )
+            })
+            .collect();
+        self.port_table.sort_rows(&mut ports, compare_ports);
+        ports
+    }
+
+    /// Move through the host list; the port table moves its own selection
+    fn move_selection(&mut self, delta: isize) {
+        self.selected_row = self.selected_row.saturating_add_signed(delta);
+        self.port_table.select(0);
+        self.selected_cpe = 0;
+        self.clamp_selection();
+    }
+
+    fn clamp_selection(&mut self) {
+        let row_count = self.host_rows().len();
+        self.selected_row = self.selected_row.min(row_count.saturating_sub(1));
+        let port_count = self.visible_ports().len();
+        self.port_table.clamp(port_count);
+    }
+}
+
+fn port_table() -> SortableTable {
+    let mut table = SortableTable::new(vec![
+        Column::new("Port", Constraint::Length(10)),
+        Column::new("State", Constraint::Length(16)),
+        Column::new("Reason", Constraint::Length(20)),
+        Column::new("Service", Constraint::Length(14)),
+        Column::new("Banner", Constraint::Min(0)).with_sortable(false),
+    ]);
+    table.set_hidden(PORT_REASON_COLUMN, true);
+    table
+}
+
+/// How two ports compare in a column of the port table; states sort in legend order
+fn compare_ports(column: usize, a: &&Port, b: &&Port) -> Ordering {
+    let service = |port: &Port| {
+        port.service
+            .as_ref()
+            .map_or(String::new(), |service| service.name.clone())
+    };
+    let state = |port: &Port| PortState::ALL.iter().position(|state| *state == port.state);
+    match column {
+        1 => state(a).cmp(&state(b)),
+        PORT_REASON_COLUMN => port_reason(a).cmp(&port_reason(b)),
+        3 => service(a).cmp(&service(b)),
+        _ => (a.port, &a.protocol).cmp(&(b.port, &b.protocol)),
+    }
+}
+
+/// Horizontal bar chart of labelled counts
+fn count_chart<'a>(title: &'a str, counts: &'a [(String, u64)], color: Color) -> BarChart<'a> {
+    let bars = counts
+        .iter()
+        .map(|(label, count)| {
+            Bar::default()
+                .label(Line::from(label.as_str()))
+                .value(*count)
+                .style(Style::default().fg(color))
+        })
+        .collect::<Vec<_>>();
+    BarChart::default()
+        .block(Block::bordered().title(title))
+        .direction(Direction::Horizontal)
+        .bar_width(1)
+        .bar_gap(0)
+        .data(BarGroup::default().bars(&bars))
+}
+
+fn cvss_color(cvss: Option<f32>) -> Color {
+    match cvss {
+        Some(cvss) if cvss >= 9.0 => Color::Red,
+        Some(cvss) if cvss >= 7.0 => Color::LightRed,
+        Some(cvss) if cvss >= 4.0 => Color::Yellow,
+        Some(_) => Color::Green,
+        None => Color::DarkGray,
+    }
+}
+
+/// The reason nmap gave for a port state, with the TTL of the response when there was one
+fn port_reason(port: &Port) -> String {
+    match (&port.reason, port.reason_ttl) {
+        (Some(reason), Some(ttl)) => format!("{} (ttl {})", reason, ttl),
+        (Some(reason), None) => reason.clone(),
+        (None, _) => String::new(),
+    }
+}
@@ -9,6 +9,8 @@ use ratatui::{
 pub struct Checkbox {
     label: String,
     checked: bool,
+    /// Character inside the brackets when checked
+    mark: char,
     focused: bool,
     checked_style: Style,
     unchecked_style: Style,
@@ -21,6 +23,7 @@ impl Checkbox {
         Self {
             label: label.into(),
             checked: false,
+            mark: 'X',
             focused: false,
             checked_style: Style::default().fg(Color::Green),
             unchecked_style: Style::default().fg(Color::Gray),
@@ -34,6 +37,11 @@ impl Checkbox {
         self
     }
 
+    pub fn with_mark(mut self, mark: char) -> Self {
+        self.mark = mark;
+        self
+    }
+
     pub fn with_focused(mut self, focused: bool) -> Self {
         self.focused = focused;
         self
@@ -85,9 +93,9 @@ impl Checkbox {
         }
 
         let (checkbox_text, style) = if self.checked {
-            ("[X]", self.checked_style)
+            (format!("[{}]", self.mark), self.checked_style)
         } else {
-            ("[ ]", self.unchecked_style)
+            ("[ ]".to_string(), self.unchecked_style)
         };
 
         // Apply focused style if focused
@@ -161,4 +169,30 @@ mod tests {
         checkbox.set_focused(true);
         assert!(checkbox.is_focused());
     }
+
+    #[test]
+    fn test_render_states() {
+        let render = |checkbox: Checkbox| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 24, 1));
+            checkbox.render(buf.area, &mut buf);
+            format!("{:?}", buf)
+        };
+        insta::assert_snapshot!("checkbox", render(Checkbox::new("Ping scan (-sn)")));
+        insta::assert_snapshot!(
+            "checkbox_focused",
+            render(
+                Checkbox::new("Ping scan (-sn)")
+                    .with_checked(true)
+                    .with_focused(true)
+            )
+        );
+        insta::assert_snapshot!(
+            "checkbox_mark",
+            render(
+                Checkbox::new("Always resolve (-R)")
+                    .with_checked(true)
+                    .with_mark('R')
+            )
+        );
+    }
 }
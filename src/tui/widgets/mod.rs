@@ -1,3 +1,7 @@
 pub mod checkbox;
+pub mod progress;
 pub mod radio;
+pub mod stepper;
+pub mod table;
+pub mod text_area;
 pub mod text_input;
@@ -0,0 +1,84 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Gauge, Paragraph},
+};
+
+/// Frames of the spinner shown while the work left is unknown
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Progress of a long operation: a gauge when the share done is known, a spinner otherwise.
+/// The spinner moves on each `tick` of the event loop
+#[derive(Debug, Clone, Default)]
+pub struct Progress {
+    frame: usize,
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tick(&mut self) {
+        self.frame = (self.frame + 1) % SPINNER.len();
+    }
+
+    pub fn spinner(&self) -> &'static str {
+        SPINNER[self.frame]
+    }
+
+    /// Render a one-line gauge filled to `ratio`, or the spinner next to the label without one
+    pub fn render(&self, frame: &mut Frame, area: Rect, ratio: Option<f64>, label: &str) {
+        match ratio {
+            Some(ratio) => {
+                let ratio = clamp_ratio(ratio);
+                let gauge = Gauge::default()
+                    .gauge_style(Style::default().fg(Color::Green))
+                    .ratio(ratio)
+                    .label(format!("{} {:.1}%", label, ratio * 100.0));
+                frame.render_widget(gauge, area);
+            }
+            None => {
+                let line = Line::from(vec![
+                    Span::styled(self.spinner(), Style::default().fg(Color::Yellow)),
+                    Span::raw(" "),
+                    Span::styled(label.to_string(), Style::default().fg(Color::DarkGray)),
+                ]);
+                frame.render_widget(Paragraph::new(line), area);
+            }
+        }
+    }
+}
+
+/// A ratio the gauge accepts; nmap may report a little over 100% near the end of a phase
+fn clamp_ratio(ratio: f64) -> f64 {
+    if ratio.is_nan() {
+        0.0
+    } else {
+        ratio.clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let mut progress = Progress::new();
+        let first = progress.spinner();
+        progress.tick();
+        assert_ne!(progress.spinner(), first);
+        for _ in 1..SPINNER.len() {
+            progress.tick();
+        }
+        assert_eq!(progress.spinner(), first);
+
+        assert_eq!(clamp_ratio(0.25), 0.25);
+        assert_eq!(clamp_ratio(1.02), 1.0);
+        assert_eq!(clamp_ratio(-0.1), 0.0);
+        assert_eq!(clamp_ratio(f64::NAN), 0.0);
+    }
+}
@@ -314,4 +314,14 @@ mod tests {
         group.previous_focus();
         assert_eq!(group.focused_index(), Some(1));
     }
+
+    #[test]
+    fn test_render_states() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 1));
+        RadioGroup::new(vec!["T3", "T4", "T5"])
+            .with_selected(Some(1))
+            .with_focused(Some(2))
+            .render(buf.area, &mut buf);
+        insta::assert_snapshot!("radio_group_focused", format!("{:?}", buf));
+    }
 }
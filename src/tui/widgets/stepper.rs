@@ -0,0 +1,97 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+/// A number within a range, stepped up and down with + and -
+#[derive(Debug, Clone)]
+pub struct Stepper {
+    label: String,
+    value: u32,
+    max: u32,
+    focused: bool,
+    dimmed: bool,
+}
+
+impl Stepper {
+    pub fn new(label: impl Into<String>, max: u32) -> Self {
+        Self {
+            label: label.into(),
+            value: 0,
+            max,
+            focused: false,
+            dimmed: false,
+        }
+    }
+
+    pub fn with_value(mut self, value: u32) -> Self {
+        self.value = value.min(self.max);
+        self
+    }
+
+    pub fn with_focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    pub fn with_dimmed(mut self, dimmed: bool) -> Self {
+        self.dimmed = dimmed;
+        self
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        let label_style = if self.focused {
+            Style::default().fg(Color::Yellow)
+        } else if self.dimmed {
+            Style::default().fg(Color::DarkGray)
+        } else {
+            Style::default()
+        };
+        // A button is grayed out at the end of the range it can no longer move towards
+        let button = |enabled: bool| {
+            if enabled && !self.dimmed {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            }
+        };
+        let value_style = if self.value > 0 && !self.dimmed {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        Line::from(vec![
+            Span::styled(format!("{}: ", self.label), label_style),
+            Span::styled("[-]", button(self.value > 0)),
+            Span::styled(format!(" {:>2} ", self.value), value_style),
+            Span::styled("[+]", button(self.value < self.max)),
+        ])
+        .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_states() {
+        let render = |stepper: Stepper| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 32, 1));
+            stepper.render(buf.area, &mut buf);
+            format!("{:?}", buf)
+        };
+        insta::assert_snapshot!("stepper", render(Stepper::new("Verbosity (-v)", 10)));
+        insta::assert_snapshot!(
+            "stepper_focused_max",
+            render(
+                Stepper::new("Verbosity (-v)", 10)
+                    .with_value(12)
+                    .with_focused(true)
+            )
+        );
+    }
+}
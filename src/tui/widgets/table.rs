@@ -0,0 +1,298 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Row, Table, TableState},
+};
+use std::cmp::Ordering;
+
+use crate::tui::widgets::text_input::EventResult;
+
+/// Rows moved by PageUp and PageDown
+const PAGE: usize = 10;
+
+/// A column of a `SortableTable`
+#[derive(Debug, Clone)]
+pub struct Column {
+    pub title: &'static str,
+    pub width: Constraint,
+    pub sortable: bool,
+    pub hidden: bool,
+}
+
+impl Column {
+    pub fn new(title: &'static str, width: Constraint) -> Self {
+        Self {
+            title,
+            width,
+            sortable: true,
+            hidden: false,
+        }
+    }
+
+    pub fn with_sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+}
+
+/// The column a table is sorted by, and whether its order is reversed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: usize,
+    pub reversed: bool,
+}
+
+/// What a key did to a table, for its owner to follow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableEvent {
+    /// Another row was selected
+    Selected(usize),
+    /// The sort changed; None is the order the rows came in
+    Sorted(Option<SortKey>),
+    /// Enter was pressed on a row
+    Activated(usize),
+}
+
+/// Selection and sort state of a table whose rows its owner keeps. `s` sorts by the next
+/// sortable column and `S` reverses the order; the owner sorts its rows with `sort_rows`
+#[derive(Debug, Clone)]
+pub struct SortableTable {
+    columns: Vec<Column>,
+    sort: Option<SortKey>,
+    /// Whether cycling through the columns comes back to the rows' own order
+    keeps_order: bool,
+    selected: usize,
+}
+
+impl SortableTable {
+    /// A table showing rows in their own order until sorted
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self {
+            columns,
+            sort: None,
+            keeps_order: true,
+            selected: 0,
+        }
+    }
+
+    /// Sort by a column from the start, so the rows' own order is never shown
+    pub fn with_sort(mut self, column: usize) -> Self {
+        self.sort = Some(SortKey {
+            column,
+            reversed: false,
+        });
+        self.keeps_order = false;
+        self
+    }
+
+    pub fn sort(&self) -> Option<SortKey> {
+        self.sort
+    }
+
+    /// Title of the column the table is sorted by, with an arrow that flips when reversed
+    pub fn sort_label(&self) -> Option<String> {
+        self.sort.map(|sort| self.column_label(sort.column))
+    }
+
+    fn column_label(&self, column: usize) -> String {
+        let title = self.columns[column].title;
+        match self.sort {
+            Some(sort) if sort.column == column && sort.reversed => format!("{} ↑", title),
+            Some(sort) if sort.column == column => format!("{} ↓", title),
+            _ => title.to_string(),
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.selected = index;
+    }
+
+    /// Width of the visible fixed-width columns and the spacing after each, which leaves the rest
+    /// of a row to the other columns
+    pub fn fixed_width(&self) -> u16 {
+        self.columns
+            .iter()
+            .filter(|column| !column.hidden)
+            .map(|column| match column.width {
+                Constraint::Length(length) => length + 1,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Keep the selection on a row after the row count changes
+    pub fn clamp(&mut self, row_count: usize) {
+        self.selected = self.selected.min(row_count.saturating_sub(1));
+    }
+
+    pub fn set_hidden(&mut self, column: usize, hidden: bool) {
+        self.columns[column].hidden = hidden;
+        if hidden && self.sort.is_some_and(|sort| sort.column == column) {
+            self.sort = None;
+        }
+    }
+
+    /// Sort rows by the sorted column, given how two rows compare in a column
+    pub fn sort_rows<T>(&self, rows: &mut [T], compare: impl Fn(usize, &T, &T) -> Ordering) {
+        let Some(sort) = self.sort else {
+            return;
+        };
+        rows.sort_by(|a, b| {
+            let ordering = compare(sort.column, a, b);
+            if sort.reversed {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    pub fn handle_event(&mut self, event: &Event, row_count: usize) -> EventResult<TableEvent> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        let last = row_count.saturating_sub(1);
+        let selected = match key.code {
+            KeyCode::Char('j') | KeyCode::Down => (self.selected + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => self.selected.saturating_sub(1),
+            KeyCode::PageDown => (self.selected + PAGE).min(last),
+            KeyCode::PageUp => self.selected.saturating_sub(PAGE),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            KeyCode::Char('s') => {
+                self.sort = self.next_sort();
+                self.selected = 0;
+                return EventResult::Submit(TableEvent::Sorted(self.sort));
+            }
+            KeyCode::Char('S') => {
+                let Some(sort) = self.sort.as_mut() else {
+                    return EventResult::Consumed;
+                };
+                sort.reversed = !sort.reversed;
+                self.selected = 0;
+                return EventResult::Submit(TableEvent::Sorted(self.sort));
+            }
+            KeyCode::Enter if row_count > 0 => {
+                return EventResult::Submit(TableEvent::Activated(self.selected));
+            }
+            _ => return EventResult::Ignored,
+        };
+        if selected == self.selected {
+            return EventResult::Consumed;
+        }
+        self.selected = selected;
+        EventResult::Submit(TableEvent::Selected(selected))
+    }
+
+    /// The sortable column after the sorted one, wrapping to the rows' own order if the table
+    /// keeps it
+    fn next_sort(&self) -> Option<SortKey> {
+        let start = self.sort.map_or(0, |sort| sort.column + 1);
+        let sortable = |column: &usize| {
+            let column = &self.columns[*column];
+            column.sortable && !column.hidden
+        };
+        let next = (start..self.columns.len()).find(sortable).or_else(|| {
+            if self.keeps_order {
+                None
+            } else {
+                (0..self.columns.len()).find(sortable)
+            }
+        });
+        next.map(|column| SortKey {
+            column,
+            reversed: false,
+        })
+    }
+
+    /// Render rows with one cell per visible column, highlighting the selection when focused
+    pub fn render<'a>(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        rows: impl IntoIterator<Item = Row<'a>>,
+        block: Block<'a>,
+        focused: bool,
+    ) {
+        let visible = self.columns.iter().enumerate().filter(|(_, c)| !c.hidden);
+        let header = visible
+            .clone()
+            .map(|(index, _)| self.column_label(index))
+            .collect::<Vec<_>>();
+        let rows = rows.into_iter().collect::<Vec<_>>();
+        let has_rows = !rows.is_empty();
+        let table = Table::new(rows, visible.map(|(_, column)| column.width))
+            .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(block)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state =
+            TableState::default().with_selected((focused && has_rows).then_some(self.selected));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::{KeyEvent, KeyModifiers};
+
+    fn press(table: &mut SortableTable, c: char) -> EventResult<TableEvent> {
+        table.handle_event(
+            &Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)),
+            3,
+        )
+    }
+
+    #[test]
+    fn test_sortable_table() {
+        let mut table = SortableTable::new(vec![
+            Column::new("Port", Constraint::Length(10)),
+            Column::new("Reason", Constraint::Length(10)),
+            Column::new("Banner", Constraint::Min(0)).with_sortable(false),
+        ]);
+        table.set_hidden(1, true);
+        let mut rows = vec![(443, "b"), (22, "c"), (80, "a")];
+        let compare = |column: usize, a: &(u16, &str), b: &(u16, &str)| match column {
+            0 => a.0.cmp(&b.0),
+            _ => a.1.cmp(b.1),
+        };
+
+        assert!(matches!(
+            press(&mut table, 's'),
+            EventResult::Submit(TableEvent::Sorted(Some(SortKey { column: 0, .. })))
+        ));
+        table.sort_rows(&mut rows, compare);
+        assert_eq!(rows, vec![(22, "c"), (80, "a"), (443, "b")]);
+        press(&mut table, 'S');
+        table.sort_rows(&mut rows, compare);
+        assert_eq!(rows[0], (443, "b"));
+        assert_eq!(table.sort_label().as_deref(), Some("Port ↑"));
+
+        // Hidden and unsortable columns are skipped, back to the rows' own order
+        assert!(matches!(
+            press(&mut table, 's'),
+            EventResult::Submit(TableEvent::Sorted(None))
+        ));
+        assert!(matches!(
+            press(&mut table, 'j'),
+            EventResult::Submit(TableEvent::Selected(1))
+        ));
+        press(&mut table, 'j');
+        assert!(matches!(press(&mut table, 'j'), EventResult::Consumed));
+        assert_eq!(table.selected(), 2);
+
+        let mut sorted = SortableTable::new(vec![
+            Column::new("ID", Constraint::Length(10)),
+            Column::new("CVSS", Constraint::Length(6)),
+        ])
+        .with_sort(1);
+        press(&mut sorted, 's');
+        assert_eq!(sorted.sort().map(|sort| sort.column), Some(0));
+    }
+}
@@ -0,0 +1,429 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::tui::widgets::text_input::EventResult;
+
+/// A screen row of wrapped text: the line it belongs to and the byte range it shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct VisualRow {
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+/// Multi-line text editor that wraps long lines; Ctrl-s submits the text, since Enter starts a
+/// new line
+#[derive(Debug, Clone)]
+pub struct TextArea {
+    lines: Vec<String>,
+    row: usize,
+    /// Byte position of the cursor in its line
+    cursor: usize,
+    /// First screen row shown, moved to keep the cursor visible
+    scroll: usize,
+    /// Columns and rows of the last render, which wrapping and paging follow
+    width: usize,
+    height: usize,
+    placeholder: Option<String>,
+}
+
+impl TextArea {
+    pub fn new() -> Self {
+        Self {
+            lines: vec![String::new()],
+            row: 0,
+            cursor: 0,
+            scroll: 0,
+            width: 0,
+            height: 0,
+            placeholder: None,
+        }
+    }
+
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Replace the text, with the cursor at its end
+    pub fn set_content(&mut self, content: &str) {
+        self.lines = content.split('\n').map(str::to_string).collect();
+        self.row = self.lines.len() - 1;
+        self.cursor = self.lines[self.row].len();
+        self.scroll = 0;
+    }
+
+    pub fn content(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.len() == 1 && self.lines[0].is_empty()
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<String> {
+        match event {
+            Event::Key(key) => self.handle_key_event(*key),
+            Event::Paste(text) => {
+                for c in text.chars().filter(|c| *c != '\r') {
+                    self.insert_char(c);
+                }
+                EventResult::Consumed
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> EventResult<String> {
+        match key.code {
+            KeyCode::Char('s') if key.modifiers == KeyModifiers::CONTROL => {
+                EventResult::Submit(self.content())
+            }
+            KeyCode::Char(c)
+                if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
+            {
+                self.insert_char(c);
+                EventResult::Consumed
+            }
+            KeyCode::Enter => {
+                self.insert_char('\n');
+                EventResult::Consumed
+            }
+            KeyCode::Backspace => {
+                self.backspace();
+                EventResult::Consumed
+            }
+            KeyCode::Delete => {
+                self.delete_char();
+                EventResult::Consumed
+            }
+            KeyCode::Left => {
+                self.move_left();
+                EventResult::Consumed
+            }
+            KeyCode::Right => {
+                self.move_right();
+                EventResult::Consumed
+            }
+            KeyCode::Up => {
+                self.move_vertically(-1);
+                EventResult::Consumed
+            }
+            KeyCode::Down => {
+                self.move_vertically(1);
+                EventResult::Consumed
+            }
+            KeyCode::PageUp => {
+                self.move_vertically(-(self.page() as isize));
+                EventResult::Consumed
+            }
+            KeyCode::PageDown => {
+                self.move_vertically(self.page() as isize);
+                EventResult::Consumed
+            }
+            KeyCode::Home => {
+                self.cursor = 0;
+                EventResult::Consumed
+            }
+            KeyCode::End => {
+                self.cursor = self.lines[self.row].len();
+                EventResult::Consumed
+            }
+            KeyCode::Esc => EventResult::Cancel,
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn insert_char(&mut self, c: char) {
+        if c == '\n' {
+            let rest = self.lines[self.row].split_off(self.cursor);
+            self.row += 1;
+            self.lines.insert(self.row, rest);
+            self.cursor = 0;
+        } else {
+            self.lines[self.row].insert(self.cursor, c);
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            let previous = self.previous_boundary();
+            self.lines[self.row].replace_range(previous..self.cursor, "");
+            self.cursor = previous;
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.cursor = self.lines[self.row].len();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn delete_char(&mut self) {
+        if self.cursor < self.lines[self.row].len() {
+            let next = self.next_boundary();
+            self.lines[self.row].replace_range(self.cursor..next, "");
+        } else if self.row + 1 < self.lines.len() {
+            let line = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn move_left(&mut self) {
+        if self.cursor > 0 {
+            self.cursor = self.previous_boundary();
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.cursor = self.lines[self.row].len();
+        }
+    }
+
+    fn move_right(&mut self) {
+        if self.cursor < self.lines[self.row].len() {
+            self.cursor = self.next_boundary();
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.cursor = 0;
+        }
+    }
+
+    /// Screen rows to move for PageUp and PageDown, keeping one row of context
+    fn page(&self) -> usize {
+        self.height.saturating_sub(1).max(1)
+    }
+
+    /// Move by screen rows, staying in the same column where the row is long enough
+    fn move_vertically(&mut self, rows: isize) {
+        let visual_rows = self.visual_rows();
+        let (current, column) = self.cursor_position(&visual_rows);
+        let target = current
+            .saturating_add_signed(rows)
+            .min(visual_rows.len() - 1);
+        let VisualRow { line, start, end } = visual_rows[target];
+        let mut cursor = start + byte_at_column(&self.lines[line][start..end], column);
+        // The end of a wrapped row is the start of the next one, so stay before it
+        if cursor == end && end < self.lines[line].len() {
+            cursor = self.lines[line][start..end]
+                .grapheme_indices(true)
+                .next_back()
+                .map_or(start, |(index, _)| start + index);
+        }
+        self.row = line;
+        self.cursor = cursor;
+    }
+
+    /// The screen rows of the text, wrapping lines to the width of the last render
+    fn visual_rows(&self) -> Vec<VisualRow> {
+        let mut rows = Vec::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            let mut start = 0;
+            loop {
+                let end = start + wrap_end(&line[start..], self.width);
+                rows.push(VisualRow {
+                    line: index,
+                    start,
+                    end,
+                });
+                if end >= line.len() {
+                    break;
+                }
+                start = end;
+            }
+        }
+        rows
+    }
+
+    /// The screen row and column of the cursor
+    fn cursor_position(&self, visual_rows: &[VisualRow]) -> (usize, usize) {
+        let index = visual_rows
+            .iter()
+            .position(|row| {
+                row.line == self.row
+                    && row.start <= self.cursor
+                    && (self.cursor < row.end || row.end == self.lines[row.line].len())
+            })
+            .unwrap_or_default();
+        let row = visual_rows[index];
+        (index, self.lines[row.line][row.start..self.cursor].width())
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.lines[self.row][..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.lines[self.row][self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |grapheme| self.cursor + grapheme.len())
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, editing: bool) {
+        // Leave a column free for the cursor at the end of a full row
+        self.width = usize::from(area.width.saturating_sub(1)).max(1);
+        self.height = usize::from(area.height).max(1);
+        let visual_rows = self.visual_rows();
+        let (cursor_row, cursor_column) = self.cursor_position(&visual_rows);
+        if cursor_row < self.scroll {
+            self.scroll = cursor_row;
+        } else if cursor_row >= self.scroll + self.height {
+            self.scroll = cursor_row + 1 - self.height;
+        }
+
+        if self.is_empty() {
+            let placeholder = self.placeholder.as_deref().unwrap_or_default();
+            Paragraph::new(Line::from(Span::styled(
+                placeholder,
+                Style::default().fg(Color::DarkGray),
+            )))
+            .render(area, buf);
+        } else {
+            let lines = visual_rows
+                .iter()
+                .skip(self.scroll)
+                .take(self.height)
+                .map(|row| Line::from(&self.lines[row.line][row.start..row.end]))
+                .collect::<Vec<_>>();
+            Paragraph::new(lines).render(area, buf);
+        }
+
+        if editing {
+            let column = cursor_column as u16;
+            let y = area.y + (cursor_row - self.scroll) as u16;
+            if column < area.width
+                && let Some(cell) = buf.cell_mut((area.x + column, y))
+            {
+                cell.set_style(Style::default().add_modifier(Modifier::REVERSED));
+            }
+        }
+    }
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Byte length of the part of `text` that fits in `width` columns, broken after a space where
+/// possible; a width of 0 doesn't wrap
+fn wrap_end(text: &str, width: usize) -> usize {
+    if width == 0 || text.width() <= width {
+        return text.len();
+    }
+    let mut used = 0;
+    let mut end = 0;
+    let mut after_space = None;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        used += grapheme.width();
+        if used > width {
+            break;
+        }
+        end = index + grapheme.len();
+        if grapheme == " " {
+            after_space = Some(end);
+        }
+    }
+    if end == 0 {
+        // A character wider than the row still needs a row of its own
+        return text.graphemes(true).next().map_or(text.len(), str::len);
+    }
+    after_space.unwrap_or(end)
+}
+
+/// Byte position of the grapheme at a terminal column, or the end of a shorter line
+fn byte_at_column(line: &str, column: usize) -> usize {
+    let mut width = 0;
+    for (index, grapheme) in line.grapheme_indices(true) {
+        if width >= column {
+            return index;
+        }
+        width += grapheme.width();
+    }
+    line.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(area: &mut TextArea, code: KeyCode) {
+        area.handle_event(&Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn test_text_area_editing() {
+        let mut area = TextArea::new();
+        area.set_content("ticket 42\nscope: 扫描 lab");
+        press(&mut area, KeyCode::Home);
+        press(&mut area, KeyCode::Backspace);
+        assert_eq!(area.content(), "ticket 42scope: 扫描 lab");
+        press(&mut area, KeyCode::Enter);
+        press(&mut area, KeyCode::Char('-'));
+        assert_eq!(area.content(), "ticket 42\n-scope: 扫描 lab");
+        press(&mut area, KeyCode::Up);
+        press(&mut area, KeyCode::End);
+        press(&mut area, KeyCode::Delete);
+        assert_eq!(area.content(), "ticket 42-scope: 扫描 lab");
+    }
+
+    #[test]
+    fn test_text_area_wrapping() {
+        let mut area = TextArea::new();
+        area.set_content("ticket 42 lab only\nabcdefghij");
+        area.width = 8;
+        let rows: Vec<&str> = area
+            .visual_rows()
+            .iter()
+            .map(|row| &area.lines[row.line][row.start..row.end])
+            .collect();
+        assert_eq!(rows, vec!["ticket ", "42 lab ", "only", "abcdefgh", "ij"]);
+
+        // Up from the end of "ij" keeps column 2 through the wrapped rows
+        press(&mut area, KeyCode::Up);
+        assert_eq!((area.row, area.cursor), (1, 2));
+        press(&mut area, KeyCode::Up);
+        assert_eq!((area.row, area.cursor), (0, 16));
+        press(&mut area, KeyCode::Up);
+        assert_eq!((area.row, area.cursor), (0, 9));
+        press(&mut area, KeyCode::Home);
+        area.height = 3;
+        press(&mut area, KeyCode::PageDown);
+        assert_eq!((area.row, area.cursor), (0, 14));
+    }
+
+    #[test]
+    fn test_text_area_vertical_movement() {
+        let mut area = TextArea::new();
+        area.set_content("扫描目标\nab\nabcdef");
+        press(&mut area, KeyCode::Up);
+        assert_eq!((area.row, area.cursor), (1, 2));
+        press(&mut area, KeyCode::Down);
+        press(&mut area, KeyCode::Left);
+        press(&mut area, KeyCode::Up);
+        press(&mut area, KeyCode::Up);
+        // Column 1 is inside the first wide character, so the cursor lands after it
+        assert_eq!((area.row, area.cursor), (0, 3));
+        press(&mut area, KeyCode::Left);
+        press(&mut area, KeyCode::Left);
+        assert_eq!((area.row, area.cursor), (0, 0));
+        assert!(matches!(
+            area.handle_event(&Event::Key(KeyEvent::new(
+                KeyCode::Char('s'),
+                KeyModifiers::CONTROL
+            ))),
+            EventResult::Submit(content) if content == "扫描目标\nab\nabcdef"
+        ));
+    }
+}
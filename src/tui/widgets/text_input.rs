@@ -8,6 +8,16 @@ use ratatui::{
 };
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::scan::{
+    duration::parse_time_spec,
+    ip_options::IpOptions,
+    parser::{dedupe, list_items},
+    proxies::check_proxy,
+};
 
 // ============================================================================
 // Event Result
@@ -28,6 +38,9 @@ pub enum InputWidget {
     VecString(TextInput<Vec<String>>),
     VecInt(TextInput<Vec<u32>>),
     Path(CompletingInput),
+    Args(ArgsInput),
+    Choice(ChoiceInput),
+    Hex(HexInput),
 }
 
 #[derive(Debug)]
@@ -49,12 +62,18 @@ impl InputWidget {
             InputWidget::VecString(input) => input.render(area, buf, focused, editing),
             InputWidget::VecInt(input) => input.render(area, buf, focused, editing),
             InputWidget::Path(input) => input.render(area, buf, focused, editing),
+            InputWidget::Args(input) => input.render(area, buf, focused, editing),
+            InputWidget::Choice(input) => input.render(area, buf, focused, editing),
+            InputWidget::Hex(input) => input.render(area, buf, focused, editing),
         }
     }
 
     pub fn render_dropdown_overlay(&self, buf: &mut Buffer) {
-        if let InputWidget::Path(input) = self {
-            input.render_dropdown_overlay(buf);
+        match self {
+            InputWidget::Path(input) => input.render_dropdown_overlay(buf),
+            InputWidget::Args(input) => input.render_dropdown_overlay(buf),
+            InputWidget::Choice(input) => input.render_dropdown_overlay(buf),
+            _ => {}
         }
     }
 
@@ -96,6 +115,24 @@ impl InputWidget {
                 EventResult::Cancel => EventResult::Cancel,
                 EventResult::Ignored => EventResult::Ignored,
             },
+            InputWidget::Args(input) => match input.handle_event(event) {
+                EventResult::Submit(v) => EventResult::Submit(InputValue::String(v)),
+                EventResult::Consumed => EventResult::Consumed,
+                EventResult::Cancel => EventResult::Cancel,
+                EventResult::Ignored => EventResult::Ignored,
+            },
+            InputWidget::Choice(input) => match input.handle_event(event) {
+                EventResult::Submit(v) => EventResult::Submit(InputValue::String(v)),
+                EventResult::Consumed => EventResult::Consumed,
+                EventResult::Cancel => EventResult::Cancel,
+                EventResult::Ignored => EventResult::Ignored,
+            },
+            InputWidget::Hex(input) => match input.handle_event(event) {
+                EventResult::Submit(v) => EventResult::Submit(InputValue::String(v)),
+                EventResult::Consumed => EventResult::Consumed,
+                EventResult::Cancel => EventResult::Cancel,
+                EventResult::Ignored => EventResult::Ignored,
+            },
         }
     }
 
@@ -107,6 +144,23 @@ impl InputWidget {
             InputWidget::VecString(input) => input.clear(),
             InputWidget::VecInt(input) => input.clear(),
             InputWidget::Path(input) => input.clear(),
+            InputWidget::Args(input) => input.clear(),
+            InputWidget::Choice(input) => input.clear(),
+            InputWidget::Hex(input) => input.input.clear(),
+        }
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        match self {
+            InputWidget::String(input) => input.set_label(label),
+            InputWidget::Int(input) => input.set_label(label),
+            InputWidget::Float(input) => input.set_label(label),
+            InputWidget::VecString(input) => input.set_label(label),
+            InputWidget::VecInt(input) => input.set_label(label),
+            InputWidget::Path(input) => input.set_label(label),
+            InputWidget::Args(input) => input.set_label(label),
+            InputWidget::Choice(input) => input.set_label(label),
+            InputWidget::Hex(input) => input.input.set_label(label),
         }
     }
 
@@ -118,6 +172,39 @@ impl InputWidget {
             InputWidget::VecString(input) => input.set_content(content),
             InputWidget::VecInt(input) => input.set_content(content),
             InputWidget::Path(input) => input.set_content(content),
+            InputWidget::Args(input) => input.set_content(content),
+            InputWidget::Choice(input) => input.set_content(content),
+            InputWidget::Hex(input) => input.input.set_content(content),
+        }
+    }
+
+    /// Dim the field when it has no effect with the rest of the scan
+    pub fn set_dimmed(&mut self, dimmed: bool) {
+        match self {
+            InputWidget::String(input) => input.set_dimmed(dimmed),
+            InputWidget::Int(input) => input.set_dimmed(dimmed),
+            InputWidget::Float(input) => input.set_dimmed(dimmed),
+            InputWidget::VecString(input) => input.set_dimmed(dimmed),
+            InputWidget::VecInt(input) => input.set_dimmed(dimmed),
+            InputWidget::Path(input) => input.input.set_dimmed(dimmed),
+            InputWidget::Args(input) => input.input.set_dimmed(dimmed),
+            InputWidget::Choice(input) => input.input.set_dimmed(dimmed),
+            InputWidget::Hex(input) => input.input.set_dimmed(dimmed),
+        }
+    }
+
+    /// Set the previously submitted values that Up and Down recall, oldest first
+    pub fn set_history(&mut self, history: Vec<String>) {
+        match self {
+            InputWidget::String(input) => input.set_history(history),
+            InputWidget::Int(input) => input.set_history(history),
+            InputWidget::Float(input) => input.set_history(history),
+            InputWidget::VecString(input) => input.set_history(history),
+            InputWidget::VecInt(input) => input.set_history(history),
+            InputWidget::Path(input) => input.set_history(history),
+            InputWidget::Args(input) => input.set_history(history),
+            InputWidget::Choice(input) => input.set_history(history),
+            InputWidget::Hex(input) => input.input.set_history(history),
         }
     }
 
@@ -131,6 +218,9 @@ impl InputWidget {
             }
             (InputWidget::VecInt(input), InputValue::VecInt(value)) => input.set_typed_value(value),
             (InputWidget::Path(input), InputValue::Path(value)) => input.set_typed_value(value),
+            (InputWidget::Args(input), InputValue::String(value)) => input.set_typed_value(value),
+            (InputWidget::Choice(input), InputValue::String(value)) => input.set_typed_value(value),
+            (InputWidget::Hex(input), InputValue::String(value)) => input.set_typed_value(value),
             _ => {}
         }
     }
@@ -143,6 +233,9 @@ impl InputWidget {
             InputWidget::VecString(input) => input.content(),
             InputWidget::VecInt(input) => input.content(),
             InputWidget::Path(input) => input.content(),
+            InputWidget::Args(input) => input.content(),
+            InputWidget::Choice(input) => input.content(),
+            InputWidget::Hex(input) => input.input.content(),
         }
     }
 }
@@ -171,40 +264,38 @@ impl InputBuffer {
     }
 
     fn delete_char(&mut self) {
-        if self.cursor < self.content.len() {
-            self.content.remove(self.cursor);
-        }
+        let next = self.next_boundary();
+        self.content.replace_range(self.cursor..next, "");
     }
 
     fn backspace(&mut self) {
-        if self.cursor > 0 {
-            let mut new_cursor = self.cursor - 1;
-            while new_cursor > 0 && !self.content.is_char_boundary(new_cursor) {
-                new_cursor -= 1;
-            }
-            self.content.remove(new_cursor);
-            self.cursor = new_cursor;
-        }
+        let previous = self.previous_boundary();
+        self.content.replace_range(previous..self.cursor, "");
+        self.cursor = previous;
     }
 
     fn move_cursor_left(&mut self) {
-        if self.cursor > 0 {
-            let mut new_cursor = self.cursor - 1;
-            while new_cursor > 0 && !self.content.is_char_boundary(new_cursor) {
-                new_cursor -= 1;
-            }
-            self.cursor = new_cursor;
-        }
+        self.cursor = self.previous_boundary();
     }
 
     fn move_cursor_right(&mut self) {
-        if self.cursor < self.content.len() {
-            let mut new_cursor = self.cursor + 1;
-            while new_cursor < self.content.len() && !self.content.is_char_boundary(new_cursor) {
-                new_cursor += 1;
-            }
-            self.cursor = new_cursor;
-        }
+        self.cursor = self.next_boundary();
+    }
+
+    // Byte position of the grapheme before the cursor, so emoji sequences move as one
+    fn previous_boundary(&self) -> usize {
+        self.content[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    // Byte position after the grapheme at the cursor
+    fn next_boundary(&self) -> usize {
+        self.content[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |grapheme| self.cursor + grapheme.len())
     }
 
     fn move_cursor_start(&mut self) {
@@ -229,9 +320,35 @@ impl InputBuffer {
         self.content = content;
     }
 
-    // Get cursor position in characters (for rendering)
+    // The same text with every grapheme shown as *, for hiding secrets
+    fn masked(&self) -> InputBuffer {
+        let before = self.content[..self.cursor].graphemes(true).count();
+        let after = self.content[self.cursor..].graphemes(true).count();
+        InputBuffer {
+            content: "*".repeat(before + after),
+            cursor: before,
+        }
+    }
+
+    // Get cursor position in terminal columns (for rendering)
     fn cursor_position(&self) -> usize {
-        self.content[..self.cursor].chars().count()
+        self.content[..self.cursor].width()
+    }
+
+    // Text shown in a box `width` columns wide, scrolled so the cursor stays visible,
+    // and the column of the cursor within it
+    fn visible(&self, width: usize) -> (&str, usize) {
+        let mut start = 0;
+        let mut column = self.cursor_position();
+        let mut graphemes = self.content[..self.cursor].grapheme_indices(true);
+        while column >= width.max(1) {
+            let Some((index, grapheme)) = graphemes.next() else {
+                break;
+            };
+            start = index + grapheme.len();
+            column -= grapheme.width();
+        }
+        (&self.content[start..], column)
     }
 }
 
@@ -288,6 +405,23 @@ impl Parser<f32> for FloatParser {
     }
 }
 
+/// Accepts a ratio from 0 to 1, such as the share of scans that found a port open
+pub struct RatioParser;
+
+impl Parser<f32> for RatioParser {
+    fn parse(&self, input: &str) -> Result<f32, String> {
+        let ratio = FloatParser.parse(input.trim())?;
+        if !(0.0..=1.0).contains(&ratio) {
+            return Err(format!("Ratio must be between 0 and 1: {}", input.trim()));
+        }
+        Ok(ratio)
+    }
+
+    fn format(&self, value: &f32) -> String {
+        value.to_string()
+    }
+}
+
 pub struct VecStringParser;
 
 impl Parser<Vec<String>> for VecStringParser {
@@ -307,22 +441,27 @@ impl Parser<Vec<String>> for VecStringParser {
     }
 }
 
+/// Accepts numbers separated by commas, semicolons or whitespace, dropping repeated ones
 pub struct VecIntParser;
 
+impl VecIntParser {
+    /// How many entries of the input parsing drops as repeats
+    pub fn duplicates(input: &str) -> usize {
+        let mut values: Vec<&str> = list_items(input).collect();
+        dedupe(&mut values)
+    }
+}
+
 impl Parser<Vec<u32>> for VecIntParser {
     fn parse(&self, input: &str) -> Result<Vec<u32>, String> {
-        if input.trim().is_empty() {
-            return Ok(Vec::new());
-        }
-        input
-            .split(",")
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
+        let mut values = list_items(input)
             .map(|s| {
                 s.parse::<u32>()
                     .map_err(|_| format!("Invalid integer: {}", s))
             })
-            .collect()
+            .collect::<Result<Vec<_>, _>>()?;
+        dedupe(&mut values);
+        Ok(values)
     }
 
     fn format(&self, value: &Vec<u32>) -> String {
@@ -334,6 +473,204 @@ impl Parser<Vec<u32>> for VecIntParser {
     }
 }
 
+pub struct DurationParser;
+
+impl Parser<Duration> for DurationParser {
+    fn parse(&self, input: &str) -> Result<Duration, String> {
+        parse_time_spec(input).ok_or_else(|| format!("Invalid duration: {}", input))
+    }
+
+    fn format(&self, value: &Duration) -> String {
+        format!("{}s", value.as_secs())
+    }
+}
+
+/// Accepts an nmap time specification such as "30m", keeping the text as typed
+pub struct TimeSpecParser;
+
+impl Parser<String> for TimeSpecParser {
+    fn parse(&self, input: &str) -> Result<String, String> {
+        let input = input.trim();
+        if input.is_empty() || parse_time_spec(input).is_some() {
+            Ok(input.to_string())
+        } else {
+            Err(format!("Invalid time: {}", input))
+        }
+    }
+
+    fn format(&self, value: &String) -> String {
+        value.to_string()
+    }
+}
+
+/// Accepts what --spoof-mac takes: 0 for a random MAC, a vendor name, a hex prefix, or a full MAC
+pub struct SpoofMacParser;
+
+impl Parser<String> for SpoofMacParser {
+    fn parse(&self, input: &str) -> Result<String, String> {
+        let input = input.trim();
+        let is_hex = |c: char| c.is_ascii_hexdigit() || ":-.".contains(c);
+        if input.is_empty() || input == "0" || !input.chars().all(is_hex) {
+            return Ok(input.to_string());
+        }
+        let digits = input.chars().filter(char::is_ascii_hexdigit).count();
+        let has_separators = input.chars().any(|c| ":-.".contains(c));
+        if digits > 12 || (has_separators && digits != 12) {
+            return Err(format!("Invalid MAC address: {}", input));
+        }
+        Ok(input.to_string())
+    }
+
+    fn format(&self, value: &String) -> String {
+        value.to_string()
+    }
+}
+
+/// Accepts what --ip-options takes, normalizing the shortcuts and hop lists
+pub struct IpOptionsParser;
+
+impl Parser<String> for IpOptionsParser {
+    fn parse(&self, input: &str) -> Result<String, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(String::new());
+        }
+        IpOptions::parse(input).map(|options| options.to_string())
+    }
+
+    fn format(&self, value: &String) -> String {
+        value.to_string()
+    }
+}
+
+/// Accepts the FTP relay of -b: [user[:pass]@]server[:port]
+pub struct FtpRelayParser;
+
+impl Parser<String> for FtpRelayParser {
+    fn parse(&self, input: &str) -> Result<String, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(String::new());
+        }
+        let server = match input.rsplit_once('@') {
+            Some((credentials, server)) => {
+                let user = credentials.split(':').next().unwrap_or_default();
+                if user.is_empty() {
+                    return Err("Missing user before the password".to_string());
+                }
+                server
+            }
+            None => input,
+        };
+        let host = match server.split_once(':') {
+            Some((host, port)) => {
+                if !port.parse::<u16>().is_ok_and(|port| port > 0) {
+                    return Err(format!("Invalid port: {}", port));
+                }
+                host
+            }
+            None => server,
+        };
+        if host.is_empty() {
+            return Err("Missing FTP server after @".to_string());
+        }
+        if let Some(c) = host
+            .chars()
+            .find(|c| !c.is_ascii_alphanumeric() && !".-_".contains(*c))
+        {
+            return Err(format!("Invalid character in FTP server: {}", c));
+        }
+        Ok(input.to_string())
+    }
+
+    fn format(&self, value: &String) -> String {
+        value.to_string()
+    }
+}
+
+/// Accepts comma-separated http:// or socks4:// proxy URLs, as --proxies takes them
+pub struct ProxiesParser;
+
+impl Parser<Vec<String>> for ProxiesParser {
+    fn parse(&self, input: &str) -> Result<Vec<String>, String> {
+        let proxies = VecStringParser.parse(input)?;
+        for proxy in &proxies {
+            check_proxy(proxy)?;
+        }
+        Ok(proxies)
+    }
+
+    fn format(&self, value: &Vec<String>) -> String {
+        value.join(", ")
+    }
+}
+
+/// Accepts a payload as hex digits, optionally written 0xcafe or \\xca\\xfe, and gives it
+/// back in the 0x form nmap takes
+pub struct HexParser;
+
+impl HexParser {
+    /// Largest payload nmap sends with --data
+    pub const MAX_BYTES: usize = 1400;
+
+    /// The bytes of a payload
+    pub fn bytes(input: &str) -> Result<Vec<u8>, String> {
+        let digits: String = input.split_whitespace().collect();
+        let digits = match digits.strip_prefix("0x").or(digits.strip_prefix("0X")) {
+            Some(digits) => digits.to_string(),
+            None => digits.replace("\\x", "").replace("\\X", ""),
+        };
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid hex digit: {}", c));
+        }
+        if digits.len() % 2 == 1 {
+            return Err("Odd number of hex digits".to_string());
+        }
+        if digits.len() / 2 > Self::MAX_BYTES {
+            return Err(format!(
+                "Payload of {} bytes, nmap sends at most {}",
+                digits.len() / 2,
+                Self::MAX_BYTES
+            ));
+        }
+        Ok((0..digits.len())
+            .step_by(2)
+            .filter_map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+            .collect())
+    }
+
+    /// The payload as text, with dots for bytes that are not printable ASCII
+    pub fn preview(bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .map(|&byte| {
+                if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    }
+}
+
+impl Parser<String> for HexParser {
+    fn parse(&self, input: &str) -> Result<String, String> {
+        let bytes = Self::bytes(input)?;
+        if bytes.is_empty() {
+            return Ok(String::new());
+        }
+        Ok(bytes.iter().fold("0x".to_string(), |mut hex, byte| {
+            hex.push_str(&format!("{:02x}", byte));
+            hex
+        }))
+    }
+
+    fn format(&self, value: &String) -> String {
+        value.to_string()
+    }
+}
+
 pub struct PathBufParser;
 
 impl Parser<PathBuf> for PathBufParser {
@@ -361,7 +698,17 @@ pub struct TextInput<T> {
     focused_style: Style,
     editing_style: Style,
     default_style: Style,
+    dimmed: bool,
+    /// Shows * in place of the text until Ctrl+R reveals it
+    masked: bool,
+    revealed: bool,
     error: Option<String>,
+    /// Shown under the text while there is no error
+    hint: Option<String>,
+    history: Vec<String>,
+    // Entry of the history being shown, and the text typed before browsing it
+    history_index: Option<usize>,
+    draft: String,
 }
 
 impl<T> TextInput<T> {
@@ -376,10 +723,23 @@ impl<T> TextInput<T> {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
             default_style: Style::default().fg(Color::Gray),
+            dimmed: false,
+            masked: false,
+            revealed: false,
             error: None,
+            hint: None,
+            history: Vec::new(),
+            history_index: None,
+            draft: String::new(),
         }
     }
 
+    /// Hide the text as with a password field; history suggestions are not offered
+    pub fn with_masked(mut self, masked: bool) -> Self {
+        self.masked = masked;
+        self
+    }
+
     pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
         self.placeholder = Some(placeholder.into());
         self
@@ -390,11 +750,97 @@ impl<T> TextInput<T> {
         self
     }
 
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = Some(label.into());
+    }
+
+    pub fn set_dimmed(&mut self, dimmed: bool) {
+        self.dimmed = dimmed;
+    }
+
+    pub fn set_hint(&mut self, hint: Option<String>) {
+        self.hint = hint;
+    }
+
+    /// Set the previously submitted values that Up and Down recall, oldest first
+    pub fn set_history(&mut self, history: Vec<String>) {
+        self.history = history;
+        self.history_index = None;
+    }
+
+    /// Whether Up has recalled a history entry that Down can move past
+    pub fn is_browsing_history(&self) -> bool {
+        self.history_index.is_some()
+    }
+
+    /// Rest of the newest history entry that starts with the text typed so far,
+    /// offered while the cursor is at the end
+    fn suggestion(&self) -> Option<&str> {
+        let content = self.buffer.content();
+        if content.is_empty()
+            || self.masked
+            || self.history_index.is_some()
+            || self.buffer.cursor < content.len()
+        {
+            return None;
+        }
+        self.history
+            .iter()
+            .rev()
+            .find(|entry| entry.len() > content.len() && entry.starts_with(content))
+            .map(|entry| &entry[content.len()..])
+    }
+
+    pub fn has_suggestion(&self) -> bool {
+        self.suggestion().is_some()
+    }
+
+    fn accept_suggestion(&mut self) -> EventResult<T> {
+        match self.suggestion() {
+            Some(rest) => {
+                let content = format!("{}{}", self.buffer.content(), rest);
+                self.buffer.set_content(content);
+                EventResult::Consumed
+            }
+            None => EventResult::Ignored,
+        }
+    }
+
     pub fn set_typed_value(&mut self, value: T) {
         let content = self.parser.format(&value);
         self.set_content(content);
     }
 
+    fn history_previous(&mut self) -> EventResult<T> {
+        if self.history.is_empty() {
+            return EventResult::Ignored;
+        }
+        let index = match self.history_index {
+            Some(index) => index.saturating_sub(1),
+            None => {
+                self.draft = self.buffer.content().to_string();
+                self.history.len() - 1
+            }
+        };
+        self.history_index = Some(index);
+        self.buffer.set_content(self.history[index].clone());
+        EventResult::Consumed
+    }
+
+    fn history_next(&mut self) -> EventResult<T> {
+        let Some(index) = self.history_index else {
+            return EventResult::Ignored;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.buffer.set_content(self.history[index + 1].clone());
+        } else {
+            self.history_index = None;
+            self.buffer.set_content(std::mem::take(&mut self.draft));
+        }
+        EventResult::Consumed
+    }
+
     pub fn handle_event(&mut self, event: &Event) -> EventResult<T> {
         if let Event::Key(key) = event {
             return self.handle_key_event(*key);
@@ -403,10 +849,29 @@ impl<T> TextInput<T> {
     }
 
     fn handle_key_event(&mut self, key: KeyEvent) -> EventResult<T> {
-        // Clear error on any key press
-        self.error = None;
+        let result = self.handle_edit_key(key);
+        if key.code != KeyCode::Enter {
+            self.validate();
+        }
+        result
+    }
+
+    /// Show the parser's error while the text is invalid; empty text is left for Enter to judge
+    fn validate(&mut self) {
+        let content = self.buffer.content();
+        self.error = if content.trim().is_empty() {
+            None
+        } else {
+            self.parser.parse(content).err()
+        };
+    }
 
+    fn handle_edit_key(&mut self, key: KeyEvent) -> EventResult<T> {
         match key.code {
+            KeyCode::Char('r') if self.masked && key.modifiers == KeyModifiers::CONTROL => {
+                self.revealed = !self.revealed;
+                EventResult::Consumed
+            }
             KeyCode::Char(c)
                 if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
             {
@@ -425,10 +890,12 @@ impl<T> TextInput<T> {
                 self.buffer.move_cursor_left();
                 EventResult::Consumed
             }
+            KeyCode::Right if self.has_suggestion() => self.accept_suggestion(),
             KeyCode::Right => {
                 self.buffer.move_cursor_right();
                 EventResult::Consumed
             }
+            KeyCode::Tab => self.accept_suggestion(),
             KeyCode::Home => {
                 self.buffer.move_cursor_start();
                 EventResult::Consumed
@@ -437,8 +904,13 @@ impl<T> TextInput<T> {
                 self.buffer.move_cursor_end();
                 EventResult::Consumed
             }
+            KeyCode::Up if key.modifiers.is_empty() => self.history_previous(),
+            KeyCode::Down if key.modifiers.is_empty() => self.history_next(),
             KeyCode::Enter => match self.parser.parse(self.buffer.content()) {
-                Ok(value) => EventResult::Submit(value),
+                Ok(value) => {
+                    self.history_index = None;
+                    EventResult::Submit(value)
+                }
                 Err(err) => {
                     self.error = Some(err);
                     EventResult::Consumed
@@ -454,12 +926,14 @@ impl<T> TextInput<T> {
             self.editing_style
         } else if focused {
             self.focused_style
+        } else if self.dimmed {
+            Style::default().fg(Color::DarkGray)
         } else {
             self.default_style
         };
 
         let (label_area, input_area) = if let Some(label) = &self.label {
-            let label_width = label.len() as u16 + 2;
+            let label_width = label.width() as u16 + 2;
 
             let chunks = Layout::default()
                 .direction(Direction::Horizontal)
@@ -489,20 +963,44 @@ impl<T> TextInput<T> {
             }
         }
 
-        let block = Block::default().borders(Borders::ALL).style(style);
+        let mut block = Block::default().borders(Borders::ALL).style(style);
+        if let Some(error) = &self.error {
+            let error_style = Style::default().fg(Color::Red);
+            block = block
+                .border_style(error_style)
+                .title_bottom(Line::from(format!(" {} ", error)).style(error_style));
+        } else if let Some(hint) = &self.hint {
+            block = block.title_bottom(
+                Line::from(format!(" {} ", hint)).style(Style::default().fg(Color::DarkGray)),
+            );
+        }
 
         let inner = block.inner(input_area);
         block.render(input_area, buf);
 
+        let masked;
+        let buffer = if self.masked && !self.revealed {
+            masked = self.buffer.masked();
+            &masked
+        } else {
+            &self.buffer
+        };
+
         // Render text or placeholder
-        let text = if self.buffer.content().is_empty() {
+        let text = if buffer.content().is_empty() {
             let placeholder_text = self.placeholder.as_deref().unwrap_or("");
             Line::from(Span::styled(
                 placeholder_text,
                 Style::default().fg(Color::DarkGray),
             ))
+        } else if editing {
+            let mut spans = vec![Span::raw(buffer.visible(inner.width as usize).0)];
+            if let Some(rest) = self.suggestion() {
+                spans.push(Span::styled(rest, Style::default().fg(Color::DarkGray)));
+            }
+            Line::from(spans)
         } else {
-            Line::from(self.buffer.content())
+            Line::from(buffer.content())
         };
 
         let paragraph = Paragraph::new(text);
@@ -510,7 +1008,7 @@ impl<T> TextInput<T> {
 
         // Render cursor ONLY if editing (not just selected)
         if editing && inner.width > 0 {
-            let cursor_pos = self.buffer.cursor_position();
+            let (_, cursor_pos) = buffer.visible(inner.width as usize);
             let cursor_x = inner.x + cursor_pos as u16;
             if cursor_x < inner.x + inner.width
                 && let Some(cell) = buf.cell_mut((cursor_x, inner.y))
@@ -518,23 +1016,6 @@ impl<T> TextInput<T> {
                 cell.set_style(Style::default().add_modifier(Modifier::REVERSED));
             }
         }
-
-        // Render error if any
-        if let Some(error) = &self.error
-            && input_area.height > 3
-        {
-            let error_area = Rect {
-                x: input_area.x,
-                y: input_area.y + input_area.height - 1,
-                width: input_area.width,
-                height: 1,
-            };
-            let error_text = Line::from(Span::styled(
-                format!(" Error: {}", error),
-                Style::default().fg(Color::Red),
-            ));
-            Paragraph::new(error_text).render(error_area, buf);
-        }
     }
 
     pub fn value(&self) -> Result<T, String> {
@@ -544,11 +1025,13 @@ impl<T> TextInput<T> {
     pub fn clear(&mut self) {
         self.buffer.clear();
         self.error = None;
+        self.history_index = None;
     }
 
     pub fn set_content(&mut self, content: String) {
         self.buffer.set_content(content);
         self.error = None;
+        self.history_index = None;
     }
 
     pub fn content(&self) -> &str {
@@ -690,6 +1173,14 @@ impl CompletingInput {
         self
     }
 
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.input.set_label(label);
+    }
+
+    pub fn set_history(&mut self, history: Vec<String>) {
+        self.input.set_history(history);
+    }
+
     pub fn set_typed_value(&mut self, value: PathBuf) {
         let content = self.input.parser.format(&value);
         self.set_content(content);
@@ -706,6 +1197,11 @@ impl CompletingInput {
         match self.mode {
             CompletionMode::Editing => {
                 match key.code {
+                    KeyCode::Tab if self.input.has_suggestion() => {
+                        let result = self.input.handle_event(&Event::Key(key));
+                        self.completer.update_suggestions(self.input.content());
+                        result
+                    }
                     KeyCode::Tab => {
                         // Update suggestions and switch to selection mode
                         self.completer.update_suggestions(self.input.content());
@@ -716,7 +1212,9 @@ impl CompletingInput {
                             EventResult::Consumed
                         }
                     }
-                    KeyCode::Down if key.modifiers.is_empty() => {
+                    KeyCode::Down
+                        if key.modifiers.is_empty() && !self.input.is_browsing_history() =>
+                    {
                         // Also allow down arrow to enter selection mode
                         self.completer.update_suggestions(self.input.content());
                         if self.completer.has_suggestions() {
@@ -807,7 +1305,7 @@ impl CompletingInput {
         let space_below = buf.area().height.saturating_sub(area.y + input_height);
         let space_above = area.y;
 
-        let offset_x = self.input.label.as_deref().unwrap_or("").len() as u16 + 2;
+        let offset_x = self.input.label.as_deref().unwrap_or("").width() as u16 + 2;
         let (dropdown_y, actual_height) = if space_below >= dropdown_height {
             (area.y + input_height, dropdown_height)
         } else if space_above >= dropdown_height {
@@ -896,3 +1394,746 @@ impl Default for CompletingInput {
         Self::new()
     }
 }
+
+// ============================================================================
+// Args Input Widget (key=value lists with key completion)
+// ============================================================================
+
+pub struct ArgsInput {
+    input: TextInput<String>,
+    keys: Vec<String>,
+    suggestions: Vec<String>,
+    selected_idx: usize,
+    mode: CompletionMode,
+    render_area: Option<Rect>,
+}
+
+impl ArgsInput {
+    pub fn new() -> Self {
+        Self {
+            input: TextInput::new(StringParser),
+            keys: Vec::new(),
+            suggestions: Vec::new(),
+            selected_idx: 0,
+            mode: CompletionMode::Editing,
+            render_area: None,
+        }
+    }
+
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.input = self.input.with_placeholder(placeholder);
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.input = self.input.with_label(label);
+        self
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.input.set_label(label);
+    }
+
+    /// Set the keys offered for completion
+    pub fn set_keys(&mut self, keys: Vec<String>) {
+        if self.keys != keys {
+            self.keys = keys;
+            self.update_suggestions();
+        }
+    }
+
+    pub fn set_history(&mut self, history: Vec<String>) {
+        self.input.set_history(history);
+    }
+
+    pub fn set_typed_value(&mut self, value: String) {
+        self.set_content(value);
+    }
+
+    /// Keys matching the key=value pair being typed, leaving out keys that are already set
+    fn update_suggestions(&mut self) {
+        self.selected_idx = 0;
+        let content = self.input.content();
+        let (done, current) = content.rsplit_once(',').unwrap_or(("", content));
+        let current = current.trim_start();
+        if current.contains('=') {
+            self.suggestions.clear();
+            return;
+        }
+
+        let used: Vec<&str> = done
+            .split(',')
+            .filter_map(|pair| pair.split_once('=').map(|(key, _)| key.trim()))
+            .collect();
+        self.suggestions = self
+            .keys
+            .iter()
+            .filter(|key| key.to_lowercase().starts_with(&current.to_lowercase()))
+            .filter(|key| !used.contains(&key.as_str()))
+            .cloned()
+            .collect();
+    }
+
+    /// Replace the pair being typed with the selected key
+    fn accept_suggestion(&mut self) {
+        let Some(key) = self.suggestions.get(self.selected_idx) else {
+            return;
+        };
+        let content = self.input.content();
+        let prefix = match content.rfind(',') {
+            Some(index) => &content[..=index],
+            None => "",
+        };
+        self.input.set_content(format!("{}{}=", prefix, key));
+        self.update_suggestions();
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<String> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        match (&self.mode, key.code) {
+            (CompletionMode::Editing, KeyCode::Down) if self.input.is_browsing_history() => {
+                self.input.handle_event(event)
+            }
+            (CompletionMode::Editing, KeyCode::Tab) if self.input.has_suggestion() => {
+                let result = self.input.handle_event(event);
+                self.update_suggestions();
+                result
+            }
+            (CompletionMode::Editing, KeyCode::Tab | KeyCode::Down) => {
+                self.update_suggestions();
+                if !self.suggestions.is_empty() {
+                    self.mode = CompletionMode::Selecting;
+                }
+                EventResult::Consumed
+            }
+            (CompletionMode::Selecting, KeyCode::Up) => {
+                if !self.suggestions.is_empty() {
+                    self.selected_idx =
+                        (self.selected_idx + self.suggestions.len() - 1) % self.suggestions.len();
+                }
+                EventResult::Consumed
+            }
+            (CompletionMode::Selecting, KeyCode::Down) => {
+                if !self.suggestions.is_empty() {
+                    self.selected_idx = (self.selected_idx + 1) % self.suggestions.len();
+                }
+                EventResult::Consumed
+            }
+            (CompletionMode::Selecting, KeyCode::Tab | KeyCode::Enter) => {
+                self.accept_suggestion();
+                self.mode = CompletionMode::Editing;
+                EventResult::Consumed
+            }
+            (CompletionMode::Selecting, KeyCode::Esc) => {
+                self.mode = CompletionMode::Editing;
+                EventResult::Consumed
+            }
+            _ => {
+                self.mode = CompletionMode::Editing;
+                let result = self.input.handle_event(event);
+                if matches!(result, EventResult::Consumed) {
+                    self.update_suggestions();
+                }
+                result
+            }
+        }
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, focused: bool, editing: bool) {
+        self.render_area = Some(area);
+        self.input.render(area, buf, focused, editing);
+    }
+
+    pub fn render_dropdown_overlay(&self, buf: &mut Buffer) {
+        if self.suggestions.is_empty() {
+            return;
+        }
+        let Some(area) = self.render_area else {
+            return;
+        };
+
+        let offset_x = self.input.label.as_deref().unwrap_or("").width() as u16 + 2;
+        let height = (self.suggestions.len().min(10) as u16 + 2)
+            .min(buf.area().height.saturating_sub(area.y + 3));
+        if height < 3 || area.width <= offset_x {
+            return;
+        }
+        let dropdown_area = Rect {
+            x: area.x + offset_x,
+            y: area.y + 3,
+            width: area.width - offset_x,
+            height,
+        };
+
+        let items: Vec<ListItem> = self
+            .suggestions
+            .iter()
+            .enumerate()
+            .skip((self.selected_idx + 1).saturating_sub(height as usize - 2))
+            .map(|(i, key)| {
+                let style = if i == self.selected_idx && self.mode == CompletionMode::Selecting {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else if i == self.selected_idx {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(key.as_str()).style(style)
+            })
+            .collect();
+
+        Clear.render(dropdown_area, buf);
+        List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Script args"))
+            .render(dropdown_area, buf);
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.suggestions.clear();
+        self.mode = CompletionMode::Editing;
+    }
+
+    pub fn set_content(&mut self, content: String) {
+        self.input.set_content(content);
+        self.update_suggestions();
+    }
+
+    pub fn content(&self) -> &str {
+        self.input.content()
+    }
+}
+
+impl Default for ArgsInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Choice Input - free text with a dropdown of known values
+// ============================================================================
+
+pub struct ChoiceInput {
+    input: TextInput<String>,
+    choices: Vec<String>,
+    suggestions: Vec<String>,
+    selected_idx: usize,
+    mode: CompletionMode,
+    render_area: Option<Rect>,
+    dropdown_title: String,
+}
+
+impl ChoiceInput {
+    pub fn new(dropdown_title: impl Into<String>) -> Self {
+        Self {
+            input: TextInput::new(StringParser),
+            choices: Vec::new(),
+            suggestions: Vec::new(),
+            selected_idx: 0,
+            mode: CompletionMode::Editing,
+            render_area: None,
+            dropdown_title: dropdown_title.into(),
+        }
+    }
+
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.input = self.input.with_placeholder(placeholder);
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.input = self.input.with_label(label);
+        self
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.input.set_label(label);
+    }
+
+    /// Set the values offered in the dropdown
+    pub fn set_choices(&mut self, choices: Vec<String>) {
+        self.choices = choices;
+        self.update_suggestions();
+    }
+
+    pub fn set_history(&mut self, history: Vec<String>) {
+        self.input.set_history(history);
+    }
+
+    pub fn set_typed_value(&mut self, value: String) {
+        self.set_content(value);
+    }
+
+    /// Choices starting with what has been typed, all of them when nothing has
+    fn update_suggestions(&mut self) {
+        self.selected_idx = 0;
+        let content = self.input.content().trim().to_lowercase();
+        self.suggestions = self
+            .choices
+            .iter()
+            .filter(|choice| choice.to_lowercase().starts_with(&content))
+            .cloned()
+            .collect();
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<String> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        match (&self.mode, key.code) {
+            (CompletionMode::Editing, KeyCode::Down) if self.input.is_browsing_history() => {
+                self.input.handle_event(event)
+            }
+            (CompletionMode::Editing, KeyCode::Tab) if self.input.has_suggestion() => {
+                let result = self.input.handle_event(event);
+                self.update_suggestions();
+                result
+            }
+            (CompletionMode::Editing, KeyCode::Tab | KeyCode::Down) => {
+                self.update_suggestions();
+                if !self.suggestions.is_empty() {
+                    self.mode = CompletionMode::Selecting;
+                }
+                EventResult::Consumed
+            }
+            (CompletionMode::Selecting, KeyCode::Up) => {
+                if !self.suggestions.is_empty() {
+                    self.selected_idx =
+                        (self.selected_idx + self.suggestions.len() - 1) % self.suggestions.len();
+                }
+                EventResult::Consumed
+            }
+            (CompletionMode::Selecting, KeyCode::Down) => {
+                if !self.suggestions.is_empty() {
+                    self.selected_idx = (self.selected_idx + 1) % self.suggestions.len();
+                }
+                EventResult::Consumed
+            }
+            (CompletionMode::Selecting, KeyCode::Tab | KeyCode::Enter) => {
+                if let Some(choice) = self.suggestions.get(self.selected_idx) {
+                    self.input.set_content(choice.clone());
+                }
+                self.mode = CompletionMode::Editing;
+                self.update_suggestions();
+                EventResult::Consumed
+            }
+            (CompletionMode::Selecting, KeyCode::Esc) => {
+                self.mode = CompletionMode::Editing;
+                EventResult::Consumed
+            }
+            _ => {
+                self.mode = CompletionMode::Editing;
+                let result = self.input.handle_event(event);
+                if matches!(result, EventResult::Consumed) {
+                    self.update_suggestions();
+                }
+                result
+            }
+        }
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, focused: bool, editing: bool) {
+        self.render_area = Some(area);
+        self.input.render(area, buf, focused, editing);
+    }
+
+    /// Draw the choices below the input while one is being picked
+    pub fn render_dropdown_overlay(&self, buf: &mut Buffer) {
+        if self.mode != CompletionMode::Selecting || self.suggestions.is_empty() {
+            return;
+        }
+        let Some(area) = self.render_area else {
+            return;
+        };
+
+        let offset_x = self.input.label.as_deref().unwrap_or("").width() as u16 + 2;
+        let height = (self.suggestions.len().min(10) as u16 + 2)
+            .min(buf.area().height.saturating_sub(area.y + 3));
+        if height < 3 || area.width <= offset_x {
+            return;
+        }
+        let dropdown_area = Rect {
+            x: area.x + offset_x,
+            y: area.y + 3,
+            width: area.width - offset_x,
+            height,
+        };
+
+        let items: Vec<ListItem> = self
+            .suggestions
+            .iter()
+            .enumerate()
+            .skip((self.selected_idx + 1).saturating_sub(height as usize - 2))
+            .map(|(i, choice)| {
+                let style = if i == self.selected_idx {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(choice.as_str()).style(style)
+            })
+            .collect();
+
+        Clear.render(dropdown_area, buf);
+        List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(self.dropdown_title.as_str()),
+            )
+            .render(dropdown_area, buf);
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.mode = CompletionMode::Editing;
+        self.update_suggestions();
+    }
+
+    pub fn set_content(&mut self, content: String) {
+        self.input.set_content(content);
+        self.update_suggestions();
+    }
+
+    pub fn content(&self) -> &str {
+        self.input.content()
+    }
+}
+
+// ============================================================================
+// Hex Input Widget (payload bytes with an ASCII preview)
+// ============================================================================
+
+pub struct HexInput {
+    input: TextInput<String>,
+}
+
+impl HexInput {
+    pub fn new() -> Self {
+        Self {
+            input: TextInput::new(HexParser),
+        }
+    }
+
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.input = self.input.with_placeholder(placeholder);
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.input = self.input.with_label(label);
+        self
+    }
+
+    pub fn set_typed_value(&mut self, value: String) {
+        self.input.set_typed_value(value);
+    }
+
+    /// Only keys that can be part of a payload are typed, others are dropped
+    pub fn handle_event(&mut self, event: &Event) -> EventResult<String> {
+        if let Event::Key(key) = event
+            && let KeyCode::Char(c) = key.code
+            && !key.modifiers.contains(KeyModifiers::CONTROL)
+            && !c.is_ascii_hexdigit()
+            && !" xX\\".contains(c)
+        {
+            return EventResult::Consumed;
+        }
+        self.input.handle_event(event)
+    }
+
+    /// Draw the input with the size and text of the payload under it
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, focused: bool, editing: bool) {
+        let hint = HexParser::bytes(self.input.content())
+            .ok()
+            .filter(|bytes| !bytes.is_empty())
+            .map(|bytes| {
+                let unit = if bytes.len() == 1 { "byte" } else { "bytes" };
+                format!("{} {}: {}", bytes.len(), unit, HexParser::preview(&bytes))
+            });
+        self.input.set_hint(hint);
+        self.input.render(area, buf, focused, editing);
+    }
+}
+
+impl Default for HexInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_buffer_graphemes() {
+        let mut buffer = InputBuffer::new();
+        buffer.set_content("/tmp/扫描👍🏽.txt".to_string());
+        for _ in 0..4 {
+            buffer.move_cursor_left();
+        }
+        assert_eq!(&buffer.content()[buffer.cursor..], ".txt");
+        assert_eq!(buffer.cursor_position(), 5 + 4 + 2);
+
+        buffer.backspace();
+        assert_eq!(buffer.content(), "/tmp/扫描.txt");
+        buffer.move_cursor_left();
+        buffer.delete_char();
+        assert_eq!(buffer.content(), "/tmp/扫.txt");
+        assert_eq!(buffer.cursor_position(), 7);
+    }
+
+    #[test]
+    fn test_input_buffer_visible() {
+        let mut buffer = InputBuffer::new();
+        buffer.set_content("扫描目标".to_string());
+        assert_eq!(buffer.visible(20), ("扫描目标", 8));
+        // The cursor needs a free column after the text, so wide characters scroll off as a whole
+        assert_eq!(buffer.visible(6), ("目标", 4));
+        assert_eq!(buffer.visible(5), ("目标", 4));
+        buffer.move_cursor_start();
+        assert_eq!(buffer.visible(5), ("扫描目标", 0));
+    }
+
+    #[test]
+    fn test_ftp_relay_parser() {
+        for valid in [
+            "ftp.example.com",
+            "anonymous:pw@10.0.0.5:2121",
+            "user@ftp",
+            "user:@ftp",
+        ] {
+            assert!(FtpRelayParser.parse(valid).is_ok(), "{}", valid);
+        }
+        for invalid in [
+            ":pw@ftp",
+            "user@",
+            "ftp:0",
+            "ftp:port",
+            "user@ftp/x",
+            "@:21",
+        ] {
+            assert!(FtpRelayParser.parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_proxies_parser() {
+        assert_eq!(
+            ProxiesParser.parse("http://10.0.0.1:8080, SOCKS4://proxy.lan:1080/"),
+            Ok(vec![
+                "http://10.0.0.1:8080".to_string(),
+                "SOCKS4://proxy.lan:1080/".to_string()
+            ])
+        );
+        for invalid in [
+            "10.0.0.1:8080",
+            "socks5://proxy:1080",
+            "http://:8080",
+            "http://proxy:0",
+            "http://proxy/path",
+            "http://proxy:8080,https://proxy",
+        ] {
+            assert!(ProxiesParser.parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_ratio_parser() {
+        assert_eq!(RatioParser.parse(" 0.25 "), Ok(0.25));
+        assert_eq!(RatioParser.parse("1"), Ok(1.0));
+        for invalid in ["1.5", "-0.1", "half", "NaN"] {
+            assert!(RatioParser.parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_hex_parser() {
+        for input in ["474554", "0x474554", "\\x47\\x45\\x54", "47 45 54"] {
+            assert_eq!(
+                HexParser.parse(input),
+                Ok("0x474554".to_string()),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            HexParser::preview(&HexParser::bytes("474554000a").unwrap()),
+            "GET.."
+        );
+        assert_eq!(HexParser.parse(""), Ok(String::new()));
+        for invalid in ["4745 5", "0xgg", "\\x47\\x4"] {
+            assert!(HexParser.parse(invalid).is_err(), "{}", invalid);
+        }
+        assert!(HexParser.parse(&"00".repeat(HexParser::MAX_BYTES)).is_ok());
+        assert!(
+            HexParser
+                .parse(&"00".repeat(HexParser::MAX_BYTES + 1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_spoof_mac_parser() {
+        for valid in ["0", "Apple", "000C29", "00:0c:29:12:34:56", "000c29123456"] {
+            assert!(SpoofMacParser.parse(valid).is_ok(), "{}", valid);
+        }
+        for invalid in ["00:0c:29", "00:0c:29:12:34:56:78", "000c291234567"] {
+            assert!(SpoofMacParser.parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_history() {
+        let mut input = TextInput::new(StringParser);
+        assert!(matches!(
+            input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Up))),
+            EventResult::Ignored
+        ));
+
+        input.set_history(vec!["22,80".to_string(), "1-1024".to_string()]);
+        input.set_content("443".to_string());
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Up)));
+        assert_eq!(input.content(), "1-1024");
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Up)));
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Up)));
+        assert_eq!(input.content(), "22,80");
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Down)));
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Down)));
+        assert_eq!(input.content(), "443");
+        assert!(!input.is_browsing_history());
+    }
+
+    #[test]
+    fn test_history_suggestion() {
+        let mut input = TextInput::new(StringParser);
+        input.set_history(vec!["10.0.0.0/24".to_string(), "10.1.0.0/16".to_string()]);
+        input.set_content("10.".to_string());
+        assert_eq!(input.suggestion(), Some("1.0.0/16"));
+        input.set_content("10.0".to_string());
+        assert_eq!(input.suggestion(), Some(".0.0/24"));
+
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Left)));
+        assert_eq!(input.suggestion(), None);
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::End)));
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Right)));
+        assert_eq!(input.content(), "10.0.0.0/24");
+        assert_eq!(input.suggestion(), None);
+        assert!(matches!(
+            input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Tab))),
+            EventResult::Ignored
+        ));
+    }
+
+    #[test]
+    fn test_validate_while_typing() {
+        let mut input = TextInput::new(VecIntParser);
+        for c in "80,8x".chars() {
+            input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Char(c))));
+        }
+        assert_eq!(input.error.as_deref(), Some("Invalid integer: 8x"));
+        assert!(matches!(
+            input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Enter))),
+            EventResult::Consumed
+        ));
+
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Backspace)));
+        assert_eq!(input.error, None);
+        assert!(matches!(
+            input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Enter))),
+            EventResult::Submit(ports) if ports == vec![80, 8]
+        ));
+
+        input.set_content("443; 80 443\t22".to_string());
+        assert_eq!(VecIntParser::duplicates(input.content()), 1);
+        assert!(matches!(
+            input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Enter))),
+            EventResult::Submit(ports) if ports == vec![443, 80, 22]
+        ));
+    }
+
+    fn press(input: &mut ArgsInput, code: KeyCode) -> EventResult<String> {
+        input.handle_event(&Event::Key(KeyEvent::from(code)))
+    }
+
+    #[test]
+    fn test_args_completion() {
+        let mut input = ArgsInput::new();
+        input.set_keys(vec![
+            "http-brute.path".to_string(),
+            "http-brute.method".to_string(),
+            "userdb".to_string(),
+        ]);
+        input.set_content("userdb=users.txt,http".to_string());
+        assert_eq!(
+            input.suggestions,
+            vec!["http-brute.path", "http-brute.method"]
+        );
+
+        press(&mut input, KeyCode::Tab);
+        press(&mut input, KeyCode::Down);
+        press(&mut input, KeyCode::Enter);
+        assert_eq!(input.content(), "userdb=users.txt,http-brute.method=");
+        assert!(input.suggestions.is_empty());
+
+        press(&mut input, KeyCode::Char('P'));
+        press(&mut input, KeyCode::Char(','));
+        assert_eq!(input.suggestions, vec!["http-brute.path"]);
+        assert!(matches!(
+            press(&mut input, KeyCode::Enter),
+            EventResult::Submit(value) if value == "userdb=users.txt,http-brute.method=P,"
+        ));
+    }
+
+    #[test]
+    fn test_render_states() {
+        let render = |input: &TextInput<u32>, focused: bool, editing: bool| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 40, 3));
+            input.render(buf.area, &mut buf, focused, editing);
+            format!("{:?}", buf)
+        };
+        let mut input = TextInput::new(IntParser)
+            .with_label("Random targets (-iR)")
+            .with_placeholder("Number of random targets");
+        insta::assert_snapshot!("text_input", render(&input, false, false));
+        insta::assert_snapshot!("text_input_focused", render(&input, true, false));
+        for c in "12".chars() {
+            input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Char(c))));
+        }
+        insta::assert_snapshot!("text_input_editing", render(&input, true, true));
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Char('x'))));
+        insta::assert_snapshot!("text_input_error", render(&input, true, true));
+    }
+
+    #[test]
+    fn test_masked() {
+        let render = |input: &TextInput<String>| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 40, 3));
+            input.render(buf.area, &mut buf, true, true);
+            format!("{:?}", buf)
+        };
+        let mut input = TextInput::new(StringParser)
+            .with_label("smbpassword")
+            .with_masked(true);
+        input.set_history(vec!["hunter2".to_string()]);
+        for c in "hunt".chars() {
+            input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Char(c))));
+        }
+        insta::assert_snapshot!("text_input_masked", render(&input));
+        assert!(!input.has_suggestion());
+
+        input.handle_event(&Event::Key(KeyEvent::new(
+            KeyCode::Char('r'),
+            KeyModifiers::CONTROL,
+        )));
+        assert!(render(&input).contains("hunt"));
+        assert!(matches!(
+            input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Enter))),
+            EventResult::Submit(value) if value == "hunt"
+        ));
+    }
+}
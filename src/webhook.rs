@@ -0,0 +1,217 @@
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::thread;
+
+use crate::{
+    config::WebhookConfig,
+    results::{diff::PortChange, model::ScanResults},
+};
+
+/// Longest message Discord accepts in a single webhook post
+const DISCORD_MAX_LENGTH: usize = 2000;
+/// Open ports listed per host before the summary is truncated
+const MAX_PORTS_LISTED: usize = 20;
+
+/// Payload format expected by the webhook receiver
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    Slack,
+    Discord,
+    #[default]
+    Generic,
+}
+
+/// Something worth telling the webhook about
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookEvent<'a> {
+    pub event: &'static str,
+    pub title: String,
+    pub command: String,
+    pub results: Option<&'a ScanResults>,
+    pub changes: Vec<PortChange>,
+}
+
+/// Build the JSON body for the given kind of webhook
+pub fn payload(kind: WebhookKind, event: &WebhookEvent) -> Value {
+    match kind {
+        WebhookKind::Slack => json!({ "text": summary_text(event) }),
+        WebhookKind::Discord => {
+            let mut text = summary_text(event);
+            if text.len() > DISCORD_MAX_LENGTH {
+                let mut end = DISCORD_MAX_LENGTH - 3;
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                text.truncate(end);
+                text.push_str("...");
+            }
+            json!({ "content": text })
+        }
+        WebhookKind::Generic => {
+            let hosts = event.results.map_or(Vec::new(), |results| {
+                results
+                    .hosts
+                    .iter()
+                    .filter(|host| host.open_ports().next().is_some())
+                    .map(|host| {
+                        let ports = host
+                            .open_ports()
+                            .map(|port| {
+                                json!({
+                                    "port": port.port,
+                                    "protocol": port.protocol,
+                                    "service": port.service.as_ref().map(|service| &service.name),
+                                    "version": port.service.as_ref().map(|service| service.product_version()),
+                                })
+                            })
+                            .collect::<Vec<_>>();
+                        json!({ "address": host.address(), "hostnames": host.hostnames, "open_ports": ports })
+                    })
+                    .collect()
+            });
+            let changes = event
+                .changes
+                .iter()
+                .map(|change| {
+                    json!({
+                        "address": change.host,
+                        "port": change.port,
+                        "protocol": change.protocol,
+                        "change": change.kind.as_str(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({
+                "event": event.event,
+                "title": event.title,
+                "command": event.command,
+                "hosts": hosts,
+                "changes": changes,
+            })
+        }
+    }
+}
+
+/// Human-readable summary of the event and the open ports found or changed
+pub fn summary_text(event: &WebhookEvent) -> String {
+    let mut lines = vec![event.title.clone(), format!("`{}`", event.command)];
+    lines.extend(event.changes.iter().map(ToString::to_string));
+    if let Some(results) = event.results {
+        for host in &results.hosts {
+            let ports: Vec<String> = host
+                .open_ports()
+                .map(|port| match &port.service {
+                    Some(service) => format!("{}/{} ({})", port.port, port.protocol, service.name),
+                    None => format!("{}/{}", port.port, port.protocol),
+                })
+                .collect();
+            if ports.is_empty() {
+                continue;
+            }
+            let mut line = format!(
+                "{}: {}",
+                host.display_name(),
+                ports[..ports.len().min(MAX_PORTS_LISTED)].join(", ")
+            );
+            if ports.len() > MAX_PORTS_LISTED {
+                line.push_str(&format!(" and {} more", ports.len() - MAX_PORTS_LISTED));
+            }
+            lines.push(line);
+        }
+        if lines.len() == 2 {
+            lines.push("No open ports found".to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+/// Post the event to the configured webhook in the background
+pub fn send(config: &WebhookConfig, event: &WebhookEvent) {
+    let Some(url) = config.url.clone() else {
+        return;
+    };
+    let body = payload(config.kind, event).to_string();
+    thread::spawn(move || {
+        match ureq::post(&url)
+            .header("Content-Type", "application/json")
+            .send(&body)
+        {
+            Ok(response) => tracing::debug!(status = %response.status(), "Sent webhook"),
+            Err(err) => tracing::warn!(%err, "Failed to send webhook"),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::{diff::ChangeKind, parser::parse_xml};
+
+    fn results() -> ScanResults {
+        parse_xml(
+            r#"<nmaprun><host><status state="up"/><address addr="10.0.0.1" addrtype="ipv4"/>
+            <ports><port protocol="tcp" portid="22"><state state="open"/><service name="ssh"/></port>
+            <port protocol="tcp" portid="25"><state state="closed"/></port></ports></host></nmaprun>"#,
+        )
+        .unwrap()
+    }
+
+    fn event(results: &ScanResults) -> WebhookEvent<'_> {
+        WebhookEvent {
+            event: "scan_completed",
+            title: "Scan completed".to_string(),
+            command: "nmap 10.0.0.1".to_string(),
+            results: Some(results),
+            changes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_summary_text() {
+        assert_eq!(
+            summary_text(&event(&results())),
+            "Scan completed\n`nmap 10.0.0.1`\n10.0.0.1: 22/tcp (ssh)"
+        );
+    }
+
+    #[test]
+    fn test_payload() {
+        let results = results();
+        assert_eq!(
+            payload(WebhookKind::Slack, &event(&results))["text"],
+            summary_text(&event(&results))
+        );
+        let generic = payload(WebhookKind::Generic, &event(&results));
+        assert_eq!(generic["event"], "scan_completed");
+        assert_eq!(generic["hosts"][0]["open_ports"][0]["port"], 22);
+        assert_eq!(
+            generic["hosts"][0]["open_ports"].as_array().unwrap().len(),
+            1
+        );
+        assert!(generic["changes"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_changes() {
+        let event = WebhookEvent {
+            event: "changes_detected",
+            title: "Monitor quick detected 1 change".to_string(),
+            command: "nmap 10.0.0.1".to_string(),
+            results: None,
+            changes: vec![PortChange {
+                host: "10.0.0.1".to_string(),
+                port: 80,
+                protocol: "tcp".to_string(),
+                kind: ChangeKind::Opened,
+            }],
+        };
+        assert_eq!(
+            summary_text(&event),
+            "Monitor quick detected 1 change\n`nmap 10.0.0.1`\n10.0.0.1: 80/tcp opened"
+        );
+        let generic = payload(WebhookKind::Generic, &event);
+        assert_eq!(generic["changes"][0]["port"], 80);
+        assert_eq!(generic["changes"][0]["change"], "opened");
+    }
+}